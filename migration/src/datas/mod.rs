@@ -5,3 +5,12 @@ pub mod m20241024_034305_insert_sys_role_menu;
 pub mod m20241024_034526_insert_sys_role;
 pub mod m20241024_034744_insert_sys_menu;
 pub mod m20241024_082926_insert_casbin_rule;
+pub mod m20261019_000002_insert_organization_casbin_rule;
+pub mod m20261019_000003_insert_domain_provision_casbin_rule;
+pub mod m20261019_000006_insert_recycle_bin_casbin_rule;
+pub mod m20261019_000009_insert_audit_export_casbin_rule;
+pub mod m20261019_000010_insert_log_cursor_casbin_rule;
+pub mod m20261019_000013_insert_dashboard_casbin_rule;
+pub mod m20261019_000016_insert_ip_rule_casbin_rule;
+pub mod m20261019_000020_insert_access_key_rotate_casbin_rule;
+pub mod m20261019_000021_insert_access_key_update_casbin_rule;
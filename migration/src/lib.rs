@@ -22,6 +22,25 @@ impl MigratorTrait for Migrator {
             Box::new(schemas::m20241023_091204_create_sys_tokens::Migration),
             Box::new(schemas::m20241023_091210_create_sys_user_role::Migration),
             Box::new(schemas::m20241023_091159_create_sys_role_menu::Migration),
+            Box::new(schemas::m20261019_000001_alter_sys_user_add_organization::Migration),
+            Box::new(schemas::m20261019_000004_alter_sys_tables_add_soft_delete::Migration),
+            Box::new(schemas::m20261019_000005_create_sys_recycle_bin::Migration),
+            Box::new(schemas::m20261019_000007_alter_sys_tables_add_version::Migration),
+            Box::new(schemas::m20261019_000008_alter_sys_operation_log_add_headers::Migration),
+            Box::new(schemas::m20261019_000011_alter_sys_login_log_add_risk::Migration),
+            Box::new(schemas::m20261019_000012_alter_sys_login_log_add_result::Migration),
+            Box::new(schemas::m20261019_000014_alter_sys_login_log_add_region::Migration),
+            Box::new(schemas::m20261019_000015_create_sys_ip_rule::Migration),
+            Box::new(schemas::m20261019_000017_alter_sys_access_key_add_rate_limit::Migration),
+            Box::new(schemas::m20261019_000018_alter_sys_access_key_add_policy::Migration),
+            Box::new(schemas::m20261019_000019_alter_sys_access_key_encrypt_secret::Migration),
+            Box::new(
+                schemas::m20261019_000022_alter_sys_access_key_add_signature_algorithm::Migration,
+            ),
+            Box::new(schemas::m20261019_000023_alter_sys_user_add_must_change_password::Migration),
+            Box::new(schemas::m20261019_000024_alter_sys_operation_log_add_status::Migration),
+            Box::new(schemas::m20261019_000025_alter_sys_recycle_bin_add_domain::Migration),
+            Box::new(schemas::m20261019_000026_alter_sys_operation_log_add_route::Migration),
             // 数据迁移
             Box::new(datas::m20241023_102950_insert_sys_domain::Migration),
             Box::new(datas::m20241024_033005_insert_sys_user::Migration),
@@ -30,6 +49,15 @@ impl MigratorTrait for Migrator {
             Box::new(datas::m20241024_033933_insert_sys_user_role::Migration),
             Box::new(datas::m20241024_034305_insert_sys_role_menu::Migration),
             Box::new(datas::m20241024_082926_insert_casbin_rule::Migration),
+            Box::new(datas::m20261019_000002_insert_organization_casbin_rule::Migration),
+            Box::new(datas::m20261019_000003_insert_domain_provision_casbin_rule::Migration),
+            Box::new(datas::m20261019_000006_insert_recycle_bin_casbin_rule::Migration),
+            Box::new(datas::m20261019_000009_insert_audit_export_casbin_rule::Migration),
+            Box::new(datas::m20261019_000010_insert_log_cursor_casbin_rule::Migration),
+            Box::new(datas::m20261019_000013_insert_dashboard_casbin_rule::Migration),
+            Box::new(datas::m20261019_000016_insert_ip_rule_casbin_rule::Migration),
+            Box::new(datas::m20261019_000020_insert_access_key_rotate_casbin_rule::Migration),
+            Box::new(datas::m20261019_000021_insert_access_key_update_casbin_rule::Migration),
         ]
     }
 }
@@ -0,0 +1,91 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 支持软删除的表
+fn soft_delete_tables() -> Vec<DynIden> {
+    vec![
+        SysUser::Table.into_iden(),
+        SysRole::Table.into_iden(),
+        SysDomain::Table.into_iden(),
+        SysMenu::Table.into_iden(),
+        SysOrganization::Table.into_iden(),
+    ]
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in soft_delete_tables() {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .add_column(
+                            ColumnDef::new(SoftDelete::DeletedAt)
+                                .timestamp()
+                                .null()
+                                .comment("删除时间"),
+                        )
+                        .add_column(
+                            ColumnDef::new(SoftDelete::DeletedBy)
+                                .string()
+                                .null()
+                                .comment("删除人"),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in soft_delete_tables() {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(SoftDelete::DeletedAt)
+                        .drop_column(SoftDelete::DeletedBy)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysUser {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum SysRole {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum SysDomain {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum SysMenu {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum SysOrganization {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum SoftDelete {
+    DeletedAt,
+    DeletedBy,
+}
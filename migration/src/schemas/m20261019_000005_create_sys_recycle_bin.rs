@@ -0,0 +1,84 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysRecycleBin::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SysRecycleBin::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SysRecycleBin::EntityType)
+                            .string()
+                            .not_null()
+                            .comment("实体类型"),
+                    )
+                    .col(
+                        ColumnDef::new(SysRecycleBin::EntityId)
+                            .string()
+                            .not_null()
+                            .comment("实体ID"),
+                    )
+                    .col(
+                        ColumnDef::new(SysRecycleBin::EntityName)
+                            .string()
+                            .not_null()
+                            .comment("实体名称"),
+                    )
+                    .col(
+                        ColumnDef::new(SysRecycleBin::Snapshot)
+                            .text()
+                            .not_null()
+                            .comment("删除时移除的绑定和策略快照"),
+                    )
+                    .col(
+                        ColumnDef::new(SysRecycleBin::DeletedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(SysRecycleBin::DeletedBy).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(SysRecycleBin::Table)
+                    .name("idx_sys_recycle_bin_entity")
+                    .col(SysRecycleBin::EntityType)
+                    .col(SysRecycleBin::EntityId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SysRecycleBin::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysRecycleBin {
+    Table,
+    Id,
+    EntityType,
+    EntityId,
+    EntityName,
+    Snapshot,
+    DeletedAt,
+    DeletedBy,
+}
@@ -0,0 +1,84 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 支持乐观锁的表
+fn versioned_tables() -> Vec<DynIden> {
+    vec![
+        SysUser::Table.into_iden(),
+        SysRole::Table.into_iden(),
+        SysDomain::Table.into_iden(),
+        SysMenu::Table.into_iden(),
+        SysOrganization::Table.into_iden(),
+    ]
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in versioned_tables() {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .add_column(
+                            ColumnDef::new(Versioned::Version)
+                                .integer()
+                                .not_null()
+                                .default(0)
+                                .comment("乐观锁版本号"),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in versioned_tables() {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(Versioned::Version)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysUser {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum SysRole {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum SysDomain {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum SysMenu {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum SysOrganization {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum Versioned {
+    Version,
+}
@@ -0,0 +1,49 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysLoginLog::Table)
+                    .add_column(
+                        ColumnDef::new(SysLoginLog::RiskScore)
+                            .integer()
+                            .not_null()
+                            .default(0)
+                            .comment("登录风险分"),
+                    )
+                    .add_column(
+                        ColumnDef::new(SysLoginLog::RiskReasons)
+                            .text()
+                            .null()
+                            .comment("命中的登录异常，逗号分隔"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysLoginLog::Table)
+                    .drop_column(SysLoginLog::RiskScore)
+                    .drop_column(SysLoginLog::RiskReasons)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysLoginLog {
+    Table,
+    RiskScore,
+    RiskReasons,
+}
@@ -0,0 +1,49 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysLoginLog::Table)
+                    .add_column(
+                        ColumnDef::new(SysLoginLog::Success)
+                            .boolean()
+                            .not_null()
+                            .default(true)
+                            .comment("是否登录成功"),
+                    )
+                    .add_column(
+                        ColumnDef::new(SysLoginLog::FailureReason)
+                            .text()
+                            .null()
+                            .comment("登录失败原因"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysLoginLog::Table)
+                    .drop_column(SysLoginLog::Success)
+                    .drop_column(SysLoginLog::FailureReason)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysLoginLog {
+    Table,
+    Success,
+    FailureReason,
+}
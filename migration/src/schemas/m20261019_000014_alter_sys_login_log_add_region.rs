@@ -0,0 +1,111 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysLoginLog::Table)
+                    .add_column(
+                        ColumnDef::new(SysLoginLog::Country)
+                            .text()
+                            .null()
+                            .comment("国家"),
+                    )
+                    .add_column(
+                        ColumnDef::new(SysLoginLog::Region)
+                            .text()
+                            .null()
+                            .comment("区域"),
+                    )
+                    .add_column(
+                        ColumnDef::new(SysLoginLog::Province)
+                            .text()
+                            .null()
+                            .comment("省份"),
+                    )
+                    .add_column(
+                        ColumnDef::new(SysLoginLog::City)
+                            .text()
+                            .null()
+                            .comment("城市"),
+                    )
+                    .add_column(
+                        ColumnDef::new(SysLoginLog::Isp)
+                            .text()
+                            .null()
+                            .comment("运营商"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sys_login_log_domain_province")
+                    .table(SysLoginLog::Table)
+                    .col(SysLoginLog::Domain)
+                    .col(SysLoginLog::Province)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 从已有的归属地 `国家|区域|省份|城市|ISP` 回填，`0` 表示未知
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                UPDATE sys_login_log
+                SET country = NULLIF(NULLIF(split_part(address, '|', 1), ''), '0'),
+                    region = NULLIF(NULLIF(split_part(address, '|', 2), ''), '0'),
+                    province = NULLIF(NULLIF(split_part(address, '|', 3), ''), '0'),
+                    city = NULLIF(NULLIF(split_part(address, '|', 4), ''), '0'),
+                    isp = NULLIF(NULLIF(split_part(address, '|', 5), ''), '0')
+                WHERE address LIKE '%|%|%|%|%'
+            "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_sys_login_log_domain_province")
+                    .table(SysLoginLog::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysLoginLog::Table)
+                    .drop_column(SysLoginLog::Country)
+                    .drop_column(SysLoginLog::Region)
+                    .drop_column(SysLoginLog::Province)
+                    .drop_column(SysLoginLog::City)
+                    .drop_column(SysLoginLog::Isp)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysLoginLog {
+    Table,
+    Domain,
+    Country,
+    Region,
+    Province,
+    City,
+    Isp,
+}
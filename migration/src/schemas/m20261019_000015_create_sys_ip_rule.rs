@@ -0,0 +1,113 @@
+use sea_orm::Iterable;
+use sea_orm_migration::prelude::*;
+
+use super::m20240815_082808_create_enum_status::Status;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysIpRule::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SysIpRule::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SysIpRule::Scope)
+                            .string()
+                            .not_null()
+                            .comment("作用范围: global/domain/access_key"),
+                    )
+                    .col(
+                        ColumnDef::new(SysIpRule::ScopeValue)
+                            .string()
+                            .null()
+                            .comment("域编码或访问密钥ID"),
+                    )
+                    .col(
+                        ColumnDef::new(SysIpRule::Action)
+                            .string()
+                            .not_null()
+                            .comment("规则动作: allow/deny"),
+                    )
+                    .col(
+                        ColumnDef::new(SysIpRule::Cidr)
+                            .string()
+                            .null()
+                            .comment("IP 网段"),
+                    )
+                    .col(
+                        ColumnDef::new(SysIpRule::Country)
+                            .string()
+                            .null()
+                            .comment("国家"),
+                    )
+                    .col(
+                        ColumnDef::new(SysIpRule::Province)
+                            .string()
+                            .null()
+                            .comment("省份"),
+                    )
+                    .col(
+                        ColumnDef::new(SysIpRule::Status)
+                            .enumeration(Alias::new("status"), Status::iter())
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(SysIpRule::Description).string().null())
+                    .col(
+                        ColumnDef::new(SysIpRule::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(SysIpRule::CreatedBy).string().not_null())
+                    .col(ColumnDef::new(SysIpRule::UpdatedAt).timestamp().null())
+                    .col(ColumnDef::new(SysIpRule::UpdatedBy).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(SysIpRule::Table)
+                    .name("idx_sys_ip_rule_scope")
+                    .col(SysIpRule::Scope)
+                    .col(SysIpRule::ScopeValue)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SysIpRule::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysIpRule {
+    Table,
+    Id,
+    Scope,
+    ScopeValue,
+    Action,
+    Cidr,
+    Country,
+    Province,
+    Status,
+    Description,
+    CreatedAt,
+    CreatedBy,
+    UpdatedAt,
+    UpdatedBy,
+}
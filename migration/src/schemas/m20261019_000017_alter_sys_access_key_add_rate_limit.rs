@@ -0,0 +1,48 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysAccessKey::Table)
+                    .add_column(
+                        ColumnDef::new(SysAccessKey::RateLimit)
+                            .integer()
+                            .null()
+                            .comment("限流窗口内允许的请求数，为空表示不限流"),
+                    )
+                    .add_column(
+                        ColumnDef::new(SysAccessKey::RateLimitWindowSecs)
+                            .integer()
+                            .null()
+                            .comment("限流窗口长度（秒）"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysAccessKey::Table)
+                    .drop_column(SysAccessKey::RateLimit)
+                    .drop_column(SysAccessKey::RateLimitWindowSecs)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysAccessKey {
+    Table,
+    RateLimit,
+    RateLimitWindowSecs,
+}
@@ -0,0 +1,90 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysAccessKey::Table)
+                    .add_column(
+                        ColumnDef::new(SysAccessKey::Scopes)
+                            .json_binary()
+                            .null()
+                            .comment("可访问范围，接口 ID 或请求方法，为空表示不限制"),
+                    )
+                    .add_column(
+                        ColumnDef::new(SysAccessKey::AllowedIps)
+                            .json_binary()
+                            .null()
+                            .comment("允许的来源 IP 网段，为空表示不限制"),
+                    )
+                    .add_column(
+                        ColumnDef::new(SysAccessKey::ExpiresAt)
+                            .timestamp()
+                            .null()
+                            .comment("过期时间"),
+                    )
+                    .add_column(
+                        ColumnDef::new(SysAccessKey::LastUsedAt)
+                            .timestamp()
+                            .null()
+                            .comment("最后使用时间"),
+                    )
+                    .add_column(
+                        ColumnDef::new(SysAccessKey::LastUsedIp)
+                            .text()
+                            .null()
+                            .comment("最后使用的 IP"),
+                    )
+                    .add_column(
+                        ColumnDef::new(SysAccessKey::UsageCount)
+                            .big_integer()
+                            .not_null()
+                            .default(0)
+                            .comment("通过校验的调用次数"),
+                    )
+                    .add_column(
+                        ColumnDef::new(SysAccessKey::RejectedCount)
+                            .big_integer()
+                            .not_null()
+                            .default(0)
+                            .comment("被访问策略拒绝的次数"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysAccessKey::Table)
+                    .drop_column(SysAccessKey::Scopes)
+                    .drop_column(SysAccessKey::AllowedIps)
+                    .drop_column(SysAccessKey::ExpiresAt)
+                    .drop_column(SysAccessKey::LastUsedAt)
+                    .drop_column(SysAccessKey::LastUsedIp)
+                    .drop_column(SysAccessKey::UsageCount)
+                    .drop_column(SysAccessKey::RejectedCount)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysAccessKey {
+    Table,
+    Scopes,
+    AllowedIps,
+    ExpiresAt,
+    LastUsedAt,
+    LastUsedIp,
+    UsageCount,
+    RejectedCount,
+}
@@ -0,0 +1,68 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 密钥改为加密保存，每次加密的密文都不同，唯一约束不再有意义；
+        // 已有的明文密钥在服务启动时按配置的主密钥加密
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE sys_access_key DROP CONSTRAINT IF EXISTS \
+                 sys_access_key_access_key_secret_key",
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysAccessKey::Table)
+                    .add_column(
+                        ColumnDef::new(SysAccessKey::PreviousSecret)
+                            .text()
+                            .null()
+                            .comment("轮换前的密钥（加密），重叠期内仍然有效"),
+                    )
+                    .add_column(
+                        ColumnDef::new(SysAccessKey::PreviousSecretExpiresAt)
+                            .timestamp()
+                            .null()
+                            .comment("轮换前密钥的失效时间"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysAccessKey::Table)
+                    .drop_column(SysAccessKey::PreviousSecret)
+                    .drop_column(SysAccessKey::PreviousSecretExpiresAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE sys_access_key ADD CONSTRAINT \
+                 sys_access_key_access_key_secret_key UNIQUE (access_key_secret)",
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysAccessKey {
+    Table,
+    PreviousSecret,
+    PreviousSecretExpiresAt,
+}
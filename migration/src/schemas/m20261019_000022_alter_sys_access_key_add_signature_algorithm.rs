@@ -0,0 +1,43 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 已有的密钥沿用此前全局默认的 MD5 签名，避免客户端失效；
+        // `security.access_key.allow_legacy_algorithms` 默认开启，关闭前须将客户端迁移到新算法
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysAccessKey::Table)
+                    .add_column(
+                        ColumnDef::new(SysAccessKey::SignatureAlgorithm)
+                            .string_len(20)
+                            .not_null()
+                            .default("md5")
+                            .comment("签名算法"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysAccessKey::Table)
+                    .drop_column(SysAccessKey::SignatureAlgorithm)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysAccessKey {
+    Table,
+    SignatureAlgorithm,
+}
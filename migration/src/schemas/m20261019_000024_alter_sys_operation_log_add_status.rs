@@ -0,0 +1,56 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysOperationLog::Table)
+                    .add_column(
+                        ColumnDef::new(SysOperationLog::Status)
+                            .integer()
+                            .not_null()
+                            .default(200)
+                            .comment("响应状态码，HTTP 状态为 2xx 时取响应体中的业务码"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 从已记录的响应体回填业务码
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                UPDATE sys_operation_log
+                SET status = (response ->> 'code')::integer
+                WHERE jsonb_typeof(response -> 'code') = 'number'
+                  AND (response ->> 'code') ~ '^[0-9]{1,5}$'
+            "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysOperationLog::Table)
+                    .drop_column(SysOperationLog::Status)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysOperationLog {
+    Table,
+    Status,
+}
@@ -0,0 +1,75 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 无法确定归属的已有条目归入内置域，仅内置域可见
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysRecycleBin::Table)
+                    .add_column(
+                        ColumnDef::new(SysRecycleBin::Domain)
+                            .string()
+                            .not_null()
+                            .default("built-in")
+                            .comment("所属域"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 已删除用户的条目按用户所在域回填
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                UPDATE sys_recycle_bin
+                SET domain = sys_user.domain
+                FROM sys_user
+                WHERE sys_recycle_bin.entity_type = 'user'
+                  AND sys_recycle_bin.entity_id = sys_user.id
+            "#,
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(SysRecycleBin::Table)
+                    .name("idx_sys_recycle_bin_domain")
+                    .col(SysRecycleBin::Domain)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .table(SysRecycleBin::Table)
+                    .name("idx_sys_recycle_bin_domain")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysRecycleBin::Table)
+                    .drop_column(SysRecycleBin::Domain)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysRecycleBin {
+    Table,
+    Domain,
+}
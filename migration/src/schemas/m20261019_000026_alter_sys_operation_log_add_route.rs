@@ -0,0 +1,54 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysOperationLog::Table)
+                    .add_column(
+                        ColumnDef::new(SysOperationLog::Route)
+                            .string()
+                            .not_null()
+                            .default("")
+                            .comment("匹配的路由模板，如 /user/:id"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 已有日志未记录路由模板，以去掉查询串的请求路径回填
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                UPDATE sys_operation_log
+                SET route = split_part(url, '?', 1)
+            "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysOperationLog::Table)
+                    .drop_column(SysOperationLog::Route)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysOperationLog {
+    Table,
+    Route,
+}
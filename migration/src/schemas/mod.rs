@@ -11,3 +11,20 @@ pub mod m20241023_091155_create_sys_organization;
 pub mod m20241023_091159_create_sys_role_menu;
 pub mod m20241023_091204_create_sys_tokens;
 pub mod m20241023_091210_create_sys_user_role;
+pub mod m20261019_000001_alter_sys_user_add_organization;
+pub mod m20261019_000004_alter_sys_tables_add_soft_delete;
+pub mod m20261019_000005_create_sys_recycle_bin;
+pub mod m20261019_000007_alter_sys_tables_add_version;
+pub mod m20261019_000008_alter_sys_operation_log_add_headers;
+pub mod m20261019_000011_alter_sys_login_log_add_risk;
+pub mod m20261019_000012_alter_sys_login_log_add_result;
+pub mod m20261019_000014_alter_sys_login_log_add_region;
+pub mod m20261019_000015_create_sys_ip_rule;
+pub mod m20261019_000017_alter_sys_access_key_add_rate_limit;
+pub mod m20261019_000018_alter_sys_access_key_add_policy;
+pub mod m20261019_000019_alter_sys_access_key_encrypt_secret;
+pub mod m20261019_000022_alter_sys_access_key_add_signature_algorithm;
+pub mod m20261019_000023_alter_sys_user_add_must_change_password;
+pub mod m20261019_000024_alter_sys_operation_log_add_status;
+pub mod m20261019_000025_alter_sys_recycle_bin_add_domain;
+pub mod m20261019_000026_alter_sys_operation_log_add_route;
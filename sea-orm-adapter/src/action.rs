@@ -68,8 +68,8 @@ pub(crate) async fn remove_policies<'conn, 'rule, C: ConnectionTrait>(
     Ok(true)
 }
 
-pub(crate) async fn remove_filtered_policy<'conn, 'rule, C: ConnectionTrait>(
-    conn: &'conn C,
+pub(crate) async fn remove_filtered_policy<'rule, C: ConnectionTrait>(
+    conn: &C,
     ptype: &'rule str,
     index_of_match_start: usize,
     rule: Rule<'rule>,
@@ -4,10 +4,12 @@ use axum::{
     extract::{Path, Query},
     Extension,
 };
-use server_core::web::{error::AppError, page::PaginatedData, res::Res, validator::ValidatedForm};
+use server_core::web::{
+    auth::User, error::AppError, page::PaginatedData, res::Res, validator::ValidatedForm,
+};
 use server_service::admin::{
-    AccessKeyPageRequest, CreateAccessKeyInput, SysAccessKeyModel, SysAccessKeyService,
-    TAccessKeyService,
+    AccessKeyPageRequest, AccessKeySecretOutput, CreateAccessKeyInput, SysAccessKeyModel,
+    SysAccessKeyService, TAccessKeyService, UpdateAccessKeyInput,
 };
 
 pub struct SysAccessKeyApi;
@@ -26,10 +28,28 @@ impl SysAccessKeyApi {
     pub async fn create_access_key(
         Extension(service): Extension<Arc<SysAccessKeyService>>,
         ValidatedForm(input): ValidatedForm<CreateAccessKeyInput>,
-    ) -> Result<Res<SysAccessKeyModel>, AppError> {
+    ) -> Result<Res<AccessKeySecretOutput>, AppError> {
         service.create_access_key(input).await.map(Res::new_data)
     }
 
+    pub async fn update_access_key(
+        Extension(service): Extension<Arc<SysAccessKeyService>>,
+        ValidatedForm(input): ValidatedForm<UpdateAccessKeyInput>,
+    ) -> Result<Res<SysAccessKeyModel>, AppError> {
+        service.update_access_key(input).await.map(Res::new_data)
+    }
+
+    pub async fn rotate_access_key(
+        Path(id): Path<String>,
+        Extension(user): Extension<User>,
+        Extension(service): Extension<Arc<SysAccessKeyService>>,
+    ) -> Result<Res<AccessKeySecretOutput>, AppError> {
+        service
+            .rotate_access_key(&id, user)
+            .await
+            .map(Res::new_data)
+    }
+
     pub async fn delete_access_key(
         Path(id): Path<String>,
         Extension(service): Extension<Arc<SysAccessKeyService>>,
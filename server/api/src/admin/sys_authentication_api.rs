@@ -9,8 +9,8 @@ use server_core::web::{
 use server_service::{
     admin::{
         dto::sys_auth_dto::LoginContext, AssignPermissionDto, AssignRouteDto, AuthOutput,
-        LoginInput, SysAuthService, SysAuthorizationService, TAuthService, TAuthorizationService,
-        UserInfoOutput, UserRoute,
+        ChangePasswordInput, LoginInput, SysAuthService, SysAuthorizationService, TAuthService,
+        TAuthorizationService, UserInfoOutput, UserRoute,
     },
     Audience,
 };
@@ -26,33 +26,63 @@ impl SysAuthenticationApi {
         Extension(service): Extension<Arc<SysAuthService>>,
         ValidatedForm(input): ValidatedForm<LoginInput>,
     ) -> Result<Res<AuthOutput>, AppError> {
-        let client_ip = {
-            let header_ip = ClientIp::get_real_ip(&headers);
-            if header_ip == "unknown" {
-                addr.ip().to_string()
-            } else {
-                header_ip
-            }
-        };
+        let login_context =
+            Self::login_context(addr, &headers, &user_agent, &request_id, &input.domain);
+
+        service
+            .pwd_login(input, login_context)
+            .await
+            .map(Res::new_data)
+    }
+
+    /// 修改密码
+    ///
+    /// 无需登录，通过当前密码校验身份；租户开通时生成的初始密码必须经此修改后才能登录。
+    /// 租户管理员在请求中传入所在域的编码，登录时同样如此。
+    pub async fn change_password(
+        ConnectInfo(addr): ConnectInfo<SocketAddr>,
+        headers: HeaderMap,
+        TypedHeader(user_agent): TypedHeader<UserAgent>,
+        Extension(request_id): Extension<RequestId>,
+        Extension(service): Extension<Arc<SysAuthService>>,
+        ValidatedForm(input): ValidatedForm<ChangePasswordInput>,
+    ) -> Result<Res<()>, AppError> {
+        let login_context =
+            Self::login_context(addr, &headers, &user_agent, &request_id, &input.domain);
+
+        service
+            .change_password(input, login_context)
+            .await
+            .map(Res::new_data)
+    }
+
+    fn login_context(
+        addr: SocketAddr,
+        headers: &HeaderMap,
+        user_agent: &UserAgent,
+        request_id: &RequestId,
+        domain: &str,
+    ) -> LoginContext {
+        let client_ip = ClientIp::resolve(Some(addr.ip()), headers);
 
-        let address = xdb::searcher::search_by_ip(client_ip.as_str())
-            .unwrap_or_else(|_| "Unknown Location".to_string());
+        let region = xdb::search_region(client_ip.as_str()).unwrap_or_default();
+        let address = if region.is_unknown() {
+            "Unknown Location".to_string()
+        } else {
+            region.to_string()
+        };
 
-        let login_context = LoginContext {
+        LoginContext {
             client_ip,
             client_port: Some(addr.port() as i32),
             address,
+            region,
             user_agent: user_agent.as_str().to_string(),
             request_id: request_id.to_string(),
             audience: Audience::ManagementPlatform,
             login_type: "PC".to_string(),
-            domain: "built-in".to_string(),
-        };
-
-        service
-            .pwd_login(input, login_context)
-            .await
-            .map(Res::new_data)
+            domain: domain.to_string(),
+        }
     }
 
     pub async fn get_user_info(
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use axum::{extract::Query, Extension};
+use server_core::web::{auth::User, error::AppError, res::Res};
+use server_service::admin::{
+    DashboardQuery, EndpointStat, LoginRegionStat, LoginStats, SysDashboardService,
+    TDashboardService,
+};
+
+pub struct SysDashboardApi;
+
+impl SysDashboardApi {
+    pub async fn get_login_stats(
+        Query(params): Query<DashboardQuery>,
+        Extension(user): Extension<User>,
+        Extension(service): Extension<Arc<SysDashboardService>>,
+    ) -> Result<Res<LoginStats>, AppError> {
+        service
+            .get_login_stats(&user.domain(), params)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn get_login_regions(
+        Query(params): Query<DashboardQuery>,
+        Extension(user): Extension<User>,
+        Extension(service): Extension<Arc<SysDashboardService>>,
+    ) -> Result<Res<Vec<LoginRegionStat>>, AppError> {
+        service
+            .get_login_regions(&user.domain(), params)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn get_endpoint_stats(
+        Query(params): Query<DashboardQuery>,
+        Extension(user): Extension<User>,
+        Extension(service): Extension<Arc<SysDashboardService>>,
+    ) -> Result<Res<Vec<EndpointStat>>, AppError> {
+        service
+            .get_endpoint_stats(&user.domain(), params)
+            .await
+            .map(Res::new_data)
+    }
+}
@@ -4,10 +4,18 @@ use axum::{
     extract::{Path, Query},
     Extension,
 };
-use server_core::web::{error::AppError, page::PaginatedData, res::Res, validator::ValidatedForm};
+use axum_casbin::CasbinAxumLayer;
+use server_core::web::{
+    auth::User,
+    error::AppError,
+    etag::{ETagged, IfMatch},
+    page::PaginatedData,
+    res::Res,
+    validator::ValidatedForm,
+};
 use server_service::admin::{
-    CreateDomainInput, DomainPageRequest, SysDomainModel, SysDomainService, TDomainService,
-    UpdateDomainInput,
+    CreateDomainInput, DeprovisionDomainInput, DomainPageRequest, ProvisionDomainInput,
+    ProvisionDomainOutput, SysDomainModel, SysDomainService, TDomainService, UpdateDomainInput,
 };
 
 pub struct SysDomainApi;
@@ -33,21 +41,51 @@ impl SysDomainApi {
     pub async fn get_domain(
         Path(id): Path<String>,
         Extension(service): Extension<Arc<SysDomainService>>,
-    ) -> Result<Res<SysDomainModel>, AppError> {
-        service.get_domain(&id).await.map(Res::new_data)
+    ) -> Result<ETagged<SysDomainModel>, AppError> {
+        service.get_domain(&id).await.map(ETagged)
     }
 
     pub async fn update_domain(
         Extension(service): Extension<Arc<SysDomainService>>,
-        ValidatedForm(input): ValidatedForm<UpdateDomainInput>,
-    ) -> Result<Res<SysDomainModel>, AppError> {
-        service.update_domain(input).await.map(Res::new_data)
+        IfMatch(if_match): IfMatch,
+        ValidatedForm(mut input): ValidatedForm<UpdateDomainInput>,
+    ) -> Result<ETagged<SysDomainModel>, AppError> {
+        input.version = if_match.or(input.version);
+        service.update_domain(input).await.map(ETagged)
     }
 
     pub async fn delete_domain(
         Path(id): Path<String>,
         Extension(service): Extension<Arc<SysDomainService>>,
+        Extension(user): Extension<User>,
     ) -> Result<Res<()>, AppError> {
-        service.delete_domain(&id).await.map(Res::new_data)
+        service.delete_domain(&id, user).await.map(Res::new_data)
+    }
+
+    pub async fn provision_domain(
+        Extension(service): Extension<Arc<SysDomainService>>,
+        Extension(user): Extension<User>,
+        Extension(mut cache_enforcer): Extension<CasbinAxumLayer>,
+        ValidatedForm(input): ValidatedForm<ProvisionDomainInput>,
+    ) -> Result<Res<ProvisionDomainOutput>, AppError> {
+        let enforcer = cache_enforcer.get_enforcer();
+        service
+            .provision_domain(input, user, enforcer)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn deprovision_domain(
+        Path(id): Path<String>,
+        Query(input): Query<DeprovisionDomainInput>,
+        Extension(service): Extension<Arc<SysDomainService>>,
+        Extension(user): Extension<User>,
+        Extension(mut cache_enforcer): Extension<CasbinAxumLayer>,
+    ) -> Result<Res<()>, AppError> {
+        let enforcer = cache_enforcer.get_enforcer();
+        service
+            .deprovision_domain(&id, input, user, enforcer)
+            .await
+            .map(Res::new_data)
     }
 }
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query},
+    Extension,
+};
+use server_core::web::{
+    auth::User, error::AppError, page::PaginatedData, res::Res, validator::ValidatedForm,
+};
+use server_service::admin::{
+    CreateIpRuleInput, IpRulePageRequest, SysIpRuleModel, SysIpRuleService, TIpRuleService,
+    UpdateIpRuleInput,
+};
+
+pub struct SysIpRuleApi;
+
+impl SysIpRuleApi {
+    pub async fn get_paginated_ip_rules(
+        Query(params): Query<IpRulePageRequest>,
+        Extension(service): Extension<Arc<SysIpRuleService>>,
+        Extension(user): Extension<User>,
+    ) -> Result<Res<PaginatedData<SysIpRuleModel>>, AppError> {
+        service
+            .find_paginated_ip_rules(params, &user.domain())
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn create_ip_rule(
+        Extension(service): Extension<Arc<SysIpRuleService>>,
+        Extension(user): Extension<User>,
+        ValidatedForm(input): ValidatedForm<CreateIpRuleInput>,
+    ) -> Result<Res<SysIpRuleModel>, AppError> {
+        service.create_ip_rule(input, user).await.map(Res::new_data)
+    }
+
+    pub async fn update_ip_rule(
+        Extension(service): Extension<Arc<SysIpRuleService>>,
+        Extension(user): Extension<User>,
+        ValidatedForm(input): ValidatedForm<UpdateIpRuleInput>,
+    ) -> Result<Res<SysIpRuleModel>, AppError> {
+        service.update_ip_rule(input, user).await.map(Res::new_data)
+    }
+
+    pub async fn delete_ip_rule(
+        Path(id): Path<String>,
+        Extension(service): Extension<Arc<SysIpRuleService>>,
+        Extension(user): Extension<User>,
+    ) -> Result<Res<()>, AppError> {
+        service
+            .delete_ip_rule(&id, &user.domain())
+            .await
+            .map(Res::new_data)
+    }
+}
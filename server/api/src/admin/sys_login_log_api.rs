@@ -1,21 +1,54 @@
 use std::sync::Arc;
 
-use axum::extract::{Extension, Query};
-use server_core::web::{error::AppError, page::PaginatedData, res::Res};
+use axum::{
+    extract::{Extension, Query},
+    response::Response,
+};
+use server_core::web::{
+    auth::User,
+    error::AppError,
+    page::{CursorPage, PaginatedData},
+    res::Res,
+};
 use server_service::admin::{
-    LoginLogPageRequest, SysLoginLogModel, SysLoginLogService, TLoginLogService,
+    LogExportRequest, LoginLogCursorRequest, LoginLogPageRequest, SysLoginLogModel,
+    SysLoginLogService, TLoginLogService,
 };
 
+use super::sys_operation_log_api::export_response;
+
 pub struct SysLoginLogApi;
 
 impl SysLoginLogApi {
     pub async fn get_paginated_login_logs(
         Query(params): Query<LoginLogPageRequest>,
+        Extension(user): Extension<User>,
         Extension(service): Extension<Arc<SysLoginLogService>>,
     ) -> Result<Res<PaginatedData<SysLoginLogModel>>, AppError> {
         service
-            .find_paginated_login_logs(params)
+            .find_paginated_login_logs(params, &user.domain())
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn get_cursor_login_logs(
+        Query(params): Query<LoginLogCursorRequest>,
+        Extension(user): Extension<User>,
+        Extension(service): Extension<Arc<SysLoginLogService>>,
+    ) -> Result<Res<CursorPage<SysLoginLogModel>>, AppError> {
+        service
+            .find_cursor_login_logs(params, &user.domain())
             .await
             .map(Res::new_data)
     }
+
+    pub async fn export_login_logs(
+        Query(params): Query<LogExportRequest>,
+        Extension(user): Extension<User>,
+        Extension(service): Extension<Arc<SysLoginLogService>>,
+    ) -> Result<Response, AppError> {
+        let format = params.format;
+        let stream = service.export_login_logs(params, &user.domain()).await?;
+        Ok(export_response("login-log", format, stream))
+    }
 }
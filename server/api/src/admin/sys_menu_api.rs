@@ -1,7 +1,13 @@
 use std::sync::Arc;
 
 use axum::{extract::Path, Extension};
-use server_core::web::{auth::User, error::AppError, res::Res, validator::ValidatedForm};
+use server_core::web::{
+    auth::User,
+    error::AppError,
+    etag::{ETagged, IfMatch},
+    res::Res,
+    validator::ValidatedForm,
+};
 use server_service::admin::{
     CreateMenuInput, MenuRoute, MenuTree, SysMenuModel, SysMenuService, TMenuService,
     UpdateMenuInput,
@@ -39,16 +45,18 @@ impl SysMenuApi {
     pub async fn get_menu(
         Path(id): Path<i32>,
         Extension(service): Extension<Arc<SysMenuService>>,
-    ) -> Result<Res<SysMenuModel>, AppError> {
-        service.get_menu(id).await.map(Res::new_data)
+    ) -> Result<ETagged<SysMenuModel>, AppError> {
+        service.get_menu(id).await.map(ETagged)
     }
 
     pub async fn update_menu(
         Extension(service): Extension<Arc<SysMenuService>>,
         Extension(user): Extension<User>,
-        ValidatedForm(input): ValidatedForm<UpdateMenuInput>,
-    ) -> Result<Res<SysMenuModel>, AppError> {
-        service.update_menu(input, user).await.map(Res::new_data)
+        IfMatch(if_match): IfMatch,
+        ValidatedForm(mut input): ValidatedForm<UpdateMenuInput>,
+    ) -> Result<ETagged<SysMenuModel>, AppError> {
+        input.version = if_match.or(input.version);
+        service.update_menu(input, user).await.map(ETagged)
     }
 
     pub async fn delete_menu(
@@ -1,9 +1,22 @@
 use std::sync::Arc;
 
-use axum::extract::{Extension, Query};
-use server_core::web::{error::AppError, page::PaginatedData, res::Res};
+use axum::{
+    body::Body,
+    extract::{Extension, Query},
+    http::header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    response::{IntoResponse, Response},
+};
+use chrono::Local;
+use futures::{stream::BoxStream, StreamExt};
+use server_core::web::{
+    auth::User,
+    error::AppError,
+    page::{CursorPage, PaginatedData},
+    res::Res,
+};
 use server_service::admin::{
-    OperationLogPageRequest, SysOperationLogModel, SysOperationLogService, TOperationLogService,
+    ExportFormat, LogExportRequest, OperationLogCursorRequest, OperationLogPageRequest,
+    SysOperationLogModel, SysOperationLogService, TOperationLogService,
 };
 
 pub struct SysOperationLogApi;
@@ -11,11 +24,70 @@ pub struct SysOperationLogApi;
 impl SysOperationLogApi {
     pub async fn get_paginated_operation_logs(
         Query(params): Query<OperationLogPageRequest>,
+        Extension(user): Extension<User>,
         Extension(service): Extension<Arc<SysOperationLogService>>,
     ) -> Result<Res<PaginatedData<SysOperationLogModel>>, AppError> {
         service
-            .find_paginated_operation_logs(params)
+            .find_paginated_operation_logs(params, &user.domain())
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn get_cursor_operation_logs(
+        Query(params): Query<OperationLogCursorRequest>,
+        Extension(user): Extension<User>,
+        Extension(service): Extension<Arc<SysOperationLogService>>,
+    ) -> Result<Res<CursorPage<SysOperationLogModel>>, AppError> {
+        service
+            .find_cursor_operation_logs(params, &user.domain())
             .await
             .map(Res::new_data)
     }
+
+    pub async fn export_operation_logs(
+        Query(params): Query<LogExportRequest>,
+        Extension(user): Extension<User>,
+        Extension(service): Extension<Arc<SysOperationLogService>>,
+    ) -> Result<Response, AppError> {
+        let format = params.format;
+        let stream = service
+            .export_operation_logs(params, &user.domain())
+            .await?;
+        Ok(export_response("operation-log", format, stream))
+    }
+}
+
+/// 以附件形式流式返回导出内容
+///
+/// 导出中途出错时中断响应流，客户端会收到不完整的文件。
+pub(crate) fn export_response(
+    name: &str,
+    format: ExportFormat,
+    stream: BoxStream<'static, Result<String, AppError>>,
+) -> Response {
+    let (content_type, extension) = match format {
+        ExportFormat::Csv => ("text/csv; charset=utf-8", "csv"),
+        ExportFormat::Ndjson => ("application/x-ndjson", "ndjson"),
+    };
+    let filename = format!(
+        "{}-{}.{}",
+        name,
+        Local::now().format("%Y%m%d%H%M%S"),
+        extension
+    );
+
+    let body =
+        Body::from_stream(stream.map(|chunk| chunk.map_err(|e| std::io::Error::other(e.message))));
+
+    (
+        [
+            (CONTENT_TYPE, content_type.to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
 }
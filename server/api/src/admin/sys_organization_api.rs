@@ -1,9 +1,20 @@
 use std::sync::Arc;
 
-use axum::extract::{Extension, Query};
-use server_core::web::{error::AppError, page::PaginatedData, res::Res};
+use axum::{
+    extract::{Path, Query},
+    Extension,
+};
+use server_core::web::{
+    auth::User,
+    error::AppError,
+    etag::{ETagged, IfMatch},
+    page::PaginatedData,
+    res::Res,
+    validator::ValidatedForm,
+};
 use server_service::admin::{
-    OrganizationPageRequest, SysOrganizationModel, SysOrganizationService, TOrganizationService,
+    CreateOrganizationInput, MoveOrganizationInput, OrganizationPageRequest, OrganizationTree,
+    SysOrganizationModel, SysOrganizationService, TOrganizationService, UpdateOrganizationInput,
 };
 
 pub struct SysOrganizationApi;
@@ -18,4 +29,59 @@ impl SysOrganizationApi {
             .await
             .map(Res::new_data)
     }
+
+    pub async fn tree_organization(
+        Extension(service): Extension<Arc<SysOrganizationService>>,
+    ) -> Result<Res<Vec<OrganizationTree>>, AppError> {
+        service.tree_organization().await.map(Res::new_data)
+    }
+
+    pub async fn create_organization(
+        Extension(service): Extension<Arc<SysOrganizationService>>,
+        Extension(user): Extension<User>,
+        ValidatedForm(input): ValidatedForm<CreateOrganizationInput>,
+    ) -> Result<Res<SysOrganizationModel>, AppError> {
+        service
+            .create_organization(input, user)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn get_organization(
+        Path(id): Path<String>,
+        Extension(service): Extension<Arc<SysOrganizationService>>,
+    ) -> Result<ETagged<SysOrganizationModel>, AppError> {
+        service.get_organization(&id).await.map(ETagged)
+    }
+
+    pub async fn update_organization(
+        Extension(service): Extension<Arc<SysOrganizationService>>,
+        Extension(user): Extension<User>,
+        IfMatch(if_match): IfMatch,
+        ValidatedForm(mut input): ValidatedForm<UpdateOrganizationInput>,
+    ) -> Result<ETagged<SysOrganizationModel>, AppError> {
+        input.version = if_match.or(input.version);
+        service.update_organization(input, user).await.map(ETagged)
+    }
+
+    pub async fn move_organization(
+        Extension(service): Extension<Arc<SysOrganizationService>>,
+        Extension(user): Extension<User>,
+        IfMatch(if_match): IfMatch,
+        ValidatedForm(mut input): ValidatedForm<MoveOrganizationInput>,
+    ) -> Result<ETagged<SysOrganizationModel>, AppError> {
+        input.version = if_match.or(input.version);
+        service.move_organization(input, user).await.map(ETagged)
+    }
+
+    pub async fn delete_organization(
+        Path(id): Path<String>,
+        Extension(service): Extension<Arc<SysOrganizationService>>,
+        Extension(user): Extension<User>,
+    ) -> Result<Res<()>, AppError> {
+        service
+            .delete_organization(&id, user)
+            .await
+            .map(Res::new_data)
+    }
 }
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query},
+    Extension,
+};
+use axum_casbin::CasbinAxumLayer;
+use server_core::web::{auth::User, error::AppError, page::PaginatedData, res::Res};
+use server_service::admin::{
+    PurgeRecycleBinInput, RecycleBinPageRequest, SysRecycleBinModel, SysRecycleBinService,
+    TRecycleBinService,
+};
+
+pub struct SysRecycleBinApi;
+
+impl SysRecycleBinApi {
+    pub async fn get_paginated_entries(
+        Query(params): Query<RecycleBinPageRequest>,
+        Extension(user): Extension<User>,
+        Extension(service): Extension<Arc<SysRecycleBinService>>,
+    ) -> Result<Res<PaginatedData<SysRecycleBinModel>>, AppError> {
+        service
+            .find_paginated_entries(params, user)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn restore_entry(
+        Path(id): Path<String>,
+        Extension(user): Extension<User>,
+        Extension(service): Extension<Arc<SysRecycleBinService>>,
+        Extension(mut cache_enforcer): Extension<CasbinAxumLayer>,
+    ) -> Result<Res<()>, AppError> {
+        let enforcer = cache_enforcer.get_enforcer();
+        service
+            .restore_entry(&id, user, enforcer)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn purge_entry(
+        Path(id): Path<String>,
+        Extension(user): Extension<User>,
+        Extension(service): Extension<Arc<SysRecycleBinService>>,
+        Extension(mut cache_enforcer): Extension<CasbinAxumLayer>,
+    ) -> Result<Res<()>, AppError> {
+        let enforcer = cache_enforcer.get_enforcer();
+        service
+            .purge_entry(&id, user, enforcer)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn purge_expired(
+        Query(input): Query<PurgeRecycleBinInput>,
+        Extension(user): Extension<User>,
+        Extension(service): Extension<Arc<SysRecycleBinService>>,
+        Extension(mut cache_enforcer): Extension<CasbinAxumLayer>,
+    ) -> Result<Res<u64>, AppError> {
+        let enforcer = cache_enforcer.get_enforcer();
+        service
+            .purge_expired(input, user, enforcer)
+            .await
+            .map(Res::new_data)
+    }
+}
@@ -4,7 +4,15 @@ use axum::{
     extract::{Path, Query},
     Extension,
 };
-use server_core::web::{error::AppError, page::PaginatedData, res::Res, validator::ValidatedForm};
+use axum_casbin::CasbinAxumLayer;
+use server_core::web::{
+    auth::User,
+    error::AppError,
+    etag::{ETagged, IfMatch},
+    page::PaginatedData,
+    res::Res,
+    validator::ValidatedForm,
+};
 use server_service::admin::{
     CreateRoleInput, RolePageRequest, SysRoleModel, SysRoleService, TRoleService, UpdateRoleInput,
 };
@@ -32,21 +40,29 @@ impl SysRoleApi {
     pub async fn get_role(
         Path(id): Path<String>,
         Extension(service): Extension<Arc<SysRoleService>>,
-    ) -> Result<Res<SysRoleModel>, AppError> {
-        service.get_role(&id).await.map(Res::new_data)
+    ) -> Result<ETagged<SysRoleModel>, AppError> {
+        service.get_role(&id).await.map(ETagged)
     }
 
     pub async fn update_role(
         Extension(service): Extension<Arc<SysRoleService>>,
-        ValidatedForm(input): ValidatedForm<UpdateRoleInput>,
-    ) -> Result<Res<SysRoleModel>, AppError> {
-        service.update_role(input).await.map(Res::new_data)
+        IfMatch(if_match): IfMatch,
+        ValidatedForm(mut input): ValidatedForm<UpdateRoleInput>,
+    ) -> Result<ETagged<SysRoleModel>, AppError> {
+        input.version = if_match.or(input.version);
+        service.update_role(input).await.map(ETagged)
     }
 
     pub async fn delete_role(
         Path(id): Path<String>,
         Extension(service): Extension<Arc<SysRoleService>>,
+        Extension(user): Extension<User>,
+        Extension(mut cache_enforcer): Extension<CasbinAxumLayer>,
     ) -> Result<Res<()>, AppError> {
-        service.delete_role(&id).await.map(Res::new_data)
+        let enforcer = cache_enforcer.get_enforcer();
+        service
+            .delete_role(&id, user, enforcer)
+            .await
+            .map(Res::new_data)
     }
 }
@@ -6,7 +6,12 @@ use axum::{
 };
 use axum_casbin::{casbin::MgmtApi, CasbinAxumLayer};
 use server_core::web::{
-    auth::User, error::AppError, page::PaginatedData, res::Res, validator::ValidatedForm,
+    auth::User,
+    error::AppError,
+    etag::{ETagged, IfMatch},
+    page::PaginatedData,
+    res::Res,
+    validator::ValidatedForm,
 };
 use server_service::admin::{
     CreateUserInput, SysUserService, TUserService, UpdateUserInput, UserPageRequest,
@@ -74,21 +79,24 @@ impl SysUserApi {
     pub async fn get_user(
         Path(id): Path<String>,
         Extension(service): Extension<Arc<SysUserService>>,
-    ) -> Result<Res<UserWithoutPassword>, AppError> {
-        service.get_user(&id).await.map(Res::new_data)
+    ) -> Result<ETagged<UserWithoutPassword>, AppError> {
+        service.get_user(&id).await.map(ETagged)
     }
 
     pub async fn update_user(
         Extension(service): Extension<Arc<SysUserService>>,
-        ValidatedForm(input): ValidatedForm<UpdateUserInput>,
-    ) -> Result<Res<UserWithoutPassword>, AppError> {
-        service.update_user(input).await.map(Res::new_data)
+        IfMatch(if_match): IfMatch,
+        ValidatedForm(mut input): ValidatedForm<UpdateUserInput>,
+    ) -> Result<ETagged<UserWithoutPassword>, AppError> {
+        input.version = if_match.or(input.version);
+        service.update_user(input).await.map(ETagged)
     }
 
     pub async fn delete_user(
         Path(id): Path<String>,
         Extension(service): Extension<Arc<SysUserService>>,
+        Extension(user): Extension<User>,
     ) -> Result<Res<()>, AppError> {
-        service.delete_user(&id).await.map(Res::new_data)
+        service.delete_user(&id, user).await.map(Res::new_data)
     }
 }
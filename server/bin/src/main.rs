@@ -15,6 +15,7 @@ async fn main() {
     // 使用多实例环境变量优先的配置加载方式
     // 支持单个配置项和多实例配置的环境变量覆盖
     server_initialize::initialize_config_with_multi_instance_env(config_path, None).await;
+    server_initialize::initialize_client_ip_resolver().await;
     let _ = server_initialize::init_xdb().await;
     server_initialize::init_primary_connection().await;
     server_initialize::init_db_pools().await;
@@ -25,12 +26,18 @@ async fn main() {
     server_initialize::init_redis_pools().await;
     server_initialize::init_primary_mongo().await;
     server_initialize::init_mongo_pools().await;
+    server_initialize::init_primary_s3().await;
+    server_initialize::init_s3_pools().await;
+    server_initialize::initialize_audit_retention().await;
+    server_initialize::initialize_operation_log_writer().await;
 
     // build our application with a route
     let app = server_initialize::initialize_admin_router().await;
 
     //需要初始化验证器init_validators之后才能初始化访问密钥
     server_initialize::initialize_access_key().await;
+    server_initialize::initialize_ip_rules().await;
+    server_initialize::initialize_token_revocation().await;
 
     let addr = match server_initialize::get_server_address().await {
         Ok(addr) => addr,
@@ -47,6 +54,40 @@ async fn main() {
         listener,
         app.into_make_service_with_connect_info::<SocketAddr>(),
     )
+    .with_graceful_shutdown(shutdown_signal())
     .await
     .unwrap();
+
+    server_initialize::flush_operation_logs().await;
+    server_initialize::flush_access_key_usage().await;
+}
+
+/// 收到 Ctrl+C 或 SIGTERM 后停止接收新连接，等待进行中的请求完成
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            eprintln!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            },
+            Err(e) => {
+                eprintln!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            },
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
 }
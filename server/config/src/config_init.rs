@@ -7,9 +7,9 @@ use crate::{
     env_config::{load_config_with_env, EnvConfigLoader},
     model::{Config, OptionalConfigs},
     multi_instance_env::MultiInstanceEnvProcessor,
-    project_error, project_info, DatabaseConfig, DatabasesInstancesConfig, JwtConfig, MongoConfig,
-    MongoInstancesConfig, RedisConfig, RedisInstancesConfig, S3Config, S3InstancesConfig,
-    ServerConfig,
+    project_error, project_info, AuditConfig, DatabaseConfig, DatabasesInstancesConfig, JwtConfig,
+    MongoConfig, MongoInstancesConfig, RateLimitConfig, RecycleBinConfig, RedisConfig,
+    RedisInstancesConfig, S3Config, S3InstancesConfig, SecurityConfig, ServerConfig,
 };
 
 #[derive(Debug, Error)]
@@ -82,6 +82,14 @@ pub async fn init_from_file(file_path: &str) -> Result<(), ConfigError> {
     }
     global::init_config::<OptionalConfigs<S3InstancesConfig>>(config.s3_instances.into()).await;
 
+    global::init_config::<AuditConfig>(config.audit).await;
+
+    global::init_config::<SecurityConfig>(config.security).await;
+
+    global::init_config::<RateLimitConfig>(config.rate_limit).await;
+
+    global::init_config::<RecycleBinConfig>(config.recycle_bin).await;
+
     project_info!("Configuration initialized successfully");
     Ok(())
 }
@@ -423,6 +431,14 @@ async fn init_global_config(config: Config) {
         global::init_config::<S3Config>(s3_config).await;
     }
     global::init_config::<OptionalConfigs<S3InstancesConfig>>(config.s3_instances.into()).await;
+
+    global::init_config::<AuditConfig>(config.audit).await;
+
+    global::init_config::<SecurityConfig>(config.security).await;
+
+    global::init_config::<RateLimitConfig>(config.rate_limit).await;
+
+    global::init_config::<RecycleBinConfig>(config.recycle_bin).await;
 }
 
 #[cfg(test)]
@@ -4,8 +4,13 @@ pub use config_init::{
 };
 pub use env_config::{load_config_from_env, load_config_with_env, EnvConfigLoader};
 pub use model::{
-    Config, DatabaseConfig, DatabasesInstancesConfig, JwtConfig, MongoConfig, MongoInstancesConfig,
-    OptionalConfigs, RedisConfig, RedisInstancesConfig, RedisMode, S3Config, S3InstancesConfig,
+    AccessKeySecurityConfig, ArchiveConfig, AuditConfig, CaptureConfig, ClientIpHeader, Config,
+    DatabaseConfig, DatabasesInstancesConfig, FileSinkConfig, JwtConfig, LogRetentionPolicy,
+    LoginRiskConfig, LoginRiskWeights, MongoConfig, MongoInstancesConfig, MongoSinkConfig,
+    OperationLogSinkConfig, OperationLogSinkKind, OptionalConfigs, RateLimitAlgorithm,
+    RateLimitBackend, RateLimitConfig, RateLimitKey, RecycleBinConfig, RedactStrategy,
+    RedactionConfig, RedactionRule, RedisConfig, RedisInstancesConfig, RedisMode, RetentionConfig,
+    RetiredEncryptionKey, RouteRateLimit, S3Config, S3InstancesConfig, SecurityConfig,
     ServerConfig,
 };
 pub use server_global::{project_error, project_info};
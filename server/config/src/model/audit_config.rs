@@ -0,0 +1,420 @@
+use std::env;
+
+use serde::Deserialize;
+
+/// 脱敏摘要密钥的环境变量
+const REDACTION_HASH_KEY_ENV: &str = "APP_AUDIT_REDACTION_HASH_KEY";
+
+/// 审计日志配置
+///
+/// 支持的环境变量：
+/// - APP_AUDIT_REDACTION_ENABLED: 是否启用敏感字段脱敏
+/// - APP_AUDIT_REDACTION_STRATEGY: 默认脱敏策略 (mask/hash/drop)
+/// - APP_AUDIT_REDACTION_HASH_KEY: `hash` 策略使用的 HMAC 密钥
+/// - APP_AUDIT_SINK_KIND: 操作日志写入目标 (database/mongo/file)
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct AuditConfig {
+    /// 敏感字段脱敏配置
+    #[serde(default)]
+    pub redaction: RedactionConfig,
+
+    /// 操作日志写入配置
+    #[serde(default)]
+    pub sink: OperationLogSinkConfig,
+
+    /// 请求与响应体采集配置
+    #[serde(default)]
+    pub capture: CaptureConfig,
+
+    /// 日志保留策略
+    #[serde(default)]
+    pub retention: RetentionConfig,
+}
+
+/// 日志保留策略
+///
+/// 后台任务按间隔清理超过保留天数的操作日志与登录日志，配置归档时先写入 S3 再删除。
+///
+/// # 示例配置（YAML）
+/// ```yaml
+/// audit:
+///   retention:
+///     interval_secs: 3600
+///     operation_log:
+///       max_age_days: 90
+///       archive:
+///         bucket: "audit-archive"
+///     login_log:
+///       max_age_days: 180
+/// ```
+#[derive(Deserialize, Debug, Clone)]
+pub struct RetentionConfig {
+    /// 清理任务执行间隔（秒）
+    #[serde(default = "default_retention_interval_secs")]
+    pub interval_secs: u64,
+
+    /// 单批清理条数
+    #[serde(default = "default_retention_batch_size")]
+    pub batch_size: u64,
+
+    /// 操作日志保留策略
+    #[serde(default)]
+    pub operation_log: LogRetentionPolicy,
+
+    /// 登录日志保留策略
+    #[serde(default)]
+    pub login_log: LogRetentionPolicy,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: default_retention_interval_secs(),
+            batch_size: default_retention_batch_size(),
+            operation_log: LogRetentionPolicy::default(),
+            login_log: LogRetentionPolicy::default(),
+        }
+    }
+}
+
+/// 单类日志的保留策略
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct LogRetentionPolicy {
+    /// 最长保留天数，未配置时永久保留
+    pub max_age_days: Option<u32>,
+
+    /// 删除前归档到主 S3 客户端，未配置时直接删除
+    pub archive: Option<ArchiveConfig>,
+}
+
+/// S3 归档配置
+#[derive(Deserialize, Debug, Clone)]
+pub struct ArchiveConfig {
+    /// 存储桶名称
+    pub bucket: String,
+
+    /// 对象键前缀
+    #[serde(default = "default_archive_prefix")]
+    pub prefix: String,
+}
+
+/// 请求与响应体采集配置
+///
+/// 超出上限的部分不进入日志，只记录截断标记，原始数据流照常转发。
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct CaptureConfig {
+    /// 请求体最大采集字节数
+    #[serde(default = "default_max_body_bytes")]
+    pub max_request_body_bytes: usize,
+
+    /// 响应体最大采集字节数
+    #[serde(default = "default_max_body_bytes")]
+    pub max_response_body_bytes: usize,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            max_request_body_bytes: default_max_body_bytes(),
+            max_response_body_bytes: default_max_body_bytes(),
+        }
+    }
+}
+
+/// 操作日志写入配置
+///
+/// 日志先进入有界队列，按条数或时间间隔批量写入所选目标。队列满时请求最多等待
+/// `send_timeout_ms`，超时后丢弃该条日志并输出告警。写入失败的批次按退避重试
+/// `max_retries` 次，仍失败时追加到 `spill_path` 文件，避免丢失。停机时会写入队列中剩余的日志。
+///
+/// # 示例配置（YAML）
+/// ```yaml
+/// audit:
+///   sink:
+///     kind: mongo
+///     batch_size: 200
+///     flush_interval_ms: 500
+///     send_timeout_ms: 1000
+///     max_retries: 3
+///     spill_path: "logs/operation_log_spill.jsonl"
+///     mongo:
+///       database: "soybean_admin"
+///       collection: "sys_operation_log"
+/// ```
+#[derive(Deserialize, Debug, Clone)]
+pub struct OperationLogSinkConfig {
+    /// 写入目标
+    /// 环境变量: APP_AUDIT_SINK_KIND
+    #[serde(default)]
+    pub kind: OperationLogSinkKind,
+
+    /// 单批最大条数，达到后立即写入
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+
+    /// 批量写入的最长间隔（毫秒）
+    #[serde(default = "default_flush_interval_ms")]
+    pub flush_interval_ms: u64,
+
+    /// 待写入队列容量
+    #[serde(default = "default_queue_capacity")]
+    pub queue_capacity: usize,
+
+    /// 队列满时请求等待的最长时间（毫秒），超时后丢弃该条日志
+    #[serde(default = "default_send_timeout_ms")]
+    pub send_timeout_ms: u64,
+
+    /// 批次写入失败后的重试次数
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+
+    /// 重试后仍写入失败的批次追加到的文件，每行一条 JSON 记录
+    #[serde(default = "default_spill_path")]
+    pub spill_path: String,
+
+    /// MongoDB 写入配置
+    #[serde(default)]
+    pub mongo: MongoSinkConfig,
+
+    /// 文件写入配置
+    #[serde(default)]
+    pub file: FileSinkConfig,
+}
+
+impl Default for OperationLogSinkConfig {
+    fn default() -> Self {
+        Self {
+            kind: OperationLogSinkKind::default(),
+            batch_size: default_batch_size(),
+            flush_interval_ms: default_flush_interval_ms(),
+            queue_capacity: default_queue_capacity(),
+            send_timeout_ms: default_send_timeout_ms(),
+            max_retries: default_max_retries(),
+            spill_path: default_spill_path(),
+            mongo: MongoSinkConfig::default(),
+            file: FileSinkConfig::default(),
+        }
+    }
+}
+
+/// 操作日志写入目标
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OperationLogSinkKind {
+    /// 主数据库 `sys_operation_log` 表
+    #[default]
+    #[serde(rename = "database")]
+    Database,
+    /// MongoDB 集合，适合较大的请求与响应体
+    #[serde(rename = "mongo")]
+    Mongo,
+    /// JSON Lines 文件
+    #[serde(rename = "file")]
+    File,
+}
+
+/// MongoDB 写入配置
+#[derive(Deserialize, Debug, Clone)]
+pub struct MongoSinkConfig {
+    /// 命名 MongoDB 实例，未配置时使用主实例
+    pub instance: Option<String>,
+
+    /// 数据库名称
+    #[serde(default = "default_mongo_database")]
+    pub database: String,
+
+    /// 集合名称
+    #[serde(default = "default_mongo_collection")]
+    pub collection: String,
+}
+
+impl Default for MongoSinkConfig {
+    fn default() -> Self {
+        Self {
+            instance: None,
+            database: default_mongo_database(),
+            collection: default_mongo_collection(),
+        }
+    }
+}
+
+/// 文件写入配置
+#[derive(Deserialize, Debug, Clone)]
+pub struct FileSinkConfig {
+    /// 日志文件路径，每行一条 JSON 记录
+    #[serde(default = "default_file_path")]
+    pub path: String,
+}
+
+impl Default for FileSinkConfig {
+    fn default() -> Self {
+        Self {
+            path: default_file_path(),
+        }
+    }
+}
+
+/// 敏感字段脱敏配置
+///
+/// 规则作用于操作日志的查询参数、请求头、请求体与响应体，在事件发送前执行。
+///
+/// `hash` 策略输出 HMAC-SHA256，密钥通过环境变量 `APP_AUDIT_REDACTION_HASH_KEY` 提供，
+/// 可用 `openssl rand -base64 32` 生成。未配置密钥时每次启动随机生成，摘要只能在同一进程内比对。
+///
+/// # 示例配置（YAML）
+/// ```yaml
+/// audit:
+///   redaction:
+///     strategy: mask
+///     rules:
+///       - pattern: "*password*"
+///       - pattern: "$.data.token"
+///         strategy: drop
+/// ```
+#[derive(Deserialize, Debug, Clone)]
+pub struct RedactionConfig {
+    /// 是否启用脱敏
+    /// 环境变量: APP_AUDIT_REDACTION_ENABLED
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// 未单独指定策略的规则使用的默认策略
+    /// 环境变量: APP_AUDIT_REDACTION_STRATEGY
+    #[serde(default)]
+    pub strategy: RedactStrategy,
+
+    /// 脱敏规则，未配置时使用内置规则
+    #[serde(default = "default_rules")]
+    pub rules: Vec<RedactionRule>,
+
+    /// `hash` 策略的 HMAC 密钥，环境变量 `APP_AUDIT_REDACTION_HASH_KEY` 优先
+    #[serde(default)]
+    pub hash_key: Option<String>,
+}
+
+impl RedactionConfig {
+    /// `hash` 策略的 HMAC 密钥，环境变量优先于配置文件
+    pub fn resolve_hash_key(&self) -> Option<String> {
+        env::var(REDACTION_HASH_KEY_ENV)
+            .ok()
+            .or_else(|| self.hash_key.clone())
+            .filter(|key| !key.trim().is_empty())
+    }
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            strategy: RedactStrategy::default(),
+            rules: default_rules(),
+            hash_key: None,
+        }
+    }
+}
+
+/// 脱敏规则
+#[derive(Deserialize, Debug, Clone)]
+pub struct RedactionRule {
+    /// 匹配模式
+    ///
+    /// - 以 `$` 开头时为 JSON 路径，如 `$.data.token`，`*` 匹配任意字段，数组透明
+    /// - 否则为字段名通配模式，如 `password`、`*secret*`，不区分大小写
+    pub pattern: String,
+
+    /// 该规则使用的策略，未指定时使用默认策略
+    pub strategy: Option<RedactStrategy>,
+}
+
+impl RedactionRule {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            strategy: None,
+        }
+    }
+}
+
+/// 脱敏策略
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RedactStrategy {
+    /// 替换为固定掩码
+    #[default]
+    #[serde(rename = "mask")]
+    Mask,
+    /// 替换为 HMAC-SHA256 摘要，便于关联比对
+    #[serde(rename = "hash")]
+    Hash,
+    /// 直接移除字段
+    #[serde(rename = "drop")]
+    Drop,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_rules() -> Vec<RedactionRule> {
+    [
+        "*password*",
+        "*secret*",
+        "*token*",
+        "authorization",
+        "cookie",
+        "set-cookie",
+    ]
+    .into_iter()
+    .map(RedactionRule::new)
+    .collect()
+}
+
+fn default_retention_interval_secs() -> u64 {
+    3600
+}
+
+fn default_retention_batch_size() -> u64 {
+    1000
+}
+
+fn default_archive_prefix() -> String {
+    "audit".to_string()
+}
+
+fn default_max_body_bytes() -> usize {
+    32 * 1024
+}
+
+fn default_batch_size() -> usize {
+    100
+}
+
+fn default_flush_interval_ms() -> u64 {
+    1000
+}
+
+fn default_queue_capacity() -> usize {
+    10_000
+}
+
+fn default_send_timeout_ms() -> u64 {
+    1000
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_spill_path() -> String {
+    "logs/operation_log_spill.jsonl".to_string()
+}
+
+fn default_mongo_database() -> String {
+    "soybean_admin".to_string()
+}
+
+fn default_mongo_collection() -> String {
+    "sys_operation_log".to_string()
+}
+
+fn default_file_path() -> String {
+    "logs/operation_log.jsonl".to_string()
+}
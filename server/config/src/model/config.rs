@@ -1,8 +1,9 @@
 use serde::Deserialize;
 
 use super::{
-    DatabaseConfig, DatabasesInstancesConfig, JwtConfig, MongoConfig, MongoInstancesConfig,
-    RedisConfig, RedisInstancesConfig, S3Config, S3InstancesConfig, ServerConfig,
+    AuditConfig, DatabaseConfig, DatabasesInstancesConfig, JwtConfig, MongoConfig,
+    MongoInstancesConfig, RateLimitConfig, RecycleBinConfig, RedisConfig, RedisInstancesConfig,
+    S3Config, S3InstancesConfig, SecurityConfig, ServerConfig,
 };
 
 /// 应用程序配置结构
@@ -57,6 +58,9 @@ use super::{
 /// - `redis_instances`: 可选的 Redis 连接池配置，用于配置多个命名的 Redis 连接
 /// - `mongo`: 主 MongoDB 配置，用于配置默认的 MongoDB 连接
 /// - `mongo_instances`: 可选的 MongoDB 连接池配置，用于配置多个命名的 MongoDB 连接
+/// - `audit`: 审计日志配置，包含敏感字段脱敏规则等
+/// - `security`: 安全配置，包含登录风险检测等
+/// - `recycle_bin`: 回收站配置，包含保留天数等
 ///
 /// # 示例配置（YAML）
 /// ```yaml
@@ -122,4 +126,20 @@ pub struct Config {
     /// 可选的 S3 连接池配置
     /// 用于配置多个命名的 S3 连接
     pub s3_instances: Option<Vec<S3InstancesConfig>>,
+
+    /// 审计日志配置
+    #[serde(default)]
+    pub audit: AuditConfig,
+
+    /// 安全配置
+    #[serde(default)]
+    pub security: SecurityConfig,
+
+    /// 限流配置
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+
+    /// 回收站配置
+    #[serde(default)]
+    pub recycle_bin: RecycleBinConfig,
 }
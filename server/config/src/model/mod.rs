@@ -1,10 +1,23 @@
+pub use audit_config::{
+    ArchiveConfig, AuditConfig, CaptureConfig, FileSinkConfig, LogRetentionPolicy, MongoSinkConfig,
+    OperationLogSinkConfig, OperationLogSinkKind, RedactStrategy, RedactionConfig, RedactionRule,
+    RetentionConfig,
+};
 pub use config::Config;
 pub use database_config::{DatabaseConfig, DatabasesInstancesConfig};
 pub use jwt_config::JwtConfig;
 pub use mongo_config::{MongoConfig, MongoInstancesConfig};
+pub use rate_limit_config::{
+    RateLimitAlgorithm, RateLimitBackend, RateLimitConfig, RateLimitKey, RouteRateLimit,
+};
+pub use recycle_bin_config::RecycleBinConfig;
 pub use redis_config::{RedisConfig, RedisInstancesConfig, RedisMode};
 pub use s3_config::{S3Config, S3InstancesConfig};
-pub use server_config::ServerConfig;
+pub use security_config::{
+    AccessKeySecurityConfig, LoginRiskConfig, LoginRiskWeights, RetiredEncryptionKey,
+    SecurityConfig,
+};
+pub use server_config::{ClientIpHeader, ServerConfig};
 
 /// 可选配置集合的包装类
 #[allow(dead_code)]
@@ -19,10 +32,14 @@ impl<T> From<Option<Vec<T>>> for OptionalConfigs<T> {
     }
 }
 
+mod audit_config;
 mod config;
 mod database_config;
 mod jwt_config;
 mod mongo_config;
+mod rate_limit_config;
+mod recycle_bin_config;
 mod redis_config;
 mod s3_config;
+mod security_config;
 mod server_config;
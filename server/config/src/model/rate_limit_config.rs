@@ -0,0 +1,150 @@
+use serde::Deserialize;
+
+/// 限流配置
+///
+/// 按路由配置限流规则，访问密钥的配额保存在 `sys_access_key` 中。
+/// 配置了 Redis 时在多个实例间共享计数，否则使用本地内存计数。
+///
+/// # 示例配置（YAML）
+/// ```yaml
+/// rate_limit:
+///   enabled: true
+///   backend: auto
+///   routes:
+///     - path: "/auth/login"
+///       method: POST
+///       key: ip
+///       algorithm: sliding_window
+///       limit: 10
+///       window_secs: 60
+///     - path: "/sandbox/complex-api-key"
+///       key: access_key
+///       algorithm: token_bucket
+///       limit: 100
+///       window_secs: 1
+/// ```
+#[derive(Deserialize, Debug, Clone)]
+pub struct RateLimitConfig {
+    /// 是否启用限流
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// 计数存储
+    #[serde(default)]
+    pub backend: RateLimitBackend,
+
+    /// Redis 键前缀
+    #[serde(default = "default_key_prefix")]
+    pub key_prefix: String,
+
+    /// 访问密钥配额使用的算法
+    #[serde(default)]
+    pub access_key_algorithm: RateLimitAlgorithm,
+
+    /// 路由限流规则，同一路由可配置多条规则，需同时满足
+    #[serde(default = "default_routes")]
+    pub routes: Vec<RouteRateLimit>,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            backend: RateLimitBackend::default(),
+            key_prefix: default_key_prefix(),
+            access_key_algorithm: RateLimitAlgorithm::default(),
+            routes: default_routes(),
+        }
+    }
+}
+
+/// 限流计数存储
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RateLimitBackend {
+    /// 已配置 Redis 时使用 Redis，否则使用内存
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    /// 主 Redis 连接，支持单机与集群
+    #[serde(rename = "redis")]
+    Redis,
+    /// 本地内存，仅在单实例内生效
+    #[serde(rename = "memory")]
+    Memory,
+}
+
+/// 限流算法
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RateLimitAlgorithm {
+    /// 滑动窗口计数，按上一窗口的剩余比例加权
+    #[default]
+    #[serde(rename = "sliding_window")]
+    SlidingWindow,
+    /// 令牌桶，桶容量为 `limit`，每个窗口补满一次，允许突发
+    #[serde(rename = "token_bucket")]
+    TokenBucket,
+}
+
+/// 限流计数维度
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RateLimitKey {
+    /// 客户端 IP
+    #[default]
+    #[serde(rename = "ip")]
+    Ip,
+    /// 登录用户，未登录时按 IP 计数
+    #[serde(rename = "user")]
+    User,
+    /// 访问密钥，未使用访问密钥时按 IP 计数
+    #[serde(rename = "access_key")]
+    AccessKey,
+    /// 整个路由共享一个计数
+    #[serde(rename = "route")]
+    Route,
+}
+
+/// 单条路由限流规则
+#[derive(Deserialize, Debug, Clone)]
+pub struct RouteRateLimit {
+    /// 路由模板，路径参数使用 `:param` 形式，如 `/user/:id`
+    pub path: String,
+
+    /// 请求方法，为空时匹配所有方法
+    pub method: Option<String>,
+
+    /// 计数维度
+    #[serde(default)]
+    pub key: RateLimitKey,
+
+    /// 限流算法
+    #[serde(default)]
+    pub algorithm: RateLimitAlgorithm,
+
+    /// 窗口内允许的请求数
+    pub limit: u64,
+
+    /// 窗口长度（秒）
+    pub window_secs: u64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_key_prefix() -> String {
+    "rate_limit".to_string()
+}
+
+fn default_routes() -> Vec<RouteRateLimit> {
+    ["/auth/login", "/auth/change-password"]
+        .into_iter()
+        .map(|path| RouteRateLimit {
+            path: path.to_string(),
+            method: Some("POST".to_string()),
+            key: RateLimitKey::Ip,
+            algorithm: RateLimitAlgorithm::SlidingWindow,
+            limit: 10,
+            window_secs: 60,
+        })
+        .collect()
+}
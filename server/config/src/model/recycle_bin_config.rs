@@ -0,0 +1,40 @@
+use serde::Deserialize;
+
+/// 回收站配置
+///
+/// 后台任务按 `interval_secs` 间隔彻底删除超过 `retention_days` 的回收站条目，
+/// `retention_days` 为 0 时永久保留，不启动清理任务。
+///
+/// # 示例配置（YAML）
+/// ```yaml
+/// recycle_bin:
+///   retention_days: 30
+///   interval_secs: 3600
+/// ```
+#[derive(Deserialize, Debug, Clone)]
+pub struct RecycleBinConfig {
+    /// 保留天数，为 0 时永久保留
+    #[serde(default = "default_retention_days")]
+    pub retention_days: u32,
+
+    /// 清理任务执行间隔（秒）
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+}
+
+impl Default for RecycleBinConfig {
+    fn default() -> Self {
+        Self {
+            retention_days: default_retention_days(),
+            interval_secs: default_interval_secs(),
+        }
+    }
+}
+
+fn default_retention_days() -> u32 {
+    30
+}
+
+fn default_interval_secs() -> u64 {
+    3600
+}
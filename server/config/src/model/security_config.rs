@@ -0,0 +1,218 @@
+use std::env;
+
+use serde::Deserialize;
+
+/// 访问密钥加密主密钥的环境变量
+const ENCRYPTION_KEY_ENV: &str = "APP_ACCESS_KEY_ENCRYPTION_KEY";
+
+/// 安全配置
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct SecurityConfig {
+    /// 登录风险检测配置
+    #[serde(default)]
+    pub login_risk: LoginRiskConfig,
+
+    /// 访问密钥配置
+    #[serde(default)]
+    pub access_key: AccessKeySecurityConfig,
+}
+
+/// 访问密钥配置
+///
+/// 密钥以信封加密的方式保存，主密钥为 Base64 编码的 32 字节随机数，可用
+/// `openssl rand -base64 32` 生成。主密钥通过环境变量 `APP_ACCESS_KEY_ENCRYPTION_KEY`
+/// 提供，不应写入配置文件；未配置主密钥时服务拒绝启动。更换主密钥时将旧主密钥移入
+/// `retired_keys`，启动时会用新主密钥重新加密已有密钥。
+///
+/// MD5、SHA1 签名已弃用。升级前创建的密钥均为 MD5，为避免客户端失效默认仍接受，启动时输出告警；
+/// 客户端迁移完成后关闭 `allow_legacy_algorithms`，使用这两种算法的密钥签名一律校验失败，也不能再创建。
+///
+/// 规范请求签名先校验 Authorization 头、时间戳与密钥，通过后才读取请求体，
+/// 请求体超过 `max_signed_body_bytes` 时直接拒绝；签名校验通过后才记录 nonce。
+///
+/// # 示例配置（YAML）
+/// ```yaml
+/// security:
+///   access_key:
+///     encryption_key_id: "2026-10"
+///     retired_keys:
+///       - id: "default"
+///         key: "base64..."
+///     rotation_overlap_secs: 86400
+///     allow_legacy_algorithms: false
+///     max_signed_body_bytes: 1048576
+/// ```
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccessKeySecurityConfig {
+    /// 当前主密钥 ID，写入密文以便区分主密钥
+    #[serde(default = "default_encryption_key_id")]
+    pub encryption_key_id: String,
+
+    /// 当前主密钥，环境变量 `APP_ACCESS_KEY_ENCRYPTION_KEY` 优先，仅供本地调试时写在配置文件中
+    #[serde(default)]
+    pub encryption_key: Option<String>,
+
+    /// 已停用的主密钥，仅用于解密
+    #[serde(default)]
+    pub retired_keys: Vec<RetiredEncryptionKey>,
+
+    /// 轮换访问密钥后旧密钥继续有效的时间（秒）
+    #[serde(default = "default_rotation_overlap_secs")]
+    pub rotation_overlap_secs: u64,
+
+    /// 是否接受已弃用的 MD5、SHA1 签名，默认接受以兼容升级前创建的密钥
+    #[serde(default = "default_enabled")]
+    pub allow_legacy_algorithms: bool,
+
+    /// 规范请求签名可校验的最大请求体（字节）
+    #[serde(default = "default_max_signed_body_bytes")]
+    pub max_signed_body_bytes: usize,
+}
+
+impl AccessKeySecurityConfig {
+    /// 当前主密钥，环境变量优先于配置文件
+    pub fn resolve_encryption_key(&self) -> Option<String> {
+        env::var(ENCRYPTION_KEY_ENV)
+            .ok()
+            .filter(|key| !key.trim().is_empty())
+            .or_else(|| self.encryption_key.clone())
+    }
+}
+
+impl Default for AccessKeySecurityConfig {
+    fn default() -> Self {
+        Self {
+            encryption_key_id: default_encryption_key_id(),
+            encryption_key: None,
+            retired_keys: Vec::new(),
+            rotation_overlap_secs: default_rotation_overlap_secs(),
+            allow_legacy_algorithms: default_enabled(),
+            max_signed_body_bytes: default_max_signed_body_bytes(),
+        }
+    }
+}
+
+/// 已停用的主密钥
+#[derive(Deserialize, Debug, Clone)]
+pub struct RetiredEncryptionKey {
+    pub id: String,
+    pub key: String,
+}
+
+/// 登录风险检测配置
+///
+/// 依据登录日志中的历史归属地与 User-Agent 识别异常登录，命中的信号按权重累加为风险分（上限 100）。
+/// 未配置阈值时只记录风险分并发送事件，不影响登录。
+///
+/// # 示例配置（YAML）
+/// ```yaml
+/// security:
+///   login_risk:
+///     history_size: 20
+///     impossible_travel_minutes: 60
+///     shared_ip_threshold: 5
+///     block_score: 90
+/// ```
+#[derive(Deserialize, Debug, Clone)]
+pub struct LoginRiskConfig {
+    /// 是否启用登录风险检测
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// 参与比对的历史登录条数
+    #[serde(default = "default_history_size")]
+    pub history_size: u64,
+
+    /// 与上次登录国家不同且间隔小于该值（分钟）时视为不可能的行程
+    #[serde(default = "default_impossible_travel_minutes")]
+    pub impossible_travel_minutes: i64,
+
+    /// 统计同一 IP 登录账号数的时间窗口（分钟）
+    #[serde(default = "default_shared_ip_window_minutes")]
+    pub shared_ip_window_minutes: i64,
+
+    /// 时间窗口内同一 IP 登录的不同账号数达到该值时视为异常
+    #[serde(default = "default_shared_ip_threshold")]
+    pub shared_ip_threshold: usize,
+
+    /// 各异常信号的权重
+    #[serde(default)]
+    pub weights: LoginRiskWeights,
+
+    /// 风险分达到该值时拒绝登录，为空则不拒绝
+    pub block_score: Option<u8>,
+}
+
+impl Default for LoginRiskConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            history_size: default_history_size(),
+            impossible_travel_minutes: default_impossible_travel_minutes(),
+            shared_ip_window_minutes: default_shared_ip_window_minutes(),
+            shared_ip_threshold: default_shared_ip_threshold(),
+            weights: LoginRiskWeights::default(),
+            block_score: None,
+        }
+    }
+}
+
+/// 登录异常信号权重
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(default)]
+pub struct LoginRiskWeights {
+    /// 首次出现的国家
+    pub new_country: u8,
+    /// 同一国家内首次出现的省份
+    pub new_province: u8,
+    /// 不可能的行程
+    pub impossible_travel: u8,
+    /// 首次出现的 User-Agent
+    pub new_user_agent: u8,
+    /// 同一 IP 登录多个账号
+    pub shared_ip: u8,
+}
+
+impl Default for LoginRiskWeights {
+    fn default() -> Self {
+        Self {
+            new_country: 40,
+            new_province: 20,
+            impossible_travel: 50,
+            new_user_agent: 10,
+            shared_ip: 30,
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_history_size() -> u64 {
+    20
+}
+
+fn default_impossible_travel_minutes() -> i64 {
+    60
+}
+
+fn default_shared_ip_window_minutes() -> i64 {
+    60
+}
+
+fn default_shared_ip_threshold() -> usize {
+    5
+}
+
+fn default_encryption_key_id() -> String {
+    "default".to_string()
+}
+
+fn default_max_signed_body_bytes() -> usize {
+    1024 * 1024
+}
+
+fn default_rotation_overlap_secs() -> u64 {
+    86400
+}
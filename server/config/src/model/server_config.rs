@@ -5,6 +5,20 @@ use serde::Deserialize;
 /// 支持的环境变量：
 /// - APP_SERVER_HOST: 服务器监听地址
 /// - APP_SERVER_PORT: 服务器监听端口
+/// - APP_SERVER_SANDBOX: 是否启用沙箱测试密钥
+///
+/// # 示例配置（YAML）
+/// ```yaml
+/// server:
+///   host: "0.0.0.0"
+///   port: 10001
+///   client_ip_header: "x-forwarded-for"
+///   trusted_proxies:
+///     - "10.0.0.0/8"
+///     - "127.0.0.1"
+///   trusted_hops: 0
+///   sandbox: false
+/// ```
 #[derive(Deserialize, Debug, Clone)]
 pub struct ServerConfig {
     /// 服务器监听地址
@@ -14,4 +28,44 @@ pub struct ServerConfig {
     /// 服务器监听端口
     /// 环境变量: APP_SERVER_PORT
     pub port: u32,
+
+    /// 传递客户端 IP 的请求头，应与最外层可信代理写入的请求头一致
+    ///
+    /// 只读取该请求头，其他请求头即使由客户端携带也会被忽略。
+    #[serde(default)]
+    pub client_ip_header: ClientIpHeader,
+
+    /// 可信代理的 IP 或网段
+    ///
+    /// 仅当直连地址可信时才解析 `client_ip_header` 指定的请求头，
+    /// 为空时直接使用连接的对端地址作为客户端 IP。
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
+
+    /// 从连接对端开始无条件信任的代理层数，用于代理地址不固定的场景（如云负载均衡）
+    #[serde(default)]
+    pub trusted_hops: usize,
+
+    /// 是否启用沙箱测试密钥
+    ///
+    /// 启用后 `/sandbox` 接口可使用内置的 `test-api-key`、`test-access-key` 调试签名，
+    /// 生产环境不应开启。
+    /// 环境变量: APP_SERVER_SANDBOX
+    #[serde(default)]
+    pub sandbox: bool,
+}
+
+/// 传递客户端 IP 的请求头
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ClientIpHeader {
+    /// RFC 7239 `Forwarded` 头中的 `for` 参数
+    #[serde(rename = "forwarded")]
+    Forwarded,
+    /// `X-Forwarded-For`，代理逐层在末尾追加对端地址
+    #[default]
+    #[serde(rename = "x-forwarded-for")]
+    XForwardedFor,
+    /// `X-Real-IP`，由最近一层代理写入单个地址
+    #[serde(rename = "x-real-ip")]
+    XRealIp,
 }
@@ -22,14 +22,23 @@ impl TokenStatus {
     }
 }
 
+/// 管理员角色编码
+///
+/// 开通租户时复制出的角色以 `{角色编码}_{域编码}` 命名，同样视为管理员角色。
+pub const ADMIN_ROLE_CODES: [&str; 2] = ["ROLE_SUPER", "ROLE_ADMIN"];
+
 /// 系统事件类型枚举
 #[derive(Debug, Clone, PartialEq, Eq, AsRefStr, Display, EnumString)]
 #[strum(serialize_all = "snake_case")]
 pub enum SystemEvent {
     /// 用户认证登录事件
     AuthLoggedInEvent,
-    /// 系统操作日志事件
-    AuditOperationLoggedEvent,
     /// API密钥验证事件
     AuthApiKeyValidatedEvent,
+    /// 登录风险检测事件
+    AuthLoginRiskDetectedEvent,
+    /// 用户登录失败事件
+    AuthLoginFailedEvent,
+    /// 访问密钥变更事件
+    AccessKeyChangedEvent,
 }
@@ -0,0 +1,247 @@
+use super::{RateLimitDecision, RateLimitQuota};
+
+/// 令牌桶状态
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct TokenBucketState {
+    /// 剩余令牌数
+    pub tokens: f64,
+    /// 上次更新时间（毫秒）
+    pub updated_at: u64,
+}
+
+/// 滑动窗口状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SlidingWindowState {
+    /// 当前窗口序号
+    pub window: u64,
+    /// 当前窗口计数
+    pub current: u64,
+    /// 上一窗口计数
+    pub previous: u64,
+}
+
+/// 计算令牌桶的下一状态与判定结果
+///
+/// 桶容量为 `limit`，每个窗口补满一次；新的键视为满桶。
+/// Redis 脚本按相同规则更新状态，判定结果统一在此计算。
+pub(crate) fn token_bucket(
+    state: Option<TokenBucketState>,
+    quota: &RateLimitQuota,
+    now: u64,
+) -> (TokenBucketState, RateLimitDecision) {
+    if quota.limit == 0 {
+        let state = TokenBucketState {
+            tokens: 0.0,
+            updated_at: now,
+        };
+        return (state, RateLimitDecision::rejected(quota));
+    }
+
+    let limit = quota.limit as f64;
+    let window = quota.window_ms() as f64;
+
+    let mut tokens = match state {
+        Some(state) => {
+            let elapsed = now.saturating_sub(state.updated_at) as f64;
+            limit.min(state.tokens + elapsed * limit / window)
+        },
+        None => limit,
+    };
+
+    let allowed = tokens >= 1.0;
+    let retry_after_ms = if allowed {
+        tokens -= 1.0;
+        0
+    } else {
+        ((1.0 - tokens) * window / limit).ceil() as u64
+    };
+
+    (
+        TokenBucketState {
+            tokens,
+            updated_at: now,
+        },
+        RateLimitDecision {
+            allowed,
+            limit: quota.limit,
+            remaining: tokens.floor() as u64,
+            reset_ms: ((limit - tokens) * window / limit).ceil() as u64,
+            retry_after_ms,
+        },
+    )
+}
+
+/// 计算滑动窗口的下一状态与判定结果
+///
+/// 估算值为上一窗口计数按剩余时间比例加权后加上当前窗口计数，
+/// 被拒绝的请求不计数。
+pub(crate) fn sliding_window(
+    state: Option<SlidingWindowState>,
+    quota: &RateLimitQuota,
+    now: u64,
+) -> (SlidingWindowState, RateLimitDecision) {
+    let window_ms = quota.window_ms();
+    let window = now / window_ms;
+    let elapsed = now % window_ms;
+
+    let (mut current, previous) = match state {
+        Some(state) if state.window == window => (state.current, state.previous),
+        Some(state) if state.window + 1 == window => (0, state.current),
+        _ => (0, 0),
+    };
+
+    let estimated = estimate(previous, current, window_ms, elapsed);
+    let limit = quota.limit as f64;
+    let allowed = estimated + 1.0 <= limit;
+
+    let (remaining, retry_after_ms) = if allowed {
+        current += 1;
+        ((limit - estimated - 1.0).floor() as u64, 0)
+    } else {
+        (
+            0,
+            sliding_window_retry(previous, current, quota.limit, window_ms, elapsed),
+        )
+    };
+
+    (
+        SlidingWindowState {
+            window,
+            current,
+            previous,
+        },
+        RateLimitDecision {
+            allowed,
+            limit: quota.limit,
+            remaining,
+            reset_ms: window_ms - elapsed,
+            retry_after_ms,
+        },
+    )
+}
+
+/// 估算滑动窗口内的请求数，运算顺序与 Redis 脚本保持一致
+fn estimate(previous: u64, current: u64, window_ms: u64, elapsed: u64) -> f64 {
+    previous as f64 * (window_ms - elapsed) as f64 / window_ms as f64 + current as f64
+}
+
+/// 计算估算值降到可再放行一个请求所需的时间
+fn sliding_window_retry(
+    previous: u64,
+    current: u64,
+    limit: u64,
+    window_ms: u64,
+    elapsed: u64,
+) -> u64 {
+    if limit == 0 {
+        return window_ms;
+    }
+
+    let window = window_ms as f64;
+    let budget = (limit - 1) as f64;
+
+    // 当前窗口内随上一窗口权重下降即可放行
+    if current < limit && previous > 0 {
+        let at = window * (1.0 - (budget - current as f64) / previous as f64);
+        return (at.ceil() as u64).saturating_sub(elapsed).max(1);
+    }
+
+    // 需要等到下一窗口，届时当前窗口计数成为上一窗口计数
+    let at = if current > 0 {
+        window * (1.0 - budget / current as f64)
+    } else {
+        0.0
+    };
+    (window_ms - elapsed + at.ceil().max(0.0) as u64).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use server_config::RateLimitAlgorithm;
+
+    use super::*;
+
+    fn quota(algorithm: RateLimitAlgorithm, limit: u64, window_secs: u64) -> RateLimitQuota {
+        RateLimitQuota {
+            algorithm,
+            limit,
+            window_secs,
+        }
+    }
+
+    #[test]
+    fn test_token_bucket() {
+        let quota = quota(RateLimitAlgorithm::TokenBucket, 2, 10);
+
+        let (state, decision) = token_bucket(None, &quota, 0);
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 1);
+        assert_eq!(decision.reset_ms, 5_000);
+
+        let (state, decision) = token_bucket(Some(state), &quota, 0);
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 0);
+
+        let (state, decision) = token_bucket(Some(state), &quota, 1_000);
+        assert!(!decision.allowed);
+        assert_eq!(decision.retry_after_ms, 4_000);
+
+        // 每 5 秒补充一个令牌
+        let (state, decision) = token_bucket(Some(state), &quota, 5_000);
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 0);
+
+        // 空闲足够久后补满且不超过容量
+        let (_, decision) = token_bucket(Some(state), &quota, 100_000);
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 1);
+    }
+
+    #[test]
+    fn test_sliding_window() {
+        let quota = quota(RateLimitAlgorithm::SlidingWindow, 2, 10);
+
+        let (state, decision) = sliding_window(None, &quota, 1_000);
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 1);
+        assert_eq!(decision.reset_ms, 9_000);
+
+        let (state, decision) = sliding_window(Some(state), &quota, 2_000);
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 0);
+
+        let (state, decision) = sliding_window(Some(state), &quota, 3_000);
+        assert!(!decision.allowed);
+        assert_eq!(state.current, 2);
+        // 下一窗口开始时估算值为 2，需等到上一窗口权重降到一半
+        assert_eq!(decision.retry_after_ms, 7_000 + 5_000);
+
+        // 下一窗口过半，上一窗口按 0.5 加权
+        let (state, decision) = sliding_window(Some(state), &quota, 15_000);
+        assert!(decision.allowed);
+        assert_eq!(state.previous, 2);
+        assert_eq!(state.current, 1);
+
+        let (state, decision) = sliding_window(Some(state), &quota, 15_000);
+        assert!(!decision.allowed);
+        assert_eq!(state.current, 1);
+        assert_eq!(decision.retry_after_ms, 5_000);
+
+        // 间隔超过一个窗口后重新计数
+        let (state, decision) = sliding_window(Some(state), &quota, 40_000);
+        assert!(decision.allowed);
+        assert_eq!(state.previous, 0);
+    }
+
+    #[test]
+    fn test_zero_limit() {
+        let quota = quota(RateLimitAlgorithm::SlidingWindow, 0, 1);
+        let (_, decision) = sliding_window(None, &quota, 0);
+        assert!(!decision.allowed);
+        assert_eq!(decision.retry_after_ms, 1_000);
+
+        let (_, decision) = token_bucket(None, &quota, 0);
+        assert!(!decision.allowed);
+        assert_eq!(decision.retry_after_ms, 1_000);
+    }
+}
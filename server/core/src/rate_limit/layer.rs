@@ -0,0 +1,391 @@
+use std::{
+    convert::Infallible,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{request::Parts, HeaderMap, HeaderValue, Method, StatusCode},
+    response::{IntoResponse, Response},
+};
+use futures::future::BoxFuture;
+use server_config::{RateLimitConfig, RateLimitKey};
+use tower_layer::Layer;
+use tower_service::Service;
+use tracing::warn;
+
+use super::{get_access_key_quota, RateLimitDecision, RateLimitQuota, RateLimitStore};
+use crate::{
+    sign::ValidatedAccessKey,
+    web::{
+        auth::User,
+        operation_log::{get_client_ip, get_route_path},
+        res::Res,
+    },
+};
+
+const RATE_LIMIT_LIMIT: &str = "ratelimit-limit";
+const RATE_LIMIT_REMAINING: &str = "ratelimit-remaining";
+const RATE_LIMIT_RESET: &str = "ratelimit-reset";
+
+/// 单条路由限流规则
+#[derive(Debug, Clone)]
+struct RouteRule {
+    method: Option<Method>,
+    path: String,
+    key: RateLimitKey,
+    quota: RateLimitQuota,
+}
+
+impl RouteRule {
+    fn matches(&self, method: &Method, path: &str) -> bool {
+        self.path == path && self.method.as_ref().is_none_or(|m| m == method)
+    }
+
+    /// 计数键，按维度区分调用方，缺少对应身份时按 IP 计数
+    fn counter_key(&self, parts: &Parts) -> String {
+        let extensions = &parts.extensions;
+        let subject = match self.key {
+            RateLimitKey::Route => Some("all".to_string()),
+            RateLimitKey::User => extensions
+                .get::<User>()
+                .map(|user| format!("user:{}", user.user_id())),
+            RateLimitKey::AccessKey => extensions
+                .get::<ValidatedAccessKey>()
+                .map(|key| format!("ak:{}", key.0)),
+            RateLimitKey::Ip => None,
+        }
+        .unwrap_or_else(|| format!("ip:{}", get_client_ip(extensions, &parts.headers)));
+
+        let method = self.method.as_ref().map_or("*", Method::as_str);
+        format!("route:{}:{}:{}", method, self.path, subject)
+    }
+}
+
+/// 限流器，按路由规则与访问密钥配额判定请求
+pub struct RateLimiter {
+    store: RateLimitStore,
+    routes: Vec<RouteRule>,
+}
+
+impl RateLimiter {
+    /// 按配置创建限流器，无效的规则会被忽略
+    pub fn new(config: &RateLimitConfig, store: RateLimitStore) -> Self {
+        let routes = config
+            .routes
+            .iter()
+            .filter_map(|route| {
+                let method = match route.method.as_deref() {
+                    Some(method) => match method.to_ascii_uppercase().parse::<Method>() {
+                        Ok(method) => Some(method),
+                        Err(_) => {
+                            warn!("Invalid rate limit method '{}' for {}", method, route.path);
+                            return None;
+                        },
+                    },
+                    None => None,
+                };
+
+                Some(RouteRule {
+                    method,
+                    path: route.path.trim_end_matches('/').to_string(),
+                    key: route.key,
+                    quota: RateLimitQuota {
+                        algorithm: route.algorithm,
+                        limit: route.limit,
+                        window_secs: route.window_secs,
+                    },
+                })
+            })
+            .collect();
+
+        Self { store, routes }
+    }
+
+    /// 判定请求，依次检查访问密钥配额与匹配的路由规则
+    ///
+    /// 先检查全部规则而不计数，任一规则拒绝时不消耗其他规则的配额；全部通过后再逐条计数。
+    /// 检查与计数之间的并发请求仍可能使计数阶段被拒绝，此时已计数的规则不会回退。
+    ///
+    /// # 返回值
+    /// * `Option<RateLimitDecision>` - 未命中任何限流时返回 None；被拒绝时返回拒绝结果，
+    ///   否则返回剩余配额最少的结果
+    pub async fn check(&self, parts: &Parts) -> Option<RateLimitDecision> {
+        let mut limits = Vec::new();
+
+        if let Some(ValidatedAccessKey(access_key)) = parts.extensions.get::<ValidatedAccessKey>() {
+            if let Some(quota) = get_access_key_quota(access_key) {
+                limits.push((format!("access_key:{}", access_key), quota));
+            }
+        }
+
+        if !self.routes.is_empty() {
+            let route_path = get_route_path(&parts.extensions, &parts.uri);
+            limits.extend(
+                self.routes
+                    .iter()
+                    .filter(|rule| rule.matches(&parts.method, &route_path))
+                    .map(|rule| (rule.counter_key(parts), rule.quota)),
+            );
+        }
+
+        for (key, quota) in &limits {
+            let decision = self.store.peek(key, quota).await;
+            if !decision.allowed {
+                return Some(decision);
+            }
+        }
+
+        let mut result: Option<RateLimitDecision> = None;
+        for (key, quota) in limits {
+            let decision = self.store.check(&key, &quota).await;
+            if !decision.allowed {
+                return Some(decision);
+            }
+            if result.is_none_or(|current| decision.remaining < current.remaining) {
+                result = Some(decision);
+            }
+        }
+
+        result
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitLayer {
+    pub fn new(limiter: RateLimiter) -> Self {
+        Self {
+            limiter: Arc::new(limiter),
+        }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer
+where
+    S: Service<Request<Body>, Response = Response<Body>, Error = Infallible>
+        + Send
+        + Clone
+        + 'static,
+{
+    type Service = RateLimitMiddleware<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        RateLimitMiddleware {
+            inner: service,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitMiddleware<S> {
+    inner: S,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<S> Service<Request<Body>> for RateLimitMiddleware<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>, Error = Infallible>
+        + Send
+        + Clone
+        + 'static,
+    S::Future: Send + 'static,
+{
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+    type Response = Response<Body>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let mut inner = self.inner.clone();
+        let limiter = self.limiter.clone();
+
+        Box::pin(async move {
+            let (parts, body) = req.into_parts();
+            let decision = limiter.check(&parts).await;
+
+            if let Some(decision) = decision.filter(|decision| !decision.allowed) {
+                let mut response = (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    Res::<()>::new_error(
+                        StatusCode::TOO_MANY_REQUESTS.as_u16(),
+                        "Too many requests",
+                    ),
+                )
+                    .into_response();
+                let headers = response.headers_mut();
+                set_rate_limit_headers(headers, &decision);
+                headers.insert(
+                    http::header::RETRY_AFTER,
+                    HeaderValue::from(to_secs(decision.retry_after_ms).max(1)),
+                );
+                return Ok(response);
+            }
+
+            let mut response = inner.call(Request::from_parts(parts, body)).await?;
+            if let Some(decision) = decision {
+                set_rate_limit_headers(response.headers_mut(), &decision);
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// 写入 `RateLimit-*` 响应头，时间单位为秒
+fn set_rate_limit_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    headers.insert(RATE_LIMIT_LIMIT, HeaderValue::from(decision.limit));
+    headers.insert(RATE_LIMIT_REMAINING, HeaderValue::from(decision.remaining));
+    headers.insert(
+        RATE_LIMIT_RESET,
+        HeaderValue::from(to_secs(decision.reset_ms)),
+    );
+}
+
+#[inline]
+fn to_secs(ms: u64) -> u64 {
+    ms.div_ceil(1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, http::Request, routing::post, Router};
+    use server_config::{RateLimitAlgorithm, RouteRateLimit};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn limiter(key: RateLimitKey) -> RateLimiter {
+        let config = RateLimitConfig {
+            routes: vec![RouteRateLimit {
+                path: "/auth/login".to_string(),
+                method: Some("post".to_string()),
+                key,
+                algorithm: RateLimitAlgorithm::SlidingWindow,
+                limit: 2,
+                window_secs: 60,
+            }],
+            ..Default::default()
+        };
+        RateLimiter::new(&config, RateLimitStore::memory())
+    }
+
+    fn login_request(ip: &str) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/auth/login")
+            .header("X-Forwarded-For", ip)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_layer() {
+        let app = Router::new()
+            .route("/auth/login", post(|| async { "ok" }))
+            .route("/auth/other", post(|| async { "ok" }))
+            .layer(RateLimitLayer::new(limiter(RateLimitKey::Ip)));
+
+        for remaining in ["1", "0"] {
+            let response = app.clone().oneshot(login_request("1.1.1.1")).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()[RATE_LIMIT_LIMIT], "2");
+            assert_eq!(response.headers()[RATE_LIMIT_REMAINING], remaining);
+        }
+
+        let response = app.clone().oneshot(login_request("1.1.1.1")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[RATE_LIMIT_REMAINING], "0");
+        assert!(response.headers().contains_key(http::header::RETRY_AFTER));
+
+        // 其他 IP 与未配置的路由不受影响
+        let response = app.clone().oneshot(login_request("2.2.2.2")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/auth/other")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key(RATE_LIMIT_LIMIT));
+    }
+
+    #[tokio::test]
+    async fn test_access_key_quota() {
+        super::super::set_access_key_quota(
+            "AK_RATE_LIMIT_TEST",
+            Some(RateLimitQuota {
+                algorithm: RateLimitAlgorithm::TokenBucket,
+                limit: 1,
+                window_secs: 60,
+            }),
+        );
+        let limiter = limiter(RateLimitKey::Route);
+
+        let (mut parts, _) = Request::builder()
+            .uri("/sandbox/complex-api-key")
+            .body(Body::empty())
+            .unwrap()
+            .into_parts();
+        parts
+            .extensions
+            .insert(ValidatedAccessKey("AK_RATE_LIMIT_TEST".to_string()));
+
+        assert!(limiter.check(&parts).await.unwrap().allowed);
+        assert!(!limiter.check(&parts).await.unwrap().allowed);
+
+        super::super::set_access_key_quota("AK_RATE_LIMIT_TEST", None);
+        assert_eq!(limiter.check(&parts).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_request_consumes_no_quota() {
+        let quota = RateLimitQuota {
+            algorithm: RateLimitAlgorithm::SlidingWindow,
+            limit: 3,
+            window_secs: 60,
+        };
+        super::super::set_access_key_quota("AK_RATE_LIMIT_ATOMIC", Some(quota));
+        let config = RateLimitConfig {
+            routes: vec![RouteRateLimit {
+                path: "/sandbox/complex-api-key".to_string(),
+                method: None,
+                key: RateLimitKey::AccessKey,
+                algorithm: RateLimitAlgorithm::SlidingWindow,
+                limit: 1,
+                window_secs: 60,
+            }],
+            ..Default::default()
+        };
+        let limiter = RateLimiter::new(&config, RateLimitStore::memory());
+
+        let (mut parts, _) = Request::builder()
+            .uri("/sandbox/complex-api-key")
+            .body(Body::empty())
+            .unwrap()
+            .into_parts();
+        parts
+            .extensions
+            .insert(ValidatedAccessKey("AK_RATE_LIMIT_ATOMIC".to_string()));
+
+        assert!(limiter.check(&parts).await.unwrap().allowed);
+        // 路由规则拒绝，先检查的访问密钥配额不被消耗
+        for _ in 0..3 {
+            assert!(!limiter.check(&parts).await.unwrap().allowed);
+        }
+        let key = "access_key:AK_RATE_LIMIT_ATOMIC";
+        assert_eq!(limiter.store.peek(key, &quota).await.remaining, 1);
+
+        super::super::set_access_key_quota("AK_RATE_LIMIT_ATOMIC", None);
+    }
+}
@@ -0,0 +1,102 @@
+use std::{sync::Arc, time::Duration};
+
+use moka::sync::Cache;
+use parking_lot::Mutex;
+use server_config::RateLimitAlgorithm;
+
+use super::{
+    algorithm::{sliding_window, token_bucket, SlidingWindowState, TokenBucketState},
+    RateLimitDecision, RateLimitQuota,
+};
+
+/// 空闲超过该时间的计数会被清除，相当于配额已恢复
+const IDLE_TTL_SECS: u64 = 3600;
+/// 最多保留的计数键数量
+const MAX_KEYS: u64 = 100_000;
+
+#[derive(Debug, Clone, Copy)]
+enum LimiterState {
+    Empty,
+    TokenBucket(TokenBucketState),
+    SlidingWindow(SlidingWindowState),
+}
+
+/// 基于内存的限流计数，仅在当前实例内生效
+#[derive(Clone)]
+pub struct MemoryRateLimitStore {
+    states: Cache<String, Arc<Mutex<LimiterState>>>,
+}
+
+impl MemoryRateLimitStore {
+    pub fn new() -> Self {
+        Self {
+            states: Cache::builder()
+                .time_to_idle(Duration::from_secs(IDLE_TTL_SECS))
+                .max_capacity(MAX_KEYS)
+                .build(),
+        }
+    }
+
+    /// 按配额判定并计数
+    ///
+    /// # 参数
+    /// * `key` - 计数键
+    /// * `quota` - 限流配额
+    /// * `now` - 当前时间（毫秒）
+    pub fn check(&self, key: &str, quota: &RateLimitQuota, now: u64) -> RateLimitDecision {
+        let entry = self
+            .states
+            .get_with_by_ref(key, || Arc::new(Mutex::new(LimiterState::Empty)));
+        let mut state = entry.lock();
+
+        let (next, decision) = evaluate(*state, quota, now);
+        *state = next;
+        decision
+    }
+
+    /// 按配额判定但不计数
+    ///
+    /// # 参数
+    /// * `key` - 计数键
+    /// * `quota` - 限流配额
+    /// * `now` - 当前时间（毫秒）
+    pub fn peek(&self, key: &str, quota: &RateLimitQuota, now: u64) -> RateLimitDecision {
+        let state = self
+            .states
+            .get(key)
+            .map_or(LimiterState::Empty, |entry| *entry.lock());
+        evaluate(state, quota, now).1
+    }
+}
+
+/// 计算下一状态与判定结果
+fn evaluate(
+    state: LimiterState,
+    quota: &RateLimitQuota,
+    now: u64,
+) -> (LimiterState, RateLimitDecision) {
+    match quota.algorithm {
+        RateLimitAlgorithm::TokenBucket => {
+            let prior = match state {
+                LimiterState::TokenBucket(prior) => Some(prior),
+                _ => None,
+            };
+            let (next, decision) = token_bucket(prior, quota, now);
+            (LimiterState::TokenBucket(next), decision)
+        },
+        RateLimitAlgorithm::SlidingWindow => {
+            let prior = match state {
+                LimiterState::SlidingWindow(prior) => Some(prior),
+                _ => None,
+            };
+            let (next, decision) = sliding_window(prior, quota, now);
+            (LimiterState::SlidingWindow(next), decision)
+        },
+    }
+}
+
+impl Default for MemoryRateLimitStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,97 @@
+mod algorithm;
+mod layer;
+mod memory_store;
+mod redis_store;
+mod store;
+
+pub use layer::{RateLimitLayer, RateLimitMiddleware, RateLimiter};
+pub use memory_store::MemoryRateLimitStore;
+pub use redis_store::RedisRateLimitStore;
+pub use store::RateLimitStore;
+
+use std::{
+    collections::HashMap,
+    sync::RwLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use once_cell::sync::Lazy;
+use server_config::RateLimitAlgorithm;
+
+/// 访问密钥的限流配额，由服务层在密钥变更时维护
+static ACCESS_KEY_QUOTAS: Lazy<RwLock<HashMap<String, RateLimitQuota>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// 限流配额
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitQuota {
+    /// 限流算法
+    pub algorithm: RateLimitAlgorithm,
+    /// 窗口内允许的请求数
+    pub limit: u64,
+    /// 窗口长度（秒）
+    pub window_secs: u64,
+}
+
+impl RateLimitQuota {
+    /// 窗口长度（毫秒），最小为 1
+    #[inline]
+    pub fn window_ms(&self) -> u64 {
+        self.window_secs.saturating_mul(1000).max(1)
+    }
+}
+
+/// 单次限流判定结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitDecision {
+    /// 是否放行
+    pub allowed: bool,
+    /// 窗口内允许的请求数
+    pub limit: u64,
+    /// 剩余可用请求数
+    pub remaining: u64,
+    /// 配额完全恢复所需时间（毫秒）
+    pub reset_ms: u64,
+    /// 被拒绝时需等待的时间（毫秒），放行时为 0
+    pub retry_after_ms: u64,
+}
+
+impl RateLimitDecision {
+    /// 配额为 0 时的拒绝结果
+    pub(crate) fn rejected(quota: &RateLimitQuota) -> Self {
+        Self {
+            allowed: false,
+            limit: 0,
+            remaining: 0,
+            reset_ms: quota.window_ms(),
+            retry_after_ms: quota.window_ms(),
+        }
+    }
+}
+
+/// 设置访问密钥的限流配额，`None` 表示不限流
+pub fn set_access_key_quota(access_key_id: &str, quota: Option<RateLimitQuota>) {
+    if let Ok(mut quotas) = ACCESS_KEY_QUOTAS.write() {
+        match quota {
+            Some(quota) => quotas.insert(access_key_id.to_string(), quota),
+            None => quotas.remove(access_key_id),
+        };
+    }
+}
+
+/// 获取访问密钥的限流配额
+pub fn get_access_key_quota(access_key_id: &str) -> Option<RateLimitQuota> {
+    ACCESS_KEY_QUOTAS
+        .read()
+        .ok()
+        .and_then(|quotas| quotas.get(access_key_id).copied())
+}
+
+/// 当前时间（毫秒）
+#[inline]
+pub(crate) fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,185 @@
+use once_cell::sync::Lazy;
+use redis::{RedisError, Script};
+use server_config::RateLimitAlgorithm;
+use server_global::global::{RedisConnection, GLOBAL_PRIMARY_REDIS};
+
+use super::{
+    algorithm::{sliding_window, token_bucket, SlidingWindowState, TokenBucketState},
+    RateLimitDecision, RateLimitQuota,
+};
+
+/// 令牌桶脚本，返回更新前的状态 `[tokens, updated_at]`
+///
+/// 状态更新规则与 `algorithm::token_bucket` 一致，空闲一个窗口后桶已补满，键随之过期。
+static TOKEN_BUCKET_SCRIPT: Lazy<Script> = Lazy::new(|| {
+    Script::new(
+        r#"
+        local limit = tonumber(ARGV[1])
+        local window = tonumber(ARGV[2])
+        local now = tonumber(ARGV[3])
+        local state = redis.call('HMGET', KEYS[1], 't', 'ts')
+        local tokens = tonumber(state[1])
+        local updated_at = tonumber(state[2])
+        if tokens == nil or updated_at == nil then
+            tokens = limit
+        else
+            tokens = math.min(limit, tokens + math.max(0, now - updated_at) * limit / window)
+        end
+        if tokens >= 1 then
+            tokens = tokens - 1
+        end
+        redis.call('HSET', KEYS[1], 't', string.format('%.17g', tokens), 'ts', now)
+        redis.call('PEXPIRE', KEYS[1], window)
+        return {state[1] or '', state[2] or ''}
+        "#,
+    )
+});
+
+/// 滑动窗口脚本，返回更新前的状态 `[window, current, previous]`
+///
+/// 估算值的运算顺序与 `algorithm::sliding_window` 一致，被拒绝的请求不计数。
+static SLIDING_WINDOW_SCRIPT: Lazy<Script> = Lazy::new(|| {
+    Script::new(
+        r#"
+        local limit = tonumber(ARGV[1])
+        local window = tonumber(ARGV[2])
+        local now = tonumber(ARGV[3])
+        local index = math.floor(now / window)
+        local state = redis.call('HMGET', KEYS[1], 'w', 'c', 'p')
+        local stored = tonumber(state[1])
+        local current = 0
+        local previous = 0
+        if stored == index then
+            current = tonumber(state[2]) or 0
+            previous = tonumber(state[3]) or 0
+        elseif stored == index - 1 then
+            previous = tonumber(state[2]) or 0
+        end
+        local estimated = previous * (window - now % window) / window + current
+        if estimated + 1 <= limit then
+            redis.call('HSET', KEYS[1], 'w', index, 'c', current + 1, 'p', previous)
+            redis.call('PEXPIRE', KEYS[1], window * 2)
+        end
+        return {state[1] or '', state[2] or '', state[3] or ''}
+        "#,
+    )
+});
+
+/// 基于 Redis 的限流计数，在多个实例间共享
+///
+/// 计数在脚本中原子更新，脚本返回更新前的状态，由 Rust 侧按相同算法计算判定结果。
+#[derive(Clone)]
+pub struct RedisRateLimitStore {
+    prefix: String,
+}
+
+impl RedisRateLimitStore {
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+
+    fn get_key(&self, key: &str) -> String {
+        format!("{}:{}", self.prefix, key)
+    }
+
+    /// 按配额判定并计数
+    ///
+    /// # 参数
+    /// * `key` - 计数键
+    /// * `quota` - 限流配额
+    /// * `now` - 当前时间（毫秒）
+    pub async fn check(
+        &self,
+        key: &str,
+        quota: &RateLimitQuota,
+        now: u64,
+    ) -> Result<RateLimitDecision, RedisError> {
+        let script = match quota.algorithm {
+            RateLimitAlgorithm::TokenBucket => &*TOKEN_BUCKET_SCRIPT,
+            RateLimitAlgorithm::SlidingWindow => &*SLIDING_WINDOW_SCRIPT,
+        };
+        let mut invocation = script.key(self.get_key(key));
+        invocation.arg(quota.limit).arg(quota.window_ms()).arg(now);
+
+        let prior: Vec<String> = match connection().await? {
+            RedisConnection::Single(client) => {
+                let mut conn = client.get_multiplexed_async_connection().await?;
+                invocation.invoke_async(&mut conn).await?
+            },
+            RedisConnection::Cluster(client) => {
+                let mut conn = client.get_async_connection().await?;
+                invocation.invoke_async(&mut conn).await?
+            },
+        };
+
+        Ok(decide(&prior, quota, now))
+    }
+
+    /// 按配额判定但不计数
+    ///
+    /// # 参数
+    /// * `key` - 计数键
+    /// * `quota` - 限流配额
+    /// * `now` - 当前时间（毫秒）
+    pub async fn peek(
+        &self,
+        key: &str,
+        quota: &RateLimitQuota,
+        now: u64,
+    ) -> Result<RateLimitDecision, RedisError> {
+        let fields: &[&str] = match quota.algorithm {
+            RateLimitAlgorithm::TokenBucket => &["t", "ts"],
+            RateLimitAlgorithm::SlidingWindow => &["w", "c", "p"],
+        };
+        let command = redis::cmd("HMGET")
+            .arg(self.get_key(key))
+            .arg(fields)
+            .to_owned();
+
+        let prior: Vec<Option<String>> = match connection().await? {
+            RedisConnection::Single(client) => {
+                let mut conn = client.get_multiplexed_async_connection().await?;
+                command.query_async(&mut conn).await?
+            },
+            RedisConnection::Cluster(client) => {
+                let mut conn = client.get_async_connection().await?;
+                command.query_async(&mut conn).await?
+            },
+        };
+        let prior: Vec<String> = prior.into_iter().map(Option::unwrap_or_default).collect();
+
+        Ok(decide(&prior, quota, now))
+    }
+}
+
+async fn connection() -> Result<RedisConnection, RedisError> {
+    GLOBAL_PRIMARY_REDIS.read().await.clone().ok_or_else(|| {
+        RedisError::from((redis::ErrorKind::ClientError, "Redis is not initialized"))
+    })
+}
+
+/// 按更新前的状态计算判定结果，字段顺序与脚本返回值一致
+fn decide(prior: &[String], quota: &RateLimitQuota, now: u64) -> RateLimitDecision {
+    let field = |index: usize| prior.get(index).and_then(|value| value.parse().ok());
+    match quota.algorithm {
+        RateLimitAlgorithm::TokenBucket => {
+            let state = field(0)
+                .zip(field(1))
+                .map(|(tokens, updated_at)| TokenBucketState {
+                    tokens,
+                    updated_at: updated_at as u64,
+                });
+            token_bucket(state, quota, now).1
+        },
+        RateLimitAlgorithm::SlidingWindow => {
+            let state = field(0).map(|window: f64| SlidingWindowState {
+                window: window as u64,
+                current: field(1).unwrap_or_default() as u64,
+                previous: field(2).unwrap_or_default() as u64,
+            });
+            sliding_window(state, quota, now).1
+        },
+    }
+}
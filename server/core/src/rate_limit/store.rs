@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use tracing::warn;
+
+use super::{
+    memory_store::MemoryRateLimitStore, now_millis, redis_store::RedisRateLimitStore,
+    RateLimitDecision, RateLimitQuota,
+};
+
+/// 限流计数存储
+#[derive(Clone)]
+pub enum RateLimitStore {
+    /// 本地内存计数
+    Memory(Arc<MemoryRateLimitStore>),
+    /// Redis 计数，Redis 不可用时退回到本地内存计数
+    Redis(Arc<RedisRateLimitStore>, Arc<MemoryRateLimitStore>),
+}
+
+impl RateLimitStore {
+    /// 创建内存存储
+    pub fn memory() -> Self {
+        RateLimitStore::Memory(Arc::new(MemoryRateLimitStore::new()))
+    }
+
+    /// 创建 Redis 存储
+    pub fn redis(prefix: impl Into<String>) -> Self {
+        RateLimitStore::Redis(
+            Arc::new(RedisRateLimitStore::new(prefix)),
+            Arc::new(MemoryRateLimitStore::new()),
+        )
+    }
+
+    /// 按配额判定并计数
+    ///
+    /// # 参数
+    /// * `key` - 计数键
+    /// * `quota` - 限流配额
+    pub async fn check(&self, key: &str, quota: &RateLimitQuota) -> RateLimitDecision {
+        let now = now_millis();
+        match self {
+            RateLimitStore::Memory(store) => store.check(key, quota, now),
+            RateLimitStore::Redis(store, fallback) => match store.check(key, quota, now).await {
+                Ok(decision) => decision,
+                Err(e) => {
+                    warn!("Rate limit falls back to memory, Redis error: {}", e);
+                    fallback.check(key, quota, now)
+                },
+            },
+        }
+    }
+
+    /// 按配额判定但不计数
+    ///
+    /// # 参数
+    /// * `key` - 计数键
+    /// * `quota` - 限流配额
+    pub async fn peek(&self, key: &str, quota: &RateLimitQuota) -> RateLimitDecision {
+        let now = now_millis();
+        match self {
+            RateLimitStore::Memory(store) => store.peek(key, quota, now),
+            RateLimitStore::Redis(store, fallback) => match store.peek(key, quota, now).await {
+                Ok(decision) => decision,
+                Err(e) => {
+                    warn!("Rate limit falls back to memory, Redis error: {}", e);
+                    fallback.peek(key, quota, now)
+                },
+            },
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use std::{collections::HashMap, net::IpAddr, sync::RwLock};
+
+use axum::http::Method;
+use chrono::{Local, NaiveDateTime};
+use ipnet::IpNet;
+use once_cell::sync::Lazy;
+use thiserror::Error;
+
+use crate::web::error::{ApiError, AppError};
+
+/// 访问密钥的访问策略，由服务层在密钥变更时维护
+static ACCESS_KEY_POLICIES: Lazy<RwLock<HashMap<String, AccessKeyPolicy>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// 访问密钥通过签名校验后被策略拒绝的原因
+///
+/// 错误码与服务层的访问密钥错误共用 5xxx 段。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum AccessKeyRejection {
+    #[error("Access key has expired")]
+    Expired,
+    #[error("Access key is not allowed to access this endpoint")]
+    OutOfScope,
+    #[error("Access key is not allowed from this IP address")]
+    IpNotAllowed,
+}
+
+impl ApiError for AccessKeyRejection {
+    fn code(&self) -> u16 {
+        match self {
+            AccessKeyRejection::Expired => 5101,
+            AccessKeyRejection::OutOfScope => 5102,
+            AccessKeyRejection::IpNotAllowed => 5103,
+        }
+    }
+
+    fn message(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl From<AccessKeyRejection> for AppError {
+    fn from(err: AccessKeyRejection) -> Self {
+        AppError {
+            code: err.code(),
+            message: err.message(),
+        }
+    }
+}
+
+/// 访问密钥可访问的范围
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccessKeyScope {
+    /// 指定请求方法的全部受保护接口
+    Method(Method),
+    /// 单个接口，路径为路由模板，如 `/user/:id`
+    Endpoint { method: Method, path: String },
+}
+
+impl AccessKeyScope {
+    fn matches(&self, method: &Method, path: &str) -> bool {
+        match self {
+            AccessKeyScope::Method(scope) => scope == method,
+            AccessKeyScope::Endpoint {
+                method: scope,
+                path: scope_path,
+            } => scope == method && scope_path == path,
+        }
+    }
+}
+
+/// 访问密钥的访问策略，未设置的项不做限制
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccessKeyPolicy {
+    /// 过期时间
+    pub expires_at: Option<NaiveDateTime>,
+    /// 可访问的范围，`Some` 的空列表表示不可访问任何接口
+    pub scopes: Option<Vec<AccessKeyScope>>,
+    /// 允许的来源 IP 网段
+    pub allowed_ips: Vec<IpNet>,
+}
+
+impl AccessKeyPolicy {
+    /// 校验请求是否符合策略
+    ///
+    /// # 参数
+    /// * `method` - 请求方法
+    /// * `path` - 路由模板
+    /// * `client_ip` - 客户端 IP
+    /// * `now` - 当前时间
+    pub fn check(
+        &self,
+        method: &Method,
+        path: &str,
+        client_ip: &str,
+        now: NaiveDateTime,
+    ) -> Result<(), AccessKeyRejection> {
+        if self.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return Err(AccessKeyRejection::Expired);
+        }
+
+        if !self.allowed_ips.is_empty() {
+            let allowed = client_ip
+                .parse::<IpAddr>()
+                .is_ok_and(|ip| self.allowed_ips.iter().any(|net| net.contains(&ip)));
+            if !allowed {
+                return Err(AccessKeyRejection::IpNotAllowed);
+            }
+        }
+
+        if let Some(scopes) = &self.scopes {
+            if !scopes.iter().any(|scope| scope.matches(method, path)) {
+                return Err(AccessKeyRejection::OutOfScope);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// 设置访问密钥的访问策略，`None` 表示不限制
+pub fn set_access_key_policy(access_key_id: &str, policy: Option<AccessKeyPolicy>) {
+    if let Ok(mut policies) = ACCESS_KEY_POLICIES.write() {
+        match policy {
+            Some(policy) => policies.insert(access_key_id.to_string(), policy),
+            None => policies.remove(access_key_id),
+        };
+    }
+}
+
+/// 按访问密钥的策略校验请求，未设置策略的密钥直接放行
+pub(crate) fn check_access_key_policy(
+    access_key_id: &str,
+    method: &Method,
+    path: &str,
+    client_ip: &str,
+) -> Result<(), AccessKeyRejection> {
+    let Ok(policies) = ACCESS_KEY_POLICIES.read() else {
+        return Ok(());
+    };
+    match policies.get(access_key_id) {
+        Some(policy) => policy.check(method, path, client_ip, Local::now().naive_local()),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_access_key_policy() {
+        let now = Local::now().naive_local();
+        let policy = AccessKeyPolicy {
+            expires_at: Some(now + Duration::hours(1)),
+            scopes: Some(vec![
+                AccessKeyScope::Method(Method::GET),
+                AccessKeyScope::Endpoint {
+                    method: Method::DELETE,
+                    path: "/user/:id".to_string(),
+                },
+            ]),
+            allowed_ips: vec!["10.0.0.0/8".parse().unwrap()],
+        };
+
+        assert_eq!(policy.check(&Method::GET, "/user", "10.1.2.3", now), Ok(()));
+        assert_eq!(
+            policy.check(&Method::DELETE, "/user/:id", "10.1.2.3", now),
+            Ok(())
+        );
+        assert_eq!(
+            policy.check(&Method::POST, "/user", "10.1.2.3", now),
+            Err(AccessKeyRejection::OutOfScope)
+        );
+        assert_eq!(
+            policy.check(&Method::GET, "/user", "192.168.1.1", now),
+            Err(AccessKeyRejection::IpNotAllowed)
+        );
+        assert_eq!(
+            policy.check(&Method::GET, "/user", "unknown", now),
+            Err(AccessKeyRejection::IpNotAllowed)
+        );
+        assert_eq!(
+            policy.check(&Method::GET, "/user", "10.1.2.3", now + Duration::hours(2)),
+            Err(AccessKeyRejection::Expired)
+        );
+        assert_eq!(
+            AccessKeyPolicy::default().check(&Method::POST, "/user", "unknown", now),
+            Ok(())
+        );
+
+        let policy = AccessKeyPolicy {
+            scopes: Some(Vec::new()),
+            ..Default::default()
+        };
+        assert_eq!(
+            policy.check(&Method::GET, "/user", "10.1.2.3", now),
+            Err(AccessKeyRejection::OutOfScope)
+        );
+    }
+}
@@ -1,8 +1,8 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
 use md5::{Digest, Md5};
 use parking_lot::RwLock;
-use ring::{digest, hmac};
+use ring::{digest, hmac, signature};
 use std::{
-    borrow::Cow,
     collections::HashMap,
     sync::Arc,
     time::{SystemTime, UNIX_EPOCH},
@@ -13,23 +13,43 @@ use crate::sign::nonce_store::{create_memory_store_factory, NonceStore, NonceSto
 /// Supported signature algorithms for API key validation.
 ///
 /// These algorithms are used to generate and validate signatures for API requests.
-/// The algorithms are ordered by performance (fastest to slowest).
+/// The digest algorithms (MD5, SHA1, SHA256) only support the legacy query signing,
+/// HMAC-SHA512 and Ed25519 only support canonical request signing.
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum SignatureAlgorithm {
-    /// MD5 signature algorithm (default, fastest)
+    /// MD5 signature algorithm (deprecated)
     Md5,
-    /// SHA1 signature algorithm
+    /// SHA1 signature algorithm (deprecated)
     Sha1,
     /// SHA256 signature algorithm
     Sha256,
-    /// HMAC-SHA256 signature algorithm (most secure)
+    /// HMAC-SHA256 signature algorithm (default)
     HmacSha256,
+    /// HMAC-SHA512 signature algorithm
+    HmacSha512,
+    /// Ed25519 public key signature, the key secret is the client's public key
+    Ed25519,
+}
+
+impl SignatureAlgorithm {
+    /// Whether the algorithm is deprecated and may be disabled by configuration.
+    #[inline]
+    pub fn is_deprecated(self) -> bool {
+        matches!(self, Self::Md5 | Self::Sha1)
+    }
+
+    /// Whether the algorithm may be used with the legacy query signing, which does not
+    /// cover the method, path or body of the request.
+    #[inline]
+    pub fn supports_legacy(self) -> bool {
+        !matches!(self, Self::HmacSha512 | Self::Ed25519)
+    }
 }
 
 impl Default for SignatureAlgorithm {
     #[inline]
     fn default() -> Self {
-        Self::Md5
+        Self::HmacSha256
     }
 }
 
@@ -39,8 +59,10 @@ impl Default for SignatureAlgorithm {
 /// It is designed to be lightweight and efficiently cloneable.
 #[derive(Debug, Clone, Copy)]
 pub struct ApiKeyConfig {
-    /// The signature algorithm to use for request validation
+    /// The signature algorithm for keys added without an explicit algorithm
     pub algorithm: SignatureAlgorithm,
+    /// Whether signatures made with deprecated algorithms (MD5, SHA1) are accepted
+    pub allow_deprecated: bool,
 }
 
 impl Default for ApiKeyConfig {
@@ -48,6 +70,7 @@ impl Default for ApiKeyConfig {
     fn default() -> Self {
         Self {
             algorithm: SignatureAlgorithm::default(),
+            allow_deprecated: false,
         }
     }
 }
@@ -116,19 +139,31 @@ impl Default for SimpleApiKeyValidator {
     }
 }
 
+/// Secrets of an access key.
+///
+/// After a rotation the previous secret keeps validating until it expires.
+#[derive(Clone, Debug)]
+struct KeySecrets {
+    /// Signature algorithm of the key, `None` uses the configured default.
+    algorithm: Option<SignatureAlgorithm>,
+    current: String,
+    /// Previous secret and its expiry in milliseconds since UNIX epoch.
+    previous: Option<(String, i64)>,
+}
+
 /// Complex API key validator that supports multiple signature algorithms and nonce validation.
 ///
 /// This validator provides advanced API key validation with features including:
-/// - Multiple signature algorithms (MD5, SHA1, SHA256, HMAC-SHA256)
+/// - Per-key signature algorithms (MD5, SHA1, SHA256, HMAC-SHA256, HMAC-SHA512, Ed25519)
 /// - Timestamp validation to prevent replay attacks
 /// - Nonce validation with automatic expiration
-/// - URL parameter signing
+/// - URL parameter signing and canonical request signing
 ///
 /// API keys and their corresponding secrets are stored permanently and can only be
 /// modified through explicit API calls.
 #[derive(Clone)]
 pub struct ComplexApiKeyValidator {
-    secrets: Arc<RwLock<HashMap<String, String>>>,
+    secrets: Arc<RwLock<HashMap<String, KeySecrets>>>,
     nonce_store: NonceStore,
     nonce_store_factory: NonceStoreFactory,
     config: ApiKeyConfig,
@@ -171,11 +206,28 @@ impl ComplexApiKeyValidator {
     /// Validates if a timestamp is within the allowed 5-minute window.
     #[inline]
     fn validate_timestamp(&self, timestamp: i64) -> bool {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as i64;
-        (now - timestamp).abs() < TIMESTAMP_DISPARITY_MS
+        (now_millis() - timestamp).abs() < TIMESTAMP_DISPARITY_MS
+    }
+
+    /// Whether the algorithm is accepted under the current configuration.
+    #[inline]
+    fn is_allowed(&self, algorithm: SignatureAlgorithm) -> bool {
+        self.config.allow_deprecated || !algorithm.is_deprecated()
+    }
+
+    /// Records the nonce of a verified request.
+    ///
+    /// Only call this once the signature has been verified, so that unauthenticated requests
+    /// cannot burn nonces of legitimate clients.
+    ///
+    /// # Returns
+    /// * `true` if the nonce was unused
+    /// * `false` if the request is a replay
+    pub fn consume_nonce(&self, nonce: &str) -> bool {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current()
+                .block_on(async { self.nonce_store.check_and_set(nonce).await })
+        })
     }
 
     /// Calculates signature for a signing string using the configured algorithm.
@@ -185,36 +237,18 @@ impl ComplexApiKeyValidator {
     /// * `secret` - The secret key to use for signing
     ///
     /// # Returns
-    /// The calculated signature as a hexadecimal string
+    /// The calculated signature as a hexadecimal string, empty for HMAC-SHA512 and Ed25519
+    /// which only support canonical request signing
     #[inline]
     pub fn calculate_signature(&self, signing_string: &str, secret: &str) -> String {
-        let signing_string = format!("{}&key={}", signing_string, secret);
-        match self.config.algorithm {
-            SignatureAlgorithm::Md5 => {
-                let mut hasher = Md5::new();
-                hasher.update(signing_string.as_bytes());
-                hex::encode(hasher.finalize())
-            },
-            SignatureAlgorithm::Sha1 => {
-                let mut context = digest::Context::new(&digest::SHA1_FOR_LEGACY_USE_ONLY);
-                context.update(signing_string.as_bytes());
-                hex::encode(context.finish())
-            },
-            SignatureAlgorithm::Sha256 => {
-                let mut context = digest::Context::new(&digest::SHA256);
-                context.update(signing_string.as_bytes());
-                hex::encode(context.finish())
-            },
-            SignatureAlgorithm::HmacSha256 => {
-                let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
-                let tag = hmac::sign(&key, signing_string.as_bytes());
-                hex::encode(tag.as_ref())
-            },
-        }
+        legacy_signature(self.config.algorithm, signing_string, secret).unwrap_or_default()
     }
 
     /// Validates a signed API request.
     ///
+    /// Keys whose algorithm only supports canonical request signing are rejected. The nonce
+    /// is only recorded after the signature has been verified.
+    ///
     /// # Arguments
     /// * `api_key` - The API key to validate
     /// * `params` - Vector of key-value pairs representing request parameters
@@ -237,20 +271,15 @@ impl ComplexApiKeyValidator {
             return false;
         }
 
-        let check_result = tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current()
-                .block_on(async { self.nonce_store.check_and_set(nonce).await })
-        });
-
-        if !check_result {
-            return false;
-        }
-
         let secrets_guard = self.secrets.read();
-        let secret = match secrets_guard.get(api_key) {
-            Some(s) => Cow::Borrowed(s),
+        let secrets = match secrets_guard.get(api_key) {
+            Some(secrets) => secrets,
             None => return false,
         };
+        let algorithm = secrets.algorithm.unwrap_or(self.config.algorithm);
+        if !algorithm.supports_legacy() || !self.is_allowed(algorithm) {
+            return false;
+        }
 
         // Pre-allocate with capacity to avoid reallocations
         let mut sorted_params: Vec<_> = Vec::with_capacity(params.len());
@@ -277,7 +306,94 @@ impl ComplexApiKeyValidator {
             }
         }
 
-        self.calculate_signature(&signing_string, &secret) == signature
+        let verified = secrets.any_valid(|secret| {
+            legacy_signature(algorithm, &signing_string, secret)
+                .is_some_and(|expected| expected == signature)
+        });
+        drop(secrets_guard);
+
+        verified && self.consume_nonce(nonce)
+    }
+
+    /// Validates a request signed with the canonical request scheme.
+    ///
+    /// Equivalent to [`authorize_canonical`](Self::authorize_canonical), then
+    /// [`verify_canonical_signature`](Self::verify_canonical_signature), then
+    /// [`consume_nonce`](Self::consume_nonce).
+    ///
+    /// # Arguments
+    /// * `api_key` - The API key to validate
+    /// * `algorithm` - The algorithm declared by the request, must match the key's algorithm
+    /// * `string_to_sign` - The string to sign built from the canonical request
+    /// * `signature` - The hexadecimal signature to validate
+    /// * `timestamp` - Request timestamp in milliseconds since UNIX epoch
+    /// * `nonce` - Unique request identifier to prevent replay attacks
+    ///
+    /// # Returns
+    /// * `true` if the request is valid
+    /// * `false` if any validation check fails
+    pub fn validate_canonical_signature(
+        &self,
+        api_key: &str,
+        algorithm: SignatureAlgorithm,
+        string_to_sign: &str,
+        signature: &str,
+        timestamp: i64,
+        nonce: &str,
+    ) -> bool {
+        self.authorize_canonical(api_key, algorithm, timestamp)
+            && self.verify_canonical_signature(api_key, algorithm, string_to_sign, signature)
+            && self.consume_nonce(nonce)
+    }
+
+    /// Checks everything of a canonical request that does not depend on its body.
+    ///
+    /// The key must exist with the declared algorithm and the timestamp must be within the
+    /// allowed window, so the request body only needs to be read once this passes. The nonce
+    /// is not touched here; record it with [`consume_nonce`](Self::consume_nonce) after
+    /// the signature has been verified.
+    ///
+    /// # Arguments
+    /// * `api_key` - The API key to validate
+    /// * `algorithm` - The algorithm declared by the request, must match the key's algorithm
+    /// * `timestamp` - Request timestamp in milliseconds since UNIX epoch
+    pub fn authorize_canonical(
+        &self,
+        api_key: &str,
+        algorithm: SignatureAlgorithm,
+        timestamp: i64,
+    ) -> bool {
+        let matches =
+            self.secrets.read().get(api_key).is_some_and(|secrets| {
+                secrets.algorithm.unwrap_or(self.config.algorithm) == algorithm
+            });
+        matches && self.is_allowed(algorithm) && self.validate_timestamp(timestamp)
+    }
+
+    /// Verifies the signature of a canonical request authorized by
+    /// [`authorize_canonical`](Self::authorize_canonical).
+    ///
+    /// # Arguments
+    /// * `api_key` - The API key of the request
+    /// * `algorithm` - The algorithm declared by the request
+    /// * `string_to_sign` - The string to sign built from the canonical request
+    /// * `signature` - The hexadecimal signature to validate
+    pub fn verify_canonical_signature(
+        &self,
+        api_key: &str,
+        algorithm: SignatureAlgorithm,
+        string_to_sign: &str,
+        signature: &str,
+    ) -> bool {
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+
+        self.secrets.read().get(api_key).is_some_and(|secrets| {
+            secrets.any_valid(|secret| {
+                verify_canonical_signature(algorithm, string_to_sign, secret, &signature)
+            })
+        })
     }
 
     /// Adds a new API key and its corresponding secret.
@@ -287,7 +403,33 @@ impl ComplexApiKeyValidator {
     /// * `secret` - The secret corresponding to the API key
     #[inline]
     pub fn add_key_secret(&self, key: String, secret: String) {
-        self.secrets.write().insert(key, secret);
+        self.add_key_secrets(key, None, secret, None);
+    }
+
+    /// Adds an API key with its algorithm, current secret and an optional previous secret.
+    ///
+    /// # Arguments
+    /// * `key` - The API key to add
+    /// * `algorithm` - The signature algorithm of the key, `None` uses the configured default
+    /// * `secret` - The current secret, or the Base64 public key for Ed25519
+    /// * `previous` - The previous secret and its expiry in milliseconds since UNIX epoch,
+    ///   signatures made with it are accepted until then
+    #[inline]
+    pub fn add_key_secrets(
+        &self,
+        key: String,
+        algorithm: Option<SignatureAlgorithm>,
+        secret: String,
+        previous: Option<(String, i64)>,
+    ) {
+        self.secrets.write().insert(
+            key,
+            KeySecrets {
+                algorithm,
+                current: secret,
+                previous,
+            },
+        );
     }
 
     /// Removes an API key and its secret.
@@ -309,6 +451,105 @@ impl ComplexApiKeyValidator {
     }
 }
 
+impl KeySecrets {
+    /// Checks the current secret, then the previous one if it has not expired.
+    fn any_valid(&self, verify: impl Fn(&str) -> bool) -> bool {
+        if verify(&self.current) {
+            return true;
+        }
+
+        match &self.previous {
+            Some((previous, expires_at)) if *expires_at > now_millis() => verify(previous),
+            _ => false,
+        }
+    }
+}
+
+/// Signs `{signing_string}&key={secret}` for the legacy query signing.
+///
+/// Returns `None` for HMAC-SHA512 and Ed25519, which only support canonical request signing.
+fn legacy_signature(
+    algorithm: SignatureAlgorithm,
+    signing_string: &str,
+    secret: &str,
+) -> Option<String> {
+    let signing_string = format!("{}&key={}", signing_string, secret);
+    let signature = match algorithm {
+        SignatureAlgorithm::Md5 => {
+            let mut hasher = Md5::new();
+            hasher.update(signing_string.as_bytes());
+            hex::encode(hasher.finalize())
+        },
+        SignatureAlgorithm::Sha1 => {
+            let mut context = digest::Context::new(&digest::SHA1_FOR_LEGACY_USE_ONLY);
+            context.update(signing_string.as_bytes());
+            hex::encode(context.finish())
+        },
+        SignatureAlgorithm::Sha256 => {
+            let mut context = digest::Context::new(&digest::SHA256);
+            context.update(signing_string.as_bytes());
+            hex::encode(context.finish())
+        },
+        SignatureAlgorithm::HmacSha256 => {
+            let tag = hmac::sign(&hmac_key(algorithm, secret)?, signing_string.as_bytes());
+            hex::encode(tag.as_ref())
+        },
+        SignatureAlgorithm::HmacSha512 | SignatureAlgorithm::Ed25519 => return None,
+    };
+    Some(signature)
+}
+
+fn hmac_key(algorithm: SignatureAlgorithm, secret: &str) -> Option<hmac::Key> {
+    let algorithm = match algorithm {
+        SignatureAlgorithm::HmacSha256 => hmac::HMAC_SHA256,
+        SignatureAlgorithm::HmacSha512 => hmac::HMAC_SHA512,
+        _ => return None,
+    };
+    Some(hmac::Key::new(algorithm, secret.as_bytes()))
+}
+
+/// Calculates the hexadecimal HMAC of a canonical request's string to sign.
+///
+/// Returns `None` for algorithms other than HMAC-SHA256 and HMAC-SHA512; Ed25519
+/// signatures are made by the client with its private key.
+pub fn calculate_canonical_signature(
+    algorithm: SignatureAlgorithm,
+    string_to_sign: &str,
+    secret: &str,
+) -> Option<String> {
+    let tag = hmac::sign(&hmac_key(algorithm, secret)?, string_to_sign.as_bytes());
+    Some(hex::encode(tag.as_ref()))
+}
+
+/// Verifies a canonical request signature in constant time.
+fn verify_canonical_signature(
+    algorithm: SignatureAlgorithm,
+    string_to_sign: &str,
+    secret: &str,
+    expected: &[u8],
+) -> bool {
+    match algorithm {
+        SignatureAlgorithm::HmacSha256 | SignatureAlgorithm::HmacSha512 => {
+            hmac_key(algorithm, secret)
+                .is_some_and(|key| hmac::verify(&key, string_to_sign.as_bytes(), expected).is_ok())
+        },
+        SignatureAlgorithm::Ed25519 => STANDARD.decode(secret).is_ok_and(|public_key| {
+            signature::UnparsedPublicKey::new(&signature::ED25519, public_key)
+                .verify(string_to_sign.as_bytes(), expected)
+                .is_ok()
+        }),
+        _ => false,
+    }
+}
+
+#[inline]
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -337,6 +578,7 @@ mod tests {
     async fn test_complex_validator() {
         let validator = ComplexApiKeyValidator::new(Some(ApiKeyConfig {
             algorithm: SignatureAlgorithm::Md5,
+            allow_deprecated: true,
         }));
 
         validator.add_key_secret("test-key".to_string(), "test-secret".to_string());
@@ -358,6 +600,169 @@ mod tests {
         assert!(validator.validate_signature("test-key", &params, &signature, now, "test-nonce"));
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_rotated_secret() {
+        let validator = ComplexApiKeyValidator::new(None);
+        let now = now_millis();
+        validator.add_key_secrets(
+            "test-key".to_string(),
+            None,
+            "new-secret".to_string(),
+            Some(("old-secret".to_string(), now + 60_000)),
+        );
+
+        let params = vec![("timestamp".to_string(), now.to_string())];
+        let signing_string = format!("timestamp={}", now);
+        for (nonce, secret) in [("n1", "new-secret"), ("n2", "old-secret")] {
+            let signature = validator.calculate_signature(&signing_string, secret);
+            assert!(validator.validate_signature("test-key", &params, &signature, now, nonce));
+        }
+
+        validator.add_key_secrets(
+            "test-key".to_string(),
+            None,
+            "new-secret".to_string(),
+            Some(("old-secret".to_string(), now - 1)),
+        );
+        let signature = validator.calculate_signature(&signing_string, "old-secret");
+        assert!(!validator.validate_signature("test-key", &params, &signature, now, "n3"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_deprecated_algorithm() {
+        let validator = ComplexApiKeyValidator::new(Some(ApiKeyConfig {
+            algorithm: SignatureAlgorithm::Md5,
+            allow_deprecated: false,
+        }));
+        validator.add_key_secret("md5-key".to_string(), "secret".to_string());
+        validator.add_key_secrets(
+            "hmac-key".to_string(),
+            Some(SignatureAlgorithm::HmacSha256),
+            "secret".to_string(),
+            None,
+        );
+
+        let now = now_millis();
+        let params = vec![("timestamp".to_string(), now.to_string())];
+        let signing_string = format!("timestamp={}", now);
+
+        let signature = validator.calculate_signature(&signing_string, "secret");
+        assert!(!validator.validate_signature("md5-key", &params, &signature, now, "n1"));
+
+        let signature =
+            legacy_signature(SignatureAlgorithm::HmacSha256, &signing_string, "secret").unwrap();
+        assert_eq!(signature.len(), 64);
+        assert!(validator.validate_signature("hmac-key", &params, &signature, now, "n2"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_canonical_only_algorithm() {
+        let validator = ComplexApiKeyValidator::new(None);
+        validator.add_key_secrets(
+            "hmac-key".to_string(),
+            Some(SignatureAlgorithm::HmacSha512),
+            "secret".to_string(),
+            None,
+        );
+
+        let now = now_millis();
+        let params = vec![("timestamp".to_string(), now.to_string())];
+        let signing_string = format!("timestamp={}", now);
+
+        // HMAC-SHA512 密钥不接受旧版查询参数签名
+        let mut context = hmac::Context::with_key(&hmac::Key::new(hmac::HMAC_SHA512, b"secret"));
+        context.update(format!("{}&key=secret", signing_string).as_bytes());
+        let signature = hex::encode(context.sign().as_ref());
+        assert!(!validator.validate_signature("hmac-key", &params, &signature, now, "n1"));
+        assert!(
+            legacy_signature(SignatureAlgorithm::HmacSha512, &signing_string, "secret").is_none()
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_canonical_signature() {
+        use ring::{rand::SystemRandom, signature::KeyPair};
+
+        let validator = ComplexApiKeyValidator::new(None);
+        validator.add_key_secrets(
+            "hmac-key".to_string(),
+            Some(SignatureAlgorithm::HmacSha512),
+            "secret".to_string(),
+            None,
+        );
+
+        let pkcs8 = signature::Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key_pair = signature::Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        validator.add_key_secrets(
+            "ed25519-key".to_string(),
+            Some(SignatureAlgorithm::Ed25519),
+            STANDARD.encode(key_pair.public_key().as_ref()),
+            None,
+        );
+
+        let now = now_millis();
+        let string_to_sign = "SOYBEAN-HMAC-SHA512\n1\nn\nhash";
+        let signature =
+            calculate_canonical_signature(SignatureAlgorithm::HmacSha512, string_to_sign, "secret")
+                .unwrap();
+        assert!(validator.validate_canonical_signature(
+            "hmac-key",
+            SignatureAlgorithm::HmacSha512,
+            string_to_sign,
+            &signature,
+            now,
+            "n1"
+        ));
+        // 算法须与密钥登记的一致
+        let signature =
+            calculate_canonical_signature(SignatureAlgorithm::HmacSha256, string_to_sign, "secret")
+                .unwrap();
+        assert!(!validator.validate_canonical_signature(
+            "hmac-key",
+            SignatureAlgorithm::HmacSha256,
+            string_to_sign,
+            &signature,
+            now,
+            "n2"
+        ));
+
+        let string_to_sign = "SOYBEAN-ED25519\n1\nn\nhash";
+        let signature = hex::encode(key_pair.sign(string_to_sign.as_bytes()));
+        assert!(validator.validate_canonical_signature(
+            "ed25519-key",
+            SignatureAlgorithm::Ed25519,
+            string_to_sign,
+            &signature,
+            now,
+            "n3"
+        ));
+        assert!(!validator.validate_canonical_signature(
+            "ed25519-key",
+            SignatureAlgorithm::Ed25519,
+            "tampered",
+            &signature,
+            now,
+            "n4"
+        ));
+        // 签名校验失败不占用 nonce，校验通过后 nonce 才被记录
+        assert!(validator.validate_canonical_signature(
+            "ed25519-key",
+            SignatureAlgorithm::Ed25519,
+            string_to_sign,
+            &signature,
+            now,
+            "n4"
+        ));
+        assert!(!validator.validate_canonical_signature(
+            "ed25519-key",
+            SignatureAlgorithm::Ed25519,
+            string_to_sign,
+            &signature,
+            now,
+            "n4"
+        ));
+    }
+
     #[test]
     fn test_concurrent_access() {
         let validator = Arc::new(ComplexApiKeyValidator::new(None));
@@ -1,7 +1,7 @@
 use axum::{
     body::Body,
     extract::Request,
-    http::{HeaderMap, StatusCode, Uri},
+    http::{header::AUTHORIZATION, request::Parts, HeaderMap, StatusCode, Uri},
     middleware::Next,
     response::IntoResponse,
 };
@@ -10,9 +10,20 @@ use server_constant::definition::consts::SystemEvent;
 use server_global::global;
 use std::{collections::HashSet, sync::RwLock};
 
-use crate::web::res::Res;
+use crate::web::{
+    error::AppError,
+    operation_log::{get_client_ip, get_route_path},
+    res::Res,
+};
+
+use super::{
+    access_key_policy::check_access_key_policy, ApiKeyEvent, CanonicalRequest,
+    ComplexApiKeyValidator, SignedAuthorization, SimpleApiKeyValidator, SIGN_NONCE_HEADER,
+    SIGN_TIMESTAMP_HEADER,
+};
 
-use super::{ApiKeyEvent, ComplexApiKeyValidator, SimpleApiKeyValidator};
+/// Default maximum size of a request body covered by canonical request signing.
+const DEFAULT_MAX_SIGNED_BODY_BYTES: usize = 1024 * 1024;
 
 /// Global set of protected paths.
 ///
@@ -63,6 +74,8 @@ pub struct ComplexApiKeyConfig {
     pub nonce_name: String,
     /// Signature parameter name.
     pub signature_name: String,
+    /// Maximum request body size in bytes for canonical request signing.
+    pub max_body_bytes: usize,
 }
 
 impl Default for ComplexApiKeyConfig {
@@ -72,10 +85,17 @@ impl Default for ComplexApiKeyConfig {
             timestamp_name: "timestamp".to_string(),
             nonce_name: "nonce".to_string(),
             signature_name: "signature".to_string(),
+            max_body_bytes: DEFAULT_MAX_SIGNED_BODY_BYTES,
         }
     }
 }
 
+/// Access key of a request that passed API key validation.
+///
+/// Inserted into request extensions so that inner layers can apply per-key policies.
+#[derive(Clone, Debug)]
+pub struct ValidatedAccessKey(pub String);
+
 /// API key validation strategy.
 ///
 /// This enum defines the possible API key validation strategies.
@@ -112,28 +132,91 @@ fn is_protected_path(uri: &Uri) -> bool {
 
 /// API key validation middleware.
 ///
-/// This middleware checks if the API key is valid for the given request.
+/// This middleware checks if the API key is valid for the given request, then applies the
+/// key's policy (expiry, IP allowlist and scopes). Every request with a valid key emits an
+/// `AuthApiKeyValidatedEvent`, including those rejected by the policy.
+///
+/// Complex validation accepts the legacy query signing, or canonical request signing when
+/// the request carries a signed `Authorization` header. The latter covers the method, path,
+/// query, signed headers and body; the body is only buffered, up to `max_body_bytes`, after
+/// the header, timestamp and key have been checked, and the nonce is only recorded once the
+/// signature has been verified.
 #[inline]
 pub async fn api_key_middleware(
     validator: ApiKeyValidation,
-    req: Request<Body>,
+    mut req: Request<Body>,
     next: Next,
 ) -> impl IntoResponse {
     if !is_protected_path(req.uri()) {
         return next.run(req).await.into_response();
     }
 
-    match validate_request(&validator, &req) {
-        Ok(true) => next.run(req).await.into_response(),
-        Ok(false) => Res::<()>::new_error(
-            StatusCode::UNAUTHORIZED.as_u16(),
-            "Invalid API key or signature",
-        )
-        .into_response(),
-        Err(e) => Res::<()>::new_error(StatusCode::BAD_REQUEST.as_u16(), e).into_response(),
+    let result = match &validator {
+        ApiKeyValidation::Complex(validator, config) if is_canonical_signed(req.headers()) => {
+            let (parts, body) = req.into_parts();
+            let signed = match authorize_canonical_request(validator, &parts) {
+                Ok(Some(signed)) => signed,
+                Ok(None) => return unauthorized().into_response(),
+                Err(e) => return bad_request(e).into_response(),
+            };
+            let body = match axum::body::to_bytes(body, config.max_body_bytes).await {
+                Ok(body) => body,
+                Err(_) => {
+                    return Res::<()>::new_error(
+                        StatusCode::PAYLOAD_TOO_LARGE.as_u16(),
+                        "Request body too large to verify",
+                    )
+                    .into_response()
+                },
+            };
+            let result = verify_canonical_request(validator, &parts, signed, &body);
+            req = Request::from_parts(parts, Body::from(body));
+            result
+        },
+        _ => validate_request(&validator, &req),
+    };
+
+    match result {
+        Ok(Some(api_key)) => {
+            let client_ip = get_client_ip(req.extensions(), req.headers());
+            let path = get_route_path(req.extensions(), req.uri());
+            let result = check_access_key_policy(&api_key, req.method(), &path, &client_ip);
+
+            global::send_dyn_event(
+                SystemEvent::AuthApiKeyValidatedEvent.as_ref(),
+                Box::new(ApiKeyEvent {
+                    api_key: api_key.clone(),
+                    client_ip,
+                    rejection: result.err(),
+                }),
+            );
+
+            match result {
+                Ok(()) => {
+                    req.extensions_mut().insert(ValidatedAccessKey(api_key));
+                    next.run(req).await.into_response()
+                },
+                Err(rejection) => AppError::from(rejection).into_response(),
+            }
+        },
+        Ok(None) => unauthorized().into_response(),
+        Err(e) => bad_request(e).into_response(),
     }
 }
 
+#[inline]
+fn unauthorized() -> Res<()> {
+    Res::new_error(
+        StatusCode::UNAUTHORIZED.as_u16(),
+        "Invalid API key or signature",
+    )
+}
+
+#[inline]
+fn bad_request(message: &str) -> Res<()> {
+    Res::new_error(StatusCode::BAD_REQUEST.as_u16(), message)
+}
+
 /// Get value from request headers.
 ///
 /// This function retrieves the value of a header from the request headers.
@@ -155,12 +238,12 @@ fn get_query_value<'a>(params: &'a [(String, String)], name: &str) -> Option<&'a
 
 /// Validate API key in request.
 ///
-/// This function validates the API key in the given request.
+/// This function validates the API key in the given request and returns the key if it is valid.
 #[inline]
 fn validate_request(
     validator: &ApiKeyValidation,
     req: &Request<Body>,
-) -> Result<bool, &'static str> {
+) -> Result<Option<String>, &'static str> {
     let headers = req.headers();
     let query = req.uri().query().unwrap_or("");
     let params = if !query.is_empty() {
@@ -177,13 +260,7 @@ fn validate_request(
             }
             .ok_or("Missing API key")?;
 
-            global::send_dyn_event(
-                SystemEvent::AuthApiKeyValidatedEvent.as_ref(),
-                Box::new(ApiKeyEvent {
-                    api_key: api_key.to_owned(),
-                }),
-            );
-            Ok(validator.validate_key(api_key))
+            Ok(validator.validate_key(api_key).then(|| api_key.to_owned()))
         },
         ApiKeyValidation::Complex(validator, config) => {
             let api_key =
@@ -205,23 +282,116 @@ fn validate_request(
                 .map(|(k, v)| (k.clone(), v.clone()))
                 .collect();
 
-            global::send_dyn_event(
-                SystemEvent::AuthApiKeyValidatedEvent.as_ref(),
-                Box::new(ApiKeyEvent {
-                    api_key: api_key.to_owned(),
-                }),
-            );
-            Ok(validator.validate_signature(
-                api_key,
-                &params_for_signing,
-                signature,
-                timestamp,
-                nonce,
-            ))
+            Ok(validator
+                .validate_signature(api_key, &params_for_signing, signature, timestamp, nonce)
+                .then(|| api_key.to_owned()))
         },
     }
 }
 
+/// Check if the request is signed with the canonical request scheme.
+#[inline]
+fn is_canonical_signed(headers: &HeaderMap) -> bool {
+    get_header_value(headers, AUTHORIZATION.as_str()).is_some_and(SignedAuthorization::matches)
+}
+
+/// A canonical request whose key and timestamp have been checked.
+struct AuthorizedCanonicalRequest {
+    authorization: SignedAuthorization,
+    timestamp: i64,
+    nonce: String,
+}
+
+/// Check the parts of a canonical request that do not depend on its body.
+///
+/// The timestamp and nonce headers must be among the signed headers. Returns `None` when the
+/// key is unknown, its algorithm differs, or the timestamp is out of the allowed window.
+fn authorize_canonical_request(
+    validator: &ComplexApiKeyValidator,
+    parts: &Parts,
+) -> Result<Option<AuthorizedCanonicalRequest>, &'static str> {
+    let authorization = get_header_value(&parts.headers, AUTHORIZATION.as_str())
+        .and_then(SignedAuthorization::parse)
+        .ok_or("Invalid Authorization header")?;
+    if [SIGN_TIMESTAMP_HEADER, SIGN_NONCE_HEADER]
+        .iter()
+        .any(|name| {
+            !authorization
+                .signed_headers
+                .iter()
+                .any(|signed| signed == name)
+        })
+    {
+        return Err("Timestamp and nonce headers must be signed");
+    }
+
+    let timestamp = get_header_value(&parts.headers, SIGN_TIMESTAMP_HEADER)
+        .ok_or("Missing timestamp")?
+        .parse::<i64>()
+        .map_err(|_| "Invalid timestamp")?;
+    let nonce = get_header_value(&parts.headers, SIGN_NONCE_HEADER).ok_or("Missing nonce")?;
+
+    Ok(validator
+        .authorize_canonical(
+            &authorization.access_key,
+            authorization.algorithm,
+            timestamp,
+        )
+        .then(|| AuthorizedCanonicalRequest {
+            authorization,
+            timestamp,
+            nonce: nonce.to_owned(),
+        }))
+}
+
+/// Verify the signature of an authorized canonical request against its body, then record
+/// its nonce.
+fn verify_canonical_request(
+    validator: &ComplexApiKeyValidator,
+    parts: &Parts,
+    signed: AuthorizedCanonicalRequest,
+    body: &[u8],
+) -> Result<Option<String>, &'static str> {
+    let AuthorizedCanonicalRequest {
+        authorization,
+        timestamp,
+        nonce,
+    } = signed;
+
+    let mut headers = Vec::with_capacity(authorization.signed_headers.len());
+    for name in &authorization.signed_headers {
+        let values: Vec<&str> = parts
+            .headers
+            .get_all(name.as_str())
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect();
+        if values.is_empty() {
+            return Err("Missing signed header");
+        }
+        headers.push((name.clone(), values.join(",")));
+    }
+
+    let canonical_request = CanonicalRequest::new(
+        parts.method.as_str(),
+        parts.uri.path(),
+        parts.uri.query().unwrap_or(""),
+        headers,
+        body,
+    );
+    let string_to_sign = canonical_request
+        .string_to_sign(authorization.algorithm, timestamp, &nonce)
+        .ok_or("Unsupported signature algorithm")?;
+
+    let verified = validator.verify_canonical_signature(
+        &authorization.access_key,
+        authorization.algorithm,
+        &string_to_sign,
+        &authorization.signature,
+    );
+    Ok((verified && validator.consume_nonce(&nonce)).then_some(authorization.access_key))
+}
+
 /// Parse query string into key-value pairs.
 ///
 /// This function parses a query string into a vector of key-value pairs.
@@ -244,7 +414,134 @@ fn parse_query(query: &str) -> Vec<(String, String)> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sign::{calculate_canonical_signature, SignatureAlgorithm};
+    use axum::{middleware::from_fn, routing::post, Router};
     use std::time::{SystemTime, UNIX_EPOCH};
+    use tower::ServiceExt;
+
+    fn signed_request(body: &'static str, signed_body: &str, nonce: &str) -> Request<Body> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        let headers = [
+            ("content-type".to_string(), "application/json".to_string()),
+            (SIGN_NONCE_HEADER.to_string(), nonce.to_string()),
+            (SIGN_TIMESTAMP_HEADER.to_string(), timestamp.to_string()),
+        ];
+        let string_to_sign = CanonicalRequest::new(
+            "POST",
+            "/canonical/orders",
+            "b=2&a=1",
+            headers,
+            signed_body.as_bytes(),
+        )
+        .string_to_sign(SignatureAlgorithm::HmacSha512, timestamp, nonce)
+        .unwrap();
+        let signature = calculate_canonical_signature(
+            SignatureAlgorithm::HmacSha512,
+            &string_to_sign,
+            "secret",
+        )
+        .unwrap();
+
+        Request::builder()
+            .method("POST")
+            .uri("/canonical/orders?a=1&b=2")
+            .header("content-type", "application/json")
+            .header(SIGN_NONCE_HEADER, nonce)
+            .header(SIGN_TIMESTAMP_HEADER, timestamp)
+            .header(
+                AUTHORIZATION,
+                format!(
+                    "SOYBEAN-HMAC-SHA512 Credential=AK1, \
+                     SignedHeaders=content-type;x-sign-nonce;x-sign-timestamp, Signature={}",
+                    signature
+                ),
+            )
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_canonical_request_middleware() {
+        let validator = ComplexApiKeyValidator::new(None);
+        validator.add_key_secrets(
+            "AK1".to_string(),
+            Some(SignatureAlgorithm::HmacSha512),
+            "secret".to_string(),
+            None,
+        );
+        let validation = ApiKeyValidation::Complex(
+            validator,
+            ComplexApiKeyConfig {
+                max_body_bytes: 32,
+                ..Default::default()
+            },
+        );
+
+        protect_route("/canonical/orders");
+        let app = Router::new()
+            .route(
+                "/canonical/orders",
+                post(|body: String| async move { body }),
+            )
+            .layer(from_fn(move |req, next| {
+                api_key_middleware(validation.clone(), req, next)
+            }));
+
+        let body = r#"{"amount":1}"#;
+        let response = app
+            .clone()
+            .oneshot(signed_request(body, body, "n1"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(bytes, body.as_bytes());
+
+        // 篡改请求体后签名失效
+        let response = app
+            .clone()
+            .oneshot(signed_request(r#"{"amount":100}"#, body, "n2"))
+            .await
+            .unwrap();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(String::from_utf8_lossy(&bytes).contains("Invalid API key or signature"));
+
+        // 签名校验失败的请求不会占用 nonce
+        let response = app
+            .clone()
+            .oneshot(signed_request(body, body, "n2"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // 重放的 nonce 被拒绝
+        let response = app
+            .clone()
+            .oneshot(signed_request(body, body, "n1"))
+            .await
+            .unwrap();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(String::from_utf8_lossy(&bytes).contains("Invalid API key or signature"));
+
+        let large = r#"{"amount":1,"note":"0123456789abcdefghij"}"#;
+        let response = app
+            .oneshot(signed_request(large, large, "n3"))
+            .await
+            .unwrap();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(String::from_utf8_lossy(&bytes).contains("Request body too large to verify"));
+    }
 
     #[test]
     fn test_api_key_sign() {
@@ -257,7 +554,7 @@ mod tests {
             .as_millis() as i64;
         let nonce = format!("nonce_{}", timestamp);
 
-        let mut params = vec![
+        let mut params = [
             ("AccessKeyId".to_string(), "test-access-key".to_string()),
             ("param1".to_string(), "value1".to_string()),
             ("param2".to_string(), "value2".to_string()),
@@ -0,0 +1,264 @@
+use ring::digest;
+
+use super::SignatureAlgorithm;
+
+/// 规范请求签名中携带时间戳（毫秒）的请求头
+pub const SIGN_TIMESTAMP_HEADER: &str = "x-sign-timestamp";
+/// 规范请求签名中携带 nonce 的请求头
+pub const SIGN_NONCE_HEADER: &str = "x-sign-nonce";
+
+/// `Authorization` 头中各签名算法的名称
+const SCHEME_NAMES: &[(&str, SignatureAlgorithm)] = &[
+    ("SOYBEAN-HMAC-SHA256", SignatureAlgorithm::HmacSha256),
+    ("SOYBEAN-HMAC-SHA512", SignatureAlgorithm::HmacSha512),
+    ("SOYBEAN-ED25519", SignatureAlgorithm::Ed25519),
+];
+
+/// 签名算法在 `Authorization` 头中的名称，不支持规范请求签名的算法返回 None
+pub fn scheme_name(algorithm: SignatureAlgorithm) -> Option<&'static str> {
+    SCHEME_NAMES
+        .iter()
+        .find(|(_, scheme)| *scheme == algorithm)
+        .map(|(name, _)| *name)
+}
+
+/// 解析后的 `Authorization` 头
+///
+/// 格式为 `SOYBEAN-HMAC-SHA512 Credential={AccessKeyId}, SignedHeaders={h1;h2}, Signature={hex}`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedAuthorization {
+    pub algorithm: SignatureAlgorithm,
+    pub access_key: String,
+    /// 参与签名的请求头，小写且已排序
+    pub signed_headers: Vec<String>,
+    pub signature: String,
+}
+
+impl SignedAuthorization {
+    /// 是否为规范请求签名的 `Authorization` 头
+    #[inline]
+    pub fn matches(value: &str) -> bool {
+        SCHEME_NAMES
+            .iter()
+            .any(|(name, _)| value.starts_with(name) && value[name.len()..].starts_with(' '))
+    }
+
+    /// 解析 `Authorization` 头，格式不正确时返回 None
+    pub fn parse(value: &str) -> Option<Self> {
+        let (name, params) = value.trim().split_once(' ')?;
+        let algorithm = SCHEME_NAMES
+            .iter()
+            .find(|(scheme, _)| *scheme == name)
+            .map(|(_, algorithm)| *algorithm)?;
+
+        let (mut access_key, mut signed_headers, mut signature) = (None, None, None);
+        for param in params.split(',') {
+            let (key, value) = param.trim().split_once('=')?;
+            match key {
+                "Credential" => access_key = Some(value.to_string()),
+                "SignedHeaders" => {
+                    let mut headers: Vec<String> = value
+                        .split(';')
+                        .filter(|header| !header.is_empty())
+                        .map(str::to_ascii_lowercase)
+                        .collect();
+                    headers.sort_unstable();
+                    headers.dedup();
+                    signed_headers = Some(headers);
+                },
+                "Signature" => signature = Some(value.to_ascii_lowercase()),
+                _ => return None,
+            }
+        }
+
+        Some(Self {
+            algorithm,
+            access_key: access_key.filter(|key| !key.is_empty())?,
+            signed_headers: signed_headers?,
+            signature: signature.filter(|signature| !signature.is_empty())?,
+        })
+    }
+}
+
+/// 规范请求，类似 AWS SigV4，覆盖请求方法、路径、排序后的查询参数、指定的请求头与请求体摘要
+///
+/// 规范请求的格式为：
+/// ```text
+/// {METHOD}\n
+/// {路径}\n
+/// {排序后的查询参数}\n
+/// {name:value\n ...}\n
+/// {参与签名的请求头，以 ; 分隔}\n
+/// {请求体 SHA-256 十六进制}
+/// ```
+#[derive(Debug, Clone)]
+pub struct CanonicalRequest {
+    method: String,
+    path: String,
+    query: String,
+    headers: Vec<(String, String)>,
+    body_hash: String,
+}
+
+impl CanonicalRequest {
+    /// 构造规范请求
+    ///
+    /// # 参数
+    /// * `method` - 请求方法
+    /// * `path` - 请求路径，按收到的原样参与签名
+    /// * `query` - 原始查询字符串
+    /// * `headers` - 参与签名的请求头，名称不区分大小写，同名的多个值以 `,` 连接
+    /// * `body` - 请求体
+    pub fn new(
+        method: &str,
+        path: &str,
+        query: &str,
+        headers: impl IntoIterator<Item = (String, String)>,
+        body: &[u8],
+    ) -> Self {
+        let mut headers: Vec<(String, String)> = headers
+            .into_iter()
+            .map(|(name, value)| {
+                (
+                    name.to_ascii_lowercase(),
+                    value.split_whitespace().collect::<Vec<_>>().join(" "),
+                )
+            })
+            .collect();
+        headers.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+        Self {
+            method: method.to_ascii_uppercase(),
+            path: if path.is_empty() { "/" } else { path }.to_string(),
+            query: canonical_query(query),
+            headers,
+            body_hash: hex_sha256(body),
+        }
+    }
+
+    /// 规范请求字符串
+    pub fn canonical_string(&self) -> String {
+        let mut canonical = format!("{}\n{}\n{}\n", self.method, self.path, self.query);
+        for (name, value) in &self.headers {
+            canonical.push_str(name);
+            canonical.push(':');
+            canonical.push_str(value);
+            canonical.push('\n');
+        }
+        canonical.push('\n');
+        let signed_headers: Vec<&str> =
+            self.headers.iter().map(|(name, _)| name.as_str()).collect();
+        canonical.push_str(&signed_headers.join(";"));
+        canonical.push('\n');
+        canonical.push_str(&self.body_hash);
+        canonical
+    }
+
+    /// 待签名字符串：`{算法名}\n{时间戳}\n{nonce}\n{规范请求的 SHA-256 十六进制}`
+    ///
+    /// 不支持规范请求签名的算法返回 None。
+    pub fn string_to_sign(
+        &self,
+        algorithm: SignatureAlgorithm,
+        timestamp: i64,
+        nonce: &str,
+    ) -> Option<String> {
+        Some(format!(
+            "{}\n{}\n{}\n{}",
+            scheme_name(algorithm)?,
+            timestamp,
+            nonce,
+            hex_sha256(self.canonical_string().as_bytes())
+        ))
+    }
+}
+
+/// 规范化查询字符串：解码后按 RFC 3986 重新编码，再按参数名与值排序
+fn canonical_query(query: &str) -> String {
+    let mut params: Vec<(String, String)> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (encode_component(key), encode_component(value))
+        })
+        .collect();
+    params.sort_unstable();
+
+    params
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn encode_component(value: &str) -> String {
+    let value = value.replace('+', " ");
+    let decoded = urlencoding::decode(&value).map_or(value.clone(), |decoded| decoded.into_owned());
+    urlencoding::encode(&decoded).into_owned()
+}
+
+#[inline]
+fn hex_sha256(data: &[u8]) -> String {
+    hex::encode(digest::digest(&digest::SHA256, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_request() {
+        let request = CanonicalRequest::new(
+            "post",
+            "/open/orders",
+            "b=2&a=hello%20world&a=%E4%BD%A0&c",
+            [
+                ("X-Sign-Nonce".to_string(), "n1".to_string()),
+                ("Content-Type".to_string(), " application/json ".to_string()),
+            ],
+            br#"{"amount":1}"#,
+        );
+
+        assert_eq!(
+            request.canonical_string(),
+            format!(
+                "POST\n/open/orders\na=%E4%BD%A0&a=hello%20world&b=2&c=\n\
+                 content-type:application/json\nx-sign-nonce:n1\n\n\
+                 content-type;x-sign-nonce\n{}",
+                hex_sha256(br#"{"amount":1}"#)
+            )
+        );
+
+        let string_to_sign = request
+            .string_to_sign(SignatureAlgorithm::HmacSha512, 1700000000000, "n1")
+            .unwrap();
+        assert!(string_to_sign.starts_with("SOYBEAN-HMAC-SHA512\n1700000000000\nn1\n"));
+        assert!(request
+            .string_to_sign(SignatureAlgorithm::Md5, 1700000000000, "n1")
+            .is_none());
+    }
+
+    #[test]
+    fn test_parse_authorization() {
+        let value = "SOYBEAN-ED25519 Credential=AK1, SignedHeaders=X-Sign-Timestamp;host, \
+                     Signature=ABCD";
+        assert!(SignedAuthorization::matches(value));
+        assert_eq!(
+            SignedAuthorization::parse(value),
+            Some(SignedAuthorization {
+                algorithm: SignatureAlgorithm::Ed25519,
+                access_key: "AK1".to_string(),
+                signed_headers: vec!["host".to_string(), "x-sign-timestamp".to_string()],
+                signature: "abcd".to_string(),
+            })
+        );
+
+        assert!(!SignedAuthorization::matches("Bearer token"));
+        assert!(SignedAuthorization::parse("SOYBEAN-MD5 Credential=AK1").is_none());
+        assert!(SignedAuthorization::parse(
+            "SOYBEAN-HMAC-SHA512 Credential=AK1, SignedHeaders=host"
+        )
+        .is_none());
+    }
+}
@@ -1,19 +1,30 @@
+mod access_key_policy;
 mod api_key;
 mod api_key_middleware;
+mod canonical_request;
 mod memory_nonce_store;
 mod nonce_store;
 mod redis_nonce_store;
+mod secret_cipher;
 
+pub use access_key_policy::{
+    set_access_key_policy, AccessKeyPolicy, AccessKeyRejection, AccessKeyScope,
+};
 pub use api_key::{
-    ApiKeyConfig, ComplexApiKeyValidator, SignatureAlgorithm, SimpleApiKeyValidator,
+    calculate_canonical_signature, ApiKeyConfig, ComplexApiKeyValidator, SignatureAlgorithm,
+    SimpleApiKeyValidator,
 };
 pub use api_key_middleware::{
     api_key_middleware, protect_route, ApiKeySource, ApiKeyValidation, ComplexApiKeyConfig,
-    SimpleApiKeyConfig,
+    SimpleApiKeyConfig, ValidatedAccessKey,
+};
+pub use canonical_request::{
+    scheme_name, CanonicalRequest, SignedAuthorization, SIGN_NONCE_HEADER, SIGN_TIMESTAMP_HEADER,
 };
 pub use memory_nonce_store::{create_memory_nonce_store_factory, MemoryNonceStore};
 pub use nonce_store::{NonceStore, NonceStoreFactory};
 pub use redis_nonce_store::{create_redis_nonce_store_factory, RedisNonceStore};
+pub use secret_cipher::{SecretCipher, SecretCipherError};
 
 use once_cell::sync::Lazy;
 use std::sync::Arc;
@@ -24,10 +35,12 @@ pub enum ValidatorType {
     Complex,
 }
 
-static API_KEY_VALIDATORS: Lazy<(
+type ApiKeyValidators = (
     Arc<RwLock<SimpleApiKeyValidator>>,
     Arc<RwLock<ComplexApiKeyValidator>>,
-)> = Lazy::new(|| {
+);
+
+static API_KEY_VALIDATORS: Lazy<ApiKeyValidators> = Lazy::new(|| {
     (
         Arc::new(RwLock::new(SimpleApiKeyValidator::new())),
         Arc::new(RwLock::new(ComplexApiKeyValidator::new(None))),
@@ -59,6 +72,27 @@ pub async fn add_key(validator_type: ValidatorType, key: &str, secret: Option<&s
     }
 }
 
+/// 添加指定签名算法的访问密钥，轮换过的旧密钥在过期前仍可用于签名
+///
+/// # 参数
+/// * `key` - 访问密钥 ID
+/// * `algorithm` - 签名算法
+/// * `secret` - 当前密钥，Ed25519 为客户端公钥
+/// * `previous` - 旧密钥及其过期时间（毫秒）
+pub async fn add_signing_key(
+    key: &str,
+    algorithm: SignatureAlgorithm,
+    secret: &str,
+    previous: Option<(&str, i64)>,
+) {
+    API_KEY_VALIDATORS.1.write().await.add_key_secrets(
+        key.to_string(),
+        Some(algorithm),
+        secret.to_string(),
+        previous.map(|(previous, expires_at)| (previous.to_string(), expires_at)),
+    );
+}
+
 pub async fn remove_key(validator_type: ValidatorType, key: &str) {
     match validator_type {
         ValidatorType::Simple => {
@@ -91,4 +125,8 @@ pub async fn init_validators_with_nonce_store(
 #[derive(Debug, Clone)]
 pub struct ApiKeyEvent {
     pub api_key: String,
+    /// 客户端 IP
+    pub client_ip: String,
+    /// 被访问策略拒绝的原因，通过时为 None
+    pub rejection: Option<AccessKeyRejection>,
 }
@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+
+use axum::http::StatusCode;
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine,
+};
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN},
+    rand::{SecureRandom, SystemRandom},
+};
+use thiserror::Error;
+
+use crate::web::error::{ApiError, AppError};
+
+/// 密文前缀，未带该前缀的值视为历史遗留的明文
+const ENCRYPTED_PREFIX: &str = "enc:v1:";
+/// 主密钥与数据密钥的长度
+const KEY_LEN: usize = 32;
+/// 生成的访问密钥的随机字节数
+const SECRET_LEN: usize = 32;
+/// 生成的访问密钥的前缀
+const SECRET_PREFIX: &str = "SK";
+
+#[derive(Debug, Error)]
+pub enum SecretCipherError {
+    #[error("Invalid encryption key '{0}', expected {KEY_LEN} bytes in Base64")]
+    InvalidKey(String),
+    #[error("Unknown encryption key '{0}'")]
+    UnknownKey(String),
+    #[error("Malformed encrypted secret")]
+    Malformed,
+    #[error("Failed to encrypt or decrypt secret")]
+    Crypto,
+}
+
+impl ApiError for SecretCipherError {
+    fn code(&self) -> u16 {
+        StatusCode::INTERNAL_SERVER_ERROR.as_u16()
+    }
+
+    fn message(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl From<SecretCipherError> for AppError {
+    fn from(err: SecretCipherError) -> Self {
+        AppError {
+            code: err.code(),
+            message: err.message(),
+        }
+    }
+}
+
+/// 访问密钥的信封加密
+///
+/// 每个密钥使用随机生成的数据密钥以 AES-256-GCM 加密，数据密钥再由主密钥加密后一同保存，
+/// 格式为 `enc:v1:{主密钥 ID}:{加密的数据密钥}:{加密的密钥}`。关联数据绑定访问密钥 ID，
+/// 密文无法被挪用到其他记录。更换主密钥时只需用新主密钥重新加密数据密钥。
+pub struct SecretCipher {
+    key_id: String,
+    keys: HashMap<String, LessSafeKey>,
+    rng: SystemRandom,
+}
+
+impl SecretCipher {
+    /// 创建加密器
+    ///
+    /// # 参数
+    /// * `key_id` - 当前主密钥 ID
+    /// * `key` - 当前主密钥，Base64 编码
+    /// * `retired_keys` - 已停用的主密钥 `(ID, Base64 密钥)`，仅用于解密
+    pub fn new<'a>(
+        key_id: &'a str,
+        key: &'a str,
+        retired_keys: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, SecretCipherError> {
+        let mut keys = HashMap::new();
+        for (id, key) in retired_keys.into_iter().chain([(key_id, key)]) {
+            keys.insert(id.to_string(), parse_key(id, key)?);
+        }
+
+        Ok(Self {
+            key_id: key_id.to_string(),
+            keys,
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// 是否为本加密器生成的密文
+    #[inline]
+    pub fn is_encrypted(value: &str) -> bool {
+        value.starts_with(ENCRYPTED_PREFIX)
+    }
+
+    /// 是否需要用当前主密钥重新加密，包括明文与旧主密钥加密的密文
+    pub fn needs_reencrypt(&self, value: &str) -> bool {
+        match value.strip_prefix(ENCRYPTED_PREFIX) {
+            Some(payload) => payload.split(':').next() != Some(self.key_id.as_str()),
+            None => true,
+        }
+    }
+
+    /// 生成访问密钥，为 `SK` 加上 32 字节随机数的 URL 安全 Base64 编码
+    pub fn generate_secret(&self) -> Result<String, SecretCipherError> {
+        let mut secret = [0u8; SECRET_LEN];
+        self.rng
+            .fill(&mut secret)
+            .map_err(|_| SecretCipherError::Crypto)?;
+        Ok(format!(
+            "{}{}",
+            SECRET_PREFIX,
+            URL_SAFE_NO_PAD.encode(secret)
+        ))
+    }
+
+    /// 加密密钥
+    ///
+    /// # 参数
+    /// * `secret` - 明文密钥
+    /// * `aad` - 关联数据，通常为访问密钥 ID
+    pub fn encrypt(&self, secret: &str, aad: &str) -> Result<String, SecretCipherError> {
+        let master = self
+            .keys
+            .get(&self.key_id)
+            .ok_or_else(|| SecretCipherError::UnknownKey(self.key_id.clone()))?;
+
+        let mut data_key = [0u8; KEY_LEN];
+        self.rng
+            .fill(&mut data_key)
+            .map_err(|_| SecretCipherError::Crypto)?;
+        let sealed_key = self.seal(master, &data_key, self.key_id.as_bytes())?;
+
+        let data_key = new_key(&data_key)?;
+        let sealed_secret = self.seal(&data_key, secret.as_bytes(), aad.as_bytes())?;
+
+        Ok(format!(
+            "{}{}:{}:{}",
+            ENCRYPTED_PREFIX,
+            self.key_id,
+            STANDARD.encode(sealed_key),
+            STANDARD.encode(sealed_secret)
+        ))
+    }
+
+    /// 解密密钥
+    ///
+    /// # 参数
+    /// * `value` - 密文
+    /// * `aad` - 加密时使用的关联数据
+    pub fn decrypt(&self, value: &str, aad: &str) -> Result<String, SecretCipherError> {
+        let payload = value
+            .strip_prefix(ENCRYPTED_PREFIX)
+            .ok_or(SecretCipherError::Malformed)?;
+        let mut parts = payload.split(':');
+        let (Some(key_id), Some(sealed_key), Some(sealed_secret), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(SecretCipherError::Malformed);
+        };
+
+        let master = self
+            .keys
+            .get(key_id)
+            .ok_or_else(|| SecretCipherError::UnknownKey(key_id.to_string()))?;
+        let data_key = open(master, sealed_key, key_id.as_bytes())?;
+        let data_key = new_key(&data_key)?;
+        let secret = open(&data_key, sealed_secret, aad.as_bytes())?;
+
+        String::from_utf8(secret).map_err(|_| SecretCipherError::Malformed)
+    }
+
+    /// 加密数据，输出为 `nonce || 密文 || tag`
+    fn seal(
+        &self,
+        key: &LessSafeKey,
+        plaintext: &[u8],
+        aad: &[u8],
+    ) -> Result<Vec<u8>, SecretCipherError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| SecretCipherError::Crypto)?;
+
+        let mut in_out = plaintext.to_vec();
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(aad),
+            &mut in_out,
+        )
+        .map_err(|_| SecretCipherError::Crypto)?;
+
+        let mut sealed = Vec::with_capacity(NONCE_LEN + in_out.len());
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&in_out);
+        Ok(sealed)
+    }
+}
+
+fn open(key: &LessSafeKey, sealed: &str, aad: &[u8]) -> Result<Vec<u8>, SecretCipherError> {
+    let sealed = STANDARD
+        .decode(sealed)
+        .map_err(|_| SecretCipherError::Malformed)?;
+    if sealed.len() < NONCE_LEN {
+        return Err(SecretCipherError::Malformed);
+    }
+
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    let nonce =
+        Nonce::try_assume_unique_for_key(nonce).map_err(|_| SecretCipherError::Malformed)?;
+    let mut in_out = ciphertext.to_vec();
+    let plaintext = key
+        .open_in_place(nonce, Aad::from(aad), &mut in_out)
+        .map_err(|_| SecretCipherError::Crypto)?;
+    Ok(plaintext.to_vec())
+}
+
+fn new_key(key: &[u8]) -> Result<LessSafeKey, SecretCipherError> {
+    UnboundKey::new(&AES_256_GCM, key)
+        .map(LessSafeKey::new)
+        .map_err(|_| SecretCipherError::Crypto)
+}
+
+fn parse_key(id: &str, key: &str) -> Result<LessSafeKey, SecretCipherError> {
+    let invalid = || SecretCipherError::InvalidKey(id.to_string());
+    if id.is_empty() || id.contains(':') {
+        return Err(invalid());
+    }
+
+    let key = STANDARD.decode(key.trim()).map_err(|_| invalid())?;
+    if key.len() != KEY_LEN {
+        return Err(invalid());
+    }
+    new_key(&key).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY_1: &str = "MDEyMzQ1Njc4OWFiY2RlZjAxMjM0NTY3ODlhYmNkZWY=";
+    const KEY_2: &str = "ZmVkY2JhOTg3NjU0MzIxMGZlZGNiYTk4NzY1NDMyMTA=";
+
+    #[test]
+    fn test_encrypt_and_decrypt() {
+        let cipher = SecretCipher::new("k1", KEY_1, []).unwrap();
+        let encrypted = cipher.encrypt("SKsecret", "AK1").unwrap();
+
+        assert!(SecretCipher::is_encrypted(&encrypted));
+        assert!(!encrypted.contains("SKsecret"));
+        assert_ne!(encrypted, cipher.encrypt("SKsecret", "AK1").unwrap());
+        assert_eq!(cipher.decrypt(&encrypted, "AK1").unwrap(), "SKsecret");
+        assert!(matches!(
+            cipher.decrypt(&encrypted, "AK2"),
+            Err(SecretCipherError::Crypto)
+        ));
+        assert!(matches!(
+            cipher.decrypt("SKsecret", "AK1"),
+            Err(SecretCipherError::Malformed)
+        ));
+    }
+
+    #[test]
+    fn test_master_key_rotation() {
+        let old = SecretCipher::new("k1", KEY_1, []).unwrap();
+        let encrypted = old.encrypt("SKsecret", "AK1").unwrap();
+
+        let cipher = SecretCipher::new("k2", KEY_2, [("k1", KEY_1)]).unwrap();
+        assert!(cipher.needs_reencrypt(&encrypted));
+        assert!(cipher.needs_reencrypt("SKsecret"));
+        assert_eq!(cipher.decrypt(&encrypted, "AK1").unwrap(), "SKsecret");
+
+        let reencrypted = cipher.encrypt("SKsecret", "AK1").unwrap();
+        assert!(!cipher.needs_reencrypt(&reencrypted));
+
+        let cipher = SecretCipher::new("k2", KEY_2, []).unwrap();
+        assert!(matches!(
+            cipher.decrypt(&encrypted, "AK1"),
+            Err(SecretCipherError::UnknownKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_generate_secret() {
+        let cipher = SecretCipher::new("k1", KEY_1, []).unwrap();
+        let secret = cipher.generate_secret().unwrap();
+
+        let encoded = secret.strip_prefix(SECRET_PREFIX).unwrap();
+        assert_eq!(URL_SAFE_NO_PAD.decode(encoded).unwrap().len(), SECRET_LEN);
+        assert_ne!(secret, cipher.generate_secret().unwrap());
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        assert!(SecretCipher::new("k1", "c2hvcnQ=", []).is_err());
+        assert!(SecretCipher::new("k:1", KEY_1, []).is_err());
+    }
+}
@@ -1,4 +1,3 @@
-use async_trait::async_trait;
 use axum::{
     extract::{FromRequest, Request},
     http::StatusCode,
@@ -94,6 +93,10 @@ impl User {
     pub fn domain(&self) -> String {
         self.domain.to_string()
     }
+
+    pub fn org(&self) -> Option<String> {
+        self.org.clone()
+    }
 }
 
 impl From<Claims> for User {
@@ -108,22 +111,16 @@ impl From<Claims> for User {
     }
 }
 
-#[async_trait]
 impl<S> FromRequest<S> for User
 where
     S: Send + Sync + 'static,
 {
     type Rejection = Res<String>;
 
-    fn from_request(
-        req: Request,
-        _state: &S,
-    ) -> impl std::future::Future<Output = Result<Self, Self::Rejection>> + Send {
-        async move {
-            req.extensions()
-                .get::<User>()
-                .cloned()
-                .ok_or_else(|| Res::new_error(StatusCode::UNAUTHORIZED.as_u16(), "Unauthorized"))
-        }
+    async fn from_request(req: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        req.extensions()
+            .get::<User>()
+            .cloned()
+            .ok_or_else(|| Res::new_error(StatusCode::UNAUTHORIZED.as_u16(), "Unauthorized"))
     }
 }
@@ -0,0 +1,158 @@
+use std::fmt::Debug;
+
+use axum::{
+    extract::FromRequestParts,
+    http::{
+        header::{ETAG, IF_MATCH},
+        request::Parts,
+        HeaderValue, StatusCode,
+    },
+    response::{IntoResponse, Response},
+};
+use sea_orm::DbErr;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::web::{
+    error::{ApiError, AppError},
+    res::Res,
+};
+
+/// 带乐观锁版本号的资源
+pub trait Versioned {
+    fn version(&self) -> i32;
+}
+
+#[derive(Debug, Error)]
+pub enum PreconditionError {
+    #[error("Resource has been modified by another request, reload and retry")]
+    VersionMismatch,
+    #[error("Expected version is required, send it in the body or the If-Match header")]
+    VersionRequired,
+    #[error("Invalid If-Match header")]
+    InvalidIfMatch,
+}
+
+impl ApiError for PreconditionError {
+    fn code(&self) -> u16 {
+        match self {
+            PreconditionError::VersionMismatch => StatusCode::CONFLICT.as_u16(),
+            PreconditionError::VersionRequired => StatusCode::PRECONDITION_REQUIRED.as_u16(),
+            PreconditionError::InvalidIfMatch => StatusCode::BAD_REQUEST.as_u16(),
+        }
+    }
+
+    fn message(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl From<PreconditionError> for AppError {
+    fn from(err: PreconditionError) -> Self {
+        AppError {
+            code: err.code(),
+            message: err.message(),
+        }
+    }
+}
+
+/// 生成版本号对应的 ETag
+pub fn etag(version: i32) -> String {
+    format!("\"{}\"", version)
+}
+
+/// 解析 ETag 中的版本号，兼容弱校验前缀 `W/`
+fn parse_etag(value: &str) -> Option<i32> {
+    let value = value.trim();
+    let value = value.strip_prefix("W/").unwrap_or(value);
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .and_then(|value| value.parse().ok())
+}
+
+/// 校验并返回期望的版本号
+///
+/// 未携带版本号时返回 428，与当前版本不一致时返回 409。
+pub fn check_version(current: i32, expected: Option<i32>) -> Result<i32, AppError> {
+    match expected {
+        None => Err(PreconditionError::VersionRequired.into()),
+        Some(expected) if expected != current => Err(PreconditionError::VersionMismatch.into()),
+        Some(expected) => Ok(expected),
+    }
+}
+
+/// 带版本条件的更新未命中任何行时视为并发修改
+pub fn map_version_conflict(err: DbErr) -> AppError {
+    match err {
+        DbErr::RecordNotUpdated => PreconditionError::VersionMismatch.into(),
+        err => AppError::from(err),
+    }
+}
+
+/// `If-Match` 请求头中的期望版本号，未携带或为 `*` 时为 `None`
+#[derive(Debug, Clone, Copy)]
+pub struct IfMatch(pub Option<i32>);
+
+impl<S> FromRequestParts<S> for IfMatch
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(value) = parts.headers.get(IF_MATCH) else {
+            return Ok(IfMatch(None));
+        };
+
+        let value = value
+            .to_str()
+            .map_err(|_| AppError::from(PreconditionError::InvalidIfMatch))?;
+
+        if value.trim() == "*" {
+            return Ok(IfMatch(None));
+        }
+
+        parse_etag(value)
+            .map(|version| IfMatch(Some(version)))
+            .ok_or_else(|| PreconditionError::InvalidIfMatch.into())
+    }
+}
+
+/// 附带 `ETag` 响应头的响应
+pub struct ETagged<T>(pub T);
+
+impl<T> IntoResponse for ETagged<T>
+where
+    T: Versioned + Serialize + Send + Sync + Debug + 'static,
+{
+    fn into_response(self) -> Response {
+        let etag = HeaderValue::from_str(&etag(self.0.version()));
+        let mut response = Res::new_data(self.0).into_response();
+        if let Ok(etag) = etag {
+            response.headers_mut().insert(ETAG, etag);
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_etag() {
+        assert_eq!(parse_etag(&etag(3)), Some(3));
+        assert_eq!(parse_etag("W/\"7\""), Some(7));
+        assert_eq!(parse_etag(" \"0\" "), Some(0));
+        assert_eq!(parse_etag("7"), None);
+        assert_eq!(parse_etag("\"abc\""), None);
+    }
+
+    #[test]
+    fn test_check_version() {
+        assert_eq!(check_version(2, Some(2)).unwrap(), 2);
+        assert_eq!(check_version(2, Some(1)).unwrap_err().code, 409);
+        assert_eq!(check_version(2, None).unwrap_err().code, 428);
+    }
+}
@@ -0,0 +1,338 @@
+use std::{
+    net::IpAddr,
+    sync::{Arc, RwLock},
+};
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use ipnet::IpNet;
+use once_cell::sync::Lazy;
+use xdb::Region;
+
+use super::{
+    auth::User,
+    operation_log::{get_client_ip, log_rejected_request},
+    redaction::Redactor,
+    res::Res,
+};
+use crate::sign::ValidatedAccessKey;
+
+/// 当前生效的 IP 规则，由服务层在规则变更后整体替换
+static IP_RULES: Lazy<RwLock<Arc<Vec<IpRule>>>> = Lazy::new(|| RwLock::new(Arc::new(Vec::new())));
+
+/// 规则动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpRuleAction {
+    Allow,
+    Deny,
+}
+
+/// 规则作用范围
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IpRuleScope {
+    /// 所有请求
+    Global,
+    /// 指定域下已登录用户的请求
+    Domain(String),
+    /// 指定访问密钥签名的请求
+    AccessKey(String),
+}
+
+/// IP 访问规则
+///
+/// `network`、`country`、`province` 中已设置的条件需要同时满足规则才算命中。
+#[derive(Debug, Clone)]
+pub struct IpRule {
+    pub id: String,
+    pub scope: IpRuleScope,
+    pub action: IpRuleAction,
+    pub network: Option<IpNet>,
+    pub country: Option<String>,
+    pub province: Option<String>,
+}
+
+impl IpRule {
+    fn needs_region(&self) -> bool {
+        self.country.is_some() || self.province.is_some()
+    }
+
+    fn matches(&self, ip: IpAddr, region: &Region) -> bool {
+        if let Some(network) = &self.network {
+            if !network.contains(&ip) {
+                return false;
+            }
+        }
+
+        let field_matches = |expected: &Option<String>, actual: &Option<String>| match expected {
+            Some(expected) => actual.as_deref() == Some(expected.as_str()),
+            None => true,
+        };
+
+        field_matches(&self.country, &region.country)
+            && field_matches(&self.province, &region.province)
+    }
+}
+
+/// 请求被拒绝的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IpRejection {
+    /// 命中拒绝规则
+    Denied { rule_id: String },
+    /// 所在范围存在允许规则但均未命中
+    NotAllowed,
+}
+
+impl IpRejection {
+    fn message(&self) -> &'static str {
+        match self {
+            IpRejection::Denied { .. } => "Access from this IP address is denied",
+            IpRejection::NotAllowed => "Access from this IP address is not allowed",
+        }
+    }
+}
+
+/// 替换当前生效的 IP 规则
+pub fn set_ip_rules(rules: Vec<IpRule>) {
+    if let Ok(mut current) = IP_RULES.write() {
+        *current = Arc::new(rules);
+    }
+}
+
+fn get_ip_rules() -> Arc<Vec<IpRule>> {
+    IP_RULES
+        .read()
+        .map(|rules| rules.clone())
+        .unwrap_or_default()
+}
+
+/// 按规则检查客户端 IP
+///
+/// 命中任一拒绝规则即拒绝；全局、域、访问密钥三个范围中，存在允许规则的范围
+/// 要求至少命中其中一条。地区仅在有规则需要时才查询，无法解析的 IP 不会命中任何规则。
+///
+/// # 参数
+/// * `rules` - 规则列表
+/// * `ip` - 客户端 IP 字符串
+/// * `domain` - 当前用户所在域
+/// * `access_key` - 当前请求使用的访问密钥
+pub fn check_ip(
+    rules: &[IpRule],
+    ip: &str,
+    domain: Option<&str>,
+    access_key: Option<&str>,
+) -> Result<(), IpRejection> {
+    let applicable: Vec<&IpRule> = rules
+        .iter()
+        .filter(|rule| match &rule.scope {
+            IpRuleScope::Global => true,
+            IpRuleScope::Domain(value) => domain == Some(value.as_str()),
+            IpRuleScope::AccessKey(value) => access_key == Some(value.as_str()),
+        })
+        .collect();
+
+    if applicable.is_empty() {
+        return Ok(());
+    }
+
+    let ip = ip.parse::<IpAddr>().ok().map(|ip| ip.to_canonical());
+    let region = match ip {
+        Some(ip) if applicable.iter().any(|rule| rule.needs_region()) => {
+            xdb::search_region(&ip.to_string()).unwrap_or_default()
+        },
+        _ => Region::default(),
+    };
+    let matches = |rule: &IpRule| ip.is_some_and(|ip| rule.matches(ip, &region));
+
+    if let Some(rule) = applicable
+        .iter()
+        .find(|rule| rule.action == IpRuleAction::Deny && matches(rule))
+    {
+        return Err(IpRejection::Denied {
+            rule_id: rule.id.clone(),
+        });
+    }
+
+    let scopes: [fn(&IpRuleScope) -> bool; 3] = [
+        |scope| matches!(scope, IpRuleScope::Global),
+        |scope| matches!(scope, IpRuleScope::Domain(_)),
+        |scope| matches!(scope, IpRuleScope::AccessKey(_)),
+    ];
+    for in_scope in scopes {
+        let mut allows = applicable
+            .iter()
+            .filter(|rule| rule.action == IpRuleAction::Allow && in_scope(&rule.scope))
+            .peekable();
+        if allows.peek().is_some() && !allows.any(|rule| matches(rule)) {
+            return Err(IpRejection::NotAllowed);
+        }
+    }
+
+    Ok(())
+}
+
+/// IP 访问控制中间件
+///
+/// 需放在认证与访问密钥校验之后，以便读取请求所属的域和访问密钥；
+/// 被拒绝的请求返回 403 错误码并记录到操作日志。
+pub async fn ip_filter_middleware(
+    redactor: Arc<Redactor>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let rules = get_ip_rules();
+    if rules.is_empty() {
+        return next.run(req).await;
+    }
+
+    let ip = get_client_ip(req.extensions(), req.headers());
+    let domain = req.extensions().get::<User>().map(User::domain);
+    let access_key = req
+        .extensions()
+        .get::<ValidatedAccessKey>()
+        .map(|key| key.0.as_str());
+
+    match check_ip(&rules, &ip, domain.as_deref(), access_key) {
+        Ok(()) => next.run(req).await,
+        Err(rejection) => {
+            let res = Res::<()>::new_error(StatusCode::FORBIDDEN.as_u16(), rejection.message());
+            let (parts, _) = req.into_parts();
+            log_rejected_request(&parts, &redactor, serde_json::to_value(&res).ok()).await;
+            res.into_response()
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(id: &str, scope: IpRuleScope, action: IpRuleAction, cidr: &str) -> IpRule {
+        IpRule {
+            id: id.to_string(),
+            scope,
+            action,
+            network: Some(cidr.parse().unwrap()),
+            country: None,
+            province: None,
+        }
+    }
+
+    #[test]
+    fn test_deny_rules() {
+        let rules = vec![
+            rule("1", IpRuleScope::Global, IpRuleAction::Deny, "10.0.0.0/8"),
+            rule(
+                "2",
+                IpRuleScope::Global,
+                IpRuleAction::Deny,
+                "2001:db8::/32",
+            ),
+        ];
+
+        assert_eq!(
+            check_ip(&rules, "10.1.2.3", None, None),
+            Err(IpRejection::Denied {
+                rule_id: "1".to_string()
+            })
+        );
+        assert_eq!(
+            check_ip(&rules, "2001:db8::1", None, None),
+            Err(IpRejection::Denied {
+                rule_id: "2".to_string()
+            })
+        );
+        assert!(check_ip(&rules, "::ffff:10.0.0.1", None, None).is_err());
+        assert_eq!(check_ip(&rules, "192.168.1.1", None, None), Ok(()));
+        assert_eq!(check_ip(&rules, "unknown", None, None), Ok(()));
+    }
+
+    #[test]
+    fn test_allow_rules_by_scope() {
+        let rules = vec![
+            rule(
+                "1",
+                IpRuleScope::Domain("built-in".to_string()),
+                IpRuleAction::Allow,
+                "192.168.0.0/16",
+            ),
+            rule(
+                "2",
+                IpRuleScope::AccessKey("AK1".to_string()),
+                IpRuleAction::Allow,
+                "172.16.0.1/32",
+            ),
+        ];
+
+        // 未命中范围的请求不受限制
+        assert_eq!(check_ip(&rules, "8.8.8.8", None, None), Ok(()));
+        assert_eq!(check_ip(&rules, "8.8.8.8", Some("other"), None), Ok(()));
+
+        assert_eq!(
+            check_ip(&rules, "192.168.1.1", Some("built-in"), None),
+            Ok(())
+        );
+        assert_eq!(
+            check_ip(&rules, "8.8.8.8", Some("built-in"), None),
+            Err(IpRejection::NotAllowed)
+        );
+        assert_eq!(
+            check_ip(&rules, "unknown", Some("built-in"), None),
+            Err(IpRejection::NotAllowed)
+        );
+
+        // 域与访问密钥的允许规则需要分别满足
+        assert_eq!(
+            check_ip(&rules, "192.168.1.1", Some("built-in"), Some("AK1")),
+            Err(IpRejection::NotAllowed)
+        );
+        assert_eq!(check_ip(&rules, "172.16.0.1", None, Some("AK1")), Ok(()));
+    }
+
+    #[test]
+    fn test_deny_takes_precedence() {
+        let rules = vec![
+            rule("1", IpRuleScope::Global, IpRuleAction::Allow, "10.0.0.0/8"),
+            rule(
+                "2",
+                IpRuleScope::Domain("built-in".to_string()),
+                IpRuleAction::Deny,
+                "10.0.0.1/32",
+            ),
+        ];
+
+        assert_eq!(check_ip(&rules, "10.0.0.1", None, None), Ok(()));
+        assert_eq!(
+            check_ip(&rules, "10.0.0.1", Some("built-in"), None),
+            Err(IpRejection::Denied {
+                rule_id: "2".to_string()
+            })
+        );
+        assert_eq!(check_ip(&rules, "10.0.0.2", Some("built-in"), None), Ok(()));
+    }
+
+    #[test]
+    fn test_region_match() {
+        let rule = IpRule {
+            id: "1".to_string(),
+            scope: IpRuleScope::Global,
+            action: IpRuleAction::Deny,
+            network: None,
+            country: Some("中国".to_string()),
+            province: Some("广东省".to_string()),
+        };
+        let ip: IpAddr = "1.2.3.4".parse().unwrap();
+
+        let region: Region = "中国|0|广东省|深圳市|电信".parse().unwrap();
+        assert!(rule.matches(ip, &region));
+
+        let region: Region = "中国|0|浙江省|杭州市|电信".parse().unwrap();
+        assert!(!rule.matches(ip, &region));
+
+        assert!(!rule.matches(ip, &Region::default()));
+    }
+}
@@ -1,8 +1,12 @@
 pub mod auth;
 pub mod error;
+pub mod etag;
+pub mod ip_filter;
 pub mod jwt;
 pub mod page;
+pub mod redaction;
 pub mod res;
+pub mod token_revocation;
 pub mod util;
 pub mod validator;
 
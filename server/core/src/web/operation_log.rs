@@ -1,40 +1,92 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     convert::Infallible,
     net::SocketAddr,
+    sync::{Arc, RwLock},
     task::{Context, Poll},
 };
 
 use axum::{
-    body::{to_bytes, Body, Bytes},
-    extract::{ConnectInfo, Request},
+    body::{Body, Bytes},
+    extract::{ConnectInfo, MatchedPath, Request},
     response::Response,
-    Extension,
 };
 use bytes::BytesMut;
 use chrono::Local;
-use futures::{future::BoxFuture, StreamExt};
-use http::{Extensions, HeaderMap, Uri};
-use serde_json::Value;
-use server_constant::definition::consts::SystemEvent;
+use futures::{future::BoxFuture, stream, StreamExt};
+use http::{
+    header::{CONTENT_DISPOSITION, CONTENT_LENGTH, CONTENT_TYPE},
+    request::Parts,
+    Extensions, HeaderMap, Method, StatusCode, Uri,
+};
+use once_cell::sync::Lazy;
+use serde_json::{Map, Value};
+use server_config::{CaptureConfig, RedactionConfig};
 use server_global::global::{self, OperationLogContext};
 use tower_layer::Layer;
 use tower_service::Service;
 
-use super::{auth::User, RequestId};
+use super::{auth::User, redaction::Redactor, RequestId};
 
 const USER_AGENT_HEADER: &str = "user-agent";
 const UNKNOWN_REQUEST_ID: &str = "unknown";
 const DEFAULT_BODY_CAPACITY: usize = 1024 * 16; // 16KB 默认缓冲区大小
+const FORM_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+const EVENT_STREAM_CONTENT_TYPE: &str = "text/event-stream";
+/// 截断标记字段
+pub const TRUNCATED_MARKER: &str = "_truncated";
+
+/// 不记录操作日志的路由集合，路由模板使用 `:param` 形式
+static SKIPPED_ROUTES: Lazy<RwLock<HashSet<(Method, String)>>> =
+    Lazy::new(|| RwLock::new(HashSet::new()));
+
+/// 将路由排除在操作日志之外
+///
+/// # 参数
+/// * `method` - 请求方法
+/// * `path` - 完整路由模板，如 `/auth/getUserInfo`、`/user/:id`
+pub fn skip_operation_log(method: Method, path: &str) {
+    if let Ok(mut routes) = SKIPPED_ROUTES.write() {
+        routes.insert((method, path.to_string()));
+    }
+}
+
+/// 判断路由是否被排除在操作日志之外
+#[inline]
+fn is_skipped(method: &Method, path: &str) -> bool {
+    SKIPPED_ROUTES
+        .read()
+        .map(|routes| routes.contains(&(method.clone(), path.to_string())))
+        .unwrap_or(false)
+}
 
 #[derive(Clone)]
 pub struct OperationLogLayer {
     pub enabled: bool,
+    redactor: Arc<Redactor>,
+    capture: CaptureConfig,
 }
 
 impl OperationLogLayer {
+    /// 创建操作日志层，使用内置脱敏规则
     pub fn new(enabled: bool) -> Self {
-        Self { enabled }
+        Self {
+            enabled,
+            redactor: Arc::new(Redactor::default()),
+            capture: CaptureConfig::default(),
+        }
+    }
+
+    /// 使用配置的脱敏规则
+    pub fn with_redaction(mut self, config: &RedactionConfig) -> Self {
+        self.redactor = Arc::new(Redactor::new(config));
+        self
+    }
+
+    /// 使用配置的请求与响应体采集上限
+    pub fn with_capture(mut self, config: CaptureConfig) -> Self {
+        self.capture = config;
+        self
     }
 }
 
@@ -51,6 +103,8 @@ where
         OperationLogMiddleware {
             inner: service,
             enabled: self.enabled,
+            redactor: self.redactor.clone(),
+            capture: self.capture,
         }
     }
 }
@@ -59,6 +113,8 @@ where
 pub struct OperationLogMiddleware<S> {
     inner: S,
     enabled: bool,
+    redactor: Arc<Redactor>,
+    capture: CaptureConfig,
 }
 
 impl<S> Service<Request<Body>> for OperationLogMiddleware<S>
@@ -83,13 +139,23 @@ where
             return Box::pin(async move { inner.call(req).await });
         }
 
+        let route_path = get_route_path(req.extensions(), req.uri());
+        if is_skipped(req.method(), &route_path) {
+            let mut inner = self.inner.clone();
+            return Box::pin(async move { inner.call(req).await });
+        }
+
         let mut inner = self.inner.clone();
+        let redactor = self.redactor.clone();
+        let capture = self.capture;
         Box::pin(async move {
             let start_time = Local::now().naive_local();
             let (parts, body) = req.into_parts();
             let headers = &parts.headers;
             let extensions = &parts.extensions;
 
+            let (module_name, description) = get_route_meta(&parts.method, &route_path);
+
             let (user_id, username, domain) = get_user_info(extensions);
 
             let request_id = extensions
@@ -97,90 +163,371 @@ where
                 .map(ToString::to_string)
                 .unwrap_or_else(|| UNKNOWN_REQUEST_ID.to_string());
 
-            if let Ok(bytes) = buffer_body(body).await {
-                let method = parts.method.to_string();
-                let uri = parts.uri.to_string();
-                let ip = get_client_ip(extensions, headers);
-                let user_agent = get_user_agent(headers);
-                let params = parse_query_params(&parts.uri);
-
-                let req = Request::from_parts(parts, Body::from(bytes.clone()));
-                let response = inner.call(req).await?;
-
-                let (response_parts, response_body) = response.into_parts();
-                let response_bytes = to_bytes(response_body, usize::MAX)
-                    .await
-                    .unwrap_or_default();
-
-                let end_time = Local::now().naive_local();
-                let duration = (end_time - start_time).num_milliseconds() as i32;
-
-                let context = OperationLogContext {
-                    user_id,
-                    username,
-                    domain,
-                    module_name: "TODO".to_string(),
-                    description: "TODO".to_string(),
-                    request_id,
-                    method,
-                    url: uri,
-                    ip,
-                    user_agent,
-                    params,
-                    body: (!bytes.is_empty())
-                        .then(|| serde_json::from_slice(&bytes).ok())
-                        .flatten(),
-                    response: serde_json::from_slice(&response_bytes).ok(),
-                    start_time,
-                    end_time,
-                    duration,
-                    created_at: start_time,
-                };
-
-                global::send_dyn_event(
-                    SystemEvent::AuditOperationLoggedEvent.as_ref(),
-                    Box::new(context),
-                );
+            let method = parts.method.to_string();
+            let uri = parts.uri.to_string();
+            let ip = get_client_ip(extensions, headers);
+            let user_agent = get_user_agent(headers);
+            let mut params = parse_query_params(&parts.uri);
+            let mut request_headers = get_headers(headers);
+
+            let request_content_type = get_content_type(headers);
+            let (body, mut request_body) = if is_loggable(request_content_type.as_deref()) {
+                let (body, captured) = capture_body(body, capture.max_request_body_bytes).await;
+                let value = captured
+                    .into_value(request_content_type.as_deref(), get_content_length(headers));
+                (body, value)
+            } else {
+                (body, None)
+            };
 
-                Ok(Response::from_parts(
-                    response_parts,
-                    Body::from(response_bytes),
-                ))
+            let req = Request::from_parts(parts, body);
+            let response = inner.call(req).await?;
+
+            let (response_parts, response_body) = response.into_parts();
+            let response_content_type = get_content_type(&response_parts.headers);
+            let (response_body, mut response_value) = if is_passthrough(&response_parts.headers)
+                || !is_loggable(response_content_type.as_deref())
+            {
+                (response_body, None)
             } else {
-                let mut inner = inner;
-                inner.call(Request::from_parts(parts, Body::empty())).await
+                let (body, captured) =
+                    capture_body(response_body, capture.max_response_body_bytes).await;
+                let value = captured.into_value(
+                    response_content_type.as_deref(),
+                    get_content_length(&response_parts.headers),
+                );
+                (body, value)
+            };
+
+            let end_time = Local::now().naive_local();
+            let duration = (end_time - start_time).num_milliseconds() as i32;
+            let status = response_status(response_parts.status, response_value.as_ref());
+
+            for value in [
+                &mut params,
+                &mut request_headers,
+                &mut request_body,
+                &mut response_value,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                redactor.redact(value);
             }
+
+            let context = OperationLogContext {
+                user_id,
+                username,
+                domain,
+                module_name,
+                description,
+                request_id,
+                method,
+                url: uri,
+                route: route_path,
+                ip,
+                user_agent,
+                params,
+                headers: request_headers,
+                body: request_body,
+                response: response_value,
+                start_time,
+                end_time,
+                duration,
+                status,
+                created_at: start_time,
+            };
+
+            // 仅供测试读取最近一次的日志上下文
+            #[cfg(test)]
+            OperationLogContext::set(context.clone()).await;
+            global::send_operation_log(context).await;
+
+            Ok(Response::from_parts(response_parts, response_body))
         })
     }
 }
 
-/// 缓冲请求体，带容量限制
+/// 记录在进入业务处理前即被拒绝的请求
+///
+/// 请求体尚未读取，因此仅记录查询参数与请求头，耗时记为 0。
 ///
 /// # 参数
-/// * `body` - 请求体，类型为 axum 的 Body
+/// * `parts` - 被拒绝请求的头部信息
+/// * `redactor` - 脱敏规则
+/// * `response` - 返回给客户端的响应体
+pub(crate) async fn log_rejected_request(
+    parts: &Parts,
+    redactor: &Redactor,
+    response: Option<Value>,
+) {
+    let now = Local::now().naive_local();
+    let extensions = &parts.extensions;
+    let headers = &parts.headers;
+
+    let route_path = get_route_path(extensions, &parts.uri);
+    let (module_name, description) = get_route_meta(&parts.method, &route_path);
+    let (user_id, username, domain) = get_user_info(extensions);
+
+    let request_id = extensions
+        .get::<RequestId>()
+        .map(ToString::to_string)
+        .unwrap_or_else(|| UNKNOWN_REQUEST_ID.to_string());
+
+    let mut params = parse_query_params(&parts.uri);
+    let mut request_headers = get_headers(headers);
+    let status = response_status(StatusCode::OK, response.as_ref());
+    let mut response = response;
+    for value in [&mut params, &mut request_headers, &mut response]
+        .into_iter()
+        .flatten()
+    {
+        redactor.redact(value);
+    }
+
+    let context = OperationLogContext {
+        user_id,
+        username,
+        domain,
+        module_name,
+        description,
+        request_id,
+        method: parts.method.to_string(),
+        url: parts.uri.to_string(),
+        route: route_path,
+        ip: get_client_ip(extensions, headers),
+        user_agent: get_user_agent(headers),
+        params,
+        headers: request_headers,
+        body: None,
+        response,
+        start_time: now,
+        end_time: now,
+        duration: 0,
+        status,
+        created_at: now,
+    };
+
+    #[cfg(test)]
+    OperationLogContext::set(context.clone()).await;
+    global::send_operation_log(context).await;
+}
+
+/// 响应状态码
+///
+/// `Res` 以 HTTP 200 返回业务错误，因此 HTTP 状态为 2xx 时取响应体中的 `code`。
+fn response_status(status: StatusCode, response: Option<&Value>) -> u16 {
+    if !status.is_success() {
+        return status.as_u16();
+    }
+    response
+        .and_then(|response| response.get("code"))
+        .and_then(Value::as_u64)
+        .and_then(|code| u16::try_from(code).ok())
+        .unwrap_or_else(|| status.as_u16())
+}
+
+/// 获取请求对应的路由模板
+///
+/// 优先使用 axum 匹配到的路由，并将 `{param}` 转换为 `RouteInfo` 使用的 `:param`
+/// 形式；未匹配时退回到请求路径。
+///
+/// # 参数
+/// * `extensions` - 请求扩展
+/// * `uri` - HTTP 请求 URI
 ///
 /// # 返回值
-/// * `Result<Bytes, Box<dyn std::error::Error + Send + Sync>>` -
-///   成功返回缓冲的字节数据，失败返回错误
+/// * `String` - 去除末尾 `/` 的路由模板
+pub(crate) fn get_route_path(extensions: &Extensions, uri: &Uri) -> String {
+    let Some(matched) = extensions.get::<MatchedPath>() else {
+        return trim_trailing_slash(uri.path()).to_string();
+    };
+
+    let path = matched
+        .as_str()
+        .split('/')
+        .map(|segment| {
+            match segment
+                .strip_prefix('{')
+                .and_then(|segment| segment.strip_suffix('}'))
+            {
+                Some(param) => format!(":{}", param.trim_start_matches('*')),
+                None => segment.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    trim_trailing_slash(&path).to_string()
+}
+
+#[inline(always)]
+fn trim_trailing_slash(path: &str) -> &str {
+    match path.strip_suffix('/') {
+        Some(path) if !path.is_empty() => path,
+        _ => path,
+    }
+}
+
+/// 根据收集的路由信息获取模块名称与操作描述
 ///
-/// # 错误
-/// * 当请求体大小超过 MAX_SIZE (32KB) 时返回错误
-/// * 当读取请求体流失败时返回错误
-#[inline]
-async fn buffer_body(body: Body) -> Result<Bytes, Box<dyn std::error::Error + Send + Sync>> {
-    const MAX_SIZE: usize = DEFAULT_BODY_CAPACITY * 2;
-    let mut bytes = BytesMut::with_capacity(DEFAULT_BODY_CAPACITY);
+/// 未登记的路由以首段路径作为模块名称，以 `METHOD path` 作为描述。
+///
+/// # 参数
+/// * `method` - 请求方法
+/// * `route_path` - 路由模板
+///
+/// # 返回值
+/// * `(String, String)` - (模块名称, 操作描述) 的元组
+fn get_route_meta(method: &Method, route_path: &str) -> (String, String) {
+    match global::find_route(method, route_path) {
+        Some(route) => (route.service_name, route.summary),
+        None => (
+            route_path
+                .split('/')
+                .find(|segment| !segment.is_empty())
+                .unwrap_or_default()
+                .to_string(),
+            format!("{} {}", method, route_path),
+        ),
+    }
+}
+
+/// 请求或响应体的采集结果
+enum CapturedBody {
+    /// 完整采集
+    Complete(Bytes),
+    /// 超出上限，仅保留前缀
+    Truncated(Bytes),
+    /// 读取数据流失败
+    Failed,
+}
 
+impl CapturedBody {
+    /// 按内容类型转换为日志值
+    ///
+    /// 截断的 JSON 与表单无法完整解析和脱敏，只记录截断标记；文本类型附带前缀预览。
+    fn into_value(self, content_type: Option<&str>, content_length: Option<u64>) -> Option<Value> {
+        match self {
+            CapturedBody::Complete(bytes) if bytes.is_empty() => None,
+            CapturedBody::Complete(bytes) => parse_body(content_type, &bytes),
+            CapturedBody::Truncated(prefix) => {
+                let mut marker = Map::new();
+                marker.insert(TRUNCATED_MARKER.to_string(), Value::Bool(true));
+                if let Some(length) = content_length {
+                    marker.insert("_size".to_string(), Value::from(length));
+                }
+                if content_type.is_some_and(is_text) {
+                    marker.insert(
+                        "_preview".to_string(),
+                        Value::String(String::from_utf8_lossy(&prefix).into_owned()),
+                    );
+                }
+                Some(Value::Object(marker))
+            },
+            CapturedBody::Failed => None,
+        }
+    }
+}
+
+/// 采集数据流前缀，原始数据完整转发
+///
+/// 最多读取 `limit` 字节（以及越界的那一块），超出部分不做缓冲，
+/// 已读取的数据与剩余数据流拼接后重新构造 `Body`。
+///
+/// # 参数
+/// * `body` - 请求体或响应体
+/// * `limit` - 最大采集字节数
+///
+/// # 返回值
+/// * `(Body, CapturedBody)` - 可继续使用的 Body 与采集结果
+async fn capture_body(body: Body, limit: usize) -> (Body, CapturedBody) {
+    let mut bytes = BytesMut::with_capacity(limit.min(DEFAULT_BODY_CAPACITY));
     let mut stream = body.into_data_stream();
+
     while let Some(chunk) = stream.next().await {
-        let chunk = chunk?;
-        if bytes.len() + chunk.len() > MAX_SIZE {
-            return Err("Request body too large".into());
-        }
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                let head = stream::iter([Ok(bytes.freeze()), Err(e)]);
+                return (Body::from_stream(head.chain(stream)), CapturedBody::Failed);
+            },
+        };
+
         bytes.extend_from_slice(&chunk);
+        if bytes.len() > limit {
+            let bytes = bytes.freeze();
+            let prefix = bytes.slice(..limit);
+            let head = stream::iter([Ok::<_, axum::Error>(bytes)]);
+            return (
+                Body::from_stream(head.chain(stream)),
+                CapturedBody::Truncated(prefix),
+            );
+        }
+    }
+
+    let bytes = bytes.freeze();
+    (Body::from(bytes.clone()), CapturedBody::Complete(bytes))
+}
+
+/// 按内容类型解析完整的请求或响应体
+#[inline]
+fn parse_body(content_type: Option<&str>, bytes: &[u8]) -> Option<Value> {
+    match content_type {
+        Some(ct) if ct == FORM_CONTENT_TYPE => {
+            let map = form_urlencoded::parse(bytes)
+                .into_owned()
+                .map(|(k, v)| (k, Value::String(v)))
+                .collect::<Map<_, _>>();
+            Some(Value::Object(map))
+        },
+        Some(ct) if is_text(ct) => Some(Value::String(String::from_utf8_lossy(bytes).into_owned())),
+        _ => serde_json::from_slice(bytes).ok(),
+    }
+}
+
+/// 获取不含参数的小写内容类型
+#[inline(always)]
+fn get_content_type(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .map(|v| v.trim().to_ascii_lowercase())
+}
+
+#[inline(always)]
+fn get_content_length(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
+
+#[inline(always)]
+fn is_text(content_type: &str) -> bool {
+    content_type.starts_with("text/") && content_type != EVENT_STREAM_CONTENT_TYPE
+}
+
+/// 内容类型是否需要记录，未声明时按 JSON 尝试解析
+#[inline]
+fn is_loggable(content_type: Option<&str>) -> bool {
+    match content_type {
+        None => true,
+        Some(ct) => {
+            ct == "application/json"
+                || ct.ends_with("+json")
+                || ct == FORM_CONTENT_TYPE
+                || is_text(ct)
+        },
     }
+}
 
-    Ok(bytes.freeze())
+/// SSE 与文件下载等响应直接透传，不做任何缓冲
+#[inline]
+fn is_passthrough(headers: &HeaderMap) -> bool {
+    get_content_type(headers).as_deref() == Some(EVENT_STREAM_CONTENT_TYPE)
+        || headers.contains_key(CONTENT_DISPOSITION)
 }
 
 /// 从请求头获取用户代理
@@ -198,7 +545,7 @@ fn get_user_agent(headers: &HeaderMap) -> Option<String> {
         .map(str::to_owned)
 }
 
-/// 获取客户端 IP，优先从扩展中获取
+/// 获取客户端 IP，按可信代理配置解析连接对端地址与转发请求头
 ///
 /// # 参数
 /// * `extensions` - 请求扩展
@@ -207,19 +554,39 @@ fn get_user_agent(headers: &HeaderMap) -> Option<String> {
 /// # 返回值
 /// * `String` - 客户端 IP 地址字符串
 #[inline(always)]
-fn get_client_ip(extensions: &Extensions, headers: &HeaderMap) -> String {
-    use std::net::IpAddr;
+pub(crate) fn get_client_ip(extensions: &Extensions, headers: &HeaderMap) -> String {
+    let peer = extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
 
-    if let Some(Extension(ConnectInfo(addr))) =
-        extensions.get::<Extension<ConnectInfo<SocketAddr>>>()
-    {
-        return match addr.ip() {
-            IpAddr::V4(ip) => ip.to_string(),
-            IpAddr::V6(ip) => ip.to_string(),
-        };
+    super::util::ClientIp::resolve(peer, headers)
+}
+
+/// 将请求头转换为 JSON 对象，同名请求头以 `, ` 拼接
+///
+/// # 参数
+/// * `headers` - HTTP 请求头映射
+///
+/// # 返回值
+/// * `Option<Value>` - 请求头为空时返回 None
+#[inline]
+fn get_headers(headers: &HeaderMap) -> Option<Value> {
+    if headers.is_empty() {
+        return None;
+    }
+
+    let mut map = Map::with_capacity(headers.keys_len());
+    for name in headers.keys() {
+        let value = headers
+            .get_all(name)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(", ");
+        map.insert(name.as_str().to_string(), Value::String(value));
     }
 
-    super::util::ClientIp::get_real_ip(headers)
+    Some(Value::Object(map))
 }
 
 /// 从扩展中获取用户信息元组
@@ -283,6 +650,9 @@ mod tests {
     use super::*;
     use crate::web::auth::{Claims, User};
 
+    /// 操作日志上下文为全局共享，读写上下文的测试需串行执行
+    static CONTEXT_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
     /// 创建测试用户
     fn create_test_user() -> User {
         let claims = Claims::new(
@@ -302,7 +672,7 @@ mod tests {
             .method(method)
             .uri(uri)
             .header("user-agent", "test-agent")
-            .header("X-Real-IP", "192.168.1.1")
+            .header("X-Forwarded-For", "192.168.1.1")
             .extension(create_test_user());
 
         if body.is_some() {
@@ -337,6 +707,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_operation_log_completeness() {
+        let _guard = CONTEXT_LOCK.lock().await;
         let test_cases = vec![
             // 基础场景
             (Method::GET, "/test", None, None),
@@ -397,14 +768,211 @@ mod tests {
             }
 
             let mut middleware = OperationLogMiddleware {
-                inner: service.clone(),
+                inner: service,
                 enabled: true,
+                redactor: Arc::new(Redactor::default()),
+                capture: CaptureConfig::default(),
             };
 
             let request = create_request(method.clone(), uri, body.clone());
             let _ = middleware.call(request).await.unwrap();
 
-            assert_context(&method.to_string(), uri, params, body).await;
+            assert_context(method.as_ref(), uri, params, body).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_operation_log_redaction() {
+        let _guard = CONTEXT_LOCK.lock().await;
+        let service = tower::service_fn(|_req: Request<Body>| async move {
+            let body = json!({"code": 200, "data": {"token": "jwt", "refreshToken": "jwt"}});
+            Ok::<_, Infallible>(Response::new(Body::from(body.to_string())))
+        });
+
+        let test_cases = vec![
+            (
+                RedactionConfig::default(),
+                json!({"userName": "admin", "password": "123456"}),
+                json!({"userName": "admin", "password": "******"}),
+                json!({"code": 200, "data": {"token": "******", "refreshToken": "******"}}),
+            ),
+            (
+                RedactionConfig {
+                    strategy: server_config::RedactStrategy::Drop,
+                    ..Default::default()
+                },
+                json!({"id": "1", "accessKeySecret": "s"}),
+                json!({"id": "1"}),
+                json!({"code": 200, "data": {}}),
+            ),
+            (
+                RedactionConfig {
+                    enabled: false,
+                    ..Default::default()
+                },
+                json!({"password": "123456"}),
+                json!({"password": "123456"}),
+                json!({"code": 200, "data": {"token": "jwt", "refreshToken": "jwt"}}),
+            ),
+        ];
+
+        for (config, body, expected_body, expected_response) in test_cases {
+            OperationLogContext::clear().await;
+
+            let mut middleware = OperationLogMiddleware {
+                inner: service,
+                enabled: true,
+                redactor: Arc::new(Redactor::new(&config)),
+                capture: CaptureConfig::default(),
+            };
+
+            let mut request =
+                create_request(Method::POST, "/test?token=abc&page=1", Some(body.clone()));
+            request
+                .headers_mut()
+                .insert("authorization", "Bearer jwt".parse().unwrap());
+            let _ = middleware.call(request).await.unwrap();
+
+            let ctx = OperationLogContext::get()
+                .await
+                .expect("Context should exist");
+            let headers = ctx.headers.expect("Headers should exist");
+            let (token, authorization) = if config.enabled {
+                match config.strategy {
+                    server_config::RedactStrategy::Drop => (None, None),
+                    _ => (Some(json!("******")), Some(json!("******"))),
+                }
+            } else {
+                (Some(json!("abc")), Some(json!("Bearer jwt")))
+            };
+
+            assert_eq!(ctx.body, Some(expected_body));
+            assert_eq!(ctx.response, Some(expected_response));
+            assert_eq!(
+                ctx.params.as_ref().and_then(|p| p.get("token")),
+                token.as_ref()
+            );
+            assert_eq!(ctx.params.unwrap()["page"], json!("1"));
+            assert_eq!(headers.get("authorization"), authorization.as_ref());
+            assert_eq!(headers["user-agent"], json!("test-agent"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_operation_log_capture_limits() {
+        let _guard = CONTEXT_LOCK.lock().await;
+        let capture = CaptureConfig {
+            max_request_body_bytes: 64,
+            max_response_body_bytes: 64,
+        };
+
+        // 超大请求体：处理器收到完整数据，日志只保留截断标记
+        let service = tower::service_fn(|req: Request<Body>| async move {
+            let bytes = axum::body::to_bytes(req.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let body = json!({"received": bytes.len(), "padding": "x".repeat(128)});
+            Ok::<_, Infallible>(
+                Response::builder()
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+        });
+        let mut middleware = OperationLogMiddleware {
+            inner: service,
+            enabled: true,
+            redactor: Arc::new(Redactor::default()),
+            capture,
+        };
+
+        let large = json!({"password": "secret", "data": "x".repeat(256)});
+        let large_len = serde_json::to_vec(&large).unwrap().len();
+        OperationLogContext::clear().await;
+        let response = middleware
+            .call(create_request(Method::POST, "/test", Some(large)))
+            .await
+            .unwrap();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(response["received"], json!(large_len));
+
+        let ctx = OperationLogContext::get()
+            .await
+            .expect("Context should exist");
+        assert_eq!(ctx.body, Some(json!({ TRUNCATED_MARKER: true })));
+        assert_eq!(ctx.response, Some(json!({ TRUNCATED_MARKER: true })));
+
+        // 文本响应截断时附带前缀预览
+        let service = tower::service_fn(|_req: Request<Body>| async move {
+            Ok::<_, Infallible>(
+                Response::builder()
+                    .header("content-type", "text/plain; charset=utf-8")
+                    .body(Body::from("y".repeat(100)))
+                    .unwrap(),
+            )
+        });
+        let mut middleware = OperationLogMiddleware {
+            inner: service,
+            enabled: true,
+            redactor: Arc::new(Redactor::default()),
+            capture,
+        };
+        OperationLogContext::clear().await;
+        let response = middleware
+            .call(create_request(Method::GET, "/test", None))
+            .await
+            .unwrap();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(bytes.len(), 100);
+        let ctx = OperationLogContext::get()
+            .await
+            .expect("Context should exist");
+        assert_eq!(
+            ctx.response,
+            Some(json!({ TRUNCATED_MARKER: true, "_preview": "y".repeat(64) }))
+        );
+
+        // SSE 与文件下载透传，不记录响应体
+        for (name, value) in [
+            ("content-type", "text/event-stream"),
+            ("content-disposition", "attachment; filename=\"a.csv\""),
+        ] {
+            let service = tower::service_fn(move |_req: Request<Body>| async move {
+                let events = futures::stream::iter([
+                    Ok::<_, Infallible>(Bytes::from("data: 1\n\n")),
+                    Ok(Bytes::from("data: 2\n\n")),
+                ]);
+                Ok::<_, Infallible>(
+                    Response::builder()
+                        .header(name, value)
+                        .body(Body::from_stream(events))
+                        .unwrap(),
+                )
+            });
+            let mut middleware = OperationLogMiddleware {
+                inner: service,
+                enabled: true,
+                redactor: Arc::new(Redactor::default()),
+                capture,
+            };
+            OperationLogContext::clear().await;
+            let response = middleware
+                .call(create_request(Method::GET, "/test", None))
+                .await
+                .unwrap();
+            let ctx = OperationLogContext::get()
+                .await
+                .expect("Context should exist");
+            assert_eq!(ctx.response, None);
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert_eq!(bytes, "data: 1\n\ndata: 2\n\n");
         }
     }
 
@@ -444,8 +1012,10 @@ mod tests {
             println!("\n▶ 测试错误场景: {:?}", expected_status);
 
             let mut middleware = OperationLogMiddleware {
-                inner: service.clone(),
+                inner: service,
                 enabled: true,
+                redactor: Arc::new(Redactor::default()),
+                capture: CaptureConfig::default(),
             };
 
             let mut request = create_request(method, uri, body);
@@ -462,9 +1032,72 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_route_meta_from_matched_path() {
+        use axum::{routing::put, Router};
+        use tower::ServiceExt;
+
+        global::add_route(global::RouteInfo::new(
+            "/meta/:id",
+            Method::PUT,
+            "MetaApi",
+            "更新元数据",
+        ))
+        .await;
+        skip_operation_log(Method::GET, "/meta");
+
+        let captured = std::sync::Arc::new(tokio::sync::Mutex::new(None));
+        let app = {
+            let captured = captured.clone();
+            Router::new()
+                .nest(
+                    "/meta",
+                    Router::new()
+                        .route("/", axum::routing::get(|| async { "ok" }))
+                        .route("/{id}", put(|| async { "ok" })),
+                )
+                .layer(axum::middleware::from_fn(
+                    move |req: Request<Body>, next: axum::middleware::Next| {
+                        let captured = captured.clone();
+                        async move {
+                            let path = get_route_path(req.extensions(), req.uri());
+                            *captured.lock().await = Some((
+                                is_skipped(req.method(), &path),
+                                get_route_meta(req.method(), &path),
+                            ));
+                            next.run(req).await
+                        }
+                    },
+                ))
+        };
+
+        let request = Request::builder()
+            .method(Method::PUT)
+            .uri("/meta/42")
+            .body(Body::empty())
+            .unwrap();
+        app.clone().oneshot(request).await.unwrap();
+        assert_eq!(
+            captured.lock().await.take(),
+            Some((false, ("MetaApi".to_string(), "更新元数据".to_string())))
+        );
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/meta")
+            .body(Body::empty())
+            .unwrap();
+        app.oneshot(request).await.unwrap();
+        assert_eq!(
+            captured.lock().await.take(),
+            Some((true, ("meta".to_string(), "GET /meta".to_string())))
+        );
+    }
+
     #[tokio::test]
     async fn test_disabled_middleware() {
         println!("\n=== Testing Disabled Middleware ===");
+        let _guard = CONTEXT_LOCK.lock().await;
         OperationLogContext::clear().await;
 
         let service = tower::service_fn(|_req: Request<Body>| async move {
@@ -474,6 +1107,8 @@ mod tests {
         let mut middleware = OperationLogMiddleware {
             inner: service,
             enabled: false,
+            redactor: Arc::new(Redactor::default()),
+            capture: CaptureConfig::default(),
         };
 
         let request = create_request(Method::POST, "/test", Some(json!({"test": true})));
@@ -482,4 +1117,28 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
         assert!(OperationLogContext::get().await.is_none());
     }
+
+    #[test]
+    fn test_response_status() {
+        assert_eq!(
+            response_status(StatusCode::OK, Some(&json!({"code": 200, "success": true}))),
+            200
+        );
+        assert_eq!(
+            response_status(
+                StatusCode::OK,
+                Some(&json!({"code": 1002, "success": false}))
+            ),
+            1002
+        );
+        assert_eq!(
+            response_status(StatusCode::PAYLOAD_TOO_LARGE, Some(&json!({"code": 200}))),
+            413
+        );
+        assert_eq!(response_status(StatusCode::OK, None), 200);
+        assert_eq!(
+            response_status(StatusCode::OK, Some(&json!({"code": "x"}))),
+            200
+        );
+    }
 }
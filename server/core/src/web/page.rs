@@ -1,4 +1,16 @@
+use std::collections::BTreeMap;
+
+use axum::http::StatusCode;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, NaiveDateTime};
+use sea_orm::{
+    sea_query::Order, ColumnTrait, ColumnType, Condition, ConnectionTrait, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Select, Value,
+};
 use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize};
+use thiserror::Error;
+
+use crate::web::error::{ApiError, AppError};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PageRequest {
@@ -22,6 +34,14 @@ where
     s.parse::<u64>().map_err(DeError::custom)
 }
 
+fn deserialize_bool_from_string<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: String = Deserialize::deserialize(deserializer)?;
+    s.parse::<bool>().map_err(DeError::custom)
+}
+
 fn default_current() -> u64 {
     1
 }
@@ -46,3 +66,491 @@ pub struct PaginatedData<T> {
     pub total: u64,
     pub records: Vec<T>,
 }
+
+/// 游标分页请求
+///
+/// 按 `(created_at, id)` 定位，翻页代价与页码无关，适合数据量持续增长的日志表。
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorRequest {
+    /// 上一页返回的 `nextCursor`，为空时从第一页开始
+    pub cursor: Option<String>,
+    #[serde(
+        default = "default_size",
+        deserialize_with = "deserialize_u64_from_string"
+    )]
+    pub size: u64,
+    /// 是否统计总数，大表上 `COUNT(*)` 代价较高，默认不统计
+    #[serde(default, deserialize_with = "deserialize_bool_from_string")]
+    pub with_total: bool,
+}
+
+impl Default for CursorRequest {
+    fn default() -> Self {
+        Self {
+            cursor: None,
+            size: default_size(),
+            with_total: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorPage<T> {
+    pub size: u64,
+    pub total: Option<u64>,
+    /// 下一页游标，没有更多数据时为空
+    pub next_cursor: Option<String>,
+    pub records: Vec<T>,
+}
+
+/// 可按 `(created_at, id)` 游标分页的记录
+pub trait CursorKey {
+    fn created_at(&self) -> NaiveDateTime;
+    fn id(&self) -> &str;
+}
+
+/// 分页游标
+///
+/// 编码为 base64url，客户端只需原样回传。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    pub created_at: NaiveDateTime,
+    pub id: String,
+}
+
+impl Cursor {
+    pub fn of<T: CursorKey>(record: &T) -> Self {
+        Self {
+            created_at: record.created_at(),
+            id: record.id().to_string(),
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!(
+            "{}:{}",
+            self.created_at.and_utc().timestamp_micros(),
+            self.id
+        ))
+    }
+
+    pub fn decode(value: &str) -> Result<Self, QueryError> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(value)
+            .map_err(|_| QueryError::InvalidCursor)?;
+        let text = String::from_utf8(bytes).map_err(|_| QueryError::InvalidCursor)?;
+        let (micros, id) = text.split_once(':').ok_or(QueryError::InvalidCursor)?;
+        let created_at = micros
+            .parse::<i64>()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or(QueryError::InvalidCursor)?
+            .naive_utc();
+
+        if id.is_empty() {
+            return Err(QueryError::InvalidCursor);
+        }
+
+        Ok(Self {
+            created_at,
+            id: id.to_string(),
+        })
+    }
+
+    /// 位于游标之后的记录，方向与排序一致
+    pub fn after<C: ColumnTrait>(&self, created_at: C, id: C, order: SortOrder) -> Condition {
+        let (created_at_after, id_after) = match order {
+            SortOrder::Asc => (created_at.gt(self.created_at), id.gt(self.id.as_str())),
+            SortOrder::Desc => (created_at.lt(self.created_at), id.lt(self.id.as_str())),
+        };
+
+        Condition::any().add(created_at_after).add(
+            Condition::all()
+                .add(created_at.eq(self.created_at))
+                .add(id_after),
+        )
+    }
+}
+
+/// 排序方向
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl From<SortOrder> for Order {
+    fn from(order: SortOrder) -> Self {
+        match order {
+            SortOrder::Asc => Order::Asc,
+            SortOrder::Desc => Order::Desc,
+        }
+    }
+}
+
+/// 列表查询条件
+///
+/// 与 `PageRequest` 一同展开到各 `*PageRequest` 中，哪些字段生效由实体的 [`QuerySpec`] 决定。
+/// 未被识别的查询参数收集到 `filters`，只有白名单内的字段会作为精确匹配条件。
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListQuery {
+    /// 排序字段
+    pub sort_by: Option<String>,
+    /// 排序方向，默认降序
+    pub sort_order: Option<SortOrder>,
+    /// 创建时间起始（含）
+    pub start_time: Option<NaiveDateTime>,
+    /// 创建时间截止（不含）
+    pub end_time: Option<NaiveDateTime>,
+    /// 状态
+    pub status: Option<String>,
+    /// 精确匹配条件
+    #[serde(flatten)]
+    pub filters: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Error)]
+pub enum QueryError {
+    #[error("Unsupported sort field: {0}")]
+    UnsupportedSortField(String),
+    #[error("Invalid status: {0}")]
+    InvalidStatus(String),
+    #[error("Invalid value for filter {0}")]
+    InvalidFilterValue(String),
+    #[error("Start time must be earlier than end time")]
+    InvalidTimeRange,
+    #[error("Invalid cursor")]
+    InvalidCursor,
+}
+
+impl ApiError for QueryError {
+    fn code(&self) -> u16 {
+        StatusCode::BAD_REQUEST.as_u16()
+    }
+
+    fn message(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl From<QueryError> for AppError {
+    fn from(err: QueryError) -> Self {
+        AppError {
+            code: err.code(),
+            message: err.message(),
+        }
+    }
+}
+
+/// 实体的列表查询白名单
+///
+/// 字段名使用接口中的驼峰命名，只有登记在此的列才会出现在 SQL 中。
+pub struct QuerySpec<C: 'static> {
+    /// 可排序字段
+    pub sort_fields: &'static [(&'static str, C)],
+    /// 可精确匹配字段
+    pub filter_fields: &'static [(&'static str, C)],
+    /// 状态列及其可选值
+    pub status: Option<(C, &'static [&'static str])>,
+    /// 创建时间列
+    pub created_at: C,
+    /// 主键列，排序值相同时用于保证顺序稳定
+    pub id: C,
+    /// 未指定排序字段时的排序
+    pub default_sort: (C, SortOrder),
+}
+
+impl<C: ColumnTrait> QuerySpec<C> {
+    /// 将查询条件应用到查询上
+    ///
+    /// 排序字段或状态不在白名单内时返回错误，实体不支持的状态参数和未登记的过滤字段会被忽略。
+    pub fn apply<E>(&self, query: Select<E>, params: &ListQuery) -> Result<Select<E>, AppError>
+    where
+        E: EntityTrait<Column = C>,
+    {
+        let query = self.apply_filters(query, params)?;
+        let (column, order) = self.sort(params)?;
+
+        Ok(query
+            .order_by(column, order.into())
+            .order_by(self.id, order.into()))
+    }
+
+    /// 只应用过滤条件，不附加排序
+    pub fn apply_filters<E>(
+        &self,
+        mut query: Select<E>,
+        params: &ListQuery,
+    ) -> Result<Select<E>, AppError>
+    where
+        E: EntityTrait<Column = C>,
+    {
+        if let (Some(start), Some(end)) = (params.start_time, params.end_time) {
+            if start >= end {
+                return Err(QueryError::InvalidTimeRange.into());
+            }
+        }
+        if let Some(start) = params.start_time {
+            query = query.filter(self.created_at.gte(start));
+        }
+        if let Some(end) = params.end_time {
+            query = query.filter(self.created_at.lt(end));
+        }
+
+        if let (Some(status), Some((column, allowed))) = (&params.status, self.status) {
+            if !allowed.contains(&status.as_str()) {
+                return Err(QueryError::InvalidStatus(status.clone()).into());
+            }
+            query = query.filter(column.eq(status.as_str()));
+        }
+
+        for (name, column) in self.filter_fields {
+            if let Some(value) = params.filters.get(*name) {
+                query = query.filter(column.eq(filter_value(column, name, value)?));
+            }
+        }
+
+        Ok(query)
+    }
+
+    /// 按 `(created_at, id)` 游标分页
+    ///
+    /// 只支持按创建时间排序，多取一条判断是否还有下一页；`with_total` 为真时才统计总数。
+    pub async fn paginate_by_cursor<E, Db>(
+        &self,
+        db: &Db,
+        query: Select<E>,
+        params: &ListQuery,
+        page: &CursorRequest,
+    ) -> Result<CursorPage<E::Model>, AppError>
+    where
+        E: EntityTrait<Column = C>,
+        E::Model: CursorKey + Sync,
+        Db: ConnectionTrait,
+    {
+        let order = match params.sort_by.as_deref() {
+            None | Some("") | Some("createdAt") => params.sort_order.unwrap_or_default(),
+            Some(field) => return Err(QueryError::UnsupportedSortField(field.to_string()).into()),
+        };
+        let mut query = self.apply_filters(query, params)?;
+
+        let total = if page.with_total {
+            Some(query.clone().count(db).await.map_err(AppError::from)?)
+        } else {
+            None
+        };
+
+        if let Some(ref cursor) = page.cursor {
+            query = query.filter(Cursor::decode(cursor)?.after(self.created_at, self.id, order));
+        }
+
+        let size = page.size.max(1);
+        let mut records = query
+            .order_by(self.created_at, order.into())
+            .order_by(self.id, order.into())
+            .limit(size + 1)
+            .all(db)
+            .await
+            .map_err(AppError::from)?;
+
+        let next_cursor = if records.len() as u64 > size {
+            records.truncate(size as usize);
+            records.last().map(|record| Cursor::of(record).encode())
+        } else {
+            None
+        };
+
+        Ok(CursorPage {
+            size,
+            total,
+            next_cursor,
+            records,
+        })
+    }
+
+    /// 解析排序列与方向
+    pub fn sort(&self, params: &ListQuery) -> Result<(C, SortOrder), AppError> {
+        let column = match params.sort_by.as_deref() {
+            None | Some("") => {
+                let (column, order) = self.default_sort;
+                return Ok((column, params.sort_order.unwrap_or(order)));
+            },
+            Some(field) => self
+                .sort_fields
+                .iter()
+                .find(|(name, _)| *name == field)
+                .map(|(_, column)| *column)
+                .ok_or_else(|| QueryError::UnsupportedSortField(field.to_string()))?,
+        };
+
+        Ok((column, params.sort_order.unwrap_or_default()))
+    }
+}
+
+/// 按列类型转换过滤值，避免布尔、整数列与文本比较
+fn filter_value<C: ColumnTrait>(column: &C, name: &str, value: &str) -> Result<Value, QueryError> {
+    let invalid = || QueryError::InvalidFilterValue(name.to_string());
+
+    Ok(match column.def().get_column_type() {
+        ColumnType::Boolean => value.parse::<bool>().map_err(|_| invalid())?.into(),
+        ColumnType::TinyInteger
+        | ColumnType::SmallInteger
+        | ColumnType::Integer
+        | ColumnType::BigInteger => value.parse::<i64>().map_err(|_| invalid())?.into(),
+        _ => value.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{extract::Query, http::Uri};
+    use sea_orm::{DbBackend, QueryTrait};
+
+    use super::*;
+
+    mod item {
+        use sea_orm::entity::prelude::*;
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+        #[sea_orm(table_name = "item")]
+        pub struct Model {
+            #[sea_orm(primary_key, auto_increment = false)]
+            pub id: String,
+            pub name: String,
+            pub built_in: bool,
+            pub status: String,
+            pub created_at: DateTime,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    const ITEM_QUERY: QuerySpec<item::Column> = QuerySpec {
+        sort_fields: &[
+            ("name", item::Column::Name),
+            ("createdAt", item::Column::CreatedAt),
+        ],
+        filter_fields: &[("builtIn", item::Column::BuiltIn)],
+        status: Some((item::Column::Status, &["enabled", "disabled"])),
+        created_at: item::Column::CreatedAt,
+        id: item::Column::Id,
+        default_sort: (item::Column::CreatedAt, SortOrder::Desc),
+    };
+
+    #[derive(Deserialize)]
+    struct ItemPageRequest {
+        #[serde(flatten)]
+        page_details: PageRequest,
+        keywords: Option<String>,
+        #[serde(flatten)]
+        query: ListQuery,
+    }
+
+    fn parse(query: &str) -> ItemPageRequest {
+        let uri: Uri = format!("/item?{}", query).parse().unwrap();
+        Query::<ItemPageRequest>::try_from_uri(&uri).unwrap().0
+    }
+
+    fn sql(query: &str) -> Result<String, AppError> {
+        let params = parse(query);
+        ITEM_QUERY
+            .apply(item::Entity::find(), &params.query)
+            .map(|select| select.build(DbBackend::Postgres).to_string())
+    }
+
+    #[test]
+    fn test_list_query_deserialize() {
+        let params = parse(
+            "current=2&size=20&keywords=a&sortBy=name&sortOrder=asc\
+             &startTime=2026-01-01T00:00:00&status=enabled&builtIn=true&_t=1",
+        );
+
+        assert_eq!(params.page_details.current, 2);
+        assert_eq!(params.page_details.size, 20);
+        assert_eq!(params.keywords.as_deref(), Some("a"));
+        assert_eq!(params.query.sort_by.as_deref(), Some("name"));
+        assert_eq!(params.query.sort_order, Some(SortOrder::Asc));
+        assert!(params.query.start_time.is_some());
+        assert_eq!(params.query.status.as_deref(), Some("enabled"));
+        assert_eq!(
+            params.query.filters.get("builtIn").map(String::as_str),
+            Some("true")
+        );
+        assert!(!params.query.filters.contains_key("current"));
+    }
+
+    #[test]
+    fn test_query_spec_apply() {
+        let query = sql("status=enabled&builtIn=true&startTime=2026-01-01T00:00:00&_t=1").unwrap();
+        assert!(query.contains(r#""item"."status" = 'enabled'"#));
+        assert!(query.contains(r#""item"."built_in" = TRUE"#));
+        assert!(query.contains(r#""item"."created_at" >= '2026-01-01 00:00:00'"#));
+        assert!(!query.contains("_t"));
+        assert!(query.ends_with(r#"ORDER BY "item"."created_at" DESC, "item"."id" DESC"#));
+
+        let query = sql("sortBy=name&sortOrder=asc").unwrap();
+        assert!(query.ends_with(r#"ORDER BY "item"."name" ASC, "item"."id" ASC"#));
+
+        assert_eq!(sql("sortBy=id;drop").unwrap_err().code, 400);
+        assert_eq!(sql("status=banned").unwrap_err().code, 400);
+        assert_eq!(sql("builtIn=yes").unwrap_err().code, 400);
+        assert_eq!(
+            sql("startTime=2026-01-02T00:00:00&endTime=2026-01-01T00:00:00")
+                .unwrap_err()
+                .code,
+            400
+        );
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let created_at = DateTime::from_timestamp_micros(1_760_000_000_123_456)
+            .unwrap()
+            .naive_utc();
+        let cursor = Cursor {
+            created_at,
+            id: "01JABCDEF".to_string(),
+        };
+
+        let encoded = cursor.encode();
+        assert!(!encoded.contains("01JABCDEF"));
+        assert_eq!(Cursor::decode(&encoded).unwrap(), cursor);
+
+        assert!(Cursor::decode("not a cursor").is_err());
+        assert!(Cursor::decode(&URL_SAFE_NO_PAD.encode("abc:id")).is_err());
+        assert!(Cursor::decode(&URL_SAFE_NO_PAD.encode("123:")).is_err());
+    }
+
+    #[test]
+    fn test_cursor_after() {
+        let cursor = Cursor {
+            created_at: DateTime::from_timestamp(1_767_225_600, 0)
+                .unwrap()
+                .naive_utc(),
+            id: "b".to_string(),
+        };
+
+        let query = item::Entity::find()
+            .filter(item::Column::Status.eq("enabled"))
+            .filter(cursor.after(item::Column::CreatedAt, item::Column::Id, SortOrder::Desc))
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(query.ends_with(
+            r#""item"."status" = 'enabled' AND ("item"."created_at" < '2026-01-01 00:00:00' OR ("item"."created_at" = '2026-01-01 00:00:00' AND "item"."id" < 'b'))"#
+        ));
+
+        let query = item::Entity::find()
+            .filter(cursor.after(item::Column::CreatedAt, item::Column::Id, SortOrder::Asc))
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(query.contains(r#""item"."id" > 'b'"#));
+    }
+}
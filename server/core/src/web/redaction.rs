@@ -0,0 +1,239 @@
+use once_cell::sync::Lazy;
+use ring::{
+    hmac,
+    rand::{SecureRandom, SystemRandom},
+};
+use serde_json::Value;
+use server_config::{RedactStrategy, RedactionConfig};
+
+const MASK: &str = "******";
+const HASH_PREFIX: &str = "hmac-sha256:";
+
+/// 未配置摘要密钥时使用的进程内随机密钥，各脱敏器共用以便同一进程内比对
+static RANDOM_HASH_KEY: Lazy<[u8; 32]> = Lazy::new(|| {
+    let mut key = [0u8; 32];
+    SystemRandom::new()
+        .fill(&mut key)
+        .expect("failed to generate redaction hash key");
+    key
+});
+
+/// 规则匹配方式
+enum Matcher {
+    /// 字段名通配模式（已转为小写）
+    Key(String),
+    /// JSON 路径分段，`*` 匹配任意字段
+    Path(Vec<String>),
+}
+
+impl Matcher {
+    fn parse(pattern: &str) -> Self {
+        match pattern.trim().strip_prefix('$') {
+            Some(path) => Matcher::Path(
+                path.split('.')
+                    .map(|segment| segment.trim_end_matches("[*]"))
+                    .filter(|segment| !segment.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            ),
+            None => Matcher::Key(pattern.trim().to_lowercase()),
+        }
+    }
+
+    fn matches(&self, key: &str, path: &[String]) -> bool {
+        match self {
+            Matcher::Key(pattern) => glob_match(pattern, &key.to_lowercase()),
+            Matcher::Path(segments) => {
+                segments.len() == path.len()
+                    && segments
+                        .iter()
+                        .zip(path)
+                        .all(|(segment, key)| segment == "*" || segment == key)
+            },
+        }
+    }
+}
+
+struct Rule {
+    matcher: Matcher,
+    strategy: RedactStrategy,
+}
+
+/// 敏感字段脱敏器
+///
+/// 按配置的规则递归处理 JSON 值，数组元素不占用路径分段。
+pub struct Redactor {
+    enabled: bool,
+    rules: Vec<Rule>,
+    hash_key: hmac::Key,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self::new(&RedactionConfig::default())
+    }
+}
+
+impl Redactor {
+    pub fn new(config: &RedactionConfig) -> Self {
+        let rules = config
+            .rules
+            .iter()
+            .map(|rule| Rule {
+                matcher: Matcher::parse(&rule.pattern),
+                strategy: rule.strategy.unwrap_or(config.strategy),
+            })
+            .collect();
+        let hash_key = match config.resolve_hash_key() {
+            Some(key) => hmac::Key::new(hmac::HMAC_SHA256, key.as_bytes()),
+            None => hmac::Key::new(hmac::HMAC_SHA256, RANDOM_HASH_KEY.as_slice()),
+        };
+
+        Self {
+            enabled: config.enabled,
+            rules,
+            hash_key,
+        }
+    }
+
+    /// 就地脱敏 JSON 值
+    pub fn redact(&self, value: &mut Value) {
+        if self.enabled && !self.rules.is_empty() {
+            self.walk(value, &mut Vec::new());
+        }
+    }
+
+    fn walk(&self, value: &mut Value, path: &mut Vec<String>) {
+        match value {
+            Value::Object(map) => {
+                let keys: Vec<String> = map.keys().cloned().collect();
+                for key in keys {
+                    path.push(key.clone());
+                    match self.find_strategy(&key, path) {
+                        Some(RedactStrategy::Drop) => {
+                            map.remove(&key);
+                        },
+                        Some(strategy) => {
+                            if let Some(field) = map.get_mut(&key) {
+                                *field = apply(strategy, field, &self.hash_key);
+                            }
+                        },
+                        None => {
+                            if let Some(field) = map.get_mut(&key) {
+                                self.walk(field, path);
+                            }
+                        },
+                    }
+                    path.pop();
+                }
+            },
+            Value::Array(items) => items.iter_mut().for_each(|item| self.walk(item, path)),
+            _ => {},
+        }
+    }
+
+    fn find_strategy(&self, key: &str, path: &[String]) -> Option<RedactStrategy> {
+        self.rules
+            .iter()
+            .find(|rule| rule.matcher.matches(key, path))
+            .map(|rule| rule.strategy)
+    }
+}
+
+/// 按策略生成替换值，`Drop` 由调用方移除字段
+fn apply(strategy: RedactStrategy, value: &Value, hash_key: &hmac::Key) -> Value {
+    match strategy {
+        RedactStrategy::Hash => {
+            let raw = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            let tag = hmac::sign(hash_key, raw.as_bytes());
+            Value::String(format!("{}{}", HASH_PREFIX, hex::encode(tag.as_ref())))
+        },
+        RedactStrategy::Mask | RedactStrategy::Drop => Value::String(MASK.to_string()),
+    }
+}
+
+/// 仅支持 `*` 的通配匹配
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use server_config::RedactionRule;
+
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("password", "password"));
+        assert!(glob_match("*secret*", "accesskeysecret"));
+        assert!(glob_match("*token", "refreshtoken"));
+        assert!(glob_match("a*b*c", "a-b-c"));
+        assert!(!glob_match("*token", "tokentype"));
+        assert!(!glob_match("password", "passwords"));
+    }
+
+    #[test]
+    fn test_redact_strategies() {
+        let redactor = Redactor::new(&RedactionConfig {
+            enabled: true,
+            strategy: RedactStrategy::Mask,
+            rules: vec![
+                RedactionRule::new("password"),
+                RedactionRule {
+                    pattern: "$.data.records.secret".to_string(),
+                    strategy: Some(RedactStrategy::Hash),
+                },
+                RedactionRule {
+                    pattern: "$.data.*.token".to_string(),
+                    strategy: Some(RedactStrategy::Drop),
+                },
+            ],
+            hash_key: Some("redaction-key".to_string()),
+        });
+
+        let mut value = json!({
+            "Password": 123,
+            "data": {
+                "records": [{"secret": "s1", "name": "a"}],
+                "session": {"token": "t", "expire": 1}
+            }
+        });
+        redactor.redact(&mut value);
+
+        let key = hmac::Key::new(hmac::HMAC_SHA256, b"redaction-key");
+        let hash = hex::encode(hmac::sign(&key, b"s1").as_ref());
+        assert_eq!(
+            value,
+            json!({
+                "Password": MASK,
+                "data": {
+                    "records": [{"secret": format!("{}{}", HASH_PREFIX, hash), "name": "a"}],
+                    "session": {"expire": 1}
+                }
+            })
+        );
+    }
+}
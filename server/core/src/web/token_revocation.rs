@@ -0,0 +1,79 @@
+use std::{collections::HashMap, sync::RwLock};
+
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use ring::digest;
+
+/// 已失效但尚未过期的访问令牌，键为令牌指纹，值为过期时间（Unix 秒）
+///
+/// 由服务层从数据库加载，撤销令牌后立即加入；过期的条目在下次写入时清理。
+static REVOKED_TOKENS: Lazy<RwLock<HashMap<String, i64>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// 访问令牌的指纹，避免在内存中保留令牌原文
+pub fn token_fingerprint(token: &str) -> String {
+    hex::encode(digest::digest(&digest::SHA256, token.as_bytes()))
+}
+
+/// 替换全部已撤销的令牌
+///
+/// # 参数
+/// * `tokens` - 令牌原文与过期时间（Unix 秒）
+pub fn set_revoked_tokens(tokens: impl IntoIterator<Item = (String, i64)>) {
+    let now = Utc::now().timestamp();
+    let tokens = tokens
+        .into_iter()
+        .filter(|(_, expires_at)| *expires_at > now)
+        .map(|(token, expires_at)| (token_fingerprint(&token), expires_at))
+        .collect();
+    if let Ok(mut revoked) = REVOKED_TOKENS.write() {
+        *revoked = tokens;
+    }
+}
+
+/// 追加已撤销的令牌
+///
+/// # 参数
+/// * `tokens` - 令牌原文与过期时间（Unix 秒）
+pub fn revoke_tokens(tokens: impl IntoIterator<Item = (String, i64)>) {
+    let now = Utc::now().timestamp();
+    if let Ok(mut revoked) = REVOKED_TOKENS.write() {
+        revoked.retain(|_, expires_at| *expires_at > now);
+        revoked.extend(
+            tokens
+                .into_iter()
+                .filter(|(_, expires_at)| *expires_at > now)
+                .map(|(token, expires_at)| (token_fingerprint(&token), expires_at)),
+        );
+    }
+}
+
+/// 令牌是否已被撤销
+pub fn is_token_revoked(token: &str) -> bool {
+    let Ok(revoked) = REVOKED_TOKENS.read() else {
+        return false;
+    };
+    !revoked.is_empty() && revoked.contains_key(&token_fingerprint(token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revoked_tokens() {
+        let expires_at = Utc::now().timestamp() + 3600;
+
+        revoke_tokens([
+            ("token-a".to_string(), expires_at),
+            ("token-expired".to_string(), expires_at - 7200),
+        ]);
+        assert!(is_token_revoked("token-a"));
+        assert!(!is_token_revoked("token-b"));
+        assert!(!is_token_revoked("token-expired"));
+
+        set_revoked_tokens([("token-b".to_string(), expires_at)]);
+        assert!(!is_token_revoked("token-a"));
+        assert!(is_token_revoked("token-b"));
+    }
+}
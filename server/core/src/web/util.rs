@@ -1,4 +1,12 @@
+use std::net::{AddrParseError, IpAddr};
+
 use axum::http::HeaderMap;
+use ipnet::IpNet;
+use once_cell::sync::OnceCell;
+use server_config::ClientIpHeader;
+
+/// 全局客户端 IP 解析器，未初始化时不信任任何代理
+static CLIENT_IP_RESOLVER: OnceCell<ClientIpResolver> = OnceCell::new();
 
 /// 客户端 IP 地址处理工具
 ///
@@ -9,6 +17,9 @@ pub struct ClientIp;
 impl ClientIp {
     /// 从请求头中获取真实的客户端 IP 地址
     ///
+    /// 该方法无条件信任请求头，结果可被客户端伪造，
+    /// 记录日志或做访问控制时应使用 [`ClientIp::resolve`]。
+    ///
     /// # 参数
     /// * `headers` - HTTP 请求头
     ///
@@ -109,22 +120,164 @@ impl ClientIp {
             .collect()
     }
 
-    /// 从 X-Forwarded-For 头中获取代理链路
+    /// 获取代理链路
+    ///
+    /// 只读取 `header` 指定的请求头，同名请求头按出现顺序拼接：
+    /// - `Forwarded` 取 RFC 7239 的 `for` 参数，缺少 `for` 参数的节点记为 `unknown`
+    /// - `X-Forwarded-For` 取逗号分隔的全部节点
+    /// - `X-Real-IP` 只取最后一个值，由最近一层代理写入
+    ///
+    /// # 参数
+    /// * `headers` - HTTP 请求头
+    /// * `header` - 传递客户端 IP 的请求头
+    ///
+    /// # 返回值
+    /// 返回从客户端到最近一层代理的节点，如果没有则返回空 Vec
+    pub fn get_proxy_chain(headers: &HeaderMap, header: ClientIpHeader) -> Vec<String> {
+        match header {
+            ClientIpHeader::Forwarded => headers
+                .get_all("Forwarded")
+                .iter()
+                .filter_map(|h| h.to_str().ok())
+                .flat_map(|value| value.split(','))
+                .map(|element| {
+                    element
+                        .split(';')
+                        .filter_map(|pair| pair.split_once('='))
+                        .find(|(key, _)| key.trim().eq_ignore_ascii_case("for"))
+                        .map(|(_, node)| node.trim().trim_matches('"').to_string())
+                        .unwrap_or_else(|| "unknown".to_string())
+                })
+                .collect(),
+            ClientIpHeader::XForwardedFor => headers
+                .get_all("X-Forwarded-For")
+                .iter()
+                .filter_map(|h| h.to_str().ok())
+                .flat_map(|ip_str| ip_str.split(','))
+                .map(|ip| ip.trim().to_string())
+                .filter(|ip| !ip.is_empty())
+                .collect(),
+            ClientIpHeader::XRealIp => headers
+                .get_all("X-Real-IP")
+                .iter()
+                .rev()
+                .filter_map(|h| h.to_str().ok())
+                .map(|ip| ip.trim().to_string())
+                .find(|ip| !ip.is_empty())
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// 设置全局客户端 IP 解析器，只能设置一次
+    pub fn init_resolver(resolver: ClientIpResolver) -> Result<(), ClientIpResolver> {
+        CLIENT_IP_RESOLVER.set(resolver)
+    }
+
+    /// 使用全局解析器获取客户端 IP
+    ///
+    /// # 参数
+    /// * `peer` - 连接的对端地址
+    /// * `headers` - HTTP 请求头
+    pub fn resolve(peer: Option<IpAddr>, headers: &HeaderMap) -> String {
+        match CLIENT_IP_RESOLVER.get() {
+            Some(resolver) => resolver.resolve(peer, headers),
+            None => ClientIpResolver::default().resolve(peer, headers),
+        }
+    }
+}
+
+/// 基于可信代理的客户端 IP 解析器
+///
+/// 将配置的请求头中的代理链路与连接对端地址组成完整链路，从右向左跳过可信节点，
+/// 第一个不可信的节点即为客户端地址；全部可信时取最左侧节点。
+/// 对端地址不可信时请求头会被忽略。
+#[derive(Debug, Clone, Default)]
+pub struct ClientIpResolver {
+    header: ClientIpHeader,
+    trusted_proxies: Vec<IpNet>,
+    trusted_hops: usize,
+}
+
+impl ClientIpResolver {
+    /// 创建解析器
     ///
     /// # 参数
+    /// * `header` - 传递客户端 IP 的请求头
+    /// * `trusted_proxies` - 可信代理网段
+    /// * `trusted_hops` - 从对端开始无条件信任的节点数
+    pub fn new(header: ClientIpHeader, trusted_proxies: Vec<IpNet>, trusted_hops: usize) -> Self {
+        Self {
+            header,
+            trusted_proxies,
+            trusted_hops,
+        }
+    }
+
+    /// 解析客户端 IP
+    ///
+    /// # 参数
+    /// * `peer` - 连接的对端地址，未知时仅依据请求头解析
     /// * `headers` - HTTP 请求头
     ///
     /// # 返回值
-    /// 返回代理链路中的所有 IP 地址，如果没有则返回空 Vec
-    pub fn get_proxy_chain(headers: &HeaderMap) -> Vec<String> {
-        headers
-            .get("X-Forwarded-For")
-            .and_then(|h| h.to_str().ok())
-            .map(|ip_str| ip_str.split(',').map(|ip| ip.trim().to_string()).collect())
-            .unwrap_or_default()
+    /// 返回客户端 IP 地址字符串，如果无法获取则返回 "unknown"
+    pub fn resolve(&self, peer: Option<IpAddr>, headers: &HeaderMap) -> String {
+        let chain = ClientIp::get_proxy_chain(headers, self.header);
+        let nodes = chain
+            .iter()
+            .map(|node| (parse_node(node), node.as_str()))
+            .chain(peer.map(|ip| (Some(ip.to_canonical()), "")));
+
+        let mut client = None;
+        for (hop, (ip, node)) in nodes.rev().enumerate() {
+            let Some(ip) = ip else {
+                return node.to_string();
+            };
+            client = Some(ip);
+            if !self.is_trusted(hop, ip) {
+                break;
+            }
+        }
+
+        client.map_or_else(|| "unknown".to_string(), |ip| ip.to_string())
+    }
+
+    fn is_trusted(&self, hop: usize, ip: IpAddr) -> bool {
+        hop < self.trusted_hops || self.trusted_proxies.iter().any(|net| net.contains(&ip))
     }
 }
 
+/// 解析 IP 网段，单个地址视为主机网段，网段的主机位会被清零
+///
+/// # 示例
+/// ```
+/// use server_core::web::util::parse_ip_net;
+///
+/// assert_eq!(parse_ip_net("10.1.2.3/8").unwrap().to_string(), "10.0.0.0/8");
+/// assert_eq!(parse_ip_net("127.0.0.1").unwrap().to_string(), "127.0.0.1/32");
+/// ```
+pub fn parse_ip_net(value: &str) -> Result<IpNet, AddrParseError> {
+    let value = value.trim();
+    value
+        .parse::<IpNet>()
+        .map(|net| net.trunc())
+        .or_else(|_| value.parse::<IpAddr>().map(IpNet::from))
+}
+
+/// 解析代理链路中的节点，支持 `ip:port`、`[ipv6]:port` 形式
+fn parse_node(node: &str) -> Option<IpAddr> {
+    let node = node.trim().trim_matches('"');
+    let ip: IpAddr = if let Some(rest) = node.strip_prefix('[') {
+        rest.split_once(']')?.0.parse().ok()?
+    } else if let Ok(ip) = node.parse() {
+        ip
+    } else {
+        node.rsplit_once(':')?.0.parse().ok()?
+    };
+    Some(ip.to_canonical())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,8 +303,120 @@ mod tests {
             "X-Forwarded-For",
             "192.168.1.1, 10.0.0.1, 172.16.0.1".parse().unwrap(),
         );
-        let chain = ClientIp::get_proxy_chain(&headers);
+        let chain = ClientIp::get_proxy_chain(&headers, ClientIpHeader::XForwardedFor);
         assert_eq!(chain.len(), 3);
         assert_eq!(chain[0], "192.168.1.1");
+        assert!(ClientIp::get_proxy_chain(&headers, ClientIpHeader::Forwarded).is_empty());
+
+        let mut headers = HeaderMap::new();
+        headers.append(
+            "Forwarded",
+            r#"for=192.0.2.43;proto=https, For="[2001:db8:cafe::17]:4711""#
+                .parse()
+                .unwrap(),
+        );
+        headers.append("Forwarded", "proto=http;by=10.0.0.1".parse().unwrap());
+        headers.insert("X-Forwarded-For", "198.51.100.1".parse().unwrap());
+        assert_eq!(
+            ClientIp::get_proxy_chain(&headers, ClientIpHeader::Forwarded),
+            vec!["192.0.2.43", "[2001:db8:cafe::17]:4711", "unknown"]
+        );
+        assert_eq!(
+            ClientIp::get_proxy_chain(&headers, ClientIpHeader::XForwardedFor),
+            vec!["198.51.100.1"]
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.append("X-Real-IP", "192.168.1.1".parse().unwrap());
+        headers.append("X-Real-IP", " 192.168.1.2 ".parse().unwrap());
+        assert_eq!(
+            ClientIp::get_proxy_chain(&headers, ClientIpHeader::XRealIp),
+            vec!["192.168.1.2"]
+        );
+    }
+
+    #[test]
+    fn test_resolve_client_ip() {
+        let peer: IpAddr = "10.0.0.2".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "X-Forwarded-For",
+            "1.1.1.1, 203.0.113.7, 10.0.0.1".parse().unwrap(),
+        );
+
+        // 不信任任何代理时忽略请求头
+        let resolver = ClientIpResolver::default();
+        assert_eq!(resolver.resolve(Some(peer), &headers), "10.0.0.2");
+
+        // 跳过可信代理，取最右侧不可信节点，左侧可伪造的节点被忽略
+        let resolver = ClientIpResolver::new(
+            ClientIpHeader::XForwardedFor,
+            vec![parse_ip_net("10.0.0.0/8").unwrap()],
+            0,
+        );
+        assert_eq!(resolver.resolve(Some(peer), &headers), "203.0.113.7");
+
+        // 按层数信任
+        let resolver = ClientIpResolver::new(ClientIpHeader::XForwardedFor, Vec::new(), 1);
+        assert_eq!(resolver.resolve(Some(peer), &headers), "10.0.0.1");
+        let resolver = ClientIpResolver::new(ClientIpHeader::XForwardedFor, Vec::new(), 10);
+        assert_eq!(resolver.resolve(Some(peer), &headers), "1.1.1.1");
+
+        // 对端未知时依据请求头解析
+        assert_eq!(
+            ClientIpResolver::default().resolve(None, &headers),
+            "10.0.0.1"
+        );
+        assert_eq!(
+            ClientIpResolver::default().resolve(None, &HeaderMap::new()),
+            "unknown"
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Real-IP", "192.168.1.1".parse().unwrap());
+        let resolver = ClientIpResolver::new(
+            ClientIpHeader::XRealIp,
+            vec![parse_ip_net("10.0.0.2").unwrap()],
+            0,
+        );
+        assert_eq!(resolver.resolve(Some(peer), &headers), "192.168.1.1");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "Forwarded",
+            r#"for="[2001:db8::1]:4711", for=192.0.2.60:8080"#.parse().unwrap(),
+        );
+        let resolver = ClientIpResolver::new(
+            ClientIpHeader::Forwarded,
+            vec![parse_ip_net("192.0.2.0/24").unwrap()],
+            1,
+        );
+        assert_eq!(resolver.resolve(Some(peer), &headers), "2001:db8::1");
+
+        // 无法解析的节点原样返回
+        headers.insert("Forwarded", "for=_hidden, for=192.0.2.60".parse().unwrap());
+        assert_eq!(resolver.resolve(Some(peer), &headers), "_hidden");
+
+        // 客户端伪造的 Forwarded 头经过追加 X-Forwarded-For 的代理后被忽略
+        let mut headers = HeaderMap::new();
+        headers.insert("Forwarded", "for=1.1.1.1".parse().unwrap());
+        headers.insert("X-Forwarded-For", "203.0.113.7".parse().unwrap());
+        let resolver = ClientIpResolver::new(
+            ClientIpHeader::XForwardedFor,
+            vec![parse_ip_net("10.0.0.0/8").unwrap()],
+            0,
+        );
+        assert_eq!(resolver.resolve(Some(peer), &headers), "203.0.113.7");
+
+        // 只读取配置的请求头，X-Real-IP 不再作为回退
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Real-IP", "1.1.1.1".parse().unwrap());
+        assert_eq!(resolver.resolve(Some(peer), &headers), "10.0.0.2");
+
+        let mapped: IpAddr = "::ffff:10.0.0.2".parse().unwrap();
+        assert_eq!(
+            ClientIpResolver::default().resolve(Some(mapped), &HeaderMap::new()),
+            "10.0.0.2"
+        );
     }
 }
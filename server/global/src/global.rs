@@ -3,9 +3,14 @@ use std::{
     collections::HashMap,
     future::Future,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 
+use arc_swap::{ArcSwap, ArcSwapOption};
 use aws_sdk_s3::Client as S3Client;
 use chrono::NaiveDateTime;
 use http::Method;
@@ -202,16 +207,43 @@ impl RouteInfo {
 
 pub static ROUTE_COLLECTOR: Lazy<Mutex<Vec<RouteInfo>>> = Lazy::new(|| Mutex::new(Vec::new()));
 
+/// 按请求方法与路由模板索引的路由信息
+///
+/// 每次登记路由后整体替换，请求路径上的查找无需加锁。
+static ROUTE_INDEX: Lazy<ArcSwap<HashMap<Method, HashMap<String, RouteInfo>>>> =
+    Lazy::new(ArcSwap::default);
+
+fn rebuild_route_index(routes: &[RouteInfo]) {
+    let mut index: HashMap<Method, HashMap<String, RouteInfo>> = HashMap::new();
+    for route in routes {
+        index
+            .entry(route.method.clone())
+            .or_default()
+            .entry(route.path.clone())
+            .or_insert_with(|| route.clone());
+    }
+    ROUTE_INDEX.store(Arc::new(index));
+}
+
 pub async fn add_route(route: RouteInfo) {
-    ROUTE_COLLECTOR.lock().await.push(route);
+    let mut routes = ROUTE_COLLECTOR.lock().await;
+    routes.push(route);
+    rebuild_route_index(&routes);
 }
 
 pub async fn get_collected_routes() -> Vec<RouteInfo> {
     ROUTE_COLLECTOR.lock().await.clone()
 }
 
+/// 按请求方法与路由模板（`:param` 形式）查找已收集的路由信息，同一路由登记多次时取首次登记的
+pub fn find_route(method: &Method, path: &str) -> Option<RouteInfo> {
+    ROUTE_INDEX.load().get(method)?.get(path).cloned()
+}
+
 pub async fn clear_routes() {
-    ROUTE_COLLECTOR.lock().await.clear();
+    let mut routes = ROUTE_COLLECTOR.lock().await;
+    routes.clear();
+    rebuild_route_index(&routes);
 }
 
 //*****************************************************************************
@@ -228,14 +260,19 @@ pub struct OperationLogContext {
     pub request_id: String,
     pub method: String,
     pub url: String,
+    /// 匹配的路由模板，如 `/user/:id`
+    pub route: String,
     pub ip: String,
     pub user_agent: Option<String>,
     pub params: Option<Value>,
+    pub headers: Option<Value>,
     pub body: Option<Value>,
     pub response: Option<Value>,
     pub start_time: NaiveDateTime,
     pub end_time: NaiveDateTime,
     pub duration: i32,
+    /// 响应状态码，HTTP 状态为 2xx 时取响应体中的业务码
+    pub status: u16,
     pub created_at: NaiveDateTime,
 }
 
@@ -258,6 +295,49 @@ impl OperationLogContext {
     }
 }
 
+/// 操作日志写入队列
+pub struct OperationLogQueue {
+    pub sender: mpsc::Sender<OperationLogContext>,
+    /// 队列已满时请求等待的最长时间
+    pub send_timeout: Duration,
+}
+
+/// 操作日志写入队列，由服务层启动批量写入任务时设置
+static OPERATION_LOG_QUEUE: Lazy<ArcSwapOption<OperationLogQueue>> =
+    Lazy::new(ArcSwapOption::empty);
+
+/// 等待超时或写入任务未启动而丢弃的操作日志条数
+static DROPPED_OPERATION_LOGS: AtomicU64 = AtomicU64::new(0);
+
+/// 设置操作日志写入队列，传入 None 会关闭队列，写入任务写完剩余日志后退出
+pub fn set_operation_log_queue(queue: Option<OperationLogQueue>) {
+    OPERATION_LOG_QUEUE.store(queue.map(Arc::new));
+}
+
+/// 将操作日志放入写入队列
+///
+/// 队列已满时等待写入任务腾出空间，使请求随日志写入一同减速；等待超过 `send_timeout`
+/// 或写入任务未启动时丢弃该条日志并计数。
+pub async fn send_operation_log(context: OperationLogContext) -> bool {
+    let sent = match OPERATION_LOG_QUEUE.load_full() {
+        Some(queue) => queue
+            .sender
+            .send_timeout(context, queue.send_timeout)
+            .await
+            .is_ok(),
+        None => false,
+    };
+    if !sent {
+        DROPPED_OPERATION_LOGS.fetch_add(1, Ordering::Relaxed);
+    }
+    sent
+}
+
+/// 累计丢弃的操作日志条数
+pub fn dropped_operation_logs() -> u64 {
+    DROPPED_OPERATION_LOGS.load(Ordering::Relaxed)
+}
+
 /// 异步发送字符串事件
 #[inline]
 pub fn send_string_event(msg: String) {
@@ -19,6 +19,23 @@ macro_rules! project_info {
     }}
 }
 
+#[macro_export]
+macro_rules! project_warn {
+    ($($arg:tt)+) => {{
+        let span = tracing::span!(
+            tracing::Level::WARN,
+            module_path!(),
+            file = file!(),
+            line = line!(),
+        );
+        let _enter = span.enter();
+        tracing::warn!(
+            target: "[soybean-admin-rust]",
+            $($arg)+
+        );
+    }}
+}
+
 #[macro_export]
 macro_rules! project_error {
     ($($arg:tt)+) => {{
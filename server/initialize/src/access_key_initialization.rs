@@ -1,10 +1,30 @@
-use server_global::project_info;
-use server_service::admin::{SysAccessKeyService, TAccessKeyService};
+use std::process;
+
+use server_global::{project_error, project_info};
+use server_service::admin::{
+    access_key_change_subscriber, access_key_usage_writer, SysAccessKeyService, TAccessKeyService,
+};
 
 pub async fn initialize_access_key() {
     let access_key_service = SysAccessKeyService;
 
-    let _ = access_key_service.initialize_access_key().await;
+    match access_key_service.initialize_access_key().await {
+        Ok(_) => project_info!("Access key initialization completed successfully"),
+        Err(e) => {
+            // 主密钥缺失或无效时拒绝启动，避免以无法加解密的状态运行
+            project_error!("Failed to initialize access keys: {}", e.message);
+            process::exit(1);
+        },
+    }
+
+    // 接收其他实例的密钥变更
+    tokio::spawn(access_key_change_subscriber());
+    // 定期写入密钥的使用情况
+    tokio::spawn(access_key_usage_writer());
+}
 
-    project_info!("Access key initialization completed successfully")
+/// 停机时写入尚未保存的密钥使用情况
+pub async fn flush_access_key_usage() {
+    server_service::admin::flush_access_key_usage().await;
+    project_info!("Access key usage flushed");
 }
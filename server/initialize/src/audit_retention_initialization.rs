@@ -0,0 +1,24 @@
+use server_config::AuditConfig;
+use server_global::global::get_config;
+use server_service::admin::audit_retention_task;
+
+use crate::project_info;
+
+/// 启动审计日志保留任务，未配置任何保留天数时不启动
+pub async fn initialize_audit_retention() {
+    let Some(config) = get_config::<AuditConfig>().await else {
+        return;
+    };
+
+    let retention = config.retention.clone();
+    if retention.operation_log.max_age_days.is_none() && retention.login_log.max_age_days.is_none()
+    {
+        return;
+    }
+
+    project_info!(
+        "Starting audit log retention task, interval {}s",
+        retention.interval_secs
+    );
+    tokio::spawn(audit_retention_task(retention));
+}
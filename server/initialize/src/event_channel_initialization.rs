@@ -3,8 +3,8 @@ use server_global::global;
 
 pub async fn initialize_event_channel() {
     use server_service::admin::{
-        api_key_validate_listener, auth_login_listener, jwt_created_listener,
-        sys_operation_log_listener,
+        access_key_changed_listener, api_key_validate_listener, auth_login_failed_listener,
+        auth_login_listener, jwt_created_listener, login_risk_listener,
     };
 
     global::register_event_listeners(
@@ -14,14 +14,22 @@ pub async fn initialize_event_channel() {
                 SystemEvent::AuthLoggedInEvent.to_string(),
                 Box::new(|rx| Box::pin(auth_login_listener(rx))),
             ),
-            (
-                SystemEvent::AuditOperationLoggedEvent.to_string(),
-                Box::new(|rx| Box::pin(sys_operation_log_listener(rx))),
-            ),
             (
                 SystemEvent::AuthApiKeyValidatedEvent.to_string(),
                 Box::new(|rx| Box::pin(api_key_validate_listener(rx))),
             ),
+            (
+                SystemEvent::AuthLoginRiskDetectedEvent.to_string(),
+                Box::new(|rx| Box::pin(login_risk_listener(rx))),
+            ),
+            (
+                SystemEvent::AuthLoginFailedEvent.to_string(),
+                Box::new(|rx| Box::pin(auth_login_failed_listener(rx))),
+            ),
+            (
+                SystemEvent::AccessKeyChangedEvent.to_string(),
+                Box::new(|rx| Box::pin(access_key_changed_listener(rx))),
+            ),
         ],
     )
     .await;
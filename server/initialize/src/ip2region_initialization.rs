@@ -1,14 +1,29 @@
-use std::error::Error;
+use std::{error::Error, path::Path, time::Duration};
 
-use xdb::searcher;
+use xdb::{searcher, IpVersion};
 
 use crate::project_info;
 
+const XDB_FILEPATH: &str = "server/resources/ip2region.xdb";
+const XDB_V6_FILEPATH: &str = "server/resources/ip2region_v6.xdb";
+/// 检查 xdb 文件是否被替换的间隔
+const XDB_RELOAD_INTERVAL: Duration = Duration::from_secs(60);
+
 pub async fn init_xdb() -> Result<(), Box<dyn Error>> {
     tokio::task::spawn_blocking(|| {
-        searcher::searcher_init(Some("server/resources/ip2region.xdb".to_string()));
+        searcher::searcher_init(Some(XDB_FILEPATH.to_string()));
+        // IPv6 数据可选，缺失时 IPv6 地址查询返回未知
+        if Path::new(XDB_V6_FILEPATH).exists() {
+            searcher::searcher_init(Some(XDB_V6_FILEPATH.to_string()));
+        }
     })
     .await?;
+
+    for version in [IpVersion::V4, IpVersion::V6] {
+        if let Ok(searcher) = searcher::global_searcher(version) {
+            searcher.watch(XDB_RELOAD_INTERVAL);
+        }
+    }
     project_info!("XDB initialized successfully");
     Ok(())
 }
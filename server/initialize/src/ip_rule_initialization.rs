@@ -0,0 +1,12 @@
+use server_global::{project_error, project_info};
+use server_service::admin::{ip_rule_subscriber, SysIpRuleService, TIpRuleService};
+
+pub async fn initialize_ip_rules() {
+    match SysIpRuleService.reload_ip_rules().await {
+        Ok(_) => project_info!("IP rule initialization completed successfully"),
+        Err(e) => project_error!("Failed to initialize IP rules: {}", e.message),
+    }
+
+    // 接收其他实例的 IP 规则变更通知
+    tokio::spawn(ip_rule_subscriber());
+}
@@ -1,4 +1,5 @@
-pub use access_key_initialization::initialize_access_key;
+pub use access_key_initialization::{flush_access_key_usage, initialize_access_key};
+pub use audit_retention_initialization::initialize_audit_retention;
 pub use aws_s3_initialization::{init_primary_s3, init_s3_pools};
 pub use casbin_initialization::initialize_casbin;
 pub use config_initialization::{
@@ -8,27 +9,35 @@ pub use config_initialization::{
 pub use db_initialization::{init_db_pools, init_primary_connection};
 pub use event_channel_initialization::initialize_event_channel;
 pub use ip2region_initialization::init_xdb;
+pub use ip_rule_initialization::initialize_ip_rules;
 pub use jwt_initialization::initialize_keys_and_validation;
 pub use log_tracing_init::initialize_log_tracing;
 pub use mongo_initialization::{init_mongo_pools, init_primary_mongo};
+pub use operation_log_initialization::{flush_operation_logs, initialize_operation_log_writer};
 pub use redis_initialization::{init_primary_redis, init_redis_pools};
 pub use router_initialization::initialize_admin_router;
-pub use server_global::{project_error, project_info};
-pub use server_initialization::get_server_address;
+pub use server_global::{project_error, project_info, project_warn};
+pub use server_initialization::{get_server_address, initialize_client_ip_resolver};
+pub use token_revocation_initialization::initialize_token_revocation;
 
 mod access_key_initialization;
+mod audit_retention_initialization;
 mod aws_s3_initialization;
 mod casbin_initialization;
 mod config_initialization;
 mod db_initialization;
 mod event_channel_initialization;
 mod ip2region_initialization;
+mod ip_rule_initialization;
 mod jwt_initialization;
 mod log_tracing_init;
 mod mongo_initialization;
+mod operation_log_initialization;
+mod recycle_bin_initialization;
 mod redis_initialization;
 mod router_initialization;
 mod server_initialization;
+mod token_revocation_initialization;
 
 // TODO: axum_test_helpers不兼容axum 0.8.x
 // #[cfg(test)]
@@ -0,0 +1,15 @@
+use server_service::admin::{shutdown_operation_log_writer, start_operation_log_writer};
+
+use crate::project_info;
+
+/// 启动操作日志批量写入任务
+pub async fn initialize_operation_log_writer() {
+    start_operation_log_writer().await;
+    project_info!("Operation log writer started");
+}
+
+/// 停机时写入队列中剩余的操作日志
+pub async fn flush_operation_logs() {
+    shutdown_operation_log_writer().await;
+    project_info!("Operation log writer flushed");
+}
@@ -0,0 +1,24 @@
+use axum_casbin::CasbinAxumLayer;
+use server_config::RecycleBinConfig;
+use server_global::global::get_config;
+use server_service::admin::recycle_bin_purge_task;
+
+use crate::project_info;
+
+/// 启动回收站清理任务，保留天数为 0 时不启动
+pub async fn initialize_recycle_bin_purge(mut casbin: CasbinAxumLayer) {
+    let config = get_config::<RecycleBinConfig>()
+        .await
+        .map(|config| (*config).clone())
+        .unwrap_or_default();
+    if config.retention_days == 0 {
+        return;
+    }
+
+    project_info!(
+        "Starting recycle bin purge task, retention {} day(s), interval {}s",
+        config.retention_days,
+        config.interval_secs
+    );
+    tokio::spawn(recycle_bin_purge_task(config, casbin.get_enforcer()));
+}
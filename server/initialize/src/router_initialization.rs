@@ -4,32 +4,37 @@ use axum::{body::Body, http::StatusCode, response::IntoResponse, Extension, Rout
 use axum_casbin::CasbinAxumLayer;
 use chrono::Local;
 use http::Request;
-use server_config::Config;
+use server_config::{AuditConfig, Config, RateLimitBackend, RateLimitConfig, SecurityConfig};
 use server_constant::definition::Audience;
+use server_core::rate_limit::{RateLimitLayer, RateLimitStore, RateLimiter};
 use server_core::sign::{
-    api_key_middleware, protect_route, ApiKeySource, ApiKeyValidation, ComplexApiKeyConfig,
-    SimpleApiKeyConfig, ValidatorType,
+    api_key_middleware, protect_route, ApiKeyConfig, ApiKeySource, ApiKeyValidation,
+    ComplexApiKeyConfig, SimpleApiKeyConfig, ValidatorType,
+};
+use server_core::web::{
+    ip_filter::ip_filter_middleware, operation_log::OperationLogLayer, redaction::Redactor,
+    RequestId, RequestIdLayer,
 };
-use server_core::web::{RequestId, RequestIdLayer};
 use server_global::global::{clear_routes, get_collected_routes, get_config};
 use server_middleware::jwt_auth_middleware;
 use server_router::admin::{
-    SysAccessKeyRouter, SysAuthenticationRouter, SysDomainRouter, SysEndpointRouter,
-    SysLoginLogRouter, SysMenuRouter, SysOperationLogRouter, SysOrganizationRouter, SysRoleRouter,
-    SysSandboxRouter, SysUserRouter,
+    SysAccessKeyRouter, SysAuthenticationRouter, SysDashboardRouter, SysDomainRouter,
+    SysEndpointRouter, SysIpRuleRouter, SysLoginLogRouter, SysMenuRouter, SysOperationLogRouter,
+    SysOrganizationRouter, SysRecycleBinRouter, SysRoleRouter, SysSandboxRouter, SysUserRouter,
 };
 use server_service::{
     admin::{
-        SysAccessKeyService, SysAuthService, SysAuthorizationService, SysDomainService,
-        SysEndpointService, SysLoginLogService, SysMenuService, SysOperationLogService,
-        SysOrganizationService, SysRoleService, SysUserService, TEndpointService,
+        SysAccessKeyService, SysAuthService, SysAuthorizationService, SysDashboardService,
+        SysDomainService, SysEndpointService, SysIpRuleService, SysLoginLogService, SysMenuService,
+        SysOperationLogService, SysOrganizationService, SysRecycleBinService, SysRoleService,
+        SysUserService, TEndpointService,
     },
     SysEndpoint,
 };
 use tower_http::trace::TraceLayer;
 use tracing::info_span;
 
-use crate::{initialize_casbin, project_error, project_info};
+use crate::{initialize_casbin, project_error, project_info, project_warn};
 
 #[derive(Clone)]
 pub enum Services<T: Send + Sync + 'static> {
@@ -37,13 +42,20 @@ pub enum Services<T: Send + Sync + 'static> {
     Single(Arc<T>),
 }
 
+/// 各路由共享的中间件
+#[derive(Clone)]
+struct SharedLayers {
+    casbin: Option<CasbinAxumLayer>,
+    rate_limit: Option<RateLimitLayer>,
+}
+
 async fn apply_layers<T: Send + Sync + 'static>(
     router: Router,
     services: Services<T>,
     need_casbin: bool,
     need_auth: bool,
     api_validation: Option<ApiKeyValidation>,
-    casbin: Option<CasbinAxumLayer>,
+    layers: SharedLayers,
     audience: Audience,
 ) -> Router {
     let mut router = match services {
@@ -51,6 +63,27 @@ async fn apply_layers<T: Send + Sync + 'static>(
         Services::Single(service) => router.layer(Extension(service)),
     };
 
+    // 需要认证的路由统一记录操作日志，单个路由可通过 `skip_operation_log` 排除
+    let audit = get_config::<AuditConfig>().await.unwrap_or_default();
+    if need_auth {
+        router = router.layer(
+            OperationLogLayer::new(true)
+                .with_redaction(&audit.redaction)
+                .with_capture(audit.capture),
+        );
+    }
+
+    // 限流位于 IP 规则之内，被 IP 规则拒绝的请求不占用配额
+    if let Some(rate_limit) = layers.rate_limit {
+        router = router.layer(rate_limit);
+    }
+
+    // IP 规则位于操作日志之外，被拒绝的请求由中间件自行记录
+    let redactor = Arc::new(Redactor::new(&audit.redaction));
+    router = router.layer(axum::middleware::from_fn(move |req, next| {
+        ip_filter_middleware(redactor.clone(), req, next)
+    }));
+
     router = router
         .layer(
             TraceLayer::new_for_http().make_span_with(|request: &Request<Body>| {
@@ -70,7 +103,7 @@ async fn apply_layers<T: Send + Sync + 'static>(
         .layer(RequestIdLayer);
 
     if need_casbin {
-        if let Some(casbin) = casbin {
+        if let Some(casbin) = layers.casbin {
             router = router.layer(Extension(casbin.clone())).layer(casbin);
         }
     }
@@ -101,25 +134,54 @@ pub async fn initialize_admin_router() -> Router {
     )
     .await
     .unwrap();
+    crate::recycle_bin_initialization::initialize_recycle_bin_purge(casbin_layer.clone()).await;
 
     // 初始化验证器
     // 根据是否配置了 Redis 来选择 nonce 存储实现
-    let nonce_store_factory =
-        if let Some(_) = crate::redis_initialization::get_primary_redis().await {
-            // 如果 Redis 可用，使用 Redis 作为 nonce 存储
-            project_info!("Using Redis for nonce storage");
-            server_core::sign::create_redis_nonce_store_factory("api_key")
-        } else {
-            // 否则使用内存存储
-            project_info!("Using memory for nonce storage");
-            server_core::sign::create_memory_nonce_store_factory()
-        };
+    let nonce_store_factory = if crate::redis_initialization::get_primary_redis()
+        .await
+        .is_some()
+    {
+        // 如果 Redis 可用，使用 Redis 作为 nonce 存储
+        project_info!("Using Redis for nonce storage");
+        server_core::sign::create_redis_nonce_store_factory("api_key")
+    } else {
+        // 否则使用内存存储
+        project_info!("Using memory for nonce storage");
+        server_core::sign::create_memory_nonce_store_factory()
+    };
 
-    server_core::sign::init_validators_with_nonce_store(None, nonce_store_factory.clone()).await;
+    let security_config = get_config::<SecurityConfig>().await.unwrap_or_default();
+    if security_config.access_key.allow_legacy_algorithms {
+        project_warn!(
+            "Deprecated MD5/SHA1 access key signatures are accepted, migrate clients and set \
+             security.access_key.allow_legacy_algorithms to false"
+        );
+    }
+    let api_key_config = ApiKeyConfig {
+        allow_deprecated: security_config.access_key.allow_legacy_algorithms,
+        ..Default::default()
+    };
+    server_core::sign::init_validators_with_nonce_store(
+        Some(api_key_config),
+        nonce_store_factory.clone(),
+    )
+    .await;
+
+    // 沙箱测试密钥仅在显式开启时加载，避免生产环境存在公开的固定密钥
+    if app_config.server.sandbox {
+        project_warn!("Sandbox API keys are enabled, do not use this in production");
+        server_core::sign::add_key(ValidatorType::Simple, "test-api-key", None).await;
+        server_core::sign::add_key(
+            ValidatorType::Complex,
+            "test-access-key",
+            Some("test-secret-key"),
+        )
+        .await;
+    }
 
     let simple_validation = {
         let validator = server_core::sign::get_simple_validator().await;
-        server_core::sign::add_key(ValidatorType::Simple, "test-api-key", None).await;
         ApiKeyValidation::Simple(
             validator,
             SimpleApiKeyConfig {
@@ -131,12 +193,6 @@ pub async fn initialize_admin_router() -> Router {
 
     let complex_validation = {
         let validator = server_core::sign::get_complex_validator().await;
-        server_core::sign::add_key(
-            ValidatorType::Complex,
-            "test-access-key",
-            Some("test-secret-key"),
-        )
-        .await;
         ApiKeyValidation::Complex(
             validator,
             ComplexApiKeyConfig {
@@ -144,6 +200,7 @@ pub async fn initialize_admin_router() -> Router {
                 timestamp_name: "t".to_string(),
                 nonce_name: "n".to_string(),
                 signature_name: "sign".to_string(),
+                max_body_bytes: security_config.access_key.max_signed_body_bytes,
             },
         )
     };
@@ -153,7 +210,10 @@ pub async fn initialize_admin_router() -> Router {
     protect_route("/sandbox/complex-api-key");
 
     let audience = Audience::ManagementPlatform;
-    let casbin = Some(casbin_layer);
+    let layers = SharedLayers {
+        casbin: Some(casbin_layer),
+        rate_limit: initialize_rate_limit().await,
+    };
     let mut app = Router::new();
 
     macro_rules! merge_router {
@@ -165,7 +225,7 @@ pub async fn initialize_admin_router() -> Router {
                     $need_casbin,
                     $need_auth,
                     $api_validation,
-                    casbin.clone(),
+                    layers.clone(),
                     audience,
                 )
                 .await,
@@ -179,7 +239,7 @@ pub async fn initialize_admin_router() -> Router {
                     $need_casbin,
                     $need_auth,
                     $api_validation,
-                    casbin.clone(),
+                    layers.clone(),
                     audience,
                 )
                 .await,
@@ -208,7 +268,7 @@ pub async fn initialize_admin_router() -> Router {
         true,
         true,
         None,
-        casbin.clone(),
+        layers.clone(),
         audience,
     )
     .await;
@@ -274,6 +334,13 @@ pub async fn initialize_admin_router() -> Router {
         true,
         None
     );
+    merge_router!(
+        SysIpRuleRouter::init_ip_rule_router().await,
+        SysIpRuleService,
+        true,
+        true,
+        None
+    );
     merge_router!(
         SysLoginLogRouter::init_login_log_router().await,
         SysLoginLogService,
@@ -281,6 +348,13 @@ pub async fn initialize_admin_router() -> Router {
         true,
         None
     );
+    merge_router!(
+        SysDashboardRouter::init_dashboard_router().await,
+        SysDashboardService,
+        true,
+        true,
+        None
+    );
     merge_router!(
         SysOperationLogRouter::init_operation_log_router().await,
         SysOperationLogService,
@@ -292,8 +366,15 @@ pub async fn initialize_admin_router() -> Router {
     merge_router!(
         SysOrganizationRouter::init_organization_router().await,
         SysOrganizationService,
-        false,
-        false,
+        true,
+        true,
+        None
+    );
+    merge_router!(
+        SysRecycleBinRouter::init_recycle_bin_router().await,
+        SysRecycleBinService,
+        true,
+        true,
         None
     );
 
@@ -321,6 +402,35 @@ pub async fn initialize_admin_router() -> Router {
     app
 }
 
+/// 按配置创建限流中间件，未启用时返回 None
+async fn initialize_rate_limit() -> Option<RateLimitLayer> {
+    let config = get_config::<RateLimitConfig>().await.unwrap_or_default();
+    if !config.enabled {
+        project_info!("Rate limiting is disabled");
+        return None;
+    }
+
+    let redis_available = crate::redis_initialization::get_primary_redis()
+        .await
+        .is_some();
+    let store = match config.backend {
+        RateLimitBackend::Redis | RateLimitBackend::Auto if redis_available => {
+            project_info!("Using Redis for rate limiting");
+            RateLimitStore::redis(config.key_prefix.as_str())
+        },
+        RateLimitBackend::Redis => {
+            project_error!("Redis is not available, using memory for rate limiting");
+            RateLimitStore::memory()
+        },
+        _ => {
+            project_info!("Using memory for rate limiting");
+            RateLimitStore::memory()
+        },
+    };
+
+    Some(RateLimitLayer::new(RateLimiter::new(&config, store)))
+}
+
 async fn handler_404() -> impl IntoResponse {
     (StatusCode::NOT_FOUND, "nothing to see here")
 }
@@ -332,7 +442,7 @@ async fn process_collected_routes() {
         .map(|route| {
             let resource = route.path.split('/').nth(1).unwrap_or("").to_string();
             SysEndpoint {
-                id: generate_id(&route.path, &route.method.to_string()),
+                id: generate_id(&route.path, route.method.as_ref()),
                 path: route.path.clone(),
                 method: route.method.to_string(),
                 action: "rw".to_string(),
@@ -1,9 +1,10 @@
 use std::error::Error;
 
 use server_config::ServerConfig;
+use server_core::web::util::{parse_ip_net, ClientIp, ClientIpResolver};
 use server_global::global;
 
-use crate::project_info;
+use crate::{project_error, project_info};
 
 pub async fn get_server_address() -> Result<String, Box<dyn Error>> {
     let server_config = global::get_config::<ServerConfig>().await.unwrap();
@@ -11,3 +12,41 @@ pub async fn get_server_address() -> Result<String, Box<dyn Error>> {
     project_info!("Server address configured: {}", addr);
     Ok(addr)
 }
+
+/// 按服务器配置初始化客户端 IP 解析器，无效的代理地址会被忽略
+pub async fn initialize_client_ip_resolver() {
+    let Some(server_config) = global::get_config::<ServerConfig>().await else {
+        project_error!("Failed to load server config");
+        return;
+    };
+
+    let trusted_proxies = server_config
+        .trusted_proxies
+        .iter()
+        .filter_map(|proxy| match parse_ip_net(proxy) {
+            Ok(net) => Some(net),
+            Err(e) => {
+                project_error!("Invalid trusted proxy '{}': {}", proxy, e);
+                None
+            },
+        })
+        .collect::<Vec<_>>();
+    let count = trusted_proxies.len();
+
+    let resolver = ClientIpResolver::new(
+        server_config.client_ip_header,
+        trusted_proxies,
+        server_config.trusted_hops,
+    );
+    if ClientIp::init_resolver(resolver).is_err() {
+        project_error!("Client IP resolver already initialized");
+        return;
+    }
+
+    project_info!(
+        "Client IP resolver initialized with header {:?}, {} trusted proxies and {} trusted hops",
+        server_config.client_ip_header,
+        count,
+        server_config.trusted_hops
+    );
+}
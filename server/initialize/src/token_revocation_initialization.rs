@@ -0,0 +1,12 @@
+use server_global::{project_error, project_info};
+use server_service::admin::{reload_revoked_tokens, token_revocation_subscriber};
+
+pub async fn initialize_token_revocation() {
+    match reload_revoked_tokens().await {
+        Ok(_) => project_info!("Token revocation initialization completed successfully"),
+        Err(e) => project_error!("Failed to initialize revoked tokens: {}", e.message),
+    }
+
+    // 接收其他实例的令牌撤销通知
+    tokio::spawn(token_revocation_subscriber());
+}
@@ -16,6 +16,7 @@ mod tests {
     use server_core::web::{
         auth::{Claims, User},
         res::Res,
+        token_revocation,
     };
     use server_initialize::{initialize_config, initialize_keys_and_validation};
     use server_middleware::jwt_auth_middleware;
@@ -65,6 +66,41 @@ mod tests {
         println!("body_str is {}", body_str);
     }
 
+    #[tokio::test]
+    async fn test_revoked_token_rejected() {
+        initialize_config("../resources/application.yaml").await;
+        initialize_keys_and_validation().await;
+
+        let app =
+            Router::new()
+                .route("/pen/1", get(user_info_handler))
+                .layer(axum::middleware::from_fn(move |req, next| {
+                    jwt_auth_middleware(req, next, Audience::ManagementPlatform.as_str())
+                }));
+
+        let token = generate_jwt();
+        let request = |token: &str| {
+            Request::builder()
+                .uri("/pen/1")
+                .header("Authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(request(&token)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        token_revocation::revoke_tokens([(token.clone(), Utc::now().timestamp() + 7200)]);
+
+        let response = app.oneshot(request(&token)).await.unwrap();
+        let body_bytes = axum::body::to_bytes(response.into_body(), 1000)
+            .await
+            .unwrap();
+        let body_str = String::from_utf8(body_bytes.to_vec()).unwrap();
+        assert!(body_str.contains(r#""code":401"#));
+        assert!(body_str.contains("Token has been revoked"));
+    }
+
     fn generate_jwt() -> String {
         let mut claims = Claims::new(
             "admin".to_string(),
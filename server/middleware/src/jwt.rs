@@ -3,7 +3,7 @@ use axum::{
 };
 use axum_casbin::CasbinVals;
 use headers::{authorization::Bearer, Authorization, HeaderMapExt};
-use server_core::web::{auth::User, jwt::JwtUtils, res::Res};
+use server_core::web::{auth::User, jwt::JwtUtils, res::Res, token_revocation};
 
 pub async fn jwt_auth_middleware(
     mut req: Request<Body>,
@@ -22,6 +22,10 @@ pub async fn jwt_auth_middleware(
     };
 
     match JwtUtils::validate_token(&token, audience).await {
+        Ok(_) if token_revocation::is_token_revoked(&token) => {
+            Res::<String>::new_error(StatusCode::UNAUTHORIZED.as_u16(), "Token has been revoked")
+                .into_response()
+        },
         Ok(data) => {
             let claims = data.claims;
             let user = User::from(claims);
@@ -28,3 +28,70 @@ pub enum Status {
     #[serde(rename = "enabled")]
     Enabled,
 }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum RecycleEntityType {
+    #[sea_orm(string_value = "user")]
+    #[serde(rename = "user")]
+    User,
+    #[sea_orm(string_value = "role")]
+    #[serde(rename = "role")]
+    Role,
+    #[sea_orm(string_value = "domain")]
+    #[serde(rename = "domain")]
+    Domain,
+    #[sea_orm(string_value = "menu")]
+    #[serde(rename = "menu")]
+    Menu,
+    #[sea_orm(string_value = "organization")]
+    #[serde(rename = "organization")]
+    Organization,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum IpRuleScope {
+    #[sea_orm(string_value = "global")]
+    #[serde(rename = "global")]
+    Global,
+    #[sea_orm(string_value = "domain")]
+    #[serde(rename = "domain")]
+    Domain,
+    #[sea_orm(string_value = "access_key")]
+    #[serde(rename = "access_key")]
+    AccessKey,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum IpRuleAction {
+    #[sea_orm(string_value = "allow")]
+    #[serde(rename = "allow")]
+    Allow,
+    #[sea_orm(string_value = "deny")]
+    #[serde(rename = "deny")]
+    Deny,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum AccessKeyAlgorithm {
+    /// 已弃用，仅为兼容旧客户端保留
+    #[sea_orm(string_value = "md5")]
+    #[serde(rename = "md5")]
+    Md5,
+    /// 已弃用，仅为兼容旧客户端保留
+    #[sea_orm(string_value = "sha1")]
+    #[serde(rename = "sha1")]
+    Sha1,
+    #[sea_orm(string_value = "sha256")]
+    #[serde(rename = "sha256")]
+    Sha256,
+    #[sea_orm(string_value = "hmac_sha256")]
+    #[serde(rename = "hmac_sha256")]
+    HmacSha256,
+    #[sea_orm(string_value = "hmac_sha512")]
+    #[serde(rename = "hmac_sha512")]
+    HmacSha512,
+    /// 公钥签名，密钥字段保存客户端的 Ed25519 公钥
+    #[sea_orm(string_value = "ed25519")]
+    #[serde(rename = "ed25519")]
+    Ed25519,
+}
@@ -2,8 +2,9 @@
 
 use sea_orm::entity::prelude::*;
 use serde::Serialize;
+use serde_json::Value as JsonValue;
 
-use super::sea_orm_active_enums::Status;
+use super::sea_orm_active_enums::{AccessKeyAlgorithm, Status};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
 #[sea_orm(table_name = "sys_access_key")]
@@ -14,11 +15,31 @@ pub struct Model {
     pub domain: String,
     #[sea_orm(column_type = "Text", unique)]
     pub access_key_id: String,
-    #[sea_orm(column_type = "Text", unique)]
+    /// 加密后的密钥，Ed25519 签名时为客户端公钥，不对外返回
+    #[sea_orm(column_type = "Text")]
+    #[serde(skip_serializing)]
     pub access_key_secret: String,
     pub status: Status,
+    pub signature_algorithm: AccessKeyAlgorithm,
     #[sea_orm(column_type = "Text", nullable)]
     pub description: Option<String>,
+    pub rate_limit: Option<i32>,
+    pub rate_limit_window_secs: Option<i32>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub scopes: Option<JsonValue>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub allowed_ips: Option<JsonValue>,
+    pub expires_at: Option<DateTime>,
+    pub last_used_at: Option<DateTime>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_used_ip: Option<String>,
+    pub usage_count: i64,
+    pub rejected_count: i64,
+    /// 轮换前的密钥（加密），不对外返回
+    #[sea_orm(column_type = "Text", nullable)]
+    #[serde(skip_serializing)]
+    pub previous_secret: Option<String>,
+    pub previous_secret_expires_at: Option<DateTime>,
     pub created_at: DateTime,
     #[sea_orm(column_type = "Text")]
     pub created_by: String,
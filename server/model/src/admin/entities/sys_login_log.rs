@@ -2,6 +2,7 @@
 
 use sea_orm::entity::prelude::*;
 use serde::Serialize;
+use server_core::web::page::CursorKey;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
 #[sea_orm(table_name = "sys_login_log")]
@@ -30,9 +31,35 @@ pub struct Model {
     pub created_at: DateTime,
     #[sea_orm(column_type = "Text")]
     pub created_by: String,
+    pub risk_score: i32,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub risk_reasons: Option<String>,
+    pub success: bool,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub failure_reason: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub country: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub region: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub province: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub city: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub isp: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {}
 
 impl ActiveModelBehavior for ActiveModel {}
+
+impl CursorKey for Model {
+    fn created_at(&self) -> DateTime {
+        self.created_at
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
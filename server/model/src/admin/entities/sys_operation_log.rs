@@ -3,6 +3,7 @@
 use sea_orm::entity::prelude::*;
 use serde::Serialize;
 use serde_json::Value as JsonValue;
+use server_core::web::page::CursorKey;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
 #[sea_orm(table_name = "sys_operation_log")]
@@ -27,18 +28,23 @@ pub struct Model {
     #[sea_orm(column_type = "Text")]
     pub url: String,
     #[sea_orm(column_type = "Text")]
+    pub route: String,
+    #[sea_orm(column_type = "Text")]
     pub ip: String,
     #[sea_orm(column_type = "Text", nullable)]
     pub user_agent: Option<String>,
     #[sea_orm(column_type = "JsonBinary", nullable)]
     pub params: Option<JsonValue>,
     #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub headers: Option<JsonValue>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
     pub body: Option<JsonValue>,
     #[sea_orm(column_type = "JsonBinary", nullable)]
     pub response: Option<JsonValue>,
     pub start_time: DateTime,
     pub end_time: DateTime,
     pub duration: i32,
+    pub status: i32,
     pub created_at: DateTime,
 }
 
@@ -46,3 +52,13 @@ pub struct Model {
 pub enum Relation {}
 
 impl ActiveModelBehavior for ActiveModel {}
+
+impl CursorKey for Model {
+    fn created_at(&self) -> DateTime {
+        self.created_at
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
@@ -2,6 +2,7 @@
 
 use sea_orm::entity::prelude::*;
 use serde::Serialize;
+use server_core::web::etag::Versioned;
 
 use super::sea_orm_active_enums::Status;
 
@@ -25,9 +26,28 @@ pub struct Model {
     pub updated_at: Option<DateTime>,
     #[sea_orm(column_type = "Text", nullable)]
     pub updated_by: Option<String>,
+    pub version: i32,
+    pub deleted_at: Option<DateTime>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub deleted_by: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::sys_user::Entity")]
+    SysUser,
+}
+
+impl Related<super::sys_user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SysUser.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
+
+impl Versioned for Model {
+    fn version(&self) -> i32 {
+        self.version
+    }
+}
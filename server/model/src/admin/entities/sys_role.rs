@@ -2,6 +2,7 @@
 
 use sea_orm::entity::prelude::*;
 use serde::Serialize;
+use server_core::web::etag::Versioned;
 
 use super::sea_orm_active_enums::Status;
 
@@ -25,6 +26,10 @@ pub struct Model {
     pub updated_at: Option<DateTime>,
     #[sea_orm(column_type = "Text", nullable)]
     pub updated_by: Option<String>,
+    pub version: i32,
+    pub deleted_at: Option<DateTime>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub deleted_by: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -48,3 +53,9 @@ impl Related<super::sys_user_role::Entity> for Entity {
 }
 
 impl ActiveModelBehavior for ActiveModel {}
+
+impl Versioned for Model {
+    fn version(&self) -> i32 {
+        self.version
+    }
+}
@@ -2,6 +2,7 @@
 
 use sea_orm::entity::prelude::*;
 use serde::Serialize;
+use server_core::web::etag::Versioned;
 
 use super::sea_orm_active_enums::Status;
 
@@ -25,6 +26,9 @@ pub struct Model {
     pub phone_number: Option<String>,
     #[sea_orm(column_type = "Text")]
     pub nick_name: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub organization_id: Option<String>,
+    pub must_change_password: bool,
     pub status: Status,
     pub created_at: DateTime,
     #[sea_orm(column_type = "Text")]
@@ -32,6 +36,10 @@ pub struct Model {
     pub updated_at: Option<DateTime>,
     #[sea_orm(column_type = "Text", nullable)]
     pub updated_by: Option<String>,
+    pub version: i32,
+    pub deleted_at: Option<DateTime>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub deleted_by: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -42,6 +50,12 @@ pub enum Relation {
         to = "super::sys_domain::Column::Code"
     )]
     SysDomain,
+    #[sea_orm(
+        belongs_to = "super::sys_organization::Entity",
+        from = "Column::OrganizationId",
+        to = "super::sys_organization::Column::Id"
+    )]
+    SysOrganization,
     #[sea_orm(has_many = "super::sys_user_role::Entity")]
     SysUserRole,
 }
@@ -52,6 +66,12 @@ impl Related<super::sys_domain::Entity> for Entity {
     }
 }
 
+impl Related<super::sys_organization::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SysOrganization.def()
+    }
+}
+
 impl Related<super::sys_user_role::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::SysUserRole.def()
@@ -59,3 +79,9 @@ impl Related<super::sys_user_role::Entity> for Entity {
 }
 
 impl ActiveModelBehavior for ActiveModel {}
+
+impl Versioned for Model {
+    fn version(&self) -> i32 {
+        self.version
+    }
+}
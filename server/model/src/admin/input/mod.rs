@@ -1,23 +1,37 @@
-pub use sys_access_key::{AccessKeyPageRequest, CreateAccessKeyInput};
-pub use sys_authentication::LoginInput;
+pub use sys_access_key::{AccessKeyPageRequest, CreateAccessKeyInput, UpdateAccessKeyInput};
+pub use sys_audit_log::{ExportFormat, LogExportRequest};
+pub use sys_authentication::{ChangePasswordInput, LoginInput};
 pub use sys_authorization::{AssignPermissionDto, AssignRouteDto, AssignUserDto};
-pub use sys_domain::{CreateDomainInput, DomainPageRequest, UpdateDomainInput};
+pub use sys_dashboard::{DashboardQuery, EndpointRank};
+pub use sys_domain::{
+    CreateDomainInput, DeprovisionDomainInput, DeprovisionMode, DomainPageRequest,
+    ProvisionDomainInput, UpdateDomainInput,
+};
 pub use sys_endpoint::EndpointPageRequest;
-pub use sys_login_log::LoginLogPageRequest;
-pub use sys_menu::{CreateMenuInput, UpdateMenuInput};
-pub use sys_operation_log::OperationLogPageRequest;
-pub use sys_organization::OrganizationPageRequest;
+pub use sys_ip_rule::{CreateIpRuleInput, IpRulePageRequest, UpdateIpRuleInput};
+pub use sys_login_log::{LoginLogCursorRequest, LoginLogPageRequest};
+pub use sys_menu::{CreateMenuInput, MenuPageRequest, UpdateMenuInput};
+pub use sys_operation_log::{OperationLogCursorRequest, OperationLogPageRequest};
+pub use sys_organization::{
+    CreateOrganizationInput, MoveOrganizationInput, OrganizationPageRequest,
+    UpdateOrganizationInput,
+};
+pub use sys_recycle_bin::{PurgeRecycleBinInput, RecycleBinPageRequest};
 pub use sys_role::{CreateRoleInput, RolePageRequest, UpdateRoleInput};
 pub use sys_user::{CreateUserInput, UpdateUserInput, UserPageRequest};
 
 mod sys_access_key;
+mod sys_audit_log;
 mod sys_authentication;
 mod sys_authorization;
+mod sys_dashboard;
 mod sys_domain;
 mod sys_endpoint;
+mod sys_ip_rule;
 mod sys_login_log;
 mod sys_menu;
 mod sys_operation_log;
 mod sys_organization;
+mod sys_recycle_bin;
 mod sys_role;
 mod sys_user;
@@ -1,22 +1,51 @@
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
-use server_core::web::page::PageRequest;
+use server_core::web::page::{ListQuery, PageRequest};
 use validator::Validate;
 
-use crate::admin::entities::sea_orm_active_enums::Status;
+use crate::admin::entities::sea_orm_active_enums::{AccessKeyAlgorithm, Status};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AccessKeyPageRequest {
     #[serde(flatten)]
     pub page_details: PageRequest,
     pub keywords: Option<String>,
+    #[serde(flatten)]
+    pub query: ListQuery,
 }
 
 #[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
 pub struct AccessKeyInput {
     pub domain: String,
     pub status: Status,
     #[validate(length(max = 200, message = "Description must not exceed 200 characters"))]
     pub description: Option<String>,
+    /// 限流窗口内允许的请求数，为空表示不限流
+    #[validate(range(min = 1, message = "Rate limit must be positive"))]
+    pub rate_limit: Option<i32>,
+    /// 限流窗口长度（秒），为空时默认 60 秒
+    #[validate(range(min = 1, message = "Rate limit window must be positive"))]
+    pub rate_limit_window_secs: Option<i32>,
+    /// 可访问范围，元素为接口 ID 或请求方法（如 `GET`），为空表示不限制
+    pub scopes: Option<Vec<String>>,
+    /// 允许的来源 IP 或网段，为空表示不限制
+    pub allowed_ips: Option<Vec<String>>,
+    /// 过期时间，为空表示永不过期
+    pub expires_at: Option<NaiveDateTime>,
+    /// 签名算法，创建时为空则使用 HMAC-SHA512，修改时为空则保持不变
+    pub signature_algorithm: Option<AccessKeyAlgorithm>,
+    /// 客户端的 Ed25519 公钥（Base64），仅 Ed25519 签名使用
+    #[validate(length(max = 100, message = "Public key must not exceed 100 characters"))]
+    pub public_key: Option<String>,
 }
 
 pub type CreateAccessKeyInput = AccessKeyInput;
+
+#[derive(Deserialize, Validate)]
+pub struct UpdateAccessKeyInput {
+    pub id: String,
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub access_key: AccessKeyInput,
+}
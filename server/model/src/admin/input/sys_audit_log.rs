@@ -0,0 +1,24 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// 日志导出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    #[default]
+    Ndjson,
+}
+
+/// 操作日志与登录日志导出请求
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogExportRequest {
+    /// 起始时间（含）
+    pub start_time: Option<NaiveDateTime>,
+    /// 结束时间（不含）
+    pub end_time: Option<NaiveDateTime>,
+    pub keywords: Option<String>,
+    #[serde(default)]
+    pub format: ExportFormat,
+}
@@ -7,4 +7,27 @@ pub struct LoginInput {
     pub identifier: String,
     #[validate(length(min = 6, message = "Password cannot be empty"))]
     pub password: String,
+    /// 用户所在域的编码，默认内置域；租户管理员须传入开通租户时的域编码
+    #[serde(default = "default_login_domain")]
+    #[validate(length(min = 1, message = "Domain cannot be empty"))]
+    pub domain: String,
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangePasswordInput {
+    #[validate(length(min = 5, message = "Username cannot be empty"))]
+    pub identifier: String,
+    #[validate(length(min = 6, message = "Password cannot be empty"))]
+    pub old_password: String,
+    #[validate(length(min = 8, message = "New password must be at least 8 characters"))]
+    pub new_password: String,
+    /// 用户所在域的编码，默认内置域
+    #[serde(default = "default_login_domain")]
+    #[validate(length(min = 1, message = "Domain cannot be empty"))]
+    pub domain: String,
+}
+
+fn default_login_domain() -> String {
+    "built-in".to_string()
 }
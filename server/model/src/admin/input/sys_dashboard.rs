@@ -0,0 +1,30 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// 仪表盘统计查询参数
+///
+/// 未指定时间范围时统计最近 7 天。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardQuery {
+    pub start_time: Option<NaiveDateTime>,
+    pub end_time: Option<NaiveDateTime>,
+    /// 排行返回的条数
+    pub limit: Option<u64>,
+    /// 接口排行依据
+    #[serde(default)]
+    pub rank_by: EndpointRank,
+}
+
+/// 接口统计的排行依据
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EndpointRank {
+    /// 调用次数
+    #[default]
+    Calls,
+    /// P95 耗时
+    P95Duration,
+    /// 错误次数
+    Errors,
+}
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use server_core::web::page::PageRequest;
+use server_core::web::page::{ListQuery, PageRequest};
 use validator::Validate;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -7,6 +7,8 @@ pub struct DomainPageRequest {
     #[serde(flatten)]
     pub page_details: PageRequest,
     pub keywords: Option<String>,
+    #[serde(flatten)]
+    pub query: ListQuery,
 }
 
 #[derive(Deserialize, Validate)]
@@ -34,4 +36,56 @@ pub struct UpdateDomainInput {
     pub id: String,
     #[serde(flatten)]
     pub domain: DomainInput,
+    /// 期望的版本号，也可通过 `If-Match` 请求头传递
+    pub version: Option<i32>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct ProvisionDomainInput {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub domain: DomainInput,
+    /// 模板域编码，缺省为内置域
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Template domain must be between 1 and 50 characters"
+    ))]
+    pub template_domain: Option<String>,
+    /// 授予初始管理员的模板角色编码
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Admin role code must be between 1 and 50 characters"
+    ))]
+    pub admin_role_code: String,
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Admin username must be between 1 and 50 characters"
+    ))]
+    pub admin_username: String,
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Admin nick name must be between 1 and 50 characters"
+    ))]
+    pub admin_nick_name: String,
+}
+
+/// 注销租户的方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeprovisionMode {
+    /// 归档：停用域及其用户，保留数据
+    #[default]
+    Archive,
+    /// 删除：删除域及其用户、角色、菜单绑定和权限策略
+    Delete,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeprovisionDomainInput {
+    #[serde(default)]
+    pub mode: DeprovisionMode,
 }
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use server_core::web::page::{ListQuery, PageRequest};
+use validator::Validate;
+
+use crate::admin::entities::sea_orm_active_enums::{IpRuleAction, IpRuleScope, Status};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IpRulePageRequest {
+    #[serde(flatten)]
+    pub page_details: PageRequest,
+    pub keywords: Option<String>,
+    #[serde(flatten)]
+    pub query: ListQuery,
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct IpRuleInput {
+    pub scope: IpRuleScope,
+    /// 域编码或访问密钥 ID，全局规则不需要
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Scope value must be between 1 and 100 characters"
+    ))]
+    pub scope_value: Option<String>,
+    pub action: IpRuleAction,
+    /// IPv4 或 IPv6 网段，单个地址按 /32、/128 处理
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "CIDR must be between 1 and 50 characters"
+    ))]
+    pub cidr: Option<String>,
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Country must be between 1 and 50 characters"
+    ))]
+    pub country: Option<String>,
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Province must be between 1 and 50 characters"
+    ))]
+    pub province: Option<String>,
+    pub status: Status,
+    #[validate(length(max = 200, message = "Description must not exceed 200 characters"))]
+    pub description: Option<String>,
+}
+
+pub type CreateIpRuleInput = IpRuleInput;
+
+#[derive(Deserialize, Validate)]
+pub struct UpdateIpRuleInput {
+    pub id: String,
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub rule: IpRuleInput,
+}
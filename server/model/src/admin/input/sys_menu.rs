@@ -66,4 +66,6 @@ pub struct UpdateMenuInput {
     pub id: i32,
     #[serde(flatten)]
     pub menu: MenuInput,
+    /// 期望的版本号，也可通过 `If-Match` 请求头传递
+    pub version: Option<i32>,
 }
@@ -1,9 +1,20 @@
 use serde::{Deserialize, Serialize};
-use server_core::web::page::PageRequest;
+use server_core::web::page::{CursorRequest, ListQuery, PageRequest};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OperationLogPageRequest {
     #[serde(flatten)]
     pub page_details: PageRequest,
     pub keywords: Option<String>,
+    #[serde(flatten)]
+    pub query: ListQuery,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OperationLogCursorRequest {
+    #[serde(flatten)]
+    pub page_details: CursorRequest,
+    pub keywords: Option<String>,
+    #[serde(flatten)]
+    pub query: ListQuery,
 }
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 use server_core::web::page::PageRequest;
+use validator::Validate;
+
+use crate::admin::entities::sea_orm_active_enums::Status;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OrganizationPageRequest {
@@ -7,3 +10,45 @@ pub struct OrganizationPageRequest {
     pub page_details: PageRequest,
     pub keywords: Option<String>,
 }
+
+#[derive(Deserialize, Validate)]
+pub struct OrganizationInput {
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Code must be between 1 and 50 characters"
+    ))]
+    pub code: String,
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must be between 1 and 100 characters"
+    ))]
+    pub name: String,
+    #[validate(length(max = 500, message = "Description must not exceed 500 characters"))]
+    pub description: Option<String>,
+    #[validate(length(min = 1, message = "Parent ID cannot be empty"))]
+    pub pid: String,
+    pub status: Status,
+}
+
+pub type CreateOrganizationInput = OrganizationInput;
+
+#[derive(Deserialize, Validate)]
+pub struct UpdateOrganizationInput {
+    pub id: String,
+    #[serde(flatten)]
+    pub organization: OrganizationInput,
+    /// 期望的版本号，也可通过 `If-Match` 请求头传递
+    pub version: Option<i32>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct MoveOrganizationInput {
+    #[validate(length(min = 1, message = "Organization ID cannot be empty"))]
+    pub id: String,
+    #[validate(length(min = 1, message = "Parent ID cannot be empty"))]
+    pub pid: String,
+    /// 期望的版本号，也可通过 `If-Match` 请求头传递
+    pub version: Option<i32>,
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use server_core::web::page::PageRequest;
+
+use crate::admin::entities::sea_orm_active_enums::RecycleEntityType;
+
+/// 回收站默认保留天数
+pub const DEFAULT_RETENTION_DAYS: u32 = 30;
+
+fn default_retention_days() -> u32 {
+    DEFAULT_RETENTION_DAYS
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecycleBinPageRequest {
+    #[serde(flatten)]
+    pub page_details: PageRequest,
+    pub entity_type: Option<RecycleEntityType>,
+    pub keywords: Option<String>,
+}
+
+/// 清理超过保留期的回收站条目，保留天数为 0 时清空回收站
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgeRecycleBinInput {
+    #[serde(default = "default_retention_days")]
+    pub retention_days: u32,
+}
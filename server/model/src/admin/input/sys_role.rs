@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use server_core::web::page::PageRequest;
+use server_core::web::page::{ListQuery, PageRequest};
 use validator::Validate;
 
 use crate::admin::entities::sea_orm_active_enums::Status;
@@ -9,6 +9,8 @@ pub struct RolePageRequest {
     #[serde(flatten)]
     pub page_details: PageRequest,
     pub keywords: Option<String>,
+    #[serde(flatten)]
+    pub query: ListQuery,
 }
 
 #[derive(Deserialize, Validate)]
@@ -38,4 +40,6 @@ pub struct UpdateRoleInput {
     pub id: String,
     #[serde(flatten)]
     pub role: RoleInput,
+    /// 期望的版本号，也可通过 `If-Match` 请求头传递
+    pub version: Option<i32>,
 }
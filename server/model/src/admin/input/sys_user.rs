@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use server_core::web::page::PageRequest;
+use server_core::web::page::{ListQuery, PageRequest};
 use validator::Validate;
 
 use crate::admin::entities::sea_orm_active_enums::Status;
@@ -9,6 +9,8 @@ pub struct UserPageRequest {
     #[serde(flatten)]
     pub page_details: PageRequest,
     pub keywords: Option<String>,
+    #[serde(flatten)]
+    pub query: ListQuery,
 }
 
 #[derive(Deserialize, Validate)]
@@ -38,6 +40,7 @@ pub struct UserInput {
     pub email: Option<String>,
     #[validate(length(max = 20, message = "Phone number must not exceed 20 characters"))]
     pub phone_number: Option<String>,
+    pub organization_id: Option<String>,
     pub status: Status,
 }
 
@@ -48,4 +51,6 @@ pub struct UpdateUserInput {
     pub id: String,
     #[serde(flatten)]
     pub user: UserInput,
+    /// 期望的版本号，也可通过 `If-Match` 请求头传递
+    pub version: Option<i32>,
 }
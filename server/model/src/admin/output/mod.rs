@@ -1,11 +1,17 @@
+pub use sys_access_key::AccessKeySecretOutput;
 pub use sys_authentication::{AuthOutput, UserInfoOutput, UserRoute};
-pub use sys_domain::DomainOutput;
+pub use sys_dashboard::{EndpointStat, LoginDailyStat, LoginRegionStat, LoginStats};
+pub use sys_domain::{DomainOutput, ProvisionDomainOutput};
 pub use sys_endpoint::EndpointTree;
 pub use sys_menu::{MenuRoute, MenuTree, RouteMeta};
+pub use sys_organization::OrganizationTree;
 pub use sys_user::{UserWithDomainAndOrgOutput, UserWithoutPassword};
 
+mod sys_access_key;
 mod sys_authentication;
+mod sys_dashboard;
 mod sys_domain;
 mod sys_endpoint;
 mod sys_menu;
+mod sys_organization;
 mod sys_user;
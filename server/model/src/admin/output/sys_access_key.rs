@@ -0,0 +1,15 @@
+use serde::Serialize;
+
+use crate::admin::entities::sys_access_key::Model as SysAccessKeyModel;
+
+/// 访问密钥及其明文密钥
+///
+/// `access_key_secret` 仅在创建与轮换时返回一次，服务端只保存其密文。
+/// Ed25519 签名的密钥由客户端持有私钥，不返回密钥。
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessKeySecretOutput {
+    pub access_key: SysAccessKeyModel,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_key_secret: Option<String>,
+}
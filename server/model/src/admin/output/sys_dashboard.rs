@@ -0,0 +1,55 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+/// 登录统计
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginStats {
+    pub start_time: NaiveDateTime,
+    pub end_time: NaiveDateTime,
+    /// 时间范围内登录成功的去重用户数
+    pub active_users: i64,
+    pub logins: i64,
+    pub failures: i64,
+    /// 失败次数占全部登录尝试的比例
+    pub failure_rate: f64,
+    /// 按天统计，日期格式为 `YYYY-MM-DD`
+    pub daily: Vec<LoginDailyStat>,
+}
+
+/// 单日登录统计
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginDailyStat {
+    pub date: String,
+    pub active_users: i64,
+    pub logins: i64,
+    pub failures: i64,
+    pub failure_rate: f64,
+}
+
+/// 按地区统计的登录次数
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginRegionStat {
+    pub country: String,
+    /// 省份未知时为空
+    pub province: Option<String>,
+    pub logins: i64,
+}
+
+/// 接口调用统计，按请求方法与路由模板聚合
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointStat {
+    pub method: String,
+    /// 路由模板，如 `/user/:id`
+    pub route: String,
+    pub module_name: String,
+    pub description: String,
+    pub calls: i64,
+    /// P95 耗时（毫秒）
+    pub p95_duration: f64,
+    pub errors: i64,
+    pub error_rate: f64,
+}
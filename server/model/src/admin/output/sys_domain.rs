@@ -1,4 +1,10 @@
 use sea_orm::FromQueryResult;
+use serde::Serialize;
+
+use super::UserWithoutPassword;
+use crate::admin::entities::{
+    sys_domain::Model as SysDomainModel, sys_role::Model as SysRoleModel,
+};
 
 #[derive(Debug, FromQueryResult)]
 pub struct DomainOutput {
@@ -7,3 +13,16 @@ pub struct DomainOutput {
     pub name: String,
     pub description: Option<String>,
 }
+
+/// 租户开通结果
+///
+/// `admin_password` 为初始管理员的一次性密码，仅在开通时返回，服务端只保存其哈希。
+/// 初始管理员修改密码和登录时须以 `domain.code` 作为所在域。
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvisionDomainOutput {
+    pub domain: SysDomainModel,
+    pub roles: Vec<SysRoleModel>,
+    pub admin_user: UserWithoutPassword,
+    pub admin_password: String,
+}
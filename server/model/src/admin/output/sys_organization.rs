@@ -0,0 +1,46 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+use crate::admin::entities::{
+    sea_orm_active_enums::Status, sys_organization::Model as SysOrganizationModel,
+};
+
+#[derive(Debug, Serialize, Clone)]
+pub struct OrganizationTree {
+    pub id: String,
+    pub code: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub pid: String,
+    pub status: Status,
+    #[serde(rename = "createdAt")]
+    pub created_at: NaiveDateTime,
+    #[serde(rename = "createdBy")]
+    pub created_by: String,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "updatedAt")]
+    pub updated_at: Option<NaiveDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "updatedBy")]
+    pub updated_by: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<OrganizationTree>>,
+}
+
+impl From<SysOrganizationModel> for OrganizationTree {
+    fn from(model: SysOrganizationModel) -> Self {
+        Self {
+            id: model.id,
+            code: model.code,
+            name: model.name,
+            description: model.description,
+            pid: model.pid,
+            status: model.status,
+            created_at: model.created_at,
+            created_by: model.created_by,
+            updated_at: model.updated_at,
+            updated_by: model.updated_by,
+            children: None,
+        }
+    }
+}
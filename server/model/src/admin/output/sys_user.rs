@@ -1,6 +1,7 @@
 use chrono::NaiveDateTime;
 use sea_orm::FromQueryResult;
 use serde::Serialize;
+use server_core::web::etag::Versioned;
 
 use crate::admin::entities::{sea_orm_active_enums::Status, sys_user::Model as SysUserModel};
 
@@ -14,6 +15,10 @@ pub struct UserWithDomainAndOrgOutput {
     pub avatar: Option<String>,
     pub domain_code: String,
     pub domain_name: String,
+    pub organization_name: Option<String>,
+    pub must_change_password: bool,
+    pub status: Status,
+    pub domain_status: Status,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
@@ -26,11 +31,14 @@ pub struct UserWithoutPassword {
     pub avatar: Option<String>,
     pub email: Option<String>,
     pub phone_number: Option<String>,
+    pub organization_id: Option<String>,
+    pub must_change_password: bool,
     pub status: Status,
     pub created_at: NaiveDateTime,
     pub created_by: String,
     pub updated_at: Option<NaiveDateTime>,
     pub updated_by: Option<String>,
+    pub version: i32,
 }
 
 impl From<SysUserModel> for UserWithoutPassword {
@@ -43,11 +51,20 @@ impl From<SysUserModel> for UserWithoutPassword {
             avatar: model.avatar,
             email: model.email,
             phone_number: model.phone_number,
+            organization_id: model.organization_id,
+            must_change_password: model.must_change_password,
             status: model.status,
             created_at: model.created_at,
             created_by: model.created_by,
             updated_at: model.updated_at,
             updated_by: model.updated_by,
+            version: model.version,
         }
     }
 }
+
+impl Versioned for UserWithoutPassword {
+    fn version(&self) -> i32 {
+        self.version
+    }
+}
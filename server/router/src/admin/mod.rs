@@ -1,23 +1,29 @@
 pub use sys_access_key_route::SysAccessKeyRouter;
 pub use sys_authentication_route::SysAuthenticationRouter;
+pub use sys_dashboard_route::SysDashboardRouter;
 pub use sys_domain_route::SysDomainRouter;
 pub use sys_endpoint_route::SysEndpointRouter;
+pub use sys_ip_rule_route::SysIpRuleRouter;
 pub use sys_login_log_route::SysLoginLogRouter;
 pub use sys_menu_route::SysMenuRouter;
 pub use sys_operation_log_route::SysOperationLogRouter;
 pub use sys_organization_route::SysOrganizationRouter;
+pub use sys_recycle_bin_route::SysRecycleBinRouter;
 pub use sys_role_route::SysRoleRouter;
 pub use sys_sandbox_route::SysSandboxRouter;
 pub use sys_user_route::SysUserRouter;
 
 mod sys_access_key_route;
 mod sys_authentication_route;
+mod sys_dashboard_route;
 mod sys_domain_route;
 mod sys_endpoint_route;
+mod sys_ip_rule_route;
 mod sys_login_log_route;
 mod sys_menu_route;
 mod sys_operation_log_route;
 mod sys_organization_route;
+mod sys_recycle_bin_route;
 mod sys_role_route;
 mod sys_sandbox_route;
 mod sys_user_route;
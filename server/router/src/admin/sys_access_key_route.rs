@@ -1,6 +1,6 @@
 use axum::{
     http::Method,
-    routing::{delete, get, post},
+    routing::{delete, get, post, put},
     Router,
 };
 use server_api::admin::SysAccessKeyApi;
@@ -16,6 +16,13 @@ impl SysAccessKeyRouter {
         let routes = vec![
             RouteInfo::new(base_path, Method::GET, service_name, "获取访问密钥列表"),
             RouteInfo::new(base_path, Method::POST, service_name, "创建访问密钥"),
+            RouteInfo::new(base_path, Method::PUT, service_name, "更新访问密钥"),
+            RouteInfo::new(
+                &format!("{}/:id/rotate", base_path),
+                Method::POST,
+                service_name,
+                "轮换访问密钥",
+            ),
             RouteInfo::new(
                 &format!("{}/:id", base_path),
                 Method::DELETE,
@@ -31,6 +38,8 @@ impl SysAccessKeyRouter {
         let router = Router::new()
             .route("/", get(SysAccessKeyApi::get_paginated_access_keys))
             .route("/", post(SysAccessKeyApi::create_access_key))
+            .route("/", put(SysAccessKeyApi::update_access_key))
+            .route("/{id}/rotate", post(SysAccessKeyApi::rotate_access_key))
             .route("/{id}", delete(SysAccessKeyApi::delete_access_key));
 
         Router::new().nest(base_path, router)
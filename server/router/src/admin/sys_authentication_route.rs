@@ -4,17 +4,27 @@ use axum::{
     Router,
 };
 use server_api::admin::SysAuthenticationApi;
+use server_core::web::operation_log::skip_operation_log;
 use server_global::global::{add_route, RouteInfo};
 
 pub struct SysAuthenticationRouter;
 
 impl SysAuthenticationRouter {
     pub async fn init_authentication_router() -> Router {
-        let router = Router::new().route("/login", post(SysAuthenticationApi::login_handler));
+        let router = Router::new()
+            .route("/login", post(SysAuthenticationApi::login_handler))
+            .route(
+                "/change-password",
+                post(SysAuthenticationApi::change_password),
+            );
         Router::new().nest("/auth", router)
     }
 
     pub async fn init_protected_router() -> Router {
+        // 会话信息查询较为频繁，不记录操作日志
+        skip_operation_log(Method::GET, "/auth/getUserInfo");
+        skip_operation_log(Method::GET, "/auth/getUserRoutes");
+
         let router = Router::new()
             .route("/getUserInfo", get(SysAuthenticationApi::get_user_info))
             .route("/getUserRoutes", get(SysAuthenticationApi::get_user_routes));
@@ -45,6 +55,8 @@ impl SysAuthenticationRouter {
             add_route(route).await;
         }
 
+        skip_operation_log(Method::GET, &format!("{}/getUserRoutes", base_path));
+
         let authorization_router = Router::new()
             .route("/getUserRoutes", get(SysAuthenticationApi::get_user_routes))
             .route(
@@ -0,0 +1,44 @@
+use axum::{http::Method, routing::get, Router};
+use server_api::admin::SysDashboardApi;
+use server_global::global::{add_route, RouteInfo};
+
+pub struct SysDashboardRouter;
+
+impl SysDashboardRouter {
+    pub async fn init_dashboard_router() -> Router {
+        let base_path = "/dashboard";
+        let service_name = "SysDashboardApi";
+
+        let routes = vec![
+            RouteInfo::new(
+                &format!("{}/login-stats", base_path),
+                Method::GET,
+                service_name,
+                "获取登录趋势统计",
+            ),
+            RouteInfo::new(
+                &format!("{}/login-regions", base_path),
+                Method::GET,
+                service_name,
+                "获取登录地区排行",
+            ),
+            RouteInfo::new(
+                &format!("{}/endpoint-stats", base_path),
+                Method::GET,
+                service_name,
+                "获取接口调用统计",
+            ),
+        ];
+
+        for route in routes {
+            add_route(route).await;
+        }
+
+        let router = Router::new()
+            .route("/login-stats", get(SysDashboardApi::get_login_stats))
+            .route("/login-regions", get(SysDashboardApi::get_login_regions))
+            .route("/endpoint-stats", get(SysDashboardApi::get_endpoint_stats));
+
+        Router::new().nest(base_path, router)
+    }
+}
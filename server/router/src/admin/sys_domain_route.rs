@@ -29,6 +29,18 @@ impl SysDomainRouter {
                 service_name,
                 "删除域名",
             ),
+            RouteInfo::new(
+                &format!("{}/provision", base_path),
+                Method::POST,
+                service_name,
+                "开通租户",
+            ),
+            RouteInfo::new(
+                &format!("{}/:id/deprovision", base_path),
+                Method::DELETE,
+                service_name,
+                "注销租户",
+            ),
         ];
 
         for route in routes {
@@ -40,7 +52,12 @@ impl SysDomainRouter {
             .route("/", post(SysDomainApi::create_domain))
             .route("/{id}", get(SysDomainApi::get_domain))
             .route("/", put(SysDomainApi::update_domain))
-            .route("/{id}", delete(SysDomainApi::delete_domain));
+            .route("/{id}", delete(SysDomainApi::delete_domain))
+            .route("/provision", post(SysDomainApi::provision_domain))
+            .route(
+                "/{id}/deprovision",
+                delete(SysDomainApi::deprovision_domain),
+            );
 
         Router::new().nest(base_path, router)
     }
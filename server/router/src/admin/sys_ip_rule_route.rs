@@ -0,0 +1,40 @@
+use axum::{
+    http::Method,
+    routing::{delete, get, post, put},
+    Router,
+};
+use server_api::admin::SysIpRuleApi;
+use server_global::global::{add_route, RouteInfo};
+
+pub struct SysIpRuleRouter;
+
+impl SysIpRuleRouter {
+    pub async fn init_ip_rule_router() -> Router {
+        let base_path = "/ip-rule";
+        let service_name = "SysIpRuleApi";
+
+        let routes = vec![
+            RouteInfo::new(base_path, Method::GET, service_name, "获取IP规则列表"),
+            RouteInfo::new(base_path, Method::POST, service_name, "创建IP规则"),
+            RouteInfo::new(base_path, Method::PUT, service_name, "更新IP规则"),
+            RouteInfo::new(
+                &format!("{}/:id", base_path),
+                Method::DELETE,
+                service_name,
+                "删除IP规则",
+            ),
+        ];
+
+        for route in routes {
+            add_route(route).await;
+        }
+
+        let router = Router::new()
+            .route("/", get(SysIpRuleApi::get_paginated_ip_rules))
+            .route("/", post(SysIpRuleApi::create_ip_rule))
+            .route("/", put(SysIpRuleApi::update_ip_rule))
+            .route("/{id}", delete(SysIpRuleApi::delete_ip_rule));
+
+        Router::new().nest(base_path, router)
+    }
+}
@@ -9,19 +9,33 @@ impl SysOperationLogRouter {
         let base_path = "/operation-log";
         let service_name = "SysOperationLogApi";
 
-        let routes = vec![RouteInfo::new(
-            base_path,
-            Method::GET,
-            service_name,
-            "获取操作日志列表",
-        )];
+        let routes = vec![
+            RouteInfo::new(base_path, Method::GET, service_name, "获取操作日志列表"),
+            RouteInfo::new(
+                &format!("{}/cursor", base_path),
+                Method::GET,
+                service_name,
+                "游标分页获取操作日志列表",
+            ),
+            RouteInfo::new(
+                &format!("{}/export", base_path),
+                Method::GET,
+                service_name,
+                "导出操作日志",
+            ),
+        ];
 
         for route in routes {
             add_route(route).await;
         }
 
-        let router =
-            Router::new().route("/", get(SysOperationLogApi::get_paginated_operation_logs));
+        let router = Router::new()
+            .route("/", get(SysOperationLogApi::get_paginated_operation_logs))
+            .route(
+                "/cursor",
+                get(SysOperationLogApi::get_cursor_operation_logs),
+            )
+            .route("/export", get(SysOperationLogApi::export_operation_logs));
 
         Router::new().nest(base_path, router)
     }
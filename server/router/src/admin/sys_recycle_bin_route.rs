@@ -0,0 +1,50 @@
+use axum::{
+    http::Method,
+    routing::{delete, get, put},
+    Router,
+};
+use server_api::admin::SysRecycleBinApi;
+use server_global::global::{add_route, RouteInfo};
+
+pub struct SysRecycleBinRouter;
+
+impl SysRecycleBinRouter {
+    pub async fn init_recycle_bin_router() -> Router {
+        let base_path = "/recycle-bin";
+        let service_name = "SysRecycleBinApi";
+
+        let routes = vec![
+            RouteInfo::new(base_path, Method::GET, service_name, "获取回收站列表"),
+            RouteInfo::new(
+                &format!("{}/:id/restore", base_path),
+                Method::PUT,
+                service_name,
+                "恢复回收站条目",
+            ),
+            RouteInfo::new(
+                &format!("{}/:id", base_path),
+                Method::DELETE,
+                service_name,
+                "彻底删除回收站条目",
+            ),
+            RouteInfo::new(
+                &format!("{}/expired", base_path),
+                Method::DELETE,
+                service_name,
+                "清理过期回收站条目",
+            ),
+        ];
+
+        for route in routes {
+            add_route(route).await;
+        }
+
+        let router = Router::new()
+            .route("/", get(SysRecycleBinApi::get_paginated_entries))
+            .route("/{id}/restore", put(SysRecycleBinApi::restore_entry))
+            .route("/expired", delete(SysRecycleBinApi::purge_expired))
+            .route("/{id}", delete(SysRecycleBinApi::purge_entry));
+
+        Router::new().nest(base_path, router)
+    }
+}
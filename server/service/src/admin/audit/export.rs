@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use futures::{stream, stream::BoxStream, StreamExt};
+use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Select};
+use server_core::web::{
+    error::AppError,
+    page::{Cursor, SortOrder},
+};
+use server_model::admin::input::ExportFormat;
+
+use super::{csv_line, ndjson_line, AuditRecord};
+
+/// 每次从数据库读取的条数
+const EXPORT_PAGE_SIZE: u64 = 500;
+
+enum ExportState {
+    Header,
+    Page(Option<Cursor>),
+    Done,
+}
+
+/// 以 `(created_at, id)` 为游标逐页读取并输出文本块
+///
+/// 每次只持有一页数据，适合导出大时间范围的日志。
+///
+/// # 参数
+/// * `db` - 数据库连接
+/// * `query` - 已应用过滤条件的查询
+/// * `created_at` - 创建时间列
+/// * `id` - 主键列
+/// * `format` - 导出格式
+pub fn export_stream<E>(
+    db: Arc<DatabaseConnection>,
+    query: Select<E>,
+    created_at: E::Column,
+    id: E::Column,
+    format: ExportFormat,
+) -> BoxStream<'static, Result<String, AppError>>
+where
+    E: EntityTrait,
+    E::Model: AuditRecord + Send + Sync,
+{
+    let initial = match format {
+        ExportFormat::Csv => ExportState::Header,
+        ExportFormat::Ndjson => ExportState::Page(None),
+    };
+
+    stream::try_unfold(initial, move |state| {
+        let db = db.clone();
+        let query = query.clone();
+        async move {
+            let cursor = match state {
+                ExportState::Header => {
+                    let header = csv_line(<E::Model as AuditRecord>::CSV_HEADER);
+                    return Ok(Some((header, ExportState::Page(None))));
+                },
+                ExportState::Page(cursor) => cursor,
+                ExportState::Done => return Ok(None),
+            };
+
+            let mut query = query.order_by_asc(created_at).order_by_asc(id);
+            if let Some(cursor) = cursor {
+                query = query.filter(cursor.after(created_at, id, SortOrder::Asc));
+            }
+
+            let records = query
+                .limit(EXPORT_PAGE_SIZE)
+                .all(db.as_ref())
+                .await
+                .map_err(AppError::from)?;
+
+            let Some(last) = records.last() else {
+                return Ok(None);
+            };
+            let next = if (records.len() as u64) < EXPORT_PAGE_SIZE {
+                ExportState::Done
+            } else {
+                ExportState::Page(Some(Cursor::of(last)))
+            };
+
+            let chunk = records
+                .iter()
+                .map(|record| match format {
+                    ExportFormat::Csv => csv_line(&record.csv_row()),
+                    ExportFormat::Ndjson => ndjson_line(record),
+                })
+                .collect::<String>();
+
+            Ok(Some((chunk, next)))
+        }
+    })
+    .boxed()
+}
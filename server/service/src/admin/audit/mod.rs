@@ -0,0 +1,80 @@
+use sea_orm::{ColumnTrait, Condition};
+use serde::Serialize;
+use server_core::web::page::CursorKey;
+
+pub use export::export_stream;
+
+use super::sys_domain_service::BUILT_IN_DOMAIN;
+pub use retention::audit_retention_task;
+
+mod export;
+mod retention;
+
+/// 可导出、可归档的审计日志记录
+pub trait AuditRecord: CursorKey + Serialize {
+    /// CSV 表头
+    const CSV_HEADER: &'static [&'static str];
+
+    /// 与表头顺序一致的 CSV 字段
+    fn csv_row(&self) -> Vec<String>;
+}
+
+/// 审计日志的查询范围：内置域可查看全部域的日志，其他域只能查看本域的日志
+pub(crate) fn domain_scope<C: ColumnTrait>(column: C, domain: &str) -> Condition {
+    if domain == BUILT_IN_DOMAIN {
+        Condition::all()
+    } else {
+        Condition::all().add(column.eq(domain))
+    }
+}
+
+/// 转义 CSV 字段
+///
+/// 包含分隔符、引号或换行时加引号；以公式字符开头时加 `'` 前缀，
+/// 防止在表格软件中被当作公式执行。
+pub(crate) fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// 生成一行 CSV，含换行符
+pub(crate) fn csv_line<S: AsRef<str>>(fields: &[S]) -> String {
+    let mut line = fields
+        .iter()
+        .map(|field| csv_field(field.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+/// 生成一行 NDJSON，含换行符
+pub(crate) fn ndjson_line<T: Serialize>(record: &T) -> String {
+    let mut line = serde_json::to_string(record).unwrap_or_default();
+    line.push('\n');
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_line() {
+        assert_eq!(csv_line(&["a", "b c"]), "a,b c\r\n");
+        assert_eq!(
+            csv_line(&["x,y", "say \"hi\"", "line\nbreak"]),
+            "\"x,y\",\"say \"\"hi\"\"\",\"line\nbreak\"\r\n"
+        );
+        assert_eq!(csv_line(&["=1+1", "@cmd", "-2"]), "'=1+1,'@cmd,'-2\r\n");
+    }
+}
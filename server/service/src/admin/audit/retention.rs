@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use chrono::Local;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use server_config::{ArchiveConfig, LogRetentionPolicy, RetentionConfig};
+use server_core::web::{error::AppError, page::CursorKey};
+use server_global::{project_error, project_info};
+use server_model::admin::entities::{
+    prelude::{SysLoginLog, SysOperationLog},
+    sys_login_log::Column as SysLoginLogColumn,
+    sys_operation_log::Column as SysOperationLogColumn,
+};
+use tokio::time::MissedTickBehavior;
+
+use super::{ndjson_line, AuditRecord};
+use crate::helper::{db_helper, s3_helper};
+
+/// 日志保留后台任务，按配置间隔清理过期的操作日志与登录日志
+pub async fn audit_retention_task(config: RetentionConfig) {
+    let mut ticker = tokio::time::interval(Duration::from_secs(config.interval_secs.max(1)));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+
+        match purge_expired::<SysOperationLog>(
+            "operation-log",
+            SysOperationLogColumn::CreatedAt,
+            SysOperationLogColumn::Id,
+            &config.operation_log,
+            config.batch_size,
+        )
+        .await
+        {
+            Ok(0) => {},
+            Ok(count) => project_info!("Purged {} expired operation log(s)", count),
+            Err(e) => project_error!("Failed to purge expired operation logs: {:?}", e),
+        }
+
+        match purge_expired::<SysLoginLog>(
+            "login-log",
+            SysLoginLogColumn::CreatedAt,
+            SysLoginLogColumn::Id,
+            &config.login_log,
+            config.batch_size,
+        )
+        .await
+        {
+            Ok(0) => {},
+            Ok(count) => project_info!("Purged {} expired login log(s)", count),
+            Err(e) => project_error!("Failed to purge expired login logs: {:?}", e),
+        }
+    }
+}
+
+/// 分批清理超过保留天数的日志
+///
+/// 配置归档时每批先上传到 S3，成功后才删除，上传失败则本轮停止，等待下次重试。
+async fn purge_expired<E>(
+    log_type: &str,
+    created_at: E::Column,
+    id: E::Column,
+    policy: &LogRetentionPolicy,
+    batch_size: u64,
+) -> Result<u64, AppError>
+where
+    E: EntityTrait,
+    E::Model: AuditRecord + Send + Sync,
+{
+    let Some(max_age_days) = policy.max_age_days else {
+        return Ok(0);
+    };
+
+    let batch_size = batch_size.max(1);
+    let cutoff = Local::now().naive_local() - chrono::Duration::days(i64::from(max_age_days));
+    let db = db_helper::get_db_connection().await?;
+    let mut total = 0;
+
+    loop {
+        let records = E::find()
+            .filter(created_at.lt(cutoff))
+            .order_by_asc(created_at)
+            .order_by_asc(id)
+            .limit(batch_size)
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        if records.is_empty() {
+            break;
+        }
+
+        if let Some(archive) = &policy.archive {
+            archive_batch(archive, log_type, &records).await?;
+        }
+
+        let ids: Vec<String> = records.iter().map(|r| r.id().to_string()).collect();
+        let result = E::delete_many()
+            .filter(id.is_in(ids))
+            .exec(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+        total += result.rows_affected;
+
+        if (records.len() as u64) < batch_size {
+            break;
+        }
+    }
+
+    Ok(total)
+}
+
+/// 将一批日志以 NDJSON 上传到 S3
+///
+/// 对象键由批次首尾 ID 决定，删除失败后重试会覆盖同一对象。
+async fn archive_batch<T: AuditRecord>(
+    archive: &ArchiveConfig,
+    log_type: &str,
+    records: &[T],
+) -> Result<(), AppError> {
+    let (Some(first), Some(last)) = (records.first(), records.last()) else {
+        return Ok(());
+    };
+
+    let key = format!(
+        "{}/{}/{}/{}-{}.ndjson",
+        archive.prefix.trim_end_matches('/'),
+        log_type,
+        first.created_at().format("%Y/%m/%d"),
+        first.id(),
+        last.id()
+    );
+    let body = records.iter().map(ndjson_line).collect::<String>();
+
+    s3_helper::put_object(
+        &archive.bucket,
+        &key,
+        body.into_bytes(),
+        "application/x-ndjson",
+    )
+    .await
+}
@@ -1 +1,2 @@
 pub mod sys_auth_dto;
+pub mod sys_recycle_bin_dto;
@@ -1,10 +1,13 @@
 use server_constant::definition::Audience;
+use xdb::Region;
 
 #[derive(Clone, Debug)]
 pub struct LoginContext {
     pub client_ip: String,
     pub client_port: Option<i32>,
     pub address: String,
+    /// 解析后的归属地
+    pub region: Region,
     pub user_agent: String,
     pub request_id: String,
     pub audience: Audience,
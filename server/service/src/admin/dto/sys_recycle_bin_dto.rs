@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// 软删除时一并移除的关联数据，恢复时据此重建
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RecycleSnapshot {
+    /// 随实体一同软删除的其他实体，如子孙菜单
+    #[serde(default)]
+    pub cascaded_ids: Vec<String>,
+    /// 用户角色绑定 `(user_id, role_id)`
+    #[serde(default)]
+    pub user_roles: Vec<(String, String)>,
+    /// 角色菜单绑定 `(role_id, menu_id, domain)`
+    #[serde(default)]
+    pub role_menus: Vec<(String, i32, String)>,
+    /// Casbin `p` 规则
+    #[serde(default)]
+    pub policies: Vec<Vec<String>>,
+    /// Casbin `g` 规则
+    #[serde(default)]
+    pub grouping_policies: Vec<Vec<String>>,
+}
@@ -1,5 +1,8 @@
 pub mod sys_access_key_error;
 pub mod sys_domain_error;
+pub mod sys_ip_rule_error;
 pub mod sys_menu_error;
+pub mod sys_organization_error;
+pub mod sys_recycle_bin_error;
 pub mod sys_role_error;
 pub mod sys_user_error;
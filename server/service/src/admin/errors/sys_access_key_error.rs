@@ -5,12 +5,39 @@ use thiserror::Error;
 pub enum AccessKeyError {
     #[error("Access key not found")]
     AccessKeyNotFound,
+    #[error("Invalid access key scope: {0}")]
+    InvalidScope(String),
+    #[error("Invalid allowed IP: {0}")]
+    InvalidAllowedIp(String),
+    #[error("Expiry time must be in the future")]
+    ExpiryInPast,
+    #[error("Access key encryption key is not configured")]
+    EncryptionKeyMissing,
+    #[error("Invalid Ed25519 public key")]
+    InvalidPublicKey,
+    #[error("Signature algorithm '{0}' is deprecated and disabled")]
+    LegacyAlgorithmDisabled(String),
+    #[error("Cannot switch between shared-secret and Ed25519 signatures")]
+    IncompatibleAlgorithm,
+    #[error("Ed25519 access keys are rotated by updating the public key")]
+    RotationNotSupported,
+    #[error("Access key secret changed during rotation, please retry")]
+    RotationConflict,
 }
 
 impl ApiError for AccessKeyError {
     fn code(&self) -> u16 {
         match self {
             AccessKeyError::AccessKeyNotFound => 5001,
+            AccessKeyError::InvalidScope(_) => 5002,
+            AccessKeyError::InvalidAllowedIp(_) => 5003,
+            AccessKeyError::ExpiryInPast => 5004,
+            AccessKeyError::EncryptionKeyMissing => 5005,
+            AccessKeyError::InvalidPublicKey => 5006,
+            AccessKeyError::LegacyAlgorithmDisabled(_) => 5007,
+            AccessKeyError::IncompatibleAlgorithm => 5008,
+            AccessKeyError::RotationNotSupported => 5009,
+            AccessKeyError::RotationConflict => 5010,
         }
     }
 
@@ -11,6 +11,14 @@ pub enum DomainError {
     DuplicateName,
     #[error("Cannot modify or delete built-in domain")]
     BuiltInDomain,
+    #[error("Template domain not found")]
+    TemplateDomainNotFound,
+    #[error("Role not found in template domain")]
+    TemplateRoleNotFound,
+    #[error("Domain is not enabled")]
+    DomainDisabled,
+    #[error("Casbin policy operation failed: {0}")]
+    PolicyError(String),
 }
 
 impl ApiError for DomainError {
@@ -20,6 +28,10 @@ impl ApiError for DomainError {
             DomainError::DuplicateCode => 2002,
             DomainError::DuplicateName => 2003,
             DomainError::BuiltInDomain => 2004,
+            DomainError::TemplateDomainNotFound => 2005,
+            DomainError::TemplateRoleNotFound => 2006,
+            DomainError::DomainDisabled => 2007,
+            DomainError::PolicyError(_) => 2008,
         }
     }
 
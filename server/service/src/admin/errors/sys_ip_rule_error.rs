@@ -0,0 +1,38 @@
+use server_core::web::error::{ApiError, AppError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum IpRuleError {
+    #[error("IP rule not found")]
+    IpRuleNotFound,
+    #[error("Invalid CIDR: {0}")]
+    InvalidCidr(String),
+    #[error("Scope value is required for domain and access key rules")]
+    MissingScopeValue,
+    #[error("IP rule must specify a CIDR, country or province")]
+    EmptyCondition,
+}
+
+impl ApiError for IpRuleError {
+    fn code(&self) -> u16 {
+        match self {
+            IpRuleError::IpRuleNotFound => 8001,
+            IpRuleError::InvalidCidr(_) => 8002,
+            IpRuleError::MissingScopeValue => 8003,
+            IpRuleError::EmptyCondition => 8004,
+        }
+    }
+
+    fn message(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl From<IpRuleError> for AppError {
+    fn from(err: IpRuleError) -> Self {
+        AppError {
+            code: err.code(),
+            message: err.message(),
+        }
+    }
+}
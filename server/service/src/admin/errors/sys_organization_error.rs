@@ -0,0 +1,44 @@
+use server_core::web::error::{ApiError, AppError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum OrganizationError {
+    #[error("Organization not found")]
+    OrganizationNotFound,
+    #[error("Organization with this code already exists")]
+    DuplicateCode,
+    #[error("Parent organization not found")]
+    ParentNotFound,
+    #[error("Organization cannot be moved under itself or its descendants")]
+    CyclicParent,
+    #[error("Organization still has child organizations")]
+    HasChildren,
+    #[error("Organization still has users assigned")]
+    HasUsers,
+}
+
+impl ApiError for OrganizationError {
+    fn code(&self) -> u16 {
+        match self {
+            OrganizationError::OrganizationNotFound => 6001,
+            OrganizationError::DuplicateCode => 6002,
+            OrganizationError::ParentNotFound => 6003,
+            OrganizationError::CyclicParent => 6004,
+            OrganizationError::HasChildren => 6005,
+            OrganizationError::HasUsers => 6006,
+        }
+    }
+
+    fn message(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl From<OrganizationError> for AppError {
+    fn from(err: OrganizationError) -> Self {
+        AppError {
+            code: err.code(),
+            message: err.message(),
+        }
+    }
+}
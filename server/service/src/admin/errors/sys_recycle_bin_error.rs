@@ -0,0 +1,49 @@
+use server_core::web::error::{ApiError, AppError};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RecycleBinError {
+    #[error("Recycle bin entry not found")]
+    EntryNotFound,
+
+    #[error("Deleted entity no longer exists")]
+    EntityNotFound,
+
+    #[error("Parent of the deleted entity has been deleted")]
+    ParentDeleted,
+
+    #[error("Domain of the deleted entity has been deleted")]
+    DomainDeleted,
+
+    #[error("Invalid recycle bin snapshot: {0}")]
+    InvalidSnapshot(String),
+
+    #[error("Casbin policy operation failed: {0}")]
+    PolicyError(String),
+}
+
+impl ApiError for RecycleBinError {
+    fn code(&self) -> u16 {
+        match self {
+            RecycleBinError::EntryNotFound => 7001,
+            RecycleBinError::EntityNotFound => 7002,
+            RecycleBinError::ParentDeleted => 7003,
+            RecycleBinError::DomainDeleted => 7004,
+            RecycleBinError::InvalidSnapshot(_) => 7005,
+            RecycleBinError::PolicyError(_) => 7006,
+        }
+    }
+
+    fn message(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl From<RecycleBinError> for AppError {
+    fn from(err: RecycleBinError) -> Self {
+        AppError {
+            code: err.code(),
+            message: err.message(),
+        }
+    }
+}
@@ -8,6 +8,9 @@ pub enum RoleError {
 
     #[error("Duplicate role code")]
     DuplicateRoleCode,
+
+    #[error("Casbin policy operation failed: {0}")]
+    PolicyError(String),
 }
 
 impl ApiError for RoleError {
@@ -15,6 +18,7 @@ impl ApiError for RoleError {
         match self {
             RoleError::RoleNotFound => 4001,
             RoleError::DuplicateRoleCode => 4002,
+            RoleError::PolicyError(_) => 4003,
         }
     }
 
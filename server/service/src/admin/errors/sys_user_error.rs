@@ -13,6 +13,16 @@ pub enum UserError {
     UsernameAlreadyExists,
     #[error("Invalid user status")]
     InvalidUserStatus,
+    #[error("Cannot delete built-in user")]
+    BuiltInUser,
+    #[error("Cannot remove the last administrator of a domain")]
+    LastAdmin,
+    #[error("Login blocked due to high risk")]
+    LoginBlocked,
+    #[error("Password must be changed before logging in")]
+    PasswordChangeRequired,
+    #[error("New password must differ from the current password")]
+    PasswordUnchanged,
 }
 
 impl ApiError for UserError {
@@ -23,6 +33,11 @@ impl ApiError for UserError {
             UserError::AuthenticationFailed => 1003,
             UserError::UsernameAlreadyExists => 1004,
             UserError::InvalidUserStatus => 1005,
+            UserError::BuiltInUser => 1006,
+            UserError::LastAdmin => 1007,
+            UserError::LoginBlocked => 1009,
+            UserError::PasswordChangeRequired => 1010,
+            UserError::PasswordUnchanged => 1011,
         }
     }
 
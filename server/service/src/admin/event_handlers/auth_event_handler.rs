@@ -1,4 +1,7 @@
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect};
 use server_core::web::error::AppError;
+use server_model::admin::entities::{prelude::SysUser, sys_user::Column as SysUserColumn};
+use xdb::Region;
 
 use crate::{
     admin::events::{access_token_event::AccessTokenEvent, login_log_event::LoginLogEvent},
@@ -14,9 +17,26 @@ pub struct AuthEvent {
     pub client_ip: String,
     pub client_port: Option<i32>,
     pub address: String,
+    pub region: Region,
     pub user_agent: String,
     pub request_id: String,
     pub login_type: String,
+    pub risk_score: i32,
+    pub risk_reasons: Option<String>,
+}
+
+/// 登录失败事件
+pub struct LoginFailedEvent {
+    pub username: String,
+    pub domain: String,
+    pub client_ip: String,
+    pub client_port: Option<i32>,
+    pub address: String,
+    pub region: Region,
+    pub user_agent: String,
+    pub request_id: String,
+    pub login_type: String,
+    pub reason: String,
 }
 
 pub struct AuthEventHandler;
@@ -33,9 +53,14 @@ impl AuthEventHandler {
             ip: event.client_ip.clone(),
             port: event.client_port,
             address: event.address.clone(),
+            region: event.region.clone(),
             user_agent: event.user_agent.clone(),
             request_id: event.request_id.clone(),
             login_type: event.login_type.clone(),
+            risk_score: event.risk_score,
+            risk_reasons: event.risk_reasons.clone(),
+            success: true,
+            failure_reason: None,
         };
 
         login_log_event.handle(&db).await?;
@@ -59,4 +84,40 @@ impl AuthEventHandler {
 
         Ok(())
     }
+
+    /// 记录登录失败，用户不存在时 `user_id` 为空
+    pub async fn handle_login_failed(event: &LoginFailedEvent) -> Result<(), AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        let user_id = SysUser::find()
+            .select_only()
+            .column(SysUserColumn::Id)
+            .filter(SysUserColumn::Username.eq(event.username.as_str()))
+            .filter(SysUserColumn::Domain.eq(event.domain.as_str()))
+            .filter(SysUserColumn::DeletedAt.is_null())
+            .into_tuple::<String>()
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .unwrap_or_default();
+
+        LoginLogEvent {
+            user_id,
+            username: event.username.clone(),
+            domain: event.domain.clone(),
+            ip: event.client_ip.clone(),
+            port: event.client_port,
+            address: event.address.clone(),
+            region: event.region.clone(),
+            user_agent: event.user_agent.clone(),
+            request_id: event.request_id.clone(),
+            login_type: event.login_type.clone(),
+            risk_score: 0,
+            risk_reasons: None,
+            success: false,
+            failure_reason: Some(event.reason.clone()),
+        }
+        .handle(&db)
+        .await
+    }
 }
@@ -3,6 +3,7 @@ use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
 use server_core::web::error::AppError;
 use server_model::admin::entities::sys_login_log::ActiveModel as SysLoginLogActiveModel;
 use ulid::Ulid;
+use xdb::Region;
 
 pub struct LoginLogEvent {
     pub user_id: String,
@@ -11,9 +12,14 @@ pub struct LoginLogEvent {
     pub ip: String,
     pub port: Option<i32>,
     pub address: String,
+    pub region: Region,
     pub user_agent: String,
     pub request_id: String,
     pub login_type: String,
+    pub risk_score: i32,
+    pub risk_reasons: Option<String>,
+    pub success: bool,
+    pub failure_reason: Option<String>,
 }
 
 impl LoginLogEvent {
@@ -32,6 +38,15 @@ impl LoginLogEvent {
             r#type: Set(self.login_type),
             created_at: Set(Local::now().naive_local()),
             created_by: Set(self.username),
+            risk_score: Set(self.risk_score),
+            risk_reasons: Set(self.risk_reasons),
+            success: Set(self.success),
+            failure_reason: Set(self.failure_reason),
+            country: Set(self.region.country),
+            region: Set(self.region.region),
+            province: Set(self.region.province),
+            city: Set(self.region.city),
+            isp: Set(self.region.isp),
         }
         .insert(db)
         .await
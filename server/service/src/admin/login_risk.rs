@@ -0,0 +1,392 @@
+use std::{any::Any, collections::HashSet};
+
+use chrono::{Duration, Local, NaiveDateTime};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use server_config::LoginRiskConfig;
+use server_core::web::error::AppError;
+use server_model::admin::entities::{
+    prelude::SysLoginLog,
+    sys_login_log::{Column as SysLoginLogColumn, Model as SysLoginLogModel},
+};
+use tracing::instrument;
+use xdb::Region;
+
+use crate::{helper::db_helper, project_warn};
+
+/// 登录异常信号
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginAnomaly {
+    /// 首次出现的国家
+    NewCountry,
+    /// 同一国家内首次出现的省份
+    NewProvince,
+    /// 与上次登录国家不同且间隔过短
+    ImpossibleTravel,
+    /// 首次出现的 User-Agent
+    NewUserAgent,
+    /// 同一 IP 短时间内登录多个账号
+    SharedIp,
+}
+
+impl LoginAnomaly {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoginAnomaly::NewCountry => "new_country",
+            LoginAnomaly::NewProvince => "new_province",
+            LoginAnomaly::ImpossibleTravel => "impossible_travel",
+            LoginAnomaly::NewUserAgent => "new_user_agent",
+            LoginAnomaly::SharedIp => "shared_ip",
+        }
+    }
+}
+
+/// 风险处置方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginRiskAction {
+    Allow,
+    Block,
+}
+
+/// 登录风险评估结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoginRisk {
+    /// 风险分，0-100
+    pub score: u8,
+    /// 命中的异常信号
+    pub anomalies: Vec<LoginAnomaly>,
+}
+
+impl LoginRisk {
+    fn add(&mut self, anomaly: LoginAnomaly, weight: u8) {
+        self.anomalies.push(anomaly);
+        self.score = self.score.saturating_add(weight).min(100);
+    }
+
+    /// 按配置的阈值决定处置方式
+    pub fn action(&self, config: &LoginRiskConfig) -> LoginRiskAction {
+        if config.block_score.is_some_and(|score| self.score >= score) {
+            LoginRiskAction::Block
+        } else {
+            LoginRiskAction::Allow
+        }
+    }
+
+    /// 逗号分隔的异常信号，未命中时为空
+    pub fn reasons(&self) -> Option<String> {
+        if self.anomalies.is_empty() {
+            return None;
+        }
+
+        Some(
+            self.anomalies
+                .iter()
+                .map(LoginAnomaly::as_str)
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+    }
+}
+
+/// 登录风险事件，命中任一异常信号时发送
+pub struct LoginRiskEvent {
+    pub user_id: String,
+    pub username: String,
+    pub domain: String,
+    pub ip: String,
+    pub address: String,
+    pub user_agent: String,
+    pub request_id: String,
+    pub risk: LoginRisk,
+    pub action: LoginRiskAction,
+}
+
+/// 本次登录的归属信息
+pub struct LoginAttempt<'a> {
+    pub region: &'a Region,
+    pub user_agent: &'a str,
+    pub time: NaiveDateTime,
+}
+
+/// 依据历史登录评估本次登录的风险
+///
+/// `history` 按登录时间倒序；没有历史记录的首次登录不做归属地与 User-Agent 比对。
+/// `ip_users` 为时间窗口内同一 IP 登录过的其他账号数。
+pub fn evaluate_login_risk(
+    config: &LoginRiskConfig,
+    attempt: &LoginAttempt<'_>,
+    history: &[SysLoginLogModel],
+    ip_users: usize,
+) -> LoginRisk {
+    let weights = &config.weights;
+    let mut risk = LoginRisk::default();
+
+    if !history.is_empty() {
+        let province = attempt.region.province.as_deref();
+
+        if let Some(country) = attempt.region.country.as_deref() {
+            let known_countries: HashSet<&str> = history
+                .iter()
+                .filter_map(|log| log.country.as_deref())
+                .collect();
+            let known_provinces: HashSet<&str> = history
+                .iter()
+                .filter(|log| log.country.as_deref() == Some(country))
+                .filter_map(|log| log.province.as_deref())
+                .collect();
+
+            if !known_countries.is_empty() && !known_countries.contains(country) {
+                risk.add(LoginAnomaly::NewCountry, weights.new_country);
+            } else if let Some(province) = province {
+                if !known_provinces.is_empty() && !known_provinces.contains(province) {
+                    risk.add(LoginAnomaly::NewProvince, weights.new_province);
+                }
+            }
+
+            let last = &history[0];
+            if let Some(last_country) = last.country.as_deref() {
+                let elapsed = attempt.time - last.login_time;
+                if last_country != country
+                    && elapsed < Duration::minutes(config.impossible_travel_minutes)
+                {
+                    risk.add(LoginAnomaly::ImpossibleTravel, weights.impossible_travel);
+                }
+            }
+        }
+
+        if !history
+            .iter()
+            .any(|log| log.user_agent == attempt.user_agent)
+        {
+            risk.add(LoginAnomaly::NewUserAgent, weights.new_user_agent);
+        }
+    }
+
+    // 加上本次登录的账号
+    if ip_users + 1 >= config.shared_ip_threshold.max(2) {
+        risk.add(LoginAnomaly::SharedIp, weights.shared_ip);
+    }
+
+    risk
+}
+
+/// 读取登录历史并评估风险
+pub async fn assess_login_risk(
+    config: &LoginRiskConfig,
+    user_id: &str,
+    ip: &str,
+    region: &Region,
+    user_agent: &str,
+) -> Result<LoginRisk, AppError> {
+    let db = db_helper::get_db_connection().await?;
+    let now = Local::now().naive_local();
+
+    let history = SysLoginLog::find()
+        .filter(SysLoginLogColumn::UserId.eq(user_id))
+        .filter(SysLoginLogColumn::Success.eq(true))
+        .order_by_desc(SysLoginLogColumn::LoginTime)
+        .limit(config.history_size.max(1))
+        .all(db.as_ref())
+        .await
+        .map_err(AppError::from)?;
+
+    let ip_users = SysLoginLog::find()
+        .select_only()
+        .column(SysLoginLogColumn::UserId)
+        .filter(SysLoginLogColumn::Ip.eq(ip))
+        .filter(SysLoginLogColumn::UserId.ne(user_id))
+        .filter(SysLoginLogColumn::Success.eq(true))
+        .filter(
+            SysLoginLogColumn::LoginTime
+                .gte(now - Duration::minutes(config.shared_ip_window_minutes)),
+        )
+        .distinct()
+        .into_tuple::<String>()
+        .all(db.as_ref())
+        .await
+        .map_err(AppError::from)?
+        .len();
+
+    let attempt = LoginAttempt {
+        region,
+        user_agent,
+        time: now,
+    };
+
+    Ok(evaluate_login_risk(config, &attempt, &history, ip_users))
+}
+
+/// 默认的登录风险事件监听器，记录告警日志
+#[instrument(skip(rx))]
+pub async fn login_risk_listener(
+    mut rx: tokio::sync::mpsc::UnboundedReceiver<Box<dyn Any + Send>>,
+) {
+    while let Some(event) = rx.recv().await {
+        if let Some(event) = event.downcast_ref::<LoginRiskEvent>() {
+            project_warn!(
+                "Login risk detected: user={} domain={} ip={} address={} score={} reasons={} action={:?} request_id={}",
+                event.username,
+                event.domain,
+                event.ip,
+                event.address,
+                event.risk.score,
+                event.risk.reasons().unwrap_or_default(),
+                event.action,
+                event.request_id
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHROME: &str = "Mozilla/5.0 Chrome/120.0";
+
+    fn log(address: &str, user_agent: &str, login_time: NaiveDateTime) -> SysLoginLogModel {
+        let region: Region = address.parse().unwrap_or_default();
+        SysLoginLogModel {
+            id: "log".to_string(),
+            user_id: "user".to_string(),
+            username: "admin".to_string(),
+            domain: "built-in".to_string(),
+            login_time,
+            ip: "1.1.1.1".to_string(),
+            port: None,
+            address: address.to_string(),
+            user_agent: user_agent.to_string(),
+            request_id: "request".to_string(),
+            r#type: "PC".to_string(),
+            created_at: login_time,
+            created_by: "admin".to_string(),
+            risk_score: 0,
+            risk_reasons: None,
+            success: true,
+            failure_reason: None,
+            country: region.country,
+            region: region.region,
+            province: region.province,
+            city: region.city,
+            isp: region.isp,
+        }
+    }
+
+    fn region(address: &str) -> Region {
+        address.parse().unwrap_or_default()
+    }
+
+    fn attempt<'a>(
+        region: &'a Region,
+        user_agent: &'a str,
+        time: NaiveDateTime,
+    ) -> LoginAttempt<'a> {
+        LoginAttempt {
+            region,
+            user_agent,
+            time,
+        }
+    }
+
+    #[test]
+    fn test_evaluate_login_risk() {
+        let config = LoginRiskConfig::default();
+        let now = Local::now().naive_local();
+        let history = vec![log(
+            "中国|0|广东省|深圳市|电信",
+            CHROME,
+            now - Duration::hours(2),
+        )];
+
+        // 首次登录不比对历史
+        let risk = evaluate_login_risk(
+            &config,
+            &attempt(&region("美国|0|加州|0|0"), "curl", now),
+            &[],
+            0,
+        );
+        assert_eq!(risk, LoginRisk::default());
+
+        // 相同归属地与 User-Agent
+        let risk = evaluate_login_risk(
+            &config,
+            &attempt(&region("中国|0|广东省|广州市|电信"), CHROME, now),
+            &history,
+            0,
+        );
+        assert_eq!(risk, LoginRisk::default());
+
+        // 新省份
+        let risk = evaluate_login_risk(
+            &config,
+            &attempt(&region("中国|0|北京|北京市|联通"), CHROME, now),
+            &history,
+            0,
+        );
+        assert_eq!(risk.anomalies, vec![LoginAnomaly::NewProvince]);
+
+        // 新国家，且距上次登录超过时间窗口
+        let risk = evaluate_login_risk(
+            &config,
+            &attempt(&region("美国|0|加州|0|0"), "curl", now),
+            &history,
+            0,
+        );
+        assert_eq!(
+            risk.anomalies,
+            vec![LoginAnomaly::NewCountry, LoginAnomaly::NewUserAgent]
+        );
+        assert_eq!(risk.score, 50);
+        assert_eq!(
+            risk.reasons().as_deref(),
+            Some("new_country,new_user_agent")
+        );
+
+        // 不可能的行程
+        let recent = vec![log(
+            "中国|0|广东省|深圳市|电信",
+            CHROME,
+            now - Duration::minutes(10),
+        )];
+        let risk = evaluate_login_risk(
+            &config,
+            &attempt(&region("美国|0|加州|0|0"), CHROME, now),
+            &recent,
+            4,
+        );
+        assert_eq!(
+            risk.anomalies,
+            vec![
+                LoginAnomaly::NewCountry,
+                LoginAnomaly::ImpossibleTravel,
+                LoginAnomaly::SharedIp
+            ]
+        );
+        assert_eq!(risk.score, 100);
+
+        // 未知归属地不参与比对
+        let risk = evaluate_login_risk(
+            &config,
+            &attempt(&region("Unknown Location"), CHROME, now),
+            &history,
+            0,
+        );
+        assert_eq!(risk, LoginRisk::default());
+    }
+
+    #[test]
+    fn test_login_risk_action() {
+        let risk = LoginRisk {
+            score: 60,
+            anomalies: vec![LoginAnomaly::NewCountry],
+        };
+
+        let mut config = LoginRiskConfig::default();
+        assert_eq!(risk.action(&config), LoginRiskAction::Allow);
+
+        config.block_score = Some(61);
+        assert_eq!(risk.action(&config), LoginRiskAction::Allow);
+
+        config.block_score = Some(60);
+        assert_eq!(risk.action(&config), LoginRiskAction::Block);
+    }
+}
@@ -1,49 +1,71 @@
+pub use audit::audit_retention_task;
 pub use errors::*;
+pub use login_risk::login_risk_listener;
 pub use server_model::admin::{
     entities::{
         prelude::{SysDomain, SysEndpoint, SysMenu, SysRole, SysUser},
         sys_access_key::Model as SysAccessKeyModel,
         sys_domain::Model as SysDomainModel,
         sys_endpoint::Model as SysEndpointModel,
+        sys_ip_rule::Model as SysIpRuleModel,
         sys_login_log::Model as SysLoginLogModel,
         sys_menu::Model as SysMenuModel,
         sys_operation_log::Model as SysOperationLogModel,
         sys_organization::Model as SysOrganizationModel,
+        sys_recycle_bin::Model as SysRecycleBinModel,
         sys_role::Model as SysRoleModel,
     },
     input::*,
     output::*,
 };
 pub use sys_access_key_service::{
-    api_key_validate_listener, SysAccessKeyService, TAccessKeyService,
+    access_key_change_subscriber, access_key_changed_listener, access_key_usage_writer,
+    api_key_validate_listener, flush_access_key_usage, AccessKeyChangeAction,
+    AccessKeyChangedEvent, SysAccessKeyService, TAccessKeyService,
 };
 pub use sys_auth_service::{
-    auth_login_listener, jwt_created_listener, SysAuthService, TAuthService,
+    auth_login_failed_listener, auth_login_listener, jwt_created_listener, reload_revoked_tokens,
+    token_revocation_subscriber, SysAuthService, TAuthService,
 };
 pub use sys_authorization_service::{SysAuthorizationService, TAuthorizationService};
+pub use sys_dashboard_service::{SysDashboardService, TDashboardService};
 pub use sys_domain_service::{SysDomainService, TDomainService};
 pub use sys_endpoint_service::{SysEndpointService, TEndpointService};
+pub use sys_ip_rule_service::{ip_rule_subscriber, SysIpRuleService, TIpRuleService};
 pub use sys_login_log_service::{SysLoginLogService, TLoginLogService};
 pub use sys_menu_service::{SysMenuService, TMenuService};
 pub use sys_operation_log_service::{
-    sys_operation_log_listener, SysOperationLogService, TOperationLogService,
+    shutdown_operation_log_writer, start_operation_log_writer, SysOperationLogService,
+    TOperationLogService,
 };
 pub use sys_organization_service::{SysOrganizationService, TOrganizationService};
+pub use sys_recycle_bin_service::{
+    recycle_bin_purge_task, SysRecycleBinService, TRecycleBinService,
+};
 pub use sys_role_service::{SysRoleService, TRoleService};
 pub use sys_user_service::{SysUserService, TUserService};
+pub mod audit;
 pub mod dto;
 pub mod errors;
+pub mod login_risk;
+pub mod sinks;
 mod sys_access_key_service;
 mod sys_auth_service;
 mod sys_authorization_service;
+mod sys_dashboard_service;
 mod sys_domain_service;
 mod sys_endpoint_service;
+mod sys_ip_rule_service;
 mod sys_login_log_service;
 mod sys_menu_service;
 mod sys_operation_log_service;
 mod sys_organization_service;
+mod sys_recycle_bin_service;
 mod sys_role_service;
 mod sys_user_service;
 
 mod event_handlers;
 mod events;
+
+/// `status` 枚举的可选值，用于列表查询的状态过滤
+pub(crate) const STATUS_VALUES: &[&str] = &["enabled", "disabled", "banned"];
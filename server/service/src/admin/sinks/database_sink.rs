@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+use sea_orm::{EntityTrait, Set};
+use server_core::web::error::AppError;
+use server_global::global::OperationLogContext;
+use server_model::admin::entities::{
+    prelude::SysOperationLog, sys_operation_log::ActiveModel as SysOperationLogActiveModel,
+};
+use ulid::Ulid;
+
+use super::OperationLogSink;
+use crate::helper::db_helper;
+
+/// 写入主数据库 `sys_operation_log` 表，每批一次 `INSERT`
+pub struct DatabaseSink;
+
+#[async_trait]
+impl OperationLogSink for DatabaseSink {
+    async fn write_batch(&self, batch: &[OperationLogContext]) -> Result<(), AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        let models = batch.iter().map(|event| SysOperationLogActiveModel {
+            id: Set(Ulid::new().to_string()),
+            user_id: Set(event.user_id.clone().unwrap_or_default()),
+            username: Set(event.username.clone().unwrap_or_default()),
+            domain: Set(event.domain.clone().unwrap_or_default()),
+            module_name: Set(event.module_name.clone()),
+            description: Set(event.description.clone()),
+            request_id: Set(event.request_id.clone()),
+            method: Set(event.method.clone()),
+            url: Set(event.url.clone()),
+            route: Set(event.route.clone()),
+            ip: Set(event.ip.clone()),
+            user_agent: Set(event.user_agent.clone()),
+            params: Set(event.params.clone()),
+            headers: Set(event.headers.clone()),
+            body: Set(event.body.clone()),
+            response: Set(event.response.clone()),
+            start_time: Set(event.start_time),
+            end_time: Set(event.end_time),
+            duration: Set(event.duration),
+            status: Set(i32::from(event.status)),
+            created_at: Set(event.created_at),
+        });
+
+        SysOperationLog::insert_many(models)
+            .exec_without_returning(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use server_core::web::error::AppError;
+use server_global::global::OperationLogContext;
+use tokio::{fs, io::AsyncWriteExt};
+
+use super::{to_record, OperationLogSink};
+
+/// 追加写入 JSON Lines 文件，每行一条记录
+pub struct FileSink {
+    path: PathBuf,
+}
+
+impl FileSink {
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: PathBuf::from(path),
+        }
+    }
+}
+
+fn io_error(e: std::io::Error) -> AppError {
+    AppError {
+        code: 500,
+        message: format!("Failed to write operation log file: {}", e),
+    }
+}
+
+#[async_trait]
+impl OperationLogSink for FileSink {
+    async fn write_batch(&self, batch: &[OperationLogContext]) -> Result<(), AppError> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).await.map_err(io_error)?;
+        }
+
+        let mut lines = String::new();
+        for context in batch {
+            lines.push_str(&to_record(context).to_string());
+            lines.push('\n');
+        }
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(io_error)?;
+        file.write_all(lines.as_bytes()).await.map_err(io_error)?;
+        file.flush().await.map_err(io_error)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,350 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use server_config::{OperationLogSinkConfig, OperationLogSinkKind};
+use server_core::web::error::AppError;
+use server_global::{
+    global::{self, OperationLogContext, OperationLogQueue},
+    project_error, project_warn,
+};
+use tokio::{
+    sync::mpsc,
+    task::JoinHandle,
+    time::{Instant, MissedTickBehavior},
+};
+use ulid::Ulid;
+
+pub use database_sink::DatabaseSink;
+pub use file_sink::FileSink;
+pub use mongo_sink::MongoSink;
+
+mod database_sink;
+mod file_sink;
+mod mongo_sink;
+
+/// 操作日志写入目标
+#[async_trait]
+pub trait OperationLogSink: Send + Sync {
+    /// 批量写入操作日志
+    async fn write_batch(&self, batch: &[OperationLogContext]) -> Result<(), AppError>;
+}
+
+/// 根据配置创建写入目标
+pub fn create_sink(config: &OperationLogSinkConfig) -> Arc<dyn OperationLogSink> {
+    match config.kind {
+        OperationLogSinkKind::Database => Arc::new(DatabaseSink),
+        OperationLogSinkKind::Mongo => Arc::new(MongoSink::new(config.mongo.clone())),
+        OperationLogSinkKind::File => Arc::new(FileSink::new(&config.file.path)),
+    }
+}
+
+/// 写入失败后首次重试前的等待时间，之后每次翻倍
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(200);
+
+/// 启动批量写入任务，返回有界队列与任务句柄
+///
+/// 发送端关闭后任务会写入队列中剩余的日志再退出。
+pub fn spawn_batch_writer(
+    sink: Arc<dyn OperationLogSink>,
+    config: &OperationLogSinkConfig,
+) -> (OperationLogQueue, JoinHandle<()>) {
+    let (tx, rx) = mpsc::channel(config.queue_capacity.max(1));
+    let writer = BatchWriter {
+        sink,
+        spill: Arc::new(FileSink::new(&config.spill_path)),
+        max_retries: config.max_retries,
+    };
+    let handle = tokio::spawn(run_batch_writer(
+        writer,
+        rx,
+        config.batch_size.max(1),
+        Duration::from_millis(config.flush_interval_ms.max(1)),
+    ));
+    let queue = OperationLogQueue {
+        sender: tx,
+        send_timeout: Duration::from_millis(config.send_timeout_ms),
+    };
+    (queue, handle)
+}
+
+/// 批量写入目标及其失败处理
+struct BatchWriter {
+    sink: Arc<dyn OperationLogSink>,
+    /// 重试后仍失败的批次写入的文件
+    spill: Arc<dyn OperationLogSink>,
+    max_retries: u32,
+}
+
+impl BatchWriter {
+    /// 写入并清空缓冲区，失败时按退避重试，仍失败则转存到文件
+    async fn flush(&self, buffer: &mut Vec<OperationLogContext>) {
+        if buffer.is_empty() {
+            return;
+        }
+
+        let mut attempt = 0;
+        while let Err(e) = self.sink.write_batch(buffer).await {
+            if attempt >= self.max_retries {
+                project_error!(
+                    "Failed to write {} operation log(s) after {} retries, spilling to file: {:?}",
+                    buffer.len(),
+                    attempt,
+                    e
+                );
+                if let Err(e) = self.spill.write_batch(buffer).await {
+                    project_error!("Lost {} operation log(s): {:?}", buffer.len(), e);
+                }
+                break;
+            }
+            project_warn!(
+                "Failed to write {} operation log(s), retrying: {:?}",
+                buffer.len(),
+                e
+            );
+            tokio::time::sleep(RETRY_BASE_DELAY * 2u32.saturating_pow(attempt)).await;
+            attempt += 1;
+        }
+        buffer.clear();
+    }
+}
+
+/// 按条数或时间间隔批量写入，队列关闭时写入剩余日志后退出
+///
+/// 写入期间不接收新的日志，队列满后请求在超时时间内等待，从而将写入压力反馈到请求路径。
+async fn run_batch_writer(
+    writer: BatchWriter,
+    mut rx: mpsc::Receiver<OperationLogContext>,
+    batch_size: usize,
+    flush_interval: Duration,
+) {
+    let mut buffer = Vec::with_capacity(batch_size);
+    let mut ticker = tokio::time::interval_at(Instant::now() + flush_interval, flush_interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut reported_dropped = global::dropped_operation_logs();
+
+    loop {
+        tokio::select! {
+            received = rx.recv() => match received {
+                Some(context) => {
+                    buffer.push(context);
+                    if buffer.len() >= batch_size {
+                        writer.flush(&mut buffer).await;
+                    }
+                },
+                None => {
+                    writer.flush(&mut buffer).await;
+                    break;
+                },
+            },
+            _ = ticker.tick() => {
+                writer.flush(&mut buffer).await;
+                report_dropped(&mut reported_dropped);
+            },
+        }
+    }
+}
+
+/// 上次报告后又有日志因等待超时被丢弃时输出告警
+fn report_dropped(reported: &mut u64) {
+    let dropped = global::dropped_operation_logs();
+    if dropped > *reported {
+        project_warn!(
+            "Dropped {} operation log(s) because the write queue stayed full ({} in total)",
+            dropped - *reported,
+            dropped
+        );
+        *reported = dropped;
+    }
+}
+
+/// 转换为与 `sys_operation_log` 字段一致的 JSON 记录
+pub(crate) fn to_record(context: &OperationLogContext) -> Value {
+    json!({
+        "id": Ulid::new().to_string(),
+        "userId": context.user_id.clone().unwrap_or_default(),
+        "username": context.username.clone().unwrap_or_default(),
+        "domain": context.domain.clone().unwrap_or_default(),
+        "moduleName": context.module_name,
+        "description": context.description,
+        "requestId": context.request_id,
+        "method": context.method,
+        "url": context.url,
+        "route": context.route,
+        "ip": context.ip,
+        "userAgent": context.user_agent,
+        "params": context.params,
+        "headers": context.headers,
+        "body": context.body,
+        "response": context.response,
+        "startTime": context.start_time,
+        "endTime": context.end_time,
+        "duration": context.duration,
+        "status": context.status,
+        "createdAt": context.created_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MemorySink {
+        batches: Mutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl OperationLogSink for MemorySink {
+        async fn write_batch(&self, batch: &[OperationLogContext]) -> Result<(), AppError> {
+            self.batches.lock().await.push(batch.len());
+            Ok(())
+        }
+    }
+
+    fn context() -> OperationLogContext {
+        let now = Local::now().naive_local();
+        OperationLogContext {
+            user_id: None,
+            username: None,
+            domain: None,
+            module_name: "SysUserApi".to_string(),
+            description: "创建用户".to_string(),
+            request_id: "test".to_string(),
+            method: "POST".to_string(),
+            url: "/user".to_string(),
+            route: "/user".to_string(),
+            ip: "127.0.0.1".to_string(),
+            user_agent: None,
+            params: None,
+            headers: None,
+            body: None,
+            response: None,
+            start_time: now,
+            end_time: now,
+            duration: 0,
+            status: 200,
+            created_at: now,
+        }
+    }
+
+    /// 前 `failures` 次写入失败的目标
+    struct FlakySink {
+        failures: Mutex<u32>,
+        inner: MemorySink,
+    }
+
+    #[async_trait]
+    impl OperationLogSink for FlakySink {
+        async fn write_batch(&self, batch: &[OperationLogContext]) -> Result<(), AppError> {
+            let mut failures = self.failures.lock().await;
+            if *failures > 0 {
+                *failures -= 1;
+                return Err(AppError {
+                    code: 500,
+                    message: "unavailable".to_string(),
+                });
+            }
+            self.inner.write_batch(batch).await
+        }
+    }
+
+    fn writer(sink: Arc<dyn OperationLogSink>, spill: Arc<dyn OperationLogSink>) -> BatchWriter {
+        BatchWriter {
+            sink,
+            spill,
+            max_retries: 2,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_batch_writer_flush_by_size_and_interval() {
+        let sink = Arc::new(MemorySink::default());
+        let (tx, rx) = mpsc::channel(16);
+        let writer = tokio::spawn(run_batch_writer(
+            writer(sink.clone(), Arc::new(MemorySink::default())),
+            rx,
+            3,
+            Duration::from_secs(1),
+        ));
+
+        for _ in 0..4 {
+            tx.send(context()).await.unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(*sink.batches.lock().await, vec![3]);
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert_eq!(*sink.batches.lock().await, vec![3, 1]);
+
+        tx.send(context()).await.unwrap();
+        drop(tx);
+        writer.await.unwrap();
+        assert_eq!(*sink.batches.lock().await, vec![3, 1, 1]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_batch_writer_retries_then_spills() {
+        let spill = Arc::new(MemorySink::default());
+
+        let sink = Arc::new(FlakySink {
+            failures: Mutex::new(2),
+            inner: MemorySink::default(),
+        });
+        let mut buffer = vec![context(), context()];
+        writer(sink.clone(), spill.clone()).flush(&mut buffer).await;
+        assert!(buffer.is_empty());
+        assert_eq!(*sink.inner.batches.lock().await, vec![2]);
+        assert!(spill.batches.lock().await.is_empty());
+
+        let sink = Arc::new(FlakySink {
+            failures: Mutex::new(3),
+            inner: MemorySink::default(),
+        });
+        let mut buffer = vec![context()];
+        writer(sink.clone(), spill.clone()).flush(&mut buffer).await;
+        assert!(sink.inner.batches.lock().await.is_empty());
+        assert_eq!(*spill.batches.lock().await, vec![1]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_operation_log_queue_waits_when_full_and_flushes_on_close() {
+        let sink = Arc::new(MemorySink::default());
+        let config = OperationLogSinkConfig {
+            batch_size: 100,
+            flush_interval_ms: 60_000,
+            queue_capacity: 2,
+            send_timeout_ms: 50,
+            ..Default::default()
+        };
+        let (queue, writer) = spawn_batch_writer(sink.clone(), &config);
+        global::set_operation_log_queue(Some(queue));
+
+        // 队列满时等待写入任务腾出空间，不丢弃
+        let dropped = global::dropped_operation_logs();
+        for _ in 0..5 {
+            assert!(global::send_operation_log(context()).await);
+        }
+        assert_eq!(global::dropped_operation_logs(), dropped);
+
+        global::set_operation_log_queue(None);
+        writer.await.unwrap();
+        assert_eq!(*sink.batches.lock().await, vec![5]);
+        assert!(!global::send_operation_log(context()).await);
+
+        // 写入任务停滞时等待超时后丢弃
+        let (tx, _rx) = mpsc::channel(1);
+        global::set_operation_log_queue(Some(OperationLogQueue {
+            sender: tx,
+            send_timeout: Duration::from_millis(50),
+        }));
+        let dropped = global::dropped_operation_logs();
+        assert!(global::send_operation_log(context()).await);
+        assert!(!global::send_operation_log(context()).await);
+        assert_eq!(global::dropped_operation_logs() - dropped, 1);
+        global::set_operation_log_queue(None);
+    }
+}
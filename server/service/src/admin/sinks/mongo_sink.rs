@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+use mongodb::bson::{self, Document};
+use server_config::MongoSinkConfig;
+use server_core::web::error::AppError;
+use server_global::global::OperationLogContext;
+
+use super::{to_record, OperationLogSink};
+use crate::helper::mongo_helper::{self, MongoSource};
+
+/// 写入 MongoDB 集合，较大的请求与响应体无需受关系库列宽约束
+pub struct MongoSink {
+    config: MongoSinkConfig,
+}
+
+impl MongoSink {
+    pub fn new(config: MongoSinkConfig) -> Self {
+        Self { config }
+    }
+
+    fn source(&self) -> MongoSource {
+        match &self.config.instance {
+            Some(name) => MongoSource::Named(name.clone()),
+            None => MongoSource::Primary,
+        }
+    }
+}
+
+#[async_trait]
+impl OperationLogSink for MongoSink {
+    async fn write_batch(&self, batch: &[OperationLogContext]) -> Result<(), AppError> {
+        let collection = mongo_helper::get_collection::<Document>(
+            self.source(),
+            &self.config.database,
+            &self.config.collection,
+        )
+        .await?;
+
+        let documents = batch
+            .iter()
+            .map(|context| bson::to_document(&to_record(context)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError {
+                code: 500,
+                message: format!("Failed to encode operation log: {}", e),
+            })?;
+
+        collection
+            .insert_many(documents)
+            .await
+            .map_err(|e| AppError {
+                code: 500,
+                message: format!("Failed to write operation log to MongoDB: {}", e),
+            })?;
+
+        Ok(())
+    }
+}
@@ -1,32 +1,109 @@
-use std::any::Any;
+use std::{any::Any, collections::HashSet, time::Duration as StdDuration};
 
 use async_trait::async_trait;
-use chrono::Local;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{Duration, Local, NaiveDateTime};
+use dashmap::{DashMap, DashSet};
+use http::Method;
+use once_cell::sync::Lazy;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, DatabaseTransaction, EntityTrait, PaginatorTrait,
-    QueryFilter, Set, TransactionTrait,
+    sea_query::{CaseStatement, Expr, SimpleExpr},
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseTransaction, DbErr,
+    EntityTrait, PaginatorTrait, QueryFilter, Set, TransactionTrait,
 };
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use server_config::{RateLimitConfig, SecurityConfig};
+use server_constant::definition::consts::SystemEvent;
 use server_core::{
-    sign::{ApiKeyEvent, ValidatorType},
-    web::{error::AppError, page::PaginatedData},
+    rate_limit::{self, RateLimitQuota},
+    sign::{
+        set_access_key_policy, AccessKeyPolicy, AccessKeyScope, ApiKeyEvent, SecretCipher,
+        SignatureAlgorithm, ValidatorType,
+    },
+    web::{
+        auth::User,
+        error::AppError,
+        page::{PaginatedData, QuerySpec, SortOrder},
+        util::parse_ip_net,
+    },
 };
-use server_global::project_info;
+use server_global::global::{self, get_config};
 use server_model::admin::{
     entities::{
-        prelude::SysAccessKey,
+        prelude::{SysAccessKey, SysEndpoint},
+        sea_orm_active_enums::{AccessKeyAlgorithm, Status},
         sys_access_key::{
             ActiveModel as SysAccessKeyActiveModel, Column as SysAccessKeyColumn,
             Model as SysAccessKeyModel,
         },
+        sys_endpoint::Column as SysEndpointColumn,
     },
-    input::{AccessKeyPageRequest, CreateAccessKeyInput},
+    input::{AccessKeyPageRequest, CreateAccessKeyInput, UpdateAccessKeyInput},
+    output::AccessKeySecretOutput,
 };
+use tokio::sync::OnceCell;
 use tracing::instrument;
 use ulid::Ulid;
 
-use crate::helper::db_helper;
+use crate::{
+    helper::{cache_sync, db_helper},
+    project_error, project_info, project_warn,
+};
+
+use super::{
+    sys_access_key_error::AccessKeyError, sys_domain_service::BUILT_IN_DOMAIN, STATUS_VALUES,
+};
+
+/// 未设置限流窗口时的默认窗口长度（秒）
+const DEFAULT_RATE_LIMIT_WINDOW_SECS: u64 = 60;
+
+/// 未指定签名算法时新建密钥使用的算法
+const DEFAULT_SIGNATURE_ALGORITHM: AccessKeyAlgorithm = AccessKeyAlgorithm::HmacSha512;
+/// Ed25519 公钥长度
+const ED25519_PUBLIC_KEY_LEN: usize = 32;
+
+/// 访问密钥的加密器，首次使用时按安全配置创建
+static SECRET_CIPHER: OnceCell<SecretCipher> = OnceCell::const_new();
 
-use super::sys_access_key_error::AccessKeyError;
+/// 访问密钥变更的 Redis 频道
+const ACCESS_KEY_CHANGED_CHANNEL: &str = "access_key:changed";
+/// 从数据库全量同步访问密钥的间隔（秒）
+const ACCESS_KEY_RECONCILE_INTERVAL_SECS: u64 = 300;
+
+/// 已加载到验证器的访问密钥 ID，全量同步时据此移除数据库中已不存在或已停用的密钥
+static LOADED_ACCESS_KEYS: Lazy<DashSet<String>> = Lazy::new(DashSet::new);
+
+/// 访问密钥使用情况的写入间隔（秒）
+const USAGE_FLUSH_INTERVAL_SECS: u64 = 10;
+
+/// 尚未写入数据库的访问密钥使用情况
+static PENDING_USAGE: Lazy<DashMap<String, AccessKeyUsage>> = Lazy::new(DashMap::new);
+
+/// 可作为访问范围的请求方法，表示该方法下的全部受保护接口
+const SCOPE_METHODS: &[Method] = &[
+    Method::GET,
+    Method::POST,
+    Method::PUT,
+    Method::PATCH,
+    Method::DELETE,
+];
+
+/// 访问密钥列表的查询白名单
+const ACCESS_KEY_QUERY: QuerySpec<SysAccessKeyColumn> = QuerySpec {
+    sort_fields: &[
+        ("createdAt", SysAccessKeyColumn::CreatedAt),
+        ("domain", SysAccessKeyColumn::Domain),
+    ],
+    filter_fields: &[
+        ("domain", SysAccessKeyColumn::Domain),
+        ("accessKeyId", SysAccessKeyColumn::AccessKeyId),
+    ],
+    status: Some((SysAccessKeyColumn::Status, STATUS_VALUES)),
+    created_at: SysAccessKeyColumn::CreatedAt,
+    id: SysAccessKeyColumn::Id,
+    default_sort: (SysAccessKeyColumn::CreatedAt, SortOrder::Desc),
+};
 
 #[async_trait]
 pub trait TAccessKeyService {
@@ -37,12 +114,44 @@ pub trait TAccessKeyService {
     async fn create_access_key(
         &self,
         input: CreateAccessKeyInput,
+    ) -> Result<AccessKeySecretOutput, AppError>;
+    async fn update_access_key(
+        &self,
+        input: UpdateAccessKeyInput,
     ) -> Result<SysAccessKeyModel, AppError>;
+    /// 轮换密钥并返回新的明文密钥，非内置域只能轮换本域的密钥
+    async fn rotate_access_key(
+        &self,
+        id: &str,
+        user: User,
+    ) -> Result<AccessKeySecretOutput, AppError>;
     async fn delete_access_key(&self, id: &str) -> Result<(), AppError>;
 
+    /// 加载启用的访问密钥，未配置或配置了无效的主密钥时返回错误
     async fn initialize_access_key(&self) -> Result<(), AppError>;
 }
 
+/// 访问密钥变更类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessKeyChangeAction {
+    /// 新增或修改，接收方按数据库中的状态重新加载
+    Upsert,
+    /// 删除
+    Remove,
+}
+
+/// 访问密钥变更事件
+///
+/// 只携带密钥标识，不含密钥内容，接收方从数据库重新加载。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessKeyChangedEvent {
+    pub id: String,
+    pub access_key_id: String,
+    pub action: AccessKeyChangeAction,
+}
+
 #[derive(Clone)]
 pub struct SysAccessKeyService;
 
@@ -51,17 +160,23 @@ impl SysAccessKeyService {
         &self,
         txn: &DatabaseTransaction,
         access_key: SysAccessKeyActiveModel,
+        secret: &str,
     ) -> Result<SysAccessKeyModel, AppError> {
         let result = access_key.insert(txn).await.map_err(AppError::from)?;
 
-        // 添加到验证器
-        server_core::sign::add_key(ValidatorType::Simple, &result.access_key_id, None).await;
-        server_core::sign::add_key(
-            ValidatorType::Complex,
-            &result.access_key_id,
-            Some(&result.access_key_secret),
-        )
-        .await;
+        // 仅启用的密钥添加到验证器
+        if result.status == Status::Enabled {
+            server_core::sign::add_key(ValidatorType::Simple, &result.access_key_id, None).await;
+            server_core::sign::add_signing_key(
+                &result.access_key_id,
+                to_signature_algorithm(result.signature_algorithm),
+                secret,
+                None,
+            )
+            .await;
+            sync_rate_limit_quota(&result).await;
+            sync_access_key_policy(txn, &result).await?;
+        }
 
         Ok(result)
     }
@@ -70,7 +185,7 @@ impl SysAccessKeyService {
         &self,
         txn: &DatabaseTransaction,
         id: &str,
-    ) -> Result<(), AppError> {
+    ) -> Result<String, AppError> {
         // 先获取 access key 信息
         let access_key = SysAccessKey::find_by_id(id)
             .one(txn)
@@ -85,8 +200,156 @@ impl SysAccessKeyService {
             .map_err(AppError::from)?;
 
         // 从验证器中移除
-        server_core::sign::remove_key(ValidatorType::Simple, &access_key.access_key_id).await;
-        server_core::sign::remove_key(ValidatorType::Complex, &access_key.access_key_id).await;
+        unload_access_key(&access_key.access_key_id).await;
+
+        Ok(access_key.access_key_id)
+    }
+
+    /// 按状态同步访问密钥，启用的密钥加载到验证器，其余的从验证器中移除
+    async fn sync_access_key<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        access_key: SysAccessKeyModel,
+    ) -> Result<(), AppError> {
+        if access_key.status == Status::Enabled {
+            let cipher = secret_cipher().await?;
+            self.load_access_key(db, cipher, access_key).await
+        } else {
+            unload_access_key(&access_key.access_key_id).await;
+            Ok(())
+        }
+    }
+
+    /// 应用其他实例发布的访问密钥变更
+    async fn apply_access_key_change(&self, event: &AccessKeyChangedEvent) -> Result<(), AppError> {
+        match event.action {
+            AccessKeyChangeAction::Upsert => {
+                let db = db_helper::get_db_connection().await?;
+                match SysAccessKey::find_by_id(&event.id)
+                    .one(db.as_ref())
+                    .await
+                    .map_err(AppError::from)?
+                {
+                    Some(access_key) => self.sync_access_key(db.as_ref(), access_key).await,
+                    None => {
+                        unload_access_key(&event.access_key_id).await;
+                        Ok(())
+                    },
+                }
+            },
+            AccessKeyChangeAction::Remove => {
+                unload_access_key(&event.access_key_id).await;
+                Ok(())
+            },
+        }
+    }
+
+    /// 加载访问密钥到验证器
+    ///
+    /// 明文或由已停用主密钥加密的密钥会用当前主密钥重新加密，已过重叠期的旧密钥会被清除。
+    async fn load_access_key<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        cipher: &SecretCipher,
+        access_key: SysAccessKeyModel,
+    ) -> Result<(), AppError> {
+        let access_key_id = access_key.access_key_id.clone();
+        let secret = reveal_secret(cipher, &access_key.access_key_secret, &access_key_id)?;
+        let previous = match (
+            &access_key.previous_secret,
+            access_key.previous_secret_expires_at,
+        ) {
+            (Some(previous), Some(expires_at)) if expires_at > Local::now().naive_local() => {
+                Some((reveal_secret(cipher, previous, &access_key_id)?, expires_at))
+            },
+            _ => None,
+        };
+
+        let needs_reencrypt = cipher.needs_reencrypt(&access_key.access_key_secret)
+            || previous.is_none() && access_key.previous_secret.is_some()
+            || access_key
+                .previous_secret
+                .as_deref()
+                .is_some_and(|previous| cipher.needs_reencrypt(previous));
+        let access_key = if needs_reencrypt {
+            let stored_secret = access_key.access_key_secret.clone();
+            let mut active: SysAccessKeyActiveModel = access_key.into();
+            active.access_key_secret = Set(cipher.encrypt(&secret, &access_key_id)?);
+            active.previous_secret = Set(previous
+                .as_ref()
+                .map(|(previous, _)| cipher.encrypt(previous, &access_key_id))
+                .transpose()?);
+            active.previous_secret_expires_at = Set(previous.as_ref().map(|(_, at)| *at));
+            // 密钥已被并发轮换时放弃，新密钥由轮换的变更通知加载
+            match SysAccessKey::update(active)
+                .filter(SysAccessKeyColumn::AccessKeySecret.eq(stored_secret))
+                .exec(db)
+                .await
+            {
+                Ok(access_key) => access_key,
+                Err(DbErr::RecordNotUpdated) => return Ok(()),
+                Err(e) => return Err(AppError::from(e)),
+            }
+        } else {
+            access_key
+        };
+
+        let algorithm = to_signature_algorithm(access_key.signature_algorithm);
+        if algorithm.is_deprecated() && !LOADED_ACCESS_KEYS.contains(&access_key_id) {
+            project_warn!(
+                "Access key {} signs with deprecated {:?}, switch it to HMAC-SHA512 or Ed25519",
+                access_key_id,
+                algorithm
+            );
+        }
+
+        server_core::sign::add_key(ValidatorType::Simple, &access_key_id, None).await;
+        server_core::sign::add_signing_key(
+            &access_key_id,
+            algorithm,
+            &secret,
+            previous
+                .as_ref()
+                .map(|(previous, expires_at)| (previous.as_str(), to_millis(*expires_at))),
+        )
+        .await;
+        LOADED_ACCESS_KEYS.insert(access_key_id);
+        sync_rate_limit_quota(&access_key).await;
+        sync_access_key_policy(db, &access_key).await
+    }
+
+    /// 从数据库全量同步访问密钥
+    ///
+    /// 加载全部启用的密钥，并移除已加载但在数据库中已删除或停用的密钥。
+    async fn reload_access_keys(&self) -> Result<(), AppError> {
+        let cipher = secret_cipher().await?;
+        let db = db_helper::get_db_connection().await?;
+
+        let access_keys = SysAccessKey::find()
+            .filter(SysAccessKeyColumn::Status.eq(Status::Enabled))
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        let enabled: HashSet<&str> = access_keys
+            .iter()
+            .map(|access_key| access_key.access_key_id.as_str())
+            .collect();
+        let stale: Vec<String> = LOADED_ACCESS_KEYS
+            .iter()
+            .filter(|access_key_id| !enabled.contains(access_key_id.as_str()))
+            .map(|access_key_id| access_key_id.clone())
+            .collect();
+        for access_key_id in stale {
+            unload_access_key(&access_key_id).await;
+        }
+
+        for access_key in access_keys {
+            let access_key_id = access_key.access_key_id.clone();
+            if let Err(e) = self.load_access_key(db.as_ref(), cipher, access_key).await {
+                project_error!("Failed to load access key {}: {}", access_key_id, e.message);
+            }
+        }
 
         Ok(())
     }
@@ -106,6 +369,8 @@ impl TAccessKeyService for SysAccessKeyService {
             query = query.filter(condition);
         }
 
+        let query = ACCESS_KEY_QUERY.apply(query, &params.query)?;
+
         let total = query
             .clone()
             .count(db.as_ref())
@@ -128,28 +393,52 @@ impl TAccessKeyService for SysAccessKeyService {
 
     async fn create_access_key(
         &self,
-        input: CreateAccessKeyInput,
-    ) -> Result<SysAccessKeyModel, AppError> {
+        mut input: CreateAccessKeyInput,
+    ) -> Result<AccessKeySecretOutput, AppError> {
         let db = db_helper::get_db_connection().await?;
+        let cipher = secret_cipher().await?;
+        normalize_input(db.as_ref(), &mut input, None).await?;
+        let (signature_algorithm, public_key) = resolve_signing(&input, None).await?;
+
+        let now = Local::now().naive_local();
         let txn = db.begin().await.map_err(AppError::from)?;
 
         let access_key_id = format!("AK{}", Ulid::new().to_string());
-        let access_key_secret = format!("SK{}", Ulid::new().to_string());
+        // Ed25519 保存客户端公钥，其余算法生成共享密钥
+        let (access_key_secret, signing_secret) = match public_key {
+            Some(public_key) => (None, public_key),
+            None => {
+                let secret = cipher.generate_secret()?;
+                (Some(secret.clone()), secret)
+            },
+        };
+        let encrypted_secret = cipher.encrypt(&signing_secret, &access_key_id)?;
 
         let access_key = SysAccessKeyActiveModel {
             id: Set(Ulid::new().to_string()),
             domain: Set(input.domain),
             status: Set(input.status),
+            signature_algorithm: Set(signature_algorithm),
             description: Set(input.description),
+            rate_limit: Set(input.rate_limit),
+            rate_limit_window_secs: Set(input.rate_limit_window_secs),
+            scopes: Set(input.scopes.map(JsonValue::from)),
+            allowed_ips: Set(input.allowed_ips.map(JsonValue::from)),
+            expires_at: Set(input.expires_at),
+            last_used_at: Set(None),
+            last_used_ip: Set(None),
+            usage_count: Set(0),
+            rejected_count: Set(0),
+            previous_secret: Set(None),
+            previous_secret_expires_at: Set(None),
             access_key_id: Set(access_key_id),
-            access_key_secret: Set(access_key_secret),
-            created_at: Set(Local::now().naive_local()),
+            access_key_secret: Set(encrypted_secret),
+            created_at: Set(now),
             created_by: Set("TODO".to_string()),
-            ..Default::default()
         };
 
         let result = match self
-            .create_access_key_in_transaction(&txn, access_key)
+            .create_access_key_in_transaction(&txn, access_key, &signing_secret)
             .await
         {
             Ok(result) => {
@@ -161,17 +450,137 @@ impl TAccessKeyService for SysAccessKeyService {
                 return Err(e);
             },
         };
+        publish_access_key_change(&result, AccessKeyChangeAction::Upsert).await;
+
+        Ok(AccessKeySecretOutput {
+            access_key: result,
+            access_key_secret,
+        })
+    }
+
+    async fn update_access_key(
+        &self,
+        input: UpdateAccessKeyInput,
+    ) -> Result<SysAccessKeyModel, AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        let existing = SysAccessKey::find_by_id(&input.id)
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .ok_or(AccessKeyError::AccessKeyNotFound)?;
+        let mut input = input.access_key;
+        normalize_input(db.as_ref(), &mut input, existing.expires_at).await?;
+        let (signature_algorithm, public_key) =
+            resolve_signing(&input, Some(existing.signature_algorithm)).await?;
+
+        let access_key_id = existing.access_key_id.clone();
+        let mut access_key: SysAccessKeyActiveModel = existing.into();
+        // 更换公钥立即生效，不保留旧公钥
+        if let Some(public_key) = public_key {
+            let cipher = secret_cipher().await?;
+            access_key.access_key_secret = Set(cipher.encrypt(&public_key, &access_key_id)?);
+            access_key.previous_secret = Set(None);
+            access_key.previous_secret_expires_at = Set(None);
+        }
+        access_key.domain = Set(input.domain);
+        access_key.status = Set(input.status);
+        access_key.signature_algorithm = Set(signature_algorithm);
+        access_key.description = Set(input.description);
+        access_key.rate_limit = Set(input.rate_limit);
+        access_key.rate_limit_window_secs = Set(input.rate_limit_window_secs);
+        access_key.scopes = Set(input.scopes.map(JsonValue::from));
+        access_key.allowed_ips = Set(input.allowed_ips.map(JsonValue::from));
+        access_key.expires_at = Set(input.expires_at);
+        let result = access_key
+            .update(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        self.sync_access_key(db.as_ref(), result.clone()).await?;
+        publish_access_key_change(&result, AccessKeyChangeAction::Upsert).await;
 
         Ok(result)
     }
 
+    async fn rotate_access_key(
+        &self,
+        id: &str,
+        user: User,
+    ) -> Result<AccessKeySecretOutput, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let cipher = secret_cipher().await?;
+        let config = get_config::<SecurityConfig>().await.unwrap_or_default();
+
+        let access_key = SysAccessKey::find_by_id(id)
+            .filter(domain_scope(&user.domain()))
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .ok_or(AccessKeyError::AccessKeyNotFound)?;
+        if access_key.signature_algorithm == AccessKeyAlgorithm::Ed25519 {
+            return Err(AccessKeyError::RotationNotSupported.into());
+        }
+        let access_key_id = access_key.access_key_id.clone();
+        let previous = reveal_secret(cipher, &access_key.access_key_secret, &access_key_id)?;
+
+        let access_key_secret = cipher.generate_secret()?;
+        let overlap_secs = config.access_key.rotation_overlap_secs;
+        let previous_expires_at = (overlap_secs > 0)
+            .then(|| Local::now().naive_local() + Duration::seconds(overlap_secs as i64));
+
+        // 仅在密钥未被并发修改（其他轮换或重新加密）时写入
+        let stored_secret = access_key.access_key_secret.clone();
+        let mut active: SysAccessKeyActiveModel = access_key.into();
+        active.access_key_secret = Set(cipher.encrypt(&access_key_secret, &access_key_id)?);
+        active.previous_secret = Set(previous_expires_at
+            .map(|_| cipher.encrypt(&previous, &access_key_id))
+            .transpose()?);
+        active.previous_secret_expires_at = Set(previous_expires_at);
+        let result = SysAccessKey::update(active)
+            .filter(SysAccessKeyColumn::AccessKeySecret.eq(stored_secret))
+            .exec(db.as_ref())
+            .await
+            .map_err(|e| match e {
+                DbErr::RecordNotUpdated => AccessKeyError::RotationConflict.into(),
+                e => AppError::from(e),
+            })?;
+
+        if result.status == Status::Enabled {
+            server_core::sign::add_signing_key(
+                &access_key_id,
+                to_signature_algorithm(result.signature_algorithm),
+                &access_key_secret,
+                previous_expires_at.map(|expires_at| (previous.as_str(), to_millis(expires_at))),
+            )
+            .await;
+        }
+        publish_access_key_change(&result, AccessKeyChangeAction::Upsert).await;
+        project_info!(
+            "Access key {} rotated, previous secret valid until {:?}",
+            access_key_id,
+            previous_expires_at
+        );
+
+        Ok(AccessKeySecretOutput {
+            access_key: result,
+            access_key_secret: Some(access_key_secret),
+        })
+    }
+
     async fn delete_access_key(&self, id: &str) -> Result<(), AppError> {
         let db = db_helper::get_db_connection().await?;
         let txn = db.begin().await.map_err(AppError::from)?;
 
         match self.delete_access_key_in_transaction(&txn, id).await {
-            Ok(_) => {
+            Ok(access_key_id) => {
                 txn.commit().await.map_err(AppError::from)?;
+                let event = AccessKeyChangedEvent {
+                    id: id.to_string(),
+                    access_key_id,
+                    action: AccessKeyChangeAction::Remove,
+                };
+                publish_event(&event).await;
                 Ok(())
             },
             Err(e) => {
@@ -182,25 +591,477 @@ impl TAccessKeyService for SysAccessKeyService {
     }
 
     async fn initialize_access_key(&self) -> Result<(), AppError> {
-        let db = db_helper::get_db_connection().await?;
+        self.reload_access_keys().await
+    }
+}
 
-        let access_keys = SysAccessKey::find()
-            .all(db.as_ref())
+/// 访问密钥的管理范围：内置域可管理全部密钥，其他域只能管理本域的密钥
+fn domain_scope(domain: &str) -> Condition {
+    if domain == BUILT_IN_DOMAIN {
+        Condition::all()
+    } else {
+        Condition::all().add(SysAccessKeyColumn::Domain.eq(domain))
+    }
+}
+
+/// 获取访问密钥的加密器
+async fn secret_cipher() -> Result<&'static SecretCipher, AppError> {
+    SECRET_CIPHER
+        .get_or_try_init(|| async {
+            let config = get_config::<SecurityConfig>().await.unwrap_or_default();
+            let config = &config.access_key;
+            let key = config
+                .resolve_encryption_key()
+                .ok_or(AccessKeyError::EncryptionKeyMissing)?;
+            let retired_keys = config
+                .retired_keys
+                .iter()
+                .map(|retired| (retired.id.as_str(), retired.key.as_str()));
+            SecretCipher::new(&config.encryption_key_id, &key, retired_keys).map_err(AppError::from)
+        })
+        .await
+}
+
+/// 解密密钥，兼容加密前保存的明文
+fn reveal_secret(
+    cipher: &SecretCipher,
+    value: &str,
+    access_key_id: &str,
+) -> Result<String, AppError> {
+    if SecretCipher::is_encrypted(value) {
+        cipher.decrypt(value, access_key_id).map_err(AppError::from)
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+#[inline]
+fn to_millis(time: NaiveDateTime) -> i64 {
+    time.and_local_timezone(Local).earliest().map_or_else(
+        || time.and_utc().timestamp_millis(),
+        |time| time.timestamp_millis(),
+    )
+}
+
+/// 从验证器、限流配额与访问策略中移除访问密钥
+async fn unload_access_key(access_key_id: &str) {
+    server_core::sign::remove_key(ValidatorType::Simple, access_key_id).await;
+    server_core::sign::remove_key(ValidatorType::Complex, access_key_id).await;
+    rate_limit::set_access_key_quota(access_key_id, None);
+    set_access_key_policy(access_key_id, None);
+    LOADED_ACCESS_KEYS.remove(access_key_id);
+}
+
+/// 校验并规范化访问密钥输入
+///
+/// # 参数
+/// * `current_expires_at` - 修改前的过期时间，未改动的过期时间不再校验是否已过去
+async fn normalize_input<C: ConnectionTrait>(
+    db: &C,
+    input: &mut CreateAccessKeyInput,
+    current_expires_at: Option<NaiveDateTime>,
+) -> Result<(), AppError> {
+    let now = Local::now().naive_local();
+    if input.expires_at != current_expires_at
+        && input.expires_at.is_some_and(|expires_at| expires_at <= now)
+    {
+        return Err(AccessKeyError::ExpiryInPast.into());
+    }
+    input.scopes = match input.scopes.take() {
+        Some(scopes) => normalize_scopes(db, scopes).await?,
+        None => None,
+    };
+    input.allowed_ips = match input.allowed_ips.take() {
+        Some(allowed_ips) => normalize_allowed_ips(allowed_ips)?,
+        None => None,
+    };
+    Ok(())
+}
+
+/// 确定签名算法与 Ed25519 公钥
+///
+/// 已弃用的算法在配置禁用后不能再选用，已在使用的密钥修改其他字段时不受影响；
+/// 共享密钥与 Ed25519 之间不能互相切换。
+///
+/// # 参数
+/// * `current` - 修改前的签名算法，创建时为 None
+///
+/// # 返回值
+/// * `(签名算法, 规范化后的公钥)` - 未提供公钥时为 None
+async fn resolve_signing(
+    input: &CreateAccessKeyInput,
+    current: Option<AccessKeyAlgorithm>,
+) -> Result<(AccessKeyAlgorithm, Option<String>), AppError> {
+    let algorithm = input
+        .signature_algorithm
+        .or(current)
+        .unwrap_or(DEFAULT_SIGNATURE_ALGORITHM);
+
+    if Some(algorithm) != current && to_signature_algorithm(algorithm).is_deprecated() {
+        let config = get_config::<SecurityConfig>().await.unwrap_or_default();
+        if !config.access_key.allow_legacy_algorithms {
+            return Err(AccessKeyError::LegacyAlgorithmDisabled(format!("{:?}", algorithm)).into());
+        }
+    }
+
+    let is_ed25519 = algorithm == AccessKeyAlgorithm::Ed25519;
+    if current.is_some_and(|current| (current == AccessKeyAlgorithm::Ed25519) != is_ed25519) {
+        return Err(AccessKeyError::IncompatibleAlgorithm.into());
+    }
+
+    let public_key = match &input.public_key {
+        Some(public_key) if is_ed25519 => Some(normalize_public_key(public_key)?),
+        Some(_) => return Err(AccessKeyError::InvalidPublicKey.into()),
+        None if is_ed25519 && current.is_none() => {
+            return Err(AccessKeyError::InvalidPublicKey.into())
+        },
+        None => None,
+    };
+
+    Ok((algorithm, public_key))
+}
+
+/// 校验 Ed25519 公钥，统一为标准 Base64 编码
+fn normalize_public_key(public_key: &str) -> Result<String, AccessKeyError> {
+    STANDARD
+        .decode(public_key.trim())
+        .ok()
+        .filter(|bytes| bytes.len() == ED25519_PUBLIC_KEY_LEN)
+        .map(|bytes| STANDARD.encode(bytes))
+        .ok_or(AccessKeyError::InvalidPublicKey)
+}
+
+fn to_signature_algorithm(algorithm: AccessKeyAlgorithm) -> SignatureAlgorithm {
+    match algorithm {
+        AccessKeyAlgorithm::Md5 => SignatureAlgorithm::Md5,
+        AccessKeyAlgorithm::Sha1 => SignatureAlgorithm::Sha1,
+        AccessKeyAlgorithm::Sha256 => SignatureAlgorithm::Sha256,
+        AccessKeyAlgorithm::HmacSha256 => SignatureAlgorithm::HmacSha256,
+        AccessKeyAlgorithm::HmacSha512 => SignatureAlgorithm::HmacSha512,
+        AccessKeyAlgorithm::Ed25519 => SignatureAlgorithm::Ed25519,
+    }
+}
+
+/// 通知其他实例访问密钥已变更，本实例的验证器由调用方直接更新
+async fn publish_access_key_change(access_key: &SysAccessKeyModel, action: AccessKeyChangeAction) {
+    let event = AccessKeyChangedEvent {
+        id: access_key.id.clone(),
+        access_key_id: access_key.access_key_id.clone(),
+        action,
+    };
+    publish_event(&event).await;
+}
+
+/// 通过 Redis 发布访问密钥变更，未配置 Redis 时视为单实例部署，不做处理
+async fn publish_event(event: &AccessKeyChangedEvent) {
+    cache_sync::publish(ACCESS_KEY_CHANGED_CHANNEL, event).await;
+}
+
+async fn reload_access_keys_logged() {
+    if let Err(e) = SysAccessKeyService.reload_access_keys().await {
+        project_error!("Failed to reload access keys: {}", e.message);
+    }
+}
+
+/// 接收其他实例发布的访问密钥变更并定期全量同步，不会返回
+///
+/// 每次（重新）订阅成功后都会全量同步，断开期间错过的变更不会遗留。
+pub async fn access_key_change_subscriber() {
+    cache_sync::run(
+        ACCESS_KEY_CHANGED_CHANNEL,
+        StdDuration::from_secs(ACCESS_KEY_RECONCILE_INTERVAL_SECS),
+        |event: AccessKeyChangedEvent| async move {
+            global::send_dyn_event(SystemEvent::AccessKeyChangedEvent.as_ref(), Box::new(event))
+        },
+        reload_access_keys_logged,
+    )
+    .await;
+}
+
+/// 同步访问密钥的限流配额，算法取自限流配置
+async fn sync_rate_limit_quota(access_key: &SysAccessKeyModel) {
+    let quota = match access_key.rate_limit {
+        Some(limit) if limit > 0 => {
+            let config = get_config::<RateLimitConfig>().await.unwrap_or_default();
+            Some(RateLimitQuota {
+                algorithm: config.access_key_algorithm,
+                limit: limit as u64,
+                window_secs: access_key
+                    .rate_limit_window_secs
+                    .filter(|secs| *secs > 0)
+                    .map_or(DEFAULT_RATE_LIMIT_WINDOW_SECS, |secs| secs as u64),
+            })
+        },
+        _ => None,
+    };
+    rate_limit::set_access_key_quota(&access_key.access_key_id, quota);
+}
+
+/// 校验并规范化访问范围，请求方法统一为大写，接口 ID 需存在于 `sys_endpoint`
+///
+/// # 返回值
+/// * `Ok(None)` - 未设置访问范围，不做限制
+async fn normalize_scopes<C: ConnectionTrait>(
+    db: &C,
+    scopes: Vec<String>,
+) -> Result<Option<Vec<String>>, AppError> {
+    let mut normalized = Vec::with_capacity(scopes.len());
+    let mut endpoint_ids = HashSet::new();
+    for scope in scopes {
+        let scope = scope.trim();
+        match parse_scope_method(scope) {
+            Some(method) => normalized.push(method.to_string()),
+            None => {
+                endpoint_ids.insert(scope.to_string());
+                normalized.push(scope.to_string());
+            },
+        }
+    }
+    normalized.sort();
+    normalized.dedup();
+
+    if !endpoint_ids.is_empty() {
+        let found: HashSet<String> = SysEndpoint::find()
+            .filter(SysEndpointColumn::Id.is_in(endpoint_ids.iter().cloned()))
+            .all(db)
             .await
-            .map_err(AppError::from)?;
+            .map_err(AppError::from)?
+            .into_iter()
+            .map(|endpoint| endpoint.id)
+            .collect();
+        if let Some(missing) = endpoint_ids.into_iter().find(|id| !found.contains(id)) {
+            return Err(AccessKeyError::InvalidScope(missing).into());
+        }
+    }
 
-        for access_key in access_keys {
-            server_core::sign::add_key(ValidatorType::Simple, &access_key.access_key_id, None)
-                .await;
-            server_core::sign::add_key(
-                ValidatorType::Complex,
-                &access_key.access_key_id,
-                Some(&access_key.access_key_secret),
-            )
-            .await;
+    Ok((!normalized.is_empty()).then_some(normalized))
+}
+
+/// 校验并规范化允许的来源 IP，单个 IP 按主机网段保存
+fn normalize_allowed_ips(allowed_ips: Vec<String>) -> Result<Option<Vec<String>>, AppError> {
+    let mut normalized = allowed_ips
+        .iter()
+        .map(|ip| {
+            parse_ip_net(ip)
+                .map(|net| net.to_string())
+                .map_err(|_| AccessKeyError::InvalidAllowedIp(ip.trim().to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    normalized.sort();
+    normalized.dedup();
+
+    Ok((!normalized.is_empty()).then_some(normalized))
+}
+
+fn parse_scope_method(scope: &str) -> Option<Method> {
+    SCOPE_METHODS
+        .iter()
+        .find(|method| method.as_str().eq_ignore_ascii_case(scope))
+        .cloned()
+}
+
+fn json_strings(value: &Option<JsonValue>) -> Option<Vec<String>> {
+    value
+        .as_ref()
+        .and_then(|value| serde_json::from_value::<Vec<String>>(value.clone()).ok())
+        .filter(|values| !values.is_empty())
+}
+
+/// 同步访问密钥的访问策略
+///
+/// 访问范围中的接口 ID 按 `sys_endpoint` 解析为路由模板，已不存在的接口会被忽略；
+/// 全部接口都已不存在时密钥不可访问任何接口，而不是退化为不限制。
+async fn sync_access_key_policy<C: ConnectionTrait>(
+    db: &C,
+    access_key: &SysAccessKeyModel,
+) -> Result<(), AppError> {
+    let scopes = match json_strings(&access_key.scopes) {
+        Some(scopes) => {
+            let mut resolved = Vec::with_capacity(scopes.len());
+            let mut endpoint_ids = Vec::new();
+            for scope in scopes {
+                match parse_scope_method(&scope) {
+                    Some(method) => resolved.push(AccessKeyScope::Method(method)),
+                    None => endpoint_ids.push(scope),
+                }
+            }
+
+            if !endpoint_ids.is_empty() {
+                let endpoints = SysEndpoint::find()
+                    .filter(SysEndpointColumn::Id.is_in(endpoint_ids.iter().cloned()))
+                    .all(db)
+                    .await
+                    .map_err(AppError::from)?;
+                if endpoints.len() < endpoint_ids.len() {
+                    project_warn!(
+                        "Access key {} has {} scopes referring to missing endpoints",
+                        access_key.access_key_id,
+                        endpoint_ids.len() - endpoints.len()
+                    );
+                }
+                resolved.extend(endpoints.into_iter().filter_map(|endpoint| {
+                    Some(AccessKeyScope::Endpoint {
+                        method: endpoint.method.parse().ok()?,
+                        path: endpoint.path,
+                    })
+                }));
+            }
+
+            Some(resolved)
+        },
+        None => None,
+    };
+
+    let allowed_ips = json_strings(&access_key.allowed_ips)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|ip| parse_ip_net(ip).ok())
+        .collect();
+
+    let policy = AccessKeyPolicy {
+        expires_at: access_key.expires_at,
+        scopes,
+        allowed_ips,
+    };
+    set_access_key_policy(
+        &access_key.access_key_id,
+        (policy != AccessKeyPolicy::default()).then_some(policy),
+    );
+
+    Ok(())
+}
+
+/// 一个写入间隔内累计的访问密钥使用情况
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct AccessKeyUsage {
+    usage_count: i64,
+    rejected_count: i64,
+    /// 最近一次通过校验的时间与来源 IP
+    last_used: Option<(NaiveDateTime, String)>,
+}
+
+/// 在内存中累计访问密钥的使用情况，由 [`access_key_usage_writer`] 定期批量写入
+fn record_access_key_usage(event: &ApiKeyEvent) {
+    let mut usage = PENDING_USAGE.entry(event.api_key.clone()).or_default();
+    match event.rejection {
+        None => {
+            usage.usage_count += 1;
+            usage.last_used = Some((Local::now().naive_local(), event.client_ip.clone()));
+        },
+        Some(_) => usage.rejected_count += 1,
+    }
+}
+
+/// 取出累计的使用情况
+fn take_pending_usage() -> Vec<(String, AccessKeyUsage)> {
+    let keys: Vec<String> = PENDING_USAGE
+        .iter()
+        .map(|entry| entry.key().clone())
+        .collect();
+    keys.into_iter()
+        .filter_map(|key| PENDING_USAGE.remove(&key))
+        .collect()
+}
+
+/// 用一条语句写入多个密钥的使用情况，未登记的密钥（如沙箱测试密钥）不会更新任何记录
+async fn write_access_key_usage<C: ConnectionTrait>(
+    db: &C,
+    usages: &[(String, AccessKeyUsage)],
+) -> Result<(), AppError> {
+    if usages.is_empty() {
+        return Ok(());
+    }
+
+    SysAccessKey::update_many()
+        .col_expr(
+            SysAccessKeyColumn::UsageCount,
+            usage_case(usages, SysAccessKeyColumn::UsageCount, |usage| {
+                (usage.usage_count > 0)
+                    .then(|| Expr::col(SysAccessKeyColumn::UsageCount).add(usage.usage_count))
+            }),
+        )
+        .col_expr(
+            SysAccessKeyColumn::RejectedCount,
+            usage_case(usages, SysAccessKeyColumn::RejectedCount, |usage| {
+                (usage.rejected_count > 0)
+                    .then(|| Expr::col(SysAccessKeyColumn::RejectedCount).add(usage.rejected_count))
+            }),
+        )
+        .col_expr(
+            SysAccessKeyColumn::LastUsedAt,
+            usage_case(usages, SysAccessKeyColumn::LastUsedAt, |usage| {
+                usage.last_used.as_ref().map(|(at, _)| Expr::value(*at))
+            }),
+        )
+        .col_expr(
+            SysAccessKeyColumn::LastUsedIp,
+            usage_case(usages, SysAccessKeyColumn::LastUsedIp, |usage| {
+                usage
+                    .last_used
+                    .as_ref()
+                    .map(|(_, ip)| Expr::value(ip.clone()))
+            }),
+        )
+        .filter(SysAccessKeyColumn::AccessKeyId.is_in(usages.iter().map(|(key, _)| key.as_str())))
+        .exec(db)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(())
+}
+
+/// 按密钥取列的新值，`value` 返回 None 的密钥保持原值
+fn usage_case(
+    usages: &[(String, AccessKeyUsage)],
+    column: SysAccessKeyColumn,
+    value: impl Fn(&AccessKeyUsage) -> Option<SimpleExpr>,
+) -> SimpleExpr {
+    let mut case = CaseStatement::new();
+    let mut matched = false;
+    for (access_key_id, usage) in usages {
+        if let Some(value) = value(usage) {
+            case = case.case(
+                SysAccessKeyColumn::AccessKeyId.eq(access_key_id.as_str()),
+                value,
+            );
+            matched = true;
         }
+    }
 
-        Ok(())
+    if matched {
+        case.finally(Expr::col(column)).into()
+    } else {
+        Expr::col(column).into()
+    }
+}
+
+/// 写入累计的访问密钥使用情况，写入失败的记录会被丢弃
+pub async fn flush_access_key_usage() {
+    let usages = take_pending_usage();
+    if usages.is_empty() {
+        return;
+    }
+
+    let result = match db_helper::get_db_connection().await {
+        Ok(db) => write_access_key_usage(db.as_ref(), &usages).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        project_error!(
+            "Failed to record usage of {} access keys: {}",
+            usages.len(),
+            e.message
+        );
+    }
+}
+
+/// 按固定间隔写入访问密钥的使用情况，不会返回
+pub async fn access_key_usage_writer() {
+    let mut ticker = tokio::time::interval(StdDuration::from_secs(USAGE_FLUSH_INTERVAL_SECS));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        flush_access_key_usage().await;
     }
 }
 
@@ -210,7 +1071,160 @@ pub async fn api_key_validate_listener(
 ) {
     while let Some(event) = rx.recv().await {
         if let Some(api_key_event) = event.downcast_ref::<ApiKeyEvent>() {
-            project_info!("API key validated: {:?}", api_key_event);
+            if let Some(rejection) = api_key_event.rejection {
+                project_info!(
+                    "API key {} rejected from {}: {}",
+                    api_key_event.api_key,
+                    api_key_event.client_ip,
+                    rejection
+                );
+            }
+            record_access_key_usage(api_key_event);
         }
     }
 }
+
+#[instrument(skip(rx))]
+pub async fn access_key_changed_listener(
+    mut rx: tokio::sync::mpsc::UnboundedReceiver<Box<dyn Any + Send>>,
+) {
+    while let Some(event) = rx.recv().await {
+        if let Some(event) = event.downcast_ref::<AccessKeyChangedEvent>() {
+            if let Err(e) = SysAccessKeyService.apply_access_key_change(event).await {
+                project_error!(
+                    "Failed to apply change of access key {}: {}",
+                    event.access_key_id,
+                    e.message
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{DbBackend, MockDatabase, MockExecResult, QueryTrait};
+    use server_core::sign::AccessKeyRejection;
+
+    use super::*;
+
+    #[test]
+    fn test_normalize_allowed_ips() {
+        assert_eq!(
+            normalize_allowed_ips(vec![
+                "10.1.2.3/8".to_string(),
+                " 192.168.1.1 ".to_string(),
+                "10.0.0.0/8".to_string(),
+            ])
+            .unwrap(),
+            Some(vec!["10.0.0.0/8".to_string(), "192.168.1.1/32".to_string()])
+        );
+        assert_eq!(normalize_allowed_ips(Vec::new()).unwrap(), None);
+        assert_eq!(
+            normalize_allowed_ips(vec!["example.com".to_string()])
+                .unwrap_err()
+                .code,
+            5003
+        );
+    }
+
+    #[test]
+    fn test_domain_scope() {
+        let sql = SysAccessKey::find_by_id("1")
+            .filter(domain_scope("tenant"))
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.ends_with(r#"AND "sys_access_key"."domain" = 'tenant'"#));
+
+        let sql = SysAccessKey::find_by_id("1")
+            .filter(domain_scope(BUILT_IN_DOMAIN))
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(!sql.contains(r#""domain" ="#));
+    }
+
+    #[test]
+    fn test_access_key_changed_event() {
+        let event = AccessKeyChangedEvent {
+            id: "1".to_string(),
+            access_key_id: "AK1".to_string(),
+            action: AccessKeyChangeAction::Remove,
+        };
+        let payload = serde_json::to_string(&event).unwrap();
+        assert_eq!(
+            payload,
+            r#"{"id":"1","accessKeyId":"AK1","action":"remove"}"#
+        );
+
+        let event: AccessKeyChangedEvent = serde_json::from_str(&payload).unwrap();
+        assert_eq!(event.action, AccessKeyChangeAction::Remove);
+    }
+
+    #[test]
+    fn test_normalize_public_key() {
+        let public_key = STANDARD.encode([7u8; ED25519_PUBLIC_KEY_LEN]);
+        assert_eq!(
+            normalize_public_key(&format!(" {} ", public_key)).unwrap(),
+            public_key
+        );
+        assert!(normalize_public_key(&STANDARD.encode([7u8; 16])).is_err());
+        assert!(normalize_public_key("not base64").is_err());
+    }
+
+    #[test]
+    fn test_parse_scope_method() {
+        assert_eq!(parse_scope_method("get"), Some(Method::GET));
+        assert_eq!(parse_scope_method("DELETE"), Some(Method::DELETE));
+        assert_eq!(parse_scope_method("OPTIONS"), None);
+        assert_eq!(parse_scope_method("a1b2c3"), None);
+    }
+
+    #[tokio::test]
+    async fn test_access_key_usage_batch() {
+        let event = |api_key: &str, client_ip: &str, rejected: bool| ApiKeyEvent {
+            api_key: api_key.to_string(),
+            client_ip: client_ip.to_string(),
+            rejection: rejected.then_some(AccessKeyRejection::OutOfScope),
+        };
+        record_access_key_usage(&event("AK_USAGE_A", "10.0.0.1", false));
+        record_access_key_usage(&event("AK_USAGE_A", "10.0.0.2", false));
+        record_access_key_usage(&event("AK_USAGE_A", "10.0.0.3", true));
+        record_access_key_usage(&event("AK_USAGE_B", "10.0.0.4", true));
+
+        let mut usages = take_pending_usage();
+        usages.sort_by(|a, b| a.0.cmp(&b.0));
+        assert!(take_pending_usage().is_empty());
+        assert_eq!(usages.len(), 2);
+        assert_eq!(usages[0].1.usage_count, 2);
+        assert_eq!(usages[0].1.rejected_count, 1);
+        assert_eq!(
+            usages[0].1.last_used.as_ref().map(|(_, ip)| ip.as_str()),
+            Some("10.0.0.2")
+        );
+        assert_eq!(usages[1].1.usage_count, 0);
+        assert_eq!(usages[1].1.last_used, None);
+
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_exec_results([MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 2,
+            }])
+            .into_connection();
+        write_access_key_usage(&db, &usages).await.unwrap();
+        write_access_key_usage(&db, &[]).await.unwrap();
+
+        let log = db.into_transaction_log();
+        assert_eq!(log.len(), 1);
+        let sql = log[0].statements()[0].to_string();
+        assert!(sql.contains(
+            r#""usage_count" = (CASE WHEN ("sys_access_key"."access_key_id" = 'AK_USAGE_A') THEN "usage_count" + 2 ELSE "usage_count" END)"#
+        ));
+        assert!(sql.contains(
+            r#"WHEN ("sys_access_key"."access_key_id" = 'AK_USAGE_B') THEN "rejected_count" + 1"#
+        ));
+        assert!(sql.contains(r#""last_used_ip" = (CASE WHEN ("sys_access_key"."access_key_id" = 'AK_USAGE_A') THEN '10.0.0.2' ELSE "last_used_ip" END)"#));
+        assert!(sql.ends_with(
+            r#"WHERE "sys_access_key"."access_key_id" IN ('AK_USAGE_A', 'AK_USAGE_B')"#
+        ));
+    }
+}
@@ -1,46 +1,64 @@
-use std::any::Any;
+use std::{any::Any, time::Duration as StdDuration};
 
 use async_trait::async_trait;
+use chrono::{Duration, Local, NaiveDateTime, Utc};
 use sea_orm::{
-    ColumnTrait, DatabaseConnection, EntityTrait, JoinType, QueryFilter, QueryOrder, QuerySelect,
-    RelationTrait,
+    sea_query::Expr, ColumnTrait, DatabaseConnection, EntityTrait, JoinType, QueryFilter,
+    QueryOrder, QuerySelect, RelationTrait,
+};
+use server_config::{JwtConfig, SecurityConfig};
+use server_constant::definition::{
+    consts::{SystemEvent, TokenStatus},
+    Audience,
 };
-use server_constant::definition::{consts::SystemEvent, Audience};
 use server_core::web::{
     auth::Claims,
     error::AppError,
     jwt::{JwtError, JwtUtils},
+    token_revocation,
 };
 use server_global::global;
 use server_model::admin::{
     entities::{
-        prelude::{SysRole, SysUser},
+        prelude::{SysRole, SysTokens, SysUser},
         sea_orm_active_enums::Status,
         sys_domain::Column as SysDomainColumn,
         sys_menu::{Column as SysMenuColumn, Entity as SysMenuEntity, Model as SysMenuModel},
+        sys_organization::Column as SysOrganizationColumn,
         sys_role::{Column as SysRoleColumn, Entity as SysRoleEntity, Relation as SysRoleRelation},
         sys_role_menu::{Column as SysRoleMenuColumn, Entity as SysRoleMenuEntity},
+        sys_tokens::Column as SysTokensColumn,
         sys_user::{Column as SysUserColumn, Relation as SysUserRelation},
         sys_user_role::Relation as SysUserRoleRelation,
     },
-    input::LoginInput,
+    input::{ChangePasswordInput, LoginInput},
     output::{AuthOutput, MenuRoute, RouteMeta, UserRoute, UserWithDomainAndOrgOutput},
 };
 use server_utils::{SecureUtil, TreeBuilder};
 use thiserror::Error;
 use tokio::sync::mpsc;
-use tracing::{error, instrument};
+use tracing::instrument;
 use ulid::Ulid;
 
 use super::{
     dto::sys_auth_dto::LoginContext, event_handlers::auth_event_handler::AuthEventHandler,
 };
 use crate::{
-    admin::{event_handlers::auth_event_handler::AuthEvent, sys_user_error::UserError},
-    helper::db_helper,
+    admin::{
+        event_handlers::auth_event_handler::{AuthEvent, LoginFailedEvent},
+        login_risk::{self, LoginRisk, LoginRiskAction, LoginRiskEvent},
+        sys_domain_error::DomainError,
+        sys_user_error::UserError,
+    },
+    helper::{cache_sync, db_helper},
     project_error, project_info,
 };
 
+/// 令牌撤销的 Redis 频道
+const TOKEN_REVOKED_CHANNEL: &str = "token:revoked";
+/// 已撤销令牌的全量重新加载间隔（秒）
+const TOKEN_RECONCILE_INTERVAL_SECS: u64 = 60;
+
 macro_rules! select_user_with_domain_and_org_info {
     ($query:expr) => {{
         $query
@@ -51,8 +69,12 @@ macro_rules! select_user_with_domain_and_org_info {
             .column_as(SysUserColumn::Password, "password")
             .column_as(SysUserColumn::NickName, "nick_name")
             .column_as(SysUserColumn::Avatar, "avatar")
+            .column_as(SysUserColumn::Status, "status")
             .column_as(SysDomainColumn::Code, "domain_code")
             .column_as(SysDomainColumn::Name, "domain_name")
+            .column_as(SysOrganizationColumn::Name, "organization_name")
+            .column_as(SysUserColumn::MustChangePassword, "must_change_password")
+            .column_as(SysDomainColumn::Status, "domain_status")
     }};
 }
 #[derive(Error, Debug)]
@@ -71,6 +93,13 @@ pub trait TAuthService: Send + Sync {
         context: LoginContext,
     ) -> Result<AuthOutput, AppError>;
 
+    /// 校验当前密码后修改密码，并清除首次登录必须改密的标记
+    async fn change_password(
+        &self,
+        input: ChangePasswordInput,
+        context: LoginContext,
+    ) -> Result<(), AppError>;
+
     async fn get_user_routes(
         &self,
         role_codes: &[String],
@@ -107,26 +136,28 @@ impl TAuthService for SysAuthService {
         input: LoginInput,
         context: LoginContext,
     ) -> Result<AuthOutput, AppError> {
-        // 验证用户并获取角色
-        let (user, role_codes) = self
-            .verify_user(&input.identifier, &input.password, &context.domain)
-            .await?;
+        let result = self.authenticate(&input, &context).await;
 
-        // 生成认证输出
-        let auth_output = generate_auth_output(
-            user.id.clone(),
-            user.username.clone(),
-            role_codes,
-            user.domain_code.clone(),
-            None,
-            context.audience,
-        )
-        .await?;
+        if let Err(ref e) = result {
+            self.send_login_failed_event(&input.identifier, &context, e);
+        }
 
-        // 发送认证事件
-        self.send_login_event(&user, &auth_output, &context).await;
+        result
+    }
 
-        Ok(auth_output)
+    #[instrument(skip(self, input), fields(username = %input.identifier, domain = %context.domain))]
+    async fn change_password(
+        &self,
+        input: ChangePasswordInput,
+        context: LoginContext,
+    ) -> Result<(), AppError> {
+        let result = self.update_password(&input, &context).await;
+
+        if let Err(ref e) = result {
+            self.send_login_failed_event(&input.identifier, &context, e);
+        }
+
+        result
     }
 
     #[instrument(skip(self), fields(roles = ?role_codes, domain = %domain))]
@@ -152,6 +183,7 @@ impl TAuthService for SysAuthService {
                 SysRoleEntity::has_many(SysRoleMenuEntity).into(),
             )
             .filter(SysRoleColumn::Code.is_in(role_codes.to_vec()))
+            .filter(SysRoleColumn::DeletedAt.is_null())
             .filter(SysRoleMenuColumn::Domain.eq(domain))
             .distinct()
             .into_tuple::<i32>()
@@ -160,6 +192,7 @@ impl TAuthService for SysAuthService {
 
         let menus = SysMenuEntity::find()
             .filter(SysMenuColumn::Id.is_in(menu_ids))
+            .filter(SysMenuColumn::DeletedAt.is_null())
             .filter(SysMenuColumn::Status.eq(Status::Enabled))
             .order_by_asc(SysMenuColumn::Sequence)
             .into_model::<SysMenuModel>()
@@ -231,14 +264,23 @@ impl SysAuthService {
         let user = select_user_with_domain_and_org_info!(SysUser::find())
             .filter(SysUserColumn::Username.eq(identifier))
             .filter(SysDomainColumn::Code.eq(domain))
+            .filter(SysUserColumn::DeletedAt.is_null())
+            .filter(SysDomainColumn::DeletedAt.is_null())
             .join(JoinType::InnerJoin, SysUserRelation::SysDomain.def())
+            .join(JoinType::LeftJoin, SysUserRelation::SysOrganization.def())
             .into_model::<UserWithDomainAndOrgOutput>()
             .one(db.as_ref())
             .await
             .map_err(AppError::from)?
             .ok_or_else(|| AppError::from(UserError::UserNotFound))?;
 
-        //TODO validate user status and domain status
+        if user.domain_status != Status::Enabled {
+            return Err(AppError::from(DomainError::DomainDisabled));
+        }
+
+        if user.status != Status::Enabled {
+            return Err(AppError::from(UserError::InvalidUserStatus));
+        }
 
         // 验证密码
         if !SecureUtil::verify_password(password.as_bytes(), &user.password)
@@ -263,6 +305,7 @@ impl SysAuthService {
             .join(JoinType::InnerJoin, SysRoleRelation::SysUserRole.def())
             .join(JoinType::InnerJoin, SysUserRoleRelation::SysUser.def())
             .filter(SysUserColumn::Id.eq(user_id))
+            .filter(SysRoleColumn::DeletedAt.is_null())
             .all(db)
             .await
             .map(|roles| roles.iter().map(|role| role.code.clone()).collect())
@@ -274,6 +317,7 @@ impl SysAuthService {
         user: &UserWithDomainAndOrgOutput,
         auth_output: &AuthOutput,
         context: &LoginContext,
+        risk: &LoginRisk,
     ) {
         let auth_event = AuthEvent {
             user_id: user.id.clone(),
@@ -284,9 +328,12 @@ impl SysAuthService {
             client_ip: context.client_ip.clone(),
             client_port: context.client_port,
             address: context.address.clone(),
+            region: context.region.clone(),
             user_agent: context.user_agent.clone(),
             request_id: context.request_id.clone(),
             login_type: context.login_type.clone(),
+            risk_score: i32::from(risk.score),
+            risk_reasons: risk.reasons(),
         };
 
         global::send_dyn_event(
@@ -295,6 +342,157 @@ impl SysAuthService {
         );
     }
 
+    /// 校验账号密码与登录风险并签发令牌
+    async fn authenticate(
+        &self,
+        input: &LoginInput,
+        context: &LoginContext,
+    ) -> Result<AuthOutput, AppError> {
+        // 验证用户并获取角色
+        let (user, role_codes) = self
+            .verify_user(&input.identifier, &input.password, &context.domain)
+            .await?;
+
+        // 初始密码需先通过修改密码接口更换后才能登录
+        if user.must_change_password {
+            return Err(UserError::PasswordChangeRequired.into());
+        }
+
+        // 登录风险检测
+        let risk = self.check_login_risk(&user, context).await?;
+
+        // 生成认证输出
+        let auth_output = generate_auth_output(
+            user.id.clone(),
+            user.username.clone(),
+            role_codes,
+            user.domain_code.clone(),
+            user.organization_name.clone(),
+            context.audience,
+        )
+        .await?;
+
+        // 发送认证事件
+        self.send_login_event(&user, &auth_output, context, &risk)
+            .await;
+
+        Ok(auth_output)
+    }
+
+    /// 校验当前密码并写入新密码
+    async fn update_password(
+        &self,
+        input: &ChangePasswordInput,
+        context: &LoginContext,
+    ) -> Result<(), AppError> {
+        if input.old_password == input.new_password {
+            return Err(UserError::PasswordUnchanged.into());
+        }
+
+        let (user, _) = self
+            .verify_user(&input.identifier, &input.old_password, &context.domain)
+            .await?;
+
+        let password_hash = SecureUtil::hash_password(input.new_password.as_bytes())
+            .map_err(|_| AppError::from(UserError::AuthenticationFailed))?;
+
+        let db = db_helper::get_db_connection().await?;
+        SysUser::update_many()
+            .col_expr(SysUserColumn::Password, Expr::value(password_hash))
+            .col_expr(SysUserColumn::MustChangePassword, Expr::value(false))
+            .col_expr(
+                SysUserColumn::UpdatedAt,
+                Expr::value(Local::now().naive_local()),
+            )
+            .col_expr(SysUserColumn::UpdatedBy, Expr::value(user.id.clone()))
+            .col_expr(
+                SysUserColumn::Version,
+                Expr::col(SysUserColumn::Version).add(1),
+            )
+            .filter(SysUserColumn::Id.eq(user.id.as_str()))
+            .exec(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    fn send_login_failed_event(&self, identifier: &str, context: &LoginContext, error: &AppError) {
+        let event = LoginFailedEvent {
+            username: identifier.to_string(),
+            domain: context.domain.clone(),
+            client_ip: context.client_ip.clone(),
+            client_port: context.client_port,
+            address: context.address.clone(),
+            region: context.region.clone(),
+            user_agent: context.user_agent.clone(),
+            request_id: context.request_id.clone(),
+            login_type: context.login_type.clone(),
+            reason: error.message.clone(),
+        };
+
+        global::send_dyn_event(SystemEvent::AuthLoginFailedEvent.as_ref(), Box::new(event));
+    }
+
+    /// 登录风险检测
+    ///
+    /// 命中异常时发送 `AuthLoginRiskDetectedEvent`，风险分达到阈值时拒绝登录。
+    /// 检测本身出错时放行，避免影响正常登录。
+    async fn check_login_risk(
+        &self,
+        user: &UserWithDomainAndOrgOutput,
+        context: &LoginContext,
+    ) -> Result<LoginRisk, AppError> {
+        let config = global::get_config::<SecurityConfig>()
+            .await
+            .map(|config| config.login_risk.clone())
+            .unwrap_or_default();
+        if !config.enabled {
+            return Ok(LoginRisk::default());
+        }
+
+        let risk = match login_risk::assess_login_risk(
+            &config,
+            &user.id,
+            &context.client_ip,
+            &context.region,
+            &context.user_agent,
+        )
+        .await
+        {
+            Ok(risk) => risk,
+            Err(e) => {
+                project_error!("Failed to assess login risk: {:?}", e);
+                return Ok(LoginRisk::default());
+            },
+        };
+
+        if risk.anomalies.is_empty() {
+            return Ok(risk);
+        }
+
+        let action = risk.action(&config);
+        global::send_dyn_event(
+            SystemEvent::AuthLoginRiskDetectedEvent.as_ref(),
+            Box::new(LoginRiskEvent {
+                user_id: user.id.clone(),
+                username: user.username.clone(),
+                domain: user.domain_code.clone(),
+                ip: context.client_ip.clone(),
+                address: context.address.clone(),
+                user_agent: context.user_agent.clone(),
+                request_id: context.request_id.clone(),
+                risk: risk.clone(),
+                action,
+            }),
+        );
+
+        match action {
+            LoginRiskAction::Allow => Ok(risk),
+            LoginRiskAction::Block => Err(UserError::LoginBlocked.into()),
+        }
+    }
+
     async fn check_login_security(
         &self,
         _username: &str,
@@ -321,6 +519,60 @@ impl SysAuthService {
     }
 }
 
+/// 从数据库重新加载已撤销且尚未过期的访问令牌
+///
+/// 令牌的过期时间按记录的登录时间加上 JWT 有效期计算，不早于令牌实际的过期时间。
+pub async fn reload_revoked_tokens() -> Result<(), AppError> {
+    let expire = global::get_config::<JwtConfig>()
+        .await
+        .map(|config| config.expire)
+        .unwrap_or_default();
+    let now = Local::now().naive_local();
+    let db = db_helper::get_db_connection().await?;
+
+    let tokens = SysTokens::find()
+        .select_only()
+        .column(SysTokensColumn::AccessToken)
+        .column(SysTokensColumn::LoginTime)
+        .filter(SysTokensColumn::Status.ne(TokenStatus::Active.to_string()))
+        .filter(SysTokensColumn::LoginTime.gt(now - Duration::seconds(expire)))
+        .into_tuple::<(String, NaiveDateTime)>()
+        .all(db.as_ref())
+        .await
+        .map_err(AppError::from)?;
+
+    let now_secs = Utc::now().timestamp();
+    token_revocation::set_revoked_tokens(tokens.into_iter().map(|(token, login_time)| {
+        let remaining = (login_time + Duration::seconds(expire) - now).num_seconds();
+        (token, now_secs + remaining)
+    }));
+
+    Ok(())
+}
+
+async fn reload_revoked_tokens_logged() {
+    if let Err(e) = reload_revoked_tokens().await {
+        project_error!("Failed to reload revoked tokens: {}", e.message);
+    }
+}
+
+/// 令牌撤销提交后调用，重新加载本实例的已撤销令牌并通知其他实例
+pub(crate) async fn sync_revoked_tokens() {
+    reload_revoked_tokens_logged().await;
+    cache_sync::publish(TOKEN_REVOKED_CHANNEL, &()).await;
+}
+
+/// 接收其他实例的令牌撤销通知并定期重新加载，不会返回
+pub async fn token_revocation_subscriber() {
+    cache_sync::run(
+        TOKEN_REVOKED_CHANNEL,
+        StdDuration::from_secs(TOKEN_RECONCILE_INTERVAL_SECS),
+        |()| reload_revoked_tokens_logged(),
+        reload_revoked_tokens_logged,
+    )
+    .await;
+}
+
 #[allow(dead_code)]
 #[instrument(skip(sender, auth_event))]
 async fn send_auth_event(
@@ -371,6 +623,19 @@ pub async fn auth_login_listener(
     }
 }
 
+#[instrument(skip(rx))]
+pub async fn auth_login_failed_listener(
+    mut rx: tokio::sync::mpsc::UnboundedReceiver<Box<dyn Any + Send>>,
+) {
+    while let Some(event) = rx.recv().await {
+        if let Some(event) = event.downcast_ref::<LoginFailedEvent>() {
+            if let Err(e) = AuthEventHandler::handle_login_failed(event).await {
+                project_error!("Failed to handle LoginFailedEvent: {:?}", e);
+            }
+        }
+    }
+}
+
 #[instrument(skip(auth_event), fields(user_id = %auth_event.user_id, username = %auth_event.username))]
 async fn handle_auth_event(auth_event: &AuthEvent) -> Result<(), EventError> {
     AuthEventHandler::handle_login(AuthEvent {
@@ -381,10 +646,13 @@ async fn handle_auth_event(auth_event: &AuthEvent) -> Result<(), EventError> {
         refresh_token: auth_event.refresh_token.clone(),
         client_ip: auth_event.client_ip.clone(),
         address: auth_event.address.clone(),
+        region: auth_event.region.clone(),
         client_port: auth_event.client_port,
         user_agent: auth_event.user_agent.clone(),
         request_id: auth_event.request_id.clone(),
         login_type: auth_event.login_type.clone(),
+        risk_score: auth_event.risk_score,
+        risk_reasons: auth_event.risk_reasons.clone(),
     })
     .await
     .map_err(|e| EventError::LoginHandlerError(format!("{:?}", e)))
@@ -397,3 +665,28 @@ pub async fn jwt_created_listener(mut rx: tokio::sync::mpsc::UnboundedReceiver<S
         // TODO: Consider storing the token into the database
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_login_domain() {
+        let input: LoginInput =
+            serde_json::from_str(r#"{"identifier":"admin","password":"123456"}"#).unwrap();
+        assert_eq!(input.domain, "built-in");
+
+        // 租户管理员以开通时的域编码登录和修改初始密码
+        let input: LoginInput = serde_json::from_str(
+            r#"{"identifier":"acme-admin","password":"123456","domain":"acme"}"#,
+        )
+        .unwrap();
+        assert_eq!(input.domain, "acme");
+
+        let input: ChangePasswordInput = serde_json::from_str(
+            r#"{"identifier":"acme-admin","oldPassword":"123456","newPassword":"12345678","domain":"acme"}"#,
+        )
+        .unwrap();
+        assert_eq!(input.domain, "acme");
+    }
+}
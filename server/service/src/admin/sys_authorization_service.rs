@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use axum_casbin::casbin::{CoreApi, MgmtApi, RbacApi};
+use axum_casbin::casbin::{CoreApi, RbacApi};
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
 use server_core::web::error::AppError;
 use server_model::admin::entities::{
@@ -11,6 +11,7 @@ use server_model::admin::entities::{
     sys_menu::Column as SysMenuColumn,
     sys_role::Column as SysRoleColumn,
     sys_role_menu::{ActiveModel as SysRoleMenuActiveModel, Column as SysRoleMenuColumn},
+    sys_user::Column as SysUserColumn,
     sys_user_role::{ActiveModel as SysUserRoleActiveModel, Column as SysUserRoleColumn},
 };
 use thiserror::Error;
@@ -19,6 +20,7 @@ use tokio::sync::RwLock;
 use crate::helper::db_helper;
 
 #[derive(Error, Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum AuthorizationError {
     #[error("Domain not found")]
     DomainNotFound,
@@ -49,7 +51,7 @@ pub trait TAuthorizationService: Send + Sync {
         domain: String,
         role_id: String,
         permissions: Vec<String>,
-        enforcer: Arc<RwLock<impl CoreApi + MgmtApi + RbacApi + Send + Sync>>,
+        enforcer: Arc<RwLock<impl CoreApi + RbacApi>>,
     ) -> Result<(), AppError>;
 
     /// 为角色分配路由
@@ -77,19 +79,21 @@ impl SysAuthorizationService {
 
         let domain = SysDomain::find()
             .filter(SysDomainColumn::Code.eq(domain_code))
+            .filter(SysDomainColumn::DeletedAt.is_null())
             .one(db.as_ref())
             .await
             .map_err(AppError::from)?;
 
-        let domain = domain.ok_or_else(|| AuthorizationError::DomainNotFound)?;
+        let domain = domain.ok_or(AuthorizationError::DomainNotFound)?;
 
         let role = SysRole::find()
             .filter(SysRoleColumn::Id.eq(role_id))
+            .filter(SysRoleColumn::DeletedAt.is_null())
             .one(db.as_ref())
             .await
             .map_err(AppError::from)?;
 
-        let role = role.ok_or_else(|| AuthorizationError::RoleNotFound)?;
+        let role = role.ok_or(AuthorizationError::RoleNotFound)?;
 
         Ok((domain.code, role_id.to_string(), role.code))
     }
@@ -99,11 +103,12 @@ impl SysAuthorizationService {
 
         let role = SysRole::find()
             .filter(SysRoleColumn::Id.eq(role_id))
+            .filter(SysRoleColumn::DeletedAt.is_null())
             .one(db.as_ref())
             .await
             .map_err(AppError::from)?;
 
-        let role = role.ok_or_else(|| AuthorizationError::RoleNotFound)?;
+        let role = role.ok_or(AuthorizationError::RoleNotFound)?;
 
         Ok(role.code)
     }
@@ -114,7 +119,7 @@ impl SysAuthorizationService {
         role_code: &str,
         domain: &str,
         new_permissions: Vec<server_model::admin::entities::sys_endpoint::Model>,
-        enforcer: Arc<RwLock<impl CoreApi + MgmtApi + RbacApi + Send + Sync>>,
+        enforcer: Arc<RwLock<impl CoreApi + RbacApi>>,
     ) -> Result<(), AppError> {
         let mut enforcer_write = enforcer.write().await;
         let existing_permissions =
@@ -193,7 +198,7 @@ impl TAuthorizationService for SysAuthorizationService {
         domain: String,
         role_id: String,
         permissions: Vec<String>,
-        enforcer: Arc<RwLock<impl CoreApi + MgmtApi + RbacApi + Send + Sync>>,
+        enforcer: Arc<RwLock<impl CoreApi + RbacApi>>,
     ) -> Result<(), AppError> {
         let (domain_code, _, role_code) = self.check_domain_and_role(&domain, &role_id).await?;
 
@@ -224,7 +229,8 @@ impl TAuthorizationService for SysAuthorizationService {
 
         let db = db_helper::get_db_connection().await?;
         let routes = SysMenu::find()
-            .filter(SysMenuColumn::Id.is_in(route_ids.clone()))
+            .filter(SysMenuColumn::Id.is_in(route_ids))
+            .filter(SysMenuColumn::DeletedAt.is_null())
             .all(db.as_ref())
             .await
             .map_err(AppError::from)?;
@@ -233,6 +239,9 @@ impl TAuthorizationService for SysAuthorizationService {
             return Err(AuthorizationError::RoutesNotFound.into());
         }
 
+        // 仅绑定未删除的菜单
+        let route_ids: Vec<i32> = routes.iter().map(|route| route.id).collect();
+
         let existing_routes = SysRoleMenu::find()
             .filter(
                 SysRoleMenuColumn::RoleId
@@ -266,7 +275,6 @@ impl TAuthorizationService for SysAuthorizationService {
                     role_id: sea_orm::Set(role_id.clone()),
                     menu_id: sea_orm::Set(*route_id),
                     domain: sea_orm::Set(domain_code.clone()),
-                    ..Default::default()
                 })
                 .collect();
 
@@ -299,7 +307,8 @@ impl TAuthorizationService for SysAuthorizationService {
 
         let db = db_helper::get_db_connection().await?;
         let users = SysUser::find()
-            .filter(server_model::admin::entities::sys_user::Column::Id.is_in(user_ids.clone()))
+            .filter(SysUserColumn::Id.is_in(user_ids))
+            .filter(SysUserColumn::DeletedAt.is_null())
             .all(db.as_ref())
             .await
             .map_err(AppError::from)?;
@@ -308,6 +317,9 @@ impl TAuthorizationService for SysAuthorizationService {
             return Err(AuthorizationError::UsersNotFound.into());
         }
 
+        // 仅绑定未删除的用户
+        let user_ids: Vec<String> = users.into_iter().map(|user| user.id).collect();
+
         let existing_user_roles = SysUserRole::find()
             .filter(SysUserRoleColumn::RoleId.eq(&role_id))
             .all(db.as_ref())
@@ -339,7 +351,6 @@ impl TAuthorizationService for SysAuthorizationService {
                 .map(|user_id| SysUserRoleActiveModel {
                     role_id: sea_orm::Set(role_id.clone()),
                     user_id: sea_orm::Set(user_id.clone()),
-                    ..Default::default()
                 })
                 .collect();
 
@@ -0,0 +1,469 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{Local, NaiveDateTime, Timelike};
+use moka::sync::Cache;
+use once_cell::sync::Lazy;
+use sea_orm::{ConnectionTrait, DbBackend, FromQueryResult, Statement, Value};
+use server_core::web::{error::AppError, page::QueryError};
+use server_model::admin::{
+    input::{DashboardQuery, EndpointRank},
+    output::{EndpointStat, LoginDailyStat, LoginRegionStat, LoginStats},
+};
+
+use crate::helper::db_helper;
+
+/// 默认统计最近的天数
+const DEFAULT_WINDOW_DAYS: i64 = 7;
+/// 允许查询的最大天数
+const MAX_WINDOW_DAYS: i64 = 366;
+const DEFAULT_LIMIT: u64 = 10;
+const MAX_LIMIT: u64 = 100;
+/// 统计结果缓存时间
+const CACHE_TTL: Duration = Duration::from_secs(60);
+const CACHE_CAPACITY: u64 = 1_000;
+
+/// 缓存键，同一租户、时间范围与排行参数的请求共享统计结果
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DashboardKey {
+    domain: String,
+    start_time: NaiveDateTime,
+    end_time: NaiveDateTime,
+    limit: u64,
+    rank_by: EndpointRank,
+}
+
+fn dashboard_cache<V: Clone + Send + Sync + 'static>() -> Cache<DashboardKey, V> {
+    Cache::builder()
+        .max_capacity(CACHE_CAPACITY)
+        .time_to_live(CACHE_TTL)
+        .build()
+}
+
+static LOGIN_STATS_CACHE: Lazy<Cache<DashboardKey, LoginStats>> = Lazy::new(dashboard_cache);
+static LOGIN_REGION_CACHE: Lazy<Cache<DashboardKey, Vec<LoginRegionStat>>> =
+    Lazy::new(dashboard_cache);
+static ENDPOINT_STATS_CACHE: Lazy<Cache<DashboardKey, Vec<EndpointStat>>> =
+    Lazy::new(dashboard_cache);
+
+#[derive(Debug, FromQueryResult)]
+struct LoginCountRow {
+    date: Option<String>,
+    active_users: i64,
+    logins: i64,
+    failures: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct LoginRegionRow {
+    country: String,
+    province: Option<String>,
+    logins: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct EndpointStatRow {
+    method: String,
+    route: String,
+    module_name: String,
+    description: String,
+    calls: i64,
+    p95_duration: f64,
+    errors: i64,
+}
+
+#[async_trait]
+pub trait TDashboardService {
+    /// 登录趋势：活跃用户数、登录次数与失败率
+    async fn get_login_stats(
+        &self,
+        domain: &str,
+        params: DashboardQuery,
+    ) -> Result<LoginStats, AppError>;
+
+    /// 登录次数最多的地区
+    async fn get_login_regions(
+        &self,
+        domain: &str,
+        params: DashboardQuery,
+    ) -> Result<Vec<LoginRegionStat>, AppError>;
+
+    /// 接口调用排行，按请求方法与路由模板聚合
+    async fn get_endpoint_stats(
+        &self,
+        domain: &str,
+        params: DashboardQuery,
+    ) -> Result<Vec<EndpointStat>, AppError>;
+}
+
+#[derive(Clone)]
+pub struct SysDashboardService;
+
+impl SysDashboardService {
+    /// 解析统计参数
+    ///
+    /// 未指定结束时间时取当前分钟的下一分钟，使一分钟内的请求命中同一缓存。
+    fn resolve_key(domain: &str, params: &DashboardQuery) -> Result<DashboardKey, AppError> {
+        let end_time = match params.end_time {
+            Some(end_time) => end_time,
+            None => {
+                let now = Local::now().naive_local();
+                now.with_second(0)
+                    .and_then(|time| time.with_nanosecond(0))
+                    .unwrap_or(now)
+                    + chrono::Duration::minutes(1)
+            },
+        };
+        let start_time = params
+            .start_time
+            .unwrap_or(end_time - chrono::Duration::days(DEFAULT_WINDOW_DAYS));
+
+        if start_time >= end_time || end_time - start_time > chrono::Duration::days(MAX_WINDOW_DAYS)
+        {
+            return Err(QueryError::InvalidTimeRange.into());
+        }
+
+        Ok(DashboardKey {
+            domain: domain.to_string(),
+            start_time,
+            end_time,
+            limit: params.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT),
+            rank_by: params.rank_by,
+        })
+    }
+
+    fn statement(sql: &str, values: Vec<Value>) -> Statement {
+        Statement::from_sql_and_values(DbBackend::Postgres, sql, values)
+    }
+
+    async fn fetch_login_stats<C: ConnectionTrait>(
+        db: &C,
+        key: &DashboardKey,
+    ) -> Result<LoginStats, AppError> {
+        let values = vec![
+            key.domain.clone().into(),
+            key.start_time.into(),
+            key.end_time.into(),
+        ];
+
+        let total =
+            LoginCountRow::find_by_statement(Self::statement(LOGIN_TOTAL_SQL, values.clone()))
+                .one(db)
+                .await
+                .map_err(AppError::from)?;
+        let daily = LoginCountRow::find_by_statement(Self::statement(LOGIN_DAILY_SQL, values))
+            .all(db)
+            .await
+            .map_err(AppError::from)?;
+
+        let (active_users, logins, failures) = total
+            .map(|row| (row.active_users, row.logins, row.failures))
+            .unwrap_or_default();
+        Ok(LoginStats {
+            start_time: key.start_time,
+            end_time: key.end_time,
+            active_users,
+            logins,
+            failures,
+            failure_rate: ratio(failures, logins + failures),
+            daily: daily
+                .into_iter()
+                .map(|row| LoginDailyStat {
+                    date: row.date.unwrap_or_default(),
+                    active_users: row.active_users,
+                    logins: row.logins,
+                    failures: row.failures,
+                    failure_rate: ratio(row.failures, row.logins + row.failures),
+                })
+                .collect(),
+        })
+    }
+
+    async fn fetch_login_regions<C: ConnectionTrait>(
+        db: &C,
+        key: &DashboardKey,
+    ) -> Result<Vec<LoginRegionStat>, AppError> {
+        let rows = LoginRegionRow::find_by_statement(Self::statement(
+            LOGIN_REGION_SQL,
+            vec![
+                key.domain.clone().into(),
+                key.start_time.into(),
+                key.end_time.into(),
+                (key.limit as i64).into(),
+            ],
+        ))
+        .all(db)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| LoginRegionStat {
+                country: row.country,
+                province: row.province,
+                logins: row.logins,
+            })
+            .collect())
+    }
+
+    async fn fetch_endpoint_stats<C: ConnectionTrait>(
+        db: &C,
+        key: &DashboardKey,
+    ) -> Result<Vec<EndpointStat>, AppError> {
+        let rows = EndpointStatRow::find_by_statement(Self::statement(
+            &endpoint_stats_sql(key.rank_by),
+            vec![
+                key.domain.clone().into(),
+                key.start_time.into(),
+                key.end_time.into(),
+                (key.limit as i64).into(),
+            ],
+        ))
+        .all(db)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| EndpointStat {
+                error_rate: ratio(row.errors, row.calls),
+                method: row.method,
+                route: row.route,
+                module_name: row.module_name,
+                description: row.description,
+                calls: row.calls,
+                p95_duration: row.p95_duration,
+                errors: row.errors,
+            })
+            .collect())
+    }
+}
+
+/// 计算比例，总数为 0 时返回 0
+fn ratio(part: i64, total: i64) -> f64 {
+    if total <= 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+const LOGIN_TOTAL_SQL: &str = r#"
+SELECT NULL::text AS date,
+       COUNT(DISTINCT user_id) FILTER (WHERE success) AS active_users,
+       COUNT(*) FILTER (WHERE success) AS logins,
+       COUNT(*) FILTER (WHERE NOT success) AS failures
+FROM sys_login_log
+WHERE domain = $1 AND login_time >= $2 AND login_time < $3
+"#;
+
+const LOGIN_DAILY_SQL: &str = r#"
+SELECT to_char(login_time::date, 'YYYY-MM-DD') AS date,
+       COUNT(DISTINCT user_id) FILTER (WHERE success) AS active_users,
+       COUNT(*) FILTER (WHERE success) AS logins,
+       COUNT(*) FILTER (WHERE NOT success) AS failures
+FROM sys_login_log
+WHERE domain = $1 AND login_time >= $2 AND login_time < $3
+GROUP BY login_time::date
+ORDER BY login_time::date
+"#;
+
+/// 国家未知的登录不参与排行
+const LOGIN_REGION_SQL: &str = r#"
+SELECT country, province, COUNT(*) AS logins
+FROM sys_login_log
+WHERE domain = $1 AND login_time >= $2 AND login_time < $3
+  AND success AND country IS NOT NULL
+GROUP BY country, province
+ORDER BY logins DESC, country, province
+LIMIT $4
+"#;
+
+fn endpoint_stats_sql(rank_by: EndpointRank) -> String {
+    let order_by = match rank_by {
+        EndpointRank::Calls => "calls DESC",
+        EndpointRank::P95Duration => "p95_duration DESC",
+        EndpointRank::Errors => "errors DESC",
+    };
+
+    format!(
+        r#"
+SELECT method, route,
+       MAX(module_name) AS module_name,
+       MAX(description) AS description,
+       COUNT(*) AS calls,
+       COALESCE(percentile_cont(0.95) WITHIN GROUP (ORDER BY duration), 0)::float8 AS p95_duration,
+       COUNT(*) FILTER (WHERE status >= 400) AS errors
+FROM sys_operation_log
+WHERE domain = $1 AND created_at >= $2 AND created_at < $3
+GROUP BY method, route
+ORDER BY {}, method, route
+LIMIT $4
+"#,
+        order_by
+    )
+}
+
+#[async_trait]
+impl TDashboardService for SysDashboardService {
+    async fn get_login_stats(
+        &self,
+        domain: &str,
+        params: DashboardQuery,
+    ) -> Result<LoginStats, AppError> {
+        let key = Self::resolve_key(domain, &params)?;
+        if let Some(stats) = LOGIN_STATS_CACHE.get(&key) {
+            return Ok(stats);
+        }
+
+        let db = db_helper::get_db_connection().await?;
+        let stats = Self::fetch_login_stats(db.as_ref(), &key).await?;
+
+        LOGIN_STATS_CACHE.insert(key, stats.clone());
+        Ok(stats)
+    }
+
+    async fn get_login_regions(
+        &self,
+        domain: &str,
+        params: DashboardQuery,
+    ) -> Result<Vec<LoginRegionStat>, AppError> {
+        let key = Self::resolve_key(domain, &params)?;
+        if let Some(stats) = LOGIN_REGION_CACHE.get(&key) {
+            return Ok(stats);
+        }
+
+        let db = db_helper::get_db_connection().await?;
+        let stats = Self::fetch_login_regions(db.as_ref(), &key).await?;
+
+        LOGIN_REGION_CACHE.insert(key, stats.clone());
+        Ok(stats)
+    }
+
+    async fn get_endpoint_stats(
+        &self,
+        domain: &str,
+        params: DashboardQuery,
+    ) -> Result<Vec<EndpointStat>, AppError> {
+        let key = Self::resolve_key(domain, &params)?;
+        if let Some(stats) = ENDPOINT_STATS_CACHE.get(&key) {
+            return Ok(stats);
+        }
+
+        let db = db_helper::get_db_connection().await?;
+        let stats = Self::fetch_endpoint_stats(db.as_ref(), &key).await?;
+
+        ENDPOINT_STATS_CACHE.insert(key, stats.clone());
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use sea_orm::MockDatabase;
+
+    use super::*;
+
+    #[test]
+    fn test_resolve_key() {
+        let key = SysDashboardService::resolve_key("built-in", &DashboardQuery::default()).unwrap();
+        assert_eq!(key.end_time.second(), 0);
+        assert_eq!(
+            key.end_time - key.start_time,
+            chrono::Duration::days(DEFAULT_WINDOW_DAYS)
+        );
+        assert_eq!(key.limit, DEFAULT_LIMIT);
+
+        let params = DashboardQuery {
+            limit: Some(1_000),
+            ..Default::default()
+        };
+        let key = SysDashboardService::resolve_key("built-in", &params).unwrap();
+        assert_eq!(key.limit, MAX_LIMIT);
+
+        let end_time = Local::now().naive_local();
+        let params = DashboardQuery {
+            start_time: Some(end_time - chrono::Duration::days(MAX_WINDOW_DAYS + 1)),
+            end_time: Some(end_time),
+            ..Default::default()
+        };
+        assert!(SysDashboardService::resolve_key("built-in", &params).is_err());
+    }
+
+    #[test]
+    fn test_ratio() {
+        assert_eq!(ratio(0, 0), 0.0);
+        assert_eq!(ratio(1, 4), 0.25);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_endpoint_stats() {
+        let key = SysDashboardService::resolve_key("tenant", &DashboardQuery::default()).unwrap();
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([vec![BTreeMap::from([
+                ("method", Value::from("POST")),
+                ("route", Value::from("/user/:id")),
+                ("module_name", Value::from("SysUserApi")),
+                ("description", Value::from("创建用户")),
+                ("calls", Value::from(8i64)),
+                ("p95_duration", Value::from(12.5f64)),
+                ("errors", Value::from(2i64)),
+            ])]])
+            .into_connection();
+
+        let stats = SysDashboardService::fetch_endpoint_stats(&db, &key)
+            .await
+            .unwrap();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].route, "/user/:id");
+        assert_eq!(stats[0].errors, 2);
+        assert_eq!(stats[0].error_rate, 0.25);
+
+        let log = db.into_transaction_log();
+        let statement = &log[0].statements()[0];
+        assert!(statement
+            .sql
+            .contains("COUNT(*) FILTER (WHERE status >= 400) AS errors"));
+        assert!(statement.sql.contains("WHERE domain = $1"));
+        assert!(statement.sql.contains("GROUP BY method, route"));
+        assert_eq!(
+            statement.values.as_ref().map(|values| values.0.len()),
+            Some(4)
+        );
+        assert_eq!(
+            statement.values.as_ref().map(|values| values.0[0].clone()),
+            Some(Value::from("tenant"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_login_stats() {
+        let key = SysDashboardService::resolve_key("tenant", &DashboardQuery::default()).unwrap();
+        let row = |date: Option<&str>, logins: i64, failures: i64| {
+            BTreeMap::from([
+                ("date", Value::from(date.map(str::to_string))),
+                ("active_users", Value::from(1i64)),
+                ("logins", Value::from(logins)),
+                ("failures", Value::from(failures)),
+            ])
+        };
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([vec![row(None, 3, 1)]])
+            .append_query_results([vec![
+                row(Some("2026-10-18"), 1, 1),
+                row(Some("2026-10-19"), 2, 0),
+            ]])
+            .into_connection();
+
+        let stats = SysDashboardService::fetch_login_stats(&db, &key)
+            .await
+            .unwrap();
+        assert_eq!(stats.failure_rate, 0.25);
+        assert_eq!(stats.daily.len(), 2);
+        assert_eq!(stats.daily[0].date, "2026-10-18");
+        assert_eq!(stats.daily[0].failure_rate, 0.5);
+        assert_eq!(stats.daily[1].failure_rate, 0.0);
+    }
+}
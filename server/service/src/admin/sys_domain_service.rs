@@ -1,22 +1,90 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
 use async_trait::async_trait;
+use axum_casbin::casbin::MgmtApi;
 use chrono::Local;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, Set,
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, Condition, DatabaseTransaction, EntityTrait,
+    PaginatorTrait, QueryFilter, QuerySelect, Set, TransactionTrait,
+};
+use server_core::web::{
+    auth::User,
+    error::AppError,
+    etag::{check_version, map_version_conflict},
+    page::{PaginatedData, QuerySpec, SortOrder},
 };
-use server_core::web::{error::AppError, page::PaginatedData};
 use server_model::admin::{
     entities::{
-        prelude::SysDomain,
-        sea_orm_active_enums::Status,
+        prelude::{SysDomain, SysRole, SysRoleMenu, SysUser, SysUserRole},
+        sea_orm_active_enums::{RecycleEntityType, Status},
         sys_domain::{
             ActiveModel as SysDomainActiveModel, Column as SysDomainColumn, Model as SysDomainModel,
         },
+        sys_role::{
+            ActiveModel as SysRoleActiveModel, Column as SysRoleColumn, Model as SysRoleModel,
+        },
+        sys_role_menu::{ActiveModel as SysRoleMenuActiveModel, Column as SysRoleMenuColumn},
+        sys_tokens::Column as SysTokensColumn,
+        sys_user::{
+            ActiveModel as SysUserActiveModel, Column as SysUserColumn, Model as SysUserModel,
+        },
+        sys_user_role::{ActiveModel as SysUserRoleActiveModel, Column as SysUserRoleColumn},
+    },
+    input::{
+        CreateDomainInput, DeprovisionDomainInput, DeprovisionMode, DomainPageRequest,
+        ProvisionDomainInput, UpdateDomainInput,
     },
-    input::{CreateDomainInput, DomainPageRequest, UpdateDomainInput},
+    output::{ProvisionDomainOutput, UserWithoutPassword},
 };
+use server_utils::SecureUtil;
+use tokio::sync::RwLock;
 use ulid::Ulid;
 
-use crate::{admin::sys_domain_error::DomainError, helper::db_helper};
+use crate::{
+    admin::{
+        dto::sys_recycle_bin_dto::RecycleSnapshot, sys_auth_service::sync_revoked_tokens,
+        sys_domain_error::DomainError, sys_role_error::RoleError, sys_user_error::UserError,
+        SysRecycleBinService, SysUserService, STATUS_VALUES,
+    },
+    helper::db_helper,
+    project_error,
+};
+
+/// 内置域编码
+pub(crate) const BUILT_IN_DOMAIN: &str = "built-in";
+
+/// 初始管理员一次性密码长度
+const INITIAL_PASSWORD_LENGTH: usize = 16;
+
+/// 仅平台可用的资源，其处理器不区分调用方所在域，开通租户时不复制这些资源上的 `p` 规则
+const PLATFORM_RESOURCES: &[&str] = &["/domain", "/access-key", "/ip-rule", "/recycle-bin"];
+
+/// 开通租户时在事务内写入的数据
+struct TenantSeed {
+    domain: SysDomainActiveModel,
+    roles: Vec<SysRoleActiveModel>,
+    role_menus: Vec<SysRoleMenuActiveModel>,
+    admin_user: SysUserActiveModel,
+    admin_role_id: String,
+}
+
+/// 域列表的查询白名单
+const DOMAIN_QUERY: QuerySpec<SysDomainColumn> = QuerySpec {
+    sort_fields: &[
+        ("createdAt", SysDomainColumn::CreatedAt),
+        ("updatedAt", SysDomainColumn::UpdatedAt),
+        ("code", SysDomainColumn::Code),
+        ("name", SysDomainColumn::Name),
+    ],
+    filter_fields: &[("code", SysDomainColumn::Code)],
+    status: Some((SysDomainColumn::Status, STATUS_VALUES)),
+    created_at: SysDomainColumn::CreatedAt,
+    id: SysDomainColumn::Id,
+    default_sort: (SysDomainColumn::CreatedAt, SortOrder::Desc),
+};
 
 #[async_trait]
 pub trait TDomainService {
@@ -28,7 +96,29 @@ pub trait TDomainService {
     async fn create_domain(&self, input: CreateDomainInput) -> Result<SysDomainModel, AppError>;
     async fn get_domain(&self, id: &str) -> Result<SysDomainModel, AppError>;
     async fn update_domain(&self, input: UpdateDomainInput) -> Result<SysDomainModel, AppError>;
-    async fn delete_domain(&self, id: &str) -> Result<(), AppError>;
+    /// 软删除域并移入回收站，撤销域内令牌；彻底删除时才级联清理域内数据和 `p` 规则
+    async fn delete_domain(&self, id: &str, user: User) -> Result<(), AppError>;
+
+    /// 以模板域为蓝本开通租户
+    ///
+    /// 复制模板域的角色、角色菜单绑定和 Casbin `p` 规则（域替换为新域），
+    /// 并创建初始管理员，返回其一次性密码。域、访问密钥、IP 规则和回收站只供平台管理，
+    /// 这些资源上的规则不会复制给租户。
+    async fn provision_domain(
+        &self,
+        input: ProvisionDomainInput,
+        user: User,
+        enforcer: Arc<RwLock<impl MgmtApi>>,
+    ) -> Result<ProvisionDomainOutput, AppError>;
+
+    /// 注销租户，按 `mode` 归档或删除租户数据
+    async fn deprovision_domain(
+        &self,
+        id: &str,
+        input: DeprovisionDomainInput,
+        user: User,
+        enforcer: Arc<RwLock<impl MgmtApi>>,
+    ) -> Result<(), AppError>;
 }
 
 #[derive(Clone)]
@@ -70,6 +160,294 @@ impl SysDomainService {
 
         Ok(())
     }
+
+    /// 租户角色编码，角色编码全局唯一，因此以域编码作为后缀区分
+    fn tenant_role_code(role_code: &str, domain_code: &str) -> String {
+        format!(
+            "{}_{}",
+            role_code,
+            domain_code.to_uppercase().replace('-', "_")
+        )
+    }
+
+    /// 是否为仅平台可用的资源
+    fn is_platform_resource(resource: &str) -> bool {
+        PLATFORM_RESOURCES.iter().any(|prefix| {
+            resource
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+
+    /// 将模板域的 `p` 规则改写为租户规则，跳过模板外角色和仅平台可用的资源
+    fn tenant_policies(
+        template_policies: &[Vec<String>],
+        role_codes: &HashMap<String, String>,
+        domain_code: &str,
+    ) -> Vec<Vec<String>> {
+        template_policies
+            .iter()
+            .filter(|policy| {
+                !policy
+                    .get(2)
+                    .is_some_and(|resource| Self::is_platform_resource(resource))
+            })
+            .filter_map(|policy| {
+                let mut rule = policy.clone();
+                rule[0] = role_codes.get(&policy[0])?.clone();
+                rule[1] = domain_code.to_string();
+                Some(rule)
+            })
+            .collect()
+    }
+
+    /// 查找模板域使用的角色：拥有该域菜单绑定或 `p` 规则的角色
+    async fn find_domain_roles(
+        &self,
+        domain_code: &str,
+        policies: &[Vec<String>],
+    ) -> Result<Vec<SysRoleModel>, AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        let role_ids: Vec<String> = SysRoleMenu::find()
+            .select_only()
+            .column(SysRoleMenuColumn::RoleId)
+            .distinct()
+            .filter(SysRoleMenuColumn::Domain.eq(domain_code))
+            .into_tuple::<String>()
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        let role_codes: HashSet<&str> = policies.iter().map(|policy| policy[0].as_str()).collect();
+
+        SysRole::find()
+            .filter(
+                Condition::any()
+                    .add(SysRoleColumn::Id.is_in(role_ids))
+                    .add(SysRoleColumn::Code.is_in(role_codes)),
+            )
+            .filter(SysRoleColumn::DeletedAt.is_null())
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)
+    }
+
+    async fn provision_domain_in_transaction(
+        &self,
+        txn: &DatabaseTransaction,
+        seed: TenantSeed,
+    ) -> Result<(SysDomainModel, Vec<SysRoleModel>, SysUserModel), AppError> {
+        let domain = seed.domain.insert(txn).await.map_err(AppError::from)?;
+
+        let mut roles = Vec::with_capacity(seed.roles.len());
+        for role in seed.roles {
+            roles.push(role.insert(txn).await.map_err(AppError::from)?);
+        }
+
+        if !seed.role_menus.is_empty() {
+            SysRoleMenu::insert_many(seed.role_menus)
+                .exec(txn)
+                .await
+                .map_err(AppError::from)?;
+        }
+
+        let admin_user = seed.admin_user.insert(txn).await.map_err(AppError::from)?;
+
+        SysUserRoleActiveModel {
+            user_id: Set(admin_user.id.clone()),
+            role_id: Set(seed.admin_role_id),
+        }
+        .insert(txn)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok((domain, roles, admin_user))
+    }
+
+    /// 归档租户：停用域及域内用户并撤销其令牌，保留角色、菜单绑定和权限策略以便恢复
+    async fn archive_domain(&self, domain: SysDomainModel, user: User) -> Result<(), AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let txn = db.begin().await.map_err(AppError::from)?;
+
+        let result = async {
+            SysUser::update_many()
+                .set(SysUserActiveModel {
+                    status: Set(Status::Disabled),
+                    updated_at: Set(Some(Local::now().naive_local())),
+                    updated_by: Set(Some(user.user_id())),
+                    ..Default::default()
+                })
+                .col_expr(
+                    SysUserColumn::Version,
+                    Expr::col(SysUserColumn::Version).add(1),
+                )
+                .filter(SysUserColumn::Domain.eq(domain.code.as_str()))
+                .exec(&txn)
+                .await
+                .map_err(AppError::from)?;
+
+            SysUserService::revoke_tokens(
+                &txn,
+                Condition::all().add(SysTokensColumn::Domain.eq(domain.code.as_str())),
+            )
+            .await?;
+
+            let mut domain: SysDomainActiveModel = domain.into();
+            domain.status = Set(Status::Disabled);
+            domain.updated_at = Set(Some(Local::now().naive_local()));
+            domain.updated_by = Set(Some(user.user_id()));
+            domain.version = Set(domain.version.as_ref() + 1);
+            domain.update(&txn).await.map_err(AppError::from)?;
+
+            Ok::<(), AppError>(())
+        }
+        .await;
+
+        match result {
+            Ok(_) => {
+                txn.commit().await.map_err(AppError::from)?;
+                sync_revoked_tokens().await;
+                Ok(())
+            },
+            Err(e) => {
+                txn.rollback().await.map_err(AppError::from)?;
+                Err(e)
+            },
+        }
+    }
+
+    /// 删除租户：删除域、域内用户及其角色绑定、菜单绑定、仅被该域使用的角色和全部 `p` 规则
+    pub(crate) async fn purge_domain(
+        &self,
+        domain: SysDomainModel,
+        enforcer: Arc<RwLock<impl MgmtApi>>,
+    ) -> Result<(), AppError> {
+        let (domain_policies, other_role_codes) = {
+            let enforcer_read = enforcer.read().await;
+            let domain_policies = enforcer_read.get_filtered_policy(1, vec![domain.code.clone()]);
+            let other_role_codes: HashSet<String> = enforcer_read
+                .get_policy()
+                .into_iter()
+                .filter(|policy| policy[1] != domain.code)
+                .map(|policy| policy[0].clone())
+                .collect();
+            (domain_policies, other_role_codes)
+        };
+
+        let db = db_helper::get_db_connection().await?;
+
+        let shared_role_ids: HashSet<String> = SysRoleMenu::find()
+            .select_only()
+            .column(SysRoleMenuColumn::RoleId)
+            .distinct()
+            .filter(SysRoleMenuColumn::Domain.ne(domain.code.as_str()))
+            .into_tuple::<String>()
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .into_iter()
+            .collect();
+
+        let role_ids: Vec<String> = self
+            .find_domain_roles(&domain.code, &domain_policies)
+            .await?
+            .into_iter()
+            .filter(|role| {
+                !shared_role_ids.contains(&role.id) && !other_role_codes.contains(&role.code)
+            })
+            .map(|role| role.id)
+            .collect();
+
+        let user_ids: Vec<String> = SysUser::find()
+            .select_only()
+            .column(SysUserColumn::Id)
+            .filter(SysUserColumn::Domain.eq(domain.code.as_str()))
+            .into_tuple::<String>()
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        let txn = db.begin().await.map_err(AppError::from)?;
+
+        let result = async {
+            SysUserRole::delete_many()
+                .filter(
+                    Condition::any()
+                        .add(SysUserRoleColumn::UserId.is_in(user_ids))
+                        .add(SysUserRoleColumn::RoleId.is_in(role_ids.clone())),
+                )
+                .exec(&txn)
+                .await
+                .map_err(AppError::from)?;
+
+            SysRoleMenu::delete_many()
+                .filter(SysRoleMenuColumn::Domain.eq(domain.code.as_str()))
+                .exec(&txn)
+                .await
+                .map_err(AppError::from)?;
+
+            SysRole::delete_many()
+                .filter(SysRoleColumn::Id.is_in(role_ids))
+                .exec(&txn)
+                .await
+                .map_err(AppError::from)?;
+
+            SysUserService::revoke_tokens(
+                &txn,
+                Condition::all().add(SysTokensColumn::Domain.eq(domain.code.as_str())),
+            )
+            .await?;
+
+            SysUser::delete_many()
+                .filter(SysUserColumn::Domain.eq(domain.code.as_str()))
+                .exec(&txn)
+                .await
+                .map_err(AppError::from)?;
+
+            SysDomain::delete_by_id(domain.id.as_str())
+                .exec(&txn)
+                .await
+                .map_err(AppError::from)?;
+
+            Ok::<(), AppError>(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            txn.rollback().await.map_err(AppError::from)?;
+            return Err(e);
+        }
+
+        if !domain_policies.is_empty() {
+            let removed = enforcer
+                .write()
+                .await
+                .remove_filtered_policy(1, vec![domain.code.clone()])
+                .await;
+            if let Err(e) = removed {
+                txn.rollback().await.map_err(AppError::from)?;
+                return Err(DomainError::PolicyError(e.to_string()).into());
+            }
+        }
+
+        if let Err(e) = txn.commit().await {
+            // 数据库提交失败，恢复已删除的策略
+            if !domain_policies.is_empty() {
+                if let Err(err) = enforcer.write().await.add_policies(domain_policies).await {
+                    project_error!(
+                        "Failed to restore policies of domain {}: {:?}",
+                        domain.code,
+                        err
+                    );
+                }
+            }
+            return Err(AppError::from(e));
+        }
+
+        sync_revoked_tokens().await;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -79,13 +457,15 @@ impl TDomainService for SysDomainService {
         params: DomainPageRequest,
     ) -> Result<PaginatedData<SysDomainModel>, AppError> {
         let db = db_helper::get_db_connection().await?;
-        let mut query = SysDomain::find();
+        let mut query = SysDomain::find().filter(SysDomainColumn::DeletedAt.is_null());
 
         if let Some(ref keywords) = params.keywords {
             let condition = Condition::any().add(SysDomainColumn::Name.contains(keywords));
             query = query.filter(condition);
         }
 
+        let query = DOMAIN_QUERY.apply(query, &params.query)?;
+
         let total = query
             .clone()
             .count(db.as_ref())
@@ -130,6 +510,7 @@ impl TDomainService for SysDomainService {
     async fn get_domain(&self, id: &str) -> Result<SysDomainModel, AppError> {
         let db = db_helper::get_db_connection().await?;
         SysDomain::find_by_id(id)
+            .filter(SysDomainColumn::DeletedAt.is_null())
             .one(db.as_ref())
             .await
             .map_err(AppError::from)?
@@ -140,10 +521,12 @@ impl TDomainService for SysDomainService {
         let db = db_helper::get_db_connection().await?;
         let existing_domain = self.get_domain(&input.id).await?;
 
-        if existing_domain.code == "built-in" {
+        if existing_domain.code == BUILT_IN_DOMAIN {
             return Err(DomainError::BuiltInDomain.into());
         }
 
+        let version = check_version(existing_domain.version, input.version)?;
+
         self.check_domain_exists(Some(&input.id), &input.domain.code, &input.domain.name)
             .await?;
 
@@ -151,23 +534,322 @@ impl TDomainService for SysDomainService {
         domain.code = Set(input.domain.code);
         domain.name = Set(input.domain.name);
         domain.description = Set(input.domain.description);
+        domain.version = Set(version + 1);
 
-        let updated_domain = domain.update(db.as_ref()).await.map_err(AppError::from)?;
+        let updated_domain = SysDomain::update(domain)
+            .filter(SysDomainColumn::Version.eq(version))
+            .exec(db.as_ref())
+            .await
+            .map_err(map_version_conflict)?;
         Ok(updated_domain)
     }
 
-    async fn delete_domain(&self, id: &str) -> Result<(), AppError> {
+    async fn delete_domain(&self, id: &str, user: User) -> Result<(), AppError> {
         let domain = self.get_domain(id).await?;
 
-        if domain.code == "built-in" {
+        if domain.code == BUILT_IN_DOMAIN {
             return Err(DomainError::BuiltInDomain.into());
         }
 
         let db = db_helper::get_db_connection().await?;
-        SysDomain::delete_by_id(id)
-            .exec(db.as_ref())
+        let txn = db.begin().await.map_err(AppError::from)?;
+
+        let result = async {
+            SysUserService::revoke_tokens(
+                &txn,
+                Condition::all().add(SysTokensColumn::Domain.eq(domain.code.as_str())),
+            )
+            .await?;
+
+            let mut deleted_domain: SysDomainActiveModel = domain.clone().into();
+            deleted_domain.deleted_at = Set(Some(Local::now().naive_local()));
+            deleted_domain.deleted_by = Set(Some(user.user_id()));
+            deleted_domain.version = Set(deleted_domain.version.as_ref() + 1);
+            deleted_domain.update(&txn).await.map_err(AppError::from)?;
+
+            SysRecycleBinService::record(
+                &txn,
+                RecycleEntityType::Domain,
+                &domain.id,
+                &domain.name,
+                &RecycleSnapshot::default(),
+                &user.domain(),
+                &user,
+            )
+            .await?;
+
+            Ok::<(), AppError>(())
+        }
+        .await;
+
+        match result {
+            Ok(_) => {
+                txn.commit().await.map_err(AppError::from)?;
+                sync_revoked_tokens().await;
+                Ok(())
+            },
+            Err(e) => {
+                txn.rollback().await.map_err(AppError::from)?;
+                Err(e)
+            },
+        }
+    }
+
+    async fn provision_domain(
+        &self,
+        input: ProvisionDomainInput,
+        user: User,
+        enforcer: Arc<RwLock<impl MgmtApi>>,
+    ) -> Result<ProvisionDomainOutput, AppError> {
+        let domain_input = input.domain;
+        self.check_domain_exists(None, &domain_input.code, &domain_input.name)
+            .await?;
+
+        let db = db_helper::get_db_connection().await?;
+
+        let template_code = input
+            .template_domain
+            .unwrap_or_else(|| BUILT_IN_DOMAIN.to_string());
+        let template = SysDomain::find()
+            .filter(SysDomainColumn::Code.eq(template_code.as_str()))
+            .filter(SysDomainColumn::DeletedAt.is_null())
+            .one(db.as_ref())
             .await
-            .map_err(AppError::from)?;
-        Ok(())
+            .map_err(AppError::from)?
+            .ok_or(DomainError::TemplateDomainNotFound)?;
+
+        let username_exists = SysUser::find()
+            .filter(SysUserColumn::Username.eq(input.admin_username.as_str()))
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .is_some();
+        if username_exists {
+            return Err(UserError::UsernameAlreadyExists.into());
+        }
+
+        let template_policies = enforcer
+            .read()
+            .await
+            .get_filtered_policy(1, vec![template.code.clone()]);
+        let template_roles = self
+            .find_domain_roles(&template.code, &template_policies)
+            .await?;
+
+        let now = Local::now().naive_local();
+        let role_ids: HashMap<String, String> = template_roles
+            .iter()
+            .map(|role| (role.id.clone(), Ulid::new().to_string()))
+            .collect();
+        let role_codes: HashMap<String, String> = template_roles
+            .iter()
+            .map(|role| {
+                (
+                    role.code.clone(),
+                    Self::tenant_role_code(&role.code, &domain_input.code),
+                )
+            })
+            .collect();
+
+        let admin_role_id = template_roles
+            .iter()
+            .find(|role| role.code == input.admin_role_code)
+            .map(|role| role_ids[&role.id].clone())
+            .ok_or(DomainError::TemplateRoleNotFound)?;
+
+        let code_conflict = SysRole::find()
+            .filter(SysRoleColumn::Code.is_in(role_codes.values().cloned()))
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .is_some();
+        if code_conflict {
+            return Err(RoleError::DuplicateRoleCode.into());
+        }
+
+        let roles = template_roles
+            .iter()
+            .map(|role| SysRoleActiveModel {
+                id: Set(role_ids[&role.id].clone()),
+                code: Set(role_codes[&role.code].clone()),
+                name: Set(role.name.clone()),
+                description: Set(role.description.clone()),
+                pid: Set(role_ids
+                    .get(&role.pid)
+                    .cloned()
+                    .unwrap_or_else(|| role.pid.clone())),
+                status: Set(role.status.clone()),
+                created_at: Set(now),
+                created_by: Set(user.user_id()),
+                ..Default::default()
+            })
+            .collect();
+
+        let role_menus = SysRoleMenu::find()
+            .filter(SysRoleMenuColumn::Domain.eq(template.code.as_str()))
+            .filter(SysRoleMenuColumn::RoleId.is_in(role_ids.keys().cloned()))
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .into_iter()
+            .map(|role_menu| SysRoleMenuActiveModel {
+                role_id: Set(role_ids[&role_menu.role_id].clone()),
+                menu_id: Set(role_menu.menu_id),
+                domain: Set(domain_input.code.clone()),
+            })
+            .collect();
+
+        let policies = Self::tenant_policies(&template_policies, &role_codes, &domain_input.code);
+
+        let admin_password = SecureUtil::generate_password(INITIAL_PASSWORD_LENGTH);
+        let password_hash = SecureUtil::hash_password(admin_password.as_bytes())
+            .map_err(|_| AppError::from(UserError::AuthenticationFailed))?;
+
+        let seed = TenantSeed {
+            domain: SysDomainActiveModel {
+                id: Set(Ulid::new().to_string()),
+                code: Set(domain_input.code.clone()),
+                name: Set(domain_input.name),
+                description: Set(domain_input.description),
+                status: Set(Status::Enabled),
+                created_at: Set(now),
+                created_by: Set(user.user_id()),
+                ..Default::default()
+            },
+            roles,
+            role_menus,
+            admin_user: SysUserActiveModel {
+                id: Set(Ulid::new().to_string()),
+                domain: Set(domain_input.code.clone()),
+                username: Set(input.admin_username),
+                password: Set(password_hash),
+                built_in: Set(false),
+                nick_name: Set(input.admin_nick_name),
+                // 初始密码以明文返回给调用方，首次登录前必须修改
+                must_change_password: Set(true),
+                status: Set(Status::Enabled),
+                created_at: Set(now),
+                created_by: Set(user.user_id()),
+                ..Default::default()
+            },
+            admin_role_id,
+        };
+
+        let txn = db.begin().await.map_err(AppError::from)?;
+
+        let (domain, roles, admin_user) =
+            match self.provision_domain_in_transaction(&txn, seed).await {
+                Ok(provisioned) => provisioned,
+                Err(e) => {
+                    txn.rollback().await.map_err(AppError::from)?;
+                    return Err(e);
+                },
+            };
+
+        if !policies.is_empty() {
+            match enforcer.write().await.add_policies(policies.clone()).await {
+                Ok(true) => {},
+                Ok(false) => {
+                    txn.rollback().await.map_err(AppError::from)?;
+                    return Err(DomainError::PolicyError(format!(
+                        "policies of domain {} already exist",
+                        domain.code
+                    ))
+                    .into());
+                },
+                Err(e) => {
+                    txn.rollback().await.map_err(AppError::from)?;
+                    return Err(DomainError::PolicyError(e.to_string()).into());
+                },
+            }
+        }
+
+        if let Err(e) = txn.commit().await {
+            // 数据库提交失败，撤销已写入 Casbin 的策略
+            if !policies.is_empty() {
+                if let Err(err) = enforcer.write().await.remove_policies(policies).await {
+                    project_error!(
+                        "Failed to revoke policies of domain {}: {:?}",
+                        domain.code,
+                        err
+                    );
+                }
+            }
+            return Err(AppError::from(e));
+        }
+
+        Ok(ProvisionDomainOutput {
+            domain,
+            roles,
+            admin_user: UserWithoutPassword::from(admin_user),
+            admin_password,
+        })
+    }
+
+    async fn deprovision_domain(
+        &self,
+        id: &str,
+        input: DeprovisionDomainInput,
+        user: User,
+        enforcer: Arc<RwLock<impl MgmtApi>>,
+    ) -> Result<(), AppError> {
+        let domain = self.get_domain(id).await?;
+
+        if domain.code == BUILT_IN_DOMAIN {
+            return Err(DomainError::BuiltInDomain.into());
+        }
+
+        match input.mode {
+            DeprovisionMode::Archive => self.archive_domain(domain, user).await,
+            DeprovisionMode::Delete => self.purge_domain(domain, enforcer).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(role: &str, domain: &str, resource: &str, action: &str) -> Vec<String> {
+        [role, domain, resource, action]
+            .iter()
+            .map(|value| value.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_tenant_policies_skip_platform_resources() {
+        let template = vec![
+            rule("ROLE_SUPER", BUILT_IN_DOMAIN, "/domain", "GET"),
+            rule("ROLE_SUPER", BUILT_IN_DOMAIN, "/domain/:id", "DELETE"),
+            rule(
+                "ROLE_SUPER",
+                BUILT_IN_DOMAIN,
+                "/access-key/:id/rotate",
+                "PUT",
+            ),
+            rule("ROLE_SUPER", BUILT_IN_DOMAIN, "/ip-rule", "POST"),
+            rule("ROLE_SUPER", BUILT_IN_DOMAIN, "/recycle-bin", "GET"),
+            rule("ROLE_SUPER", BUILT_IN_DOMAIN, "/user", "GET"),
+            rule("ROLE_SUPER", BUILT_IN_DOMAIN, "/domains-report", "GET"),
+            rule("ROLE_OTHER", BUILT_IN_DOMAIN, "/user", "GET"),
+        ];
+        let role_codes = HashMap::from([(
+            "ROLE_SUPER".to_string(),
+            SysDomainService::tenant_role_code("ROLE_SUPER", "acme"),
+        )]);
+
+        let policies = SysDomainService::tenant_policies(&template, &role_codes, "acme");
+
+        assert_eq!(
+            policies,
+            vec![
+                rule("ROLE_SUPER_ACME", "acme", "/user", "GET"),
+                rule("ROLE_SUPER_ACME", "acme", "/domains-report", "GET"),
+            ]
+        );
+        assert!(!policies
+            .iter()
+            .any(|policy| policy[2].starts_with("/domain/") || policy[2] == "/domain"));
     }
 }
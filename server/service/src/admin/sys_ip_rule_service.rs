@@ -0,0 +1,409 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Local;
+use ipnet::IpNet;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, Select, Set,
+};
+use server_core::web::{
+    auth::User,
+    error::AppError,
+    ip_filter::{self, IpRule},
+    page::{PaginatedData, QuerySpec, SortOrder},
+    util::parse_ip_net,
+};
+use server_model::admin::{
+    entities::{
+        prelude::SysIpRule,
+        sea_orm_active_enums::{IpRuleAction, IpRuleScope, Status},
+        sys_ip_rule::{
+            ActiveModel as SysIpRuleActiveModel, Column as SysIpRuleColumn, Model as SysIpRuleModel,
+        },
+    },
+    input::{CreateIpRuleInput, IpRulePageRequest, UpdateIpRuleInput},
+};
+use tracing::instrument;
+use ulid::Ulid;
+
+use crate::{
+    helper::{cache_sync, db_helper},
+    project_error, project_warn,
+};
+
+use super::{sys_domain_service::BUILT_IN_DOMAIN, sys_ip_rule_error::IpRuleError, STATUS_VALUES};
+
+/// IP 规则变更的通知频道
+const IP_RULE_CHANGED_CHANNEL: &str = "ip_rule:changed";
+
+/// 全量重新加载 IP 规则的间隔（秒）
+const IP_RULE_RECONCILE_INTERVAL_SECS: u64 = 60;
+
+/// IP 规则列表的查询白名单
+const IP_RULE_QUERY: QuerySpec<SysIpRuleColumn> = QuerySpec {
+    sort_fields: &[
+        ("createdAt", SysIpRuleColumn::CreatedAt),
+        ("scope", SysIpRuleColumn::Scope),
+        ("action", SysIpRuleColumn::Action),
+    ],
+    filter_fields: &[
+        ("scope", SysIpRuleColumn::Scope),
+        ("scopeValue", SysIpRuleColumn::ScopeValue),
+        ("action", SysIpRuleColumn::Action),
+        ("country", SysIpRuleColumn::Country),
+        ("province", SysIpRuleColumn::Province),
+    ],
+    status: Some((SysIpRuleColumn::Status, STATUS_VALUES)),
+    created_at: SysIpRuleColumn::CreatedAt,
+    id: SysIpRuleColumn::Id,
+    default_sort: (SysIpRuleColumn::CreatedAt, SortOrder::Desc),
+};
+
+#[async_trait]
+pub trait TIpRuleService {
+    async fn find_paginated_ip_rules(
+        &self,
+        params: IpRulePageRequest,
+        domain: &str,
+    ) -> Result<PaginatedData<SysIpRuleModel>, AppError>;
+    async fn create_ip_rule(
+        &self,
+        input: CreateIpRuleInput,
+        user: User,
+    ) -> Result<SysIpRuleModel, AppError>;
+    async fn update_ip_rule(
+        &self,
+        input: UpdateIpRuleInput,
+        user: User,
+    ) -> Result<SysIpRuleModel, AppError>;
+    async fn delete_ip_rule(&self, id: &str, domain: &str) -> Result<(), AppError>;
+
+    /// 从数据库重新加载启用的规则到中间件缓存
+    async fn reload_ip_rules(&self) -> Result<(), AppError>;
+}
+
+#[derive(Clone)]
+pub struct SysIpRuleService;
+
+impl SysIpRuleService {
+    /// 校验并规范化规则条件
+    ///
+    /// 全局规则忽略范围值；单个 IP 地址按主机网段保存。
+    fn normalize_input(input: &mut CreateIpRuleInput) -> Result<(), AppError> {
+        match input.scope {
+            IpRuleScope::Global => input.scope_value = None,
+            IpRuleScope::Domain | IpRuleScope::AccessKey if input.scope_value.is_none() => {
+                return Err(IpRuleError::MissingScopeValue.into());
+            },
+            _ => {},
+        }
+
+        if input.cidr.is_none() && input.country.is_none() && input.province.is_none() {
+            return Err(IpRuleError::EmptyCondition.into());
+        }
+
+        if let Some(cidr) = input.cidr.as_deref() {
+            input.cidr = Some(parse_network(cidr)?.to_string());
+        }
+
+        Ok(())
+    }
+
+    async fn get_ip_rule(&self, id: &str, domain: &str) -> Result<SysIpRuleModel, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        scoped_query(domain)
+            .filter(SysIpRuleColumn::Id.eq(id))
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .ok_or_else(|| IpRuleError::IpRuleNotFound.into())
+    }
+}
+
+/// IP 规则的管理范围：内置域可管理全部规则，其他域只能管理本域的域级规则
+fn domain_scope(domain: &str) -> Condition {
+    if domain == BUILT_IN_DOMAIN {
+        Condition::all()
+    } else {
+        Condition::all()
+            .add(SysIpRuleColumn::Scope.eq(IpRuleScope::Domain))
+            .add(SysIpRuleColumn::ScopeValue.eq(domain))
+    }
+}
+
+fn scoped_query(domain: &str) -> Select<SysIpRule> {
+    SysIpRule::find().filter(domain_scope(domain))
+}
+
+/// 非内置域只能创建本域的域级规则，忽略提交的范围
+fn restrict_to_domain(input: &mut CreateIpRuleInput, domain: &str) {
+    if domain != BUILT_IN_DOMAIN {
+        input.scope = IpRuleScope::Domain;
+        input.scope_value = Some(domain.to_string());
+    }
+}
+
+/// 规则变更提交后调用，重新加载本实例的规则并通知其他实例
+async fn sync_ip_rules() -> Result<(), AppError> {
+    let result = SysIpRuleService.reload_ip_rules().await;
+    cache_sync::publish(IP_RULE_CHANGED_CHANNEL, &()).await;
+    result
+}
+
+async fn reload_ip_rules_logged() {
+    if let Err(e) = SysIpRuleService.reload_ip_rules().await {
+        project_error!("Failed to reload IP rules: {}", e.message);
+    }
+}
+
+/// 接收其他实例的 IP 规则变更通知并定期重新加载，不会返回
+pub async fn ip_rule_subscriber() {
+    cache_sync::run(
+        IP_RULE_CHANGED_CHANNEL,
+        Duration::from_secs(IP_RULE_RECONCILE_INTERVAL_SECS),
+        |()| reload_ip_rules_logged(),
+        reload_ip_rules_logged,
+    )
+    .await;
+}
+
+/// 解析网段，单个地址视为主机网段
+fn parse_network(cidr: &str) -> Result<IpNet, IpRuleError> {
+    parse_ip_net(cidr).map_err(|_| IpRuleError::InvalidCidr(cidr.trim().to_string()))
+}
+
+/// 将数据库中的规则转换为中间件使用的规则，无法解析的规则会被跳过
+fn to_ip_rule(model: SysIpRuleModel) -> Option<IpRule> {
+    let scope = match (model.scope, model.scope_value) {
+        (IpRuleScope::Global, _) => ip_filter::IpRuleScope::Global,
+        (IpRuleScope::Domain, Some(value)) => ip_filter::IpRuleScope::Domain(value),
+        (IpRuleScope::AccessKey, Some(value)) => ip_filter::IpRuleScope::AccessKey(value),
+        _ => return None,
+    };
+    let network = match model.cidr.as_deref() {
+        Some(cidr) => Some(parse_network(cidr).ok()?),
+        None => None,
+    };
+
+    Some(IpRule {
+        id: model.id,
+        scope,
+        action: match model.action {
+            IpRuleAction::Allow => ip_filter::IpRuleAction::Allow,
+            IpRuleAction::Deny => ip_filter::IpRuleAction::Deny,
+        },
+        network,
+        country: model.country,
+        province: model.province,
+    })
+}
+
+#[async_trait]
+impl TIpRuleService for SysIpRuleService {
+    async fn find_paginated_ip_rules(
+        &self,
+        params: IpRulePageRequest,
+        domain: &str,
+    ) -> Result<PaginatedData<SysIpRuleModel>, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let mut query = scoped_query(domain);
+
+        if let Some(ref keywords) = params.keywords {
+            let condition = Condition::any()
+                .add(SysIpRuleColumn::Cidr.contains(keywords))
+                .add(SysIpRuleColumn::ScopeValue.contains(keywords))
+                .add(SysIpRuleColumn::Description.contains(keywords));
+            query = query.filter(condition);
+        }
+
+        let query = IP_RULE_QUERY.apply(query, &params.query)?;
+
+        let total = query
+            .clone()
+            .count(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        let paginator = query.paginate(db.as_ref(), params.page_details.size);
+        let records = paginator
+            .fetch_page(params.page_details.current - 1)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(PaginatedData {
+            current: params.page_details.current,
+            size: params.page_details.size,
+            total,
+            records,
+        })
+    }
+
+    async fn create_ip_rule(
+        &self,
+        mut input: CreateIpRuleInput,
+        user: User,
+    ) -> Result<SysIpRuleModel, AppError> {
+        restrict_to_domain(&mut input, &user.domain());
+        Self::normalize_input(&mut input)?;
+
+        let db = db_helper::get_db_connection().await?;
+
+        let rule = SysIpRuleActiveModel {
+            id: Set(Ulid::new().to_string()),
+            scope: Set(input.scope),
+            scope_value: Set(input.scope_value),
+            action: Set(input.action),
+            cidr: Set(input.cidr),
+            country: Set(input.country),
+            province: Set(input.province),
+            status: Set(input.status),
+            description: Set(input.description),
+            created_at: Set(Local::now().naive_local()),
+            created_by: Set(user.user_id()),
+            ..Default::default()
+        };
+
+        let result = rule.insert(db.as_ref()).await.map_err(AppError::from)?;
+        sync_ip_rules().await?;
+        Ok(result)
+    }
+
+    async fn update_ip_rule(
+        &self,
+        mut input: UpdateIpRuleInput,
+        user: User,
+    ) -> Result<SysIpRuleModel, AppError> {
+        let domain = user.domain();
+        let existing_rule = self.get_ip_rule(&input.id, &domain).await?;
+        restrict_to_domain(&mut input.rule, &domain);
+        Self::normalize_input(&mut input.rule)?;
+
+        let db = db_helper::get_db_connection().await?;
+
+        let mut rule: SysIpRuleActiveModel = existing_rule.into();
+        rule.scope = Set(input.rule.scope);
+        rule.scope_value = Set(input.rule.scope_value);
+        rule.action = Set(input.rule.action);
+        rule.cidr = Set(input.rule.cidr);
+        rule.country = Set(input.rule.country);
+        rule.province = Set(input.rule.province);
+        rule.status = Set(input.rule.status);
+        rule.description = Set(input.rule.description);
+        rule.updated_at = Set(Some(Local::now().naive_local()));
+        rule.updated_by = Set(Some(user.user_id()));
+
+        let result = rule.update(db.as_ref()).await.map_err(AppError::from)?;
+        sync_ip_rules().await?;
+        Ok(result)
+    }
+
+    async fn delete_ip_rule(&self, id: &str, domain: &str) -> Result<(), AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        let result = SysIpRule::delete_many()
+            .filter(SysIpRuleColumn::Id.eq(id))
+            .filter(domain_scope(domain))
+            .exec(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+        if result.rows_affected == 0 {
+            return Err(IpRuleError::IpRuleNotFound.into());
+        }
+
+        sync_ip_rules().await
+    }
+
+    #[instrument(skip(self))]
+    async fn reload_ip_rules(&self) -> Result<(), AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        let rules = SysIpRule::find()
+            .filter(SysIpRuleColumn::Status.eq(Status::Enabled))
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        let total = rules.len();
+        let rules: Vec<IpRule> = rules.into_iter().filter_map(to_ip_rule).collect();
+        if rules.len() < total {
+            project_warn!("Skipped {} invalid IP rules", total - rules.len());
+        }
+
+        ip_filter::set_ip_rules(rules);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{DbBackend, QueryTrait};
+    use server_model::admin::entities::sea_orm_active_enums::IpRuleAction;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_network() {
+        assert_eq!(
+            parse_network("10.1.2.3/8").unwrap().to_string(),
+            "10.0.0.0/8"
+        );
+        assert_eq!(
+            parse_network(" 192.168.1.1 ").unwrap().to_string(),
+            "192.168.1.1/32"
+        );
+        assert_eq!(
+            parse_network("2001:db8::1").unwrap().to_string(),
+            "2001:db8::1/128"
+        );
+        assert!(matches!(
+            parse_network("10.0.0.0/33"),
+            Err(IpRuleError::InvalidCidr(_))
+        ));
+        assert!(parse_network("example.com").is_err());
+    }
+
+    fn rule_input(scope: IpRuleScope, scope_value: Option<&str>) -> CreateIpRuleInput {
+        CreateIpRuleInput {
+            scope,
+            scope_value: scope_value.map(str::to_string),
+            action: IpRuleAction::Deny,
+            cidr: Some("10.0.0.0/8".to_string()),
+            country: None,
+            province: None,
+            status: Status::Enabled,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_restrict_to_domain() {
+        let mut input = rule_input(IpRuleScope::Global, None);
+        restrict_to_domain(&mut input, "tenant");
+        assert_eq!(input.scope, IpRuleScope::Domain);
+        assert_eq!(input.scope_value.as_deref(), Some("tenant"));
+
+        let mut input = rule_input(IpRuleScope::Domain, Some("other"));
+        restrict_to_domain(&mut input, "tenant");
+        assert_eq!(input.scope_value.as_deref(), Some("tenant"));
+
+        let mut input = rule_input(IpRuleScope::AccessKey, Some("key"));
+        restrict_to_domain(&mut input, BUILT_IN_DOMAIN);
+        assert_eq!(input.scope, IpRuleScope::AccessKey);
+        assert_eq!(input.scope_value.as_deref(), Some("key"));
+    }
+
+    #[test]
+    fn test_scoped_query() {
+        let sql = scoped_query("tenant")
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.ends_with(
+            r#"WHERE "sys_ip_rule"."scope" = 'domain' AND "sys_ip_rule"."scope_value" = 'tenant'"#
+        ));
+
+        let sql = scoped_query(BUILT_IN_DOMAIN)
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(!sql.contains(r#""scope_value" ="#));
+    }
+}
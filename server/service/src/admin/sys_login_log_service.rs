@@ -1,22 +1,67 @@
 use async_trait::async_trait;
-use sea_orm::{ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder};
-use server_core::web::{error::AppError, page::PaginatedData};
+use futures::stream::BoxStream;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, Select};
+use server_core::web::{
+    error::AppError,
+    page::{CursorPage, PaginatedData, QuerySpec, SortOrder},
+};
 use server_model::admin::{
     entities::{
         prelude::SysLoginLog,
         sys_login_log::{Column as SysLoginLogColumn, Model as SysLoginLogModel},
     },
-    input::LoginLogPageRequest,
+    input::{LogExportRequest, LoginLogCursorRequest, LoginLogPageRequest},
 };
 
+use super::{audit, audit::AuditRecord};
 use crate::helper::db_helper;
 
+/// 登录日志列表的查询白名单
+const LOGIN_LOG_QUERY: QuerySpec<SysLoginLogColumn> = QuerySpec {
+    sort_fields: &[
+        ("createdAt", SysLoginLogColumn::CreatedAt),
+        ("loginTime", SysLoginLogColumn::LoginTime),
+        ("username", SysLoginLogColumn::Username),
+    ],
+    filter_fields: &[
+        ("userId", SysLoginLogColumn::UserId),
+        ("username", SysLoginLogColumn::Username),
+        ("domain", SysLoginLogColumn::Domain),
+        ("ip", SysLoginLogColumn::Ip),
+        ("type", SysLoginLogColumn::Type),
+        ("success", SysLoginLogColumn::Success),
+        ("country", SysLoginLogColumn::Country),
+        ("province", SysLoginLogColumn::Province),
+        ("city", SysLoginLogColumn::City),
+        ("isp", SysLoginLogColumn::Isp),
+    ],
+    status: None,
+    created_at: SysLoginLogColumn::CreatedAt,
+    id: SysLoginLogColumn::Id,
+    default_sort: (SysLoginLogColumn::CreatedAt, SortOrder::Desc),
+};
+
 #[async_trait]
 pub trait TLoginLogService {
     async fn find_paginated_login_logs(
         &self,
         params: LoginLogPageRequest,
+        domain: &str,
     ) -> Result<PaginatedData<SysLoginLogModel>, AppError>;
+
+    /// 按 `(created_at, id)` 游标分页查询登录日志，翻页代价与页码无关
+    async fn find_cursor_login_logs(
+        &self,
+        params: LoginLogCursorRequest,
+        domain: &str,
+    ) -> Result<CursorPage<SysLoginLogModel>, AppError>;
+
+    /// 按时间范围导出登录日志，返回逐页生成的 CSV 或 NDJSON 文本块
+    async fn export_login_logs(
+        &self,
+        params: LogExportRequest,
+        domain: &str,
+    ) -> Result<BoxStream<'static, Result<String, AppError>>, AppError>;
 }
 
 pub struct SysLoginLogService;
@@ -26,21 +71,17 @@ impl TLoginLogService for SysLoginLogService {
     async fn find_paginated_login_logs(
         &self,
         params: LoginLogPageRequest,
+        domain: &str,
     ) -> Result<PaginatedData<SysLoginLogModel>, AppError> {
         let db = db_helper::get_db_connection().await?;
-        let mut query = SysLoginLog::find();
+        let mut query =
+            SysLoginLog::find().filter(audit::domain_scope(SysLoginLogColumn::Domain, domain));
 
         if let Some(ref keywords) = params.keywords {
-            let condition = Condition::any()
-                .add(SysLoginLogColumn::Domain.contains(keywords))
-                .add(SysLoginLogColumn::Username.contains(keywords))
-                .add(SysLoginLogColumn::Ip.contains(keywords))
-                .add(SysLoginLogColumn::Address.contains(keywords))
-                .add(SysLoginLogColumn::UserAgent.contains(keywords));
-            query = query.filter(condition);
+            query = query.filter(keywords_condition(keywords));
         }
 
-        query = query.order_by_desc(SysLoginLogColumn::CreatedAt);
+        let query = LOGIN_LOG_QUERY.apply(query, &params.query)?;
 
         let total = query
             .clone()
@@ -61,4 +102,146 @@ impl TLoginLogService for SysLoginLogService {
             records,
         })
     }
+
+    async fn find_cursor_login_logs(
+        &self,
+        params: LoginLogCursorRequest,
+        domain: &str,
+    ) -> Result<CursorPage<SysLoginLogModel>, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let mut query =
+            SysLoginLog::find().filter(audit::domain_scope(SysLoginLogColumn::Domain, domain));
+
+        if let Some(ref keywords) = params.keywords {
+            query = query.filter(keywords_condition(keywords));
+        }
+
+        LOGIN_LOG_QUERY
+            .paginate_by_cursor(db.as_ref(), query, &params.query, &params.page_details)
+            .await
+    }
+
+    async fn export_login_logs(
+        &self,
+        params: LogExportRequest,
+        domain: &str,
+    ) -> Result<BoxStream<'static, Result<String, AppError>>, AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        Ok(audit::export_stream(
+            db,
+            export_query(&params, domain),
+            SysLoginLogColumn::CreatedAt,
+            SysLoginLogColumn::Id,
+            params.format,
+        ))
+    }
+}
+
+/// 导出的查询条件，与列表查询一样限定在调用方可见的域内
+fn export_query(params: &LogExportRequest, domain: &str) -> Select<SysLoginLog> {
+    let mut query =
+        SysLoginLog::find().filter(audit::domain_scope(SysLoginLogColumn::Domain, domain));
+
+    if let Some(start_time) = params.start_time {
+        query = query.filter(SysLoginLogColumn::CreatedAt.gte(start_time));
+    }
+    if let Some(end_time) = params.end_time {
+        query = query.filter(SysLoginLogColumn::CreatedAt.lt(end_time));
+    }
+    if let Some(ref keywords) = params.keywords {
+        query = query.filter(keywords_condition(keywords));
+    }
+    query
+}
+
+fn keywords_condition(keywords: &str) -> Condition {
+    Condition::any()
+        .add(SysLoginLogColumn::Domain.contains(keywords))
+        .add(SysLoginLogColumn::Username.contains(keywords))
+        .add(SysLoginLogColumn::Ip.contains(keywords))
+        .add(SysLoginLogColumn::Address.contains(keywords))
+        .add(SysLoginLogColumn::UserAgent.contains(keywords))
+}
+
+impl AuditRecord for SysLoginLogModel {
+    const CSV_HEADER: &'static [&'static str] = &[
+        "id",
+        "userId",
+        "username",
+        "domain",
+        "loginTime",
+        "ip",
+        "port",
+        "address",
+        "userAgent",
+        "requestId",
+        "type",
+        "createdAt",
+        "createdBy",
+        "riskScore",
+        "riskReasons",
+        "success",
+        "failureReason",
+        "country",
+        "region",
+        "province",
+        "city",
+        "isp",
+    ];
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.user_id.clone(),
+            self.username.clone(),
+            self.domain.clone(),
+            self.login_time.to_string(),
+            self.ip.clone(),
+            self.port.map(|port| port.to_string()).unwrap_or_default(),
+            self.address.clone(),
+            self.user_agent.clone(),
+            self.request_id.clone(),
+            self.r#type.clone(),
+            self.created_at.to_string(),
+            self.created_by.clone(),
+            self.risk_score.to_string(),
+            self.risk_reasons.clone().unwrap_or_default(),
+            self.success.to_string(),
+            self.failure_reason.clone().unwrap_or_default(),
+            self.country.clone().unwrap_or_default(),
+            self.region.clone().unwrap_or_default(),
+            self.province.clone().unwrap_or_default(),
+            self.city.clone().unwrap_or_default(),
+            self.isp.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{DbBackend, QueryTrait};
+    use server_model::admin::input::ExportFormat;
+
+    use super::*;
+
+    #[test]
+    fn test_export_query_scoped_to_domain() {
+        let params = LogExportRequest {
+            start_time: None,
+            end_time: None,
+            keywords: None,
+            format: ExportFormat::Csv,
+        };
+
+        let sql = export_query(&params, "tenant")
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.ends_with(r#"WHERE "sys_login_log"."domain" = 'tenant'"#));
+
+        let sql = export_query(&params, "built-in")
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(!sql.contains(r#""domain" ="#));
+    }
 }
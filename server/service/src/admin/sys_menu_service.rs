@@ -1,11 +1,20 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use chrono::Local;
-use sea_orm::{ActiveModelTrait, ColumnTrait, Condition, EntityTrait, QueryFilter, Set};
-use server_core::web::{auth::User, error::AppError};
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, Condition, EntityTrait, QueryFilter,
+    QuerySelect, Set, TransactionTrait,
+};
+use server_core::web::{
+    auth::User,
+    error::AppError,
+    etag::{check_version, map_version_conflict},
+};
 use server_model::admin::{
     entities::{
         prelude::{SysMenu, SysRoleMenu},
-        sea_orm_active_enums::Status,
+        sea_orm_active_enums::{RecycleEntityType, Status},
         sys_menu::{
             ActiveModel as SysMenuActiveModel, Column as SysMenuColumn, Model as SysMenuModel,
         },
@@ -16,7 +25,12 @@ use server_model::admin::{
 };
 use server_utils::TreeBuilder;
 
-use crate::{admin::sys_menu_error::MenuError, helper::db_helper};
+use crate::{
+    admin::{
+        dto::sys_recycle_bin_dto::RecycleSnapshot, sys_menu_error::MenuError, SysRecycleBinService,
+    },
+    helper::db_helper,
+};
 
 #[async_trait]
 pub trait TMenuService {
@@ -37,6 +51,7 @@ pub trait TMenuService {
         input: UpdateMenuInput,
         user: User,
     ) -> Result<SysMenuModel, AppError>;
+    /// 软删除菜单及其子孙菜单并移入回收站，同时移除其角色绑定
     async fn delete_menu(&self, id: i32, user: User) -> Result<(), AppError>;
     async fn get_menu_ids_by_role_id(
         &self,
@@ -55,7 +70,7 @@ impl SysMenuService {
             pid: menu.pid.clone(),
             menu_type: menu.menu_type.clone(),
             menu_name: menu.menu_name.clone(),
-            icon_type: menu.icon_type.clone(),
+            icon_type: menu.icon_type,
             icon: menu.icon.clone(),
             route_name: menu.route_name.clone(),
             route_path: menu.route_path.clone(),
@@ -119,6 +134,7 @@ impl TMenuService for SysMenuService {
         let db = db_helper::get_db_connection().await?;
 
         let menus = SysMenu::find()
+            .filter(SysMenuColumn::DeletedAt.is_null())
             .filter(SysMenuColumn::Constant.eq(false))
             .filter(SysMenuColumn::Status.eq(Status::Enabled))
             .all(db.as_ref())
@@ -132,6 +148,7 @@ impl TMenuService for SysMenuService {
     async fn get_menu_list(&self) -> Result<Vec<MenuTree>, AppError> {
         let db = db_helper::get_db_connection().await?;
         let menus = SysMenu::find()
+            .filter(SysMenuColumn::DeletedAt.is_null())
             .all(db.as_ref())
             .await
             .map_err(AppError::from)?;
@@ -144,6 +161,7 @@ impl TMenuService for SysMenuService {
         let db = db_helper::get_db_connection().await?;
 
         let menus: Vec<SysMenuModel> = SysMenu::find()
+            .filter(SysMenuColumn::DeletedAt.is_null())
             .filter(SysMenuColumn::Constant.eq(true))
             .filter(SysMenuColumn::Status.eq(Status::Enabled))
             .all(db.as_ref())
@@ -217,6 +235,7 @@ impl TMenuService for SysMenuService {
     async fn get_menu(&self, id: i32) -> Result<SysMenuModel, AppError> {
         let db = db_helper::get_db_connection().await?;
         SysMenu::find_by_id(id)
+            .filter(SysMenuColumn::DeletedAt.is_null())
             .one(db.as_ref())
             .await
             .map_err(AppError::from)?
@@ -230,6 +249,7 @@ impl TMenuService for SysMenuService {
     ) -> Result<SysMenuModel, AppError> {
         let db = db_helper::get_db_connection().await?;
         let existing_menu = self.get_menu(input.id).await?;
+        let version = check_version(existing_menu.version, input.version)?;
 
         self.check_menu_exists(Some(input.id), &input.menu.route_name)
             .await?;
@@ -256,18 +276,109 @@ impl TMenuService for SysMenuService {
 
         menu.updated_at = Set(Some(Local::now().naive_local()));
         menu.updated_by = Set(Some(user.user_id()));
+        menu.version = Set(version + 1);
 
-        let updated_menu = menu.update(db.as_ref()).await.map_err(AppError::from)?;
+        let updated_menu = SysMenu::update(menu)
+            .filter(SysMenuColumn::Version.eq(version))
+            .exec(db.as_ref())
+            .await
+            .map_err(map_version_conflict)?;
         Ok(updated_menu)
     }
 
-    async fn delete_menu(&self, id: i32, _user: User) -> Result<(), AppError> {
+    async fn delete_menu(&self, id: i32, user: User) -> Result<(), AppError> {
+        let menu = self.get_menu(id).await?;
         let db = db_helper::get_db_connection().await?;
-        SysMenu::delete_by_id(id)
-            .exec(db.as_ref())
+
+        let parents: HashMap<i32, i32> = SysMenu::find()
+            .filter(SysMenuColumn::DeletedAt.is_null())
+            .select_only()
+            .column(SysMenuColumn::Id)
+            .column(SysMenuColumn::Pid)
+            .into_tuple::<(i32, String)>()
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .into_iter()
+            .map(|(id, pid)| (id, pid.parse().unwrap_or_default()))
+            .collect();
+
+        // 连同所有子孙菜单一起删除
+        let menu_ids: Vec<i32> = parents
+            .keys()
+            .filter(|candidate| {
+                TreeBuilder::is_descendant_or_self(&id, candidate, |node| {
+                    parents.get(node).copied()
+                })
+            })
+            .copied()
+            .collect();
+
+        let role_menus = SysRoleMenu::find()
+            .filter(SysRoleMenuColumn::MenuId.is_in(menu_ids.clone()))
+            .all(db.as_ref())
             .await
             .map_err(AppError::from)?;
-        Ok(())
+
+        let snapshot = RecycleSnapshot {
+            cascaded_ids: menu_ids
+                .iter()
+                .filter(|menu_id| **menu_id != id)
+                .map(i32::to_string)
+                .collect(),
+            role_menus: role_menus
+                .into_iter()
+                .map(|role_menu| (role_menu.role_id, role_menu.menu_id, role_menu.domain))
+                .collect(),
+            ..Default::default()
+        };
+
+        let txn = db.begin().await.map_err(AppError::from)?;
+
+        let result = async {
+            SysRoleMenu::delete_many()
+                .filter(SysRoleMenuColumn::MenuId.is_in(menu_ids.clone()))
+                .exec(&txn)
+                .await
+                .map_err(AppError::from)?;
+
+            SysMenu::update_many()
+                .set(SysMenuActiveModel {
+                    deleted_at: Set(Some(Local::now().naive_local())),
+                    deleted_by: Set(Some(user.user_id())),
+                    ..Default::default()
+                })
+                .col_expr(
+                    SysMenuColumn::Version,
+                    Expr::col(SysMenuColumn::Version).add(1),
+                )
+                .filter(SysMenuColumn::Id.is_in(menu_ids))
+                .exec(&txn)
+                .await
+                .map_err(AppError::from)?;
+
+            SysRecycleBinService::record(
+                &txn,
+                RecycleEntityType::Menu,
+                &menu.id.to_string(),
+                &menu.menu_name,
+                &snapshot,
+                &user.domain(),
+                &user,
+            )
+            .await?;
+
+            Ok::<(), AppError>(())
+        }
+        .await;
+
+        match result {
+            Ok(_) => txn.commit().await.map_err(AppError::from),
+            Err(e) => {
+                txn.rollback().await.map_err(AppError::from)?;
+                Err(e)
+            },
+        }
     }
 
     async fn get_menu_ids_by_role_id(
@@ -297,6 +408,7 @@ impl TMenuService for SysMenuService {
             .filter(
                 Condition::all()
                     .add(SysMenuColumn::Id.is_in(menu_ids))
+                    .add(SysMenuColumn::DeletedAt.is_null())
                     .add(SysMenuColumn::Status.eq(Status::Enabled))
                     .add(SysMenuColumn::Constant.eq(false)),
             )
@@ -1,35 +1,69 @@
-use std::any::Any;
-
 use async_trait::async_trait;
-use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
-    Set,
+use futures::stream::BoxStream;
+use once_cell::sync::Lazy;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, Select};
+use server_config::AuditConfig;
+use server_core::web::{
+    error::AppError,
+    page::{CursorPage, PaginatedData, QuerySpec, SortOrder},
 };
-use server_core::web::{error::AppError, page::PaginatedData};
-use server_global::{global::OperationLogContext, project_error};
+use server_global::{global, project_error};
 use server_model::admin::{
     entities::{
         prelude::SysOperationLog,
-        sys_operation_log::{
-            ActiveModel as SysOperationLogActiveModel, Column as SysOperationLogColumn,
-            Model as SysOperationLogModel,
-        },
+        sys_operation_log::{Column as SysOperationLogColumn, Model as SysOperationLogModel},
     },
-    input::OperationLogPageRequest,
+    input::{LogExportRequest, OperationLogCursorRequest, OperationLogPageRequest},
 };
-use tracing::instrument;
-use ulid::Ulid;
+use tokio::{sync::Mutex, task::JoinHandle};
 
+use super::{audit, audit::AuditRecord, sinks};
 use crate::helper::db_helper;
 
+/// 操作日志列表的查询白名单
+const OPERATION_LOG_QUERY: QuerySpec<SysOperationLogColumn> = QuerySpec {
+    sort_fields: &[
+        ("createdAt", SysOperationLogColumn::CreatedAt),
+        ("duration", SysOperationLogColumn::Duration),
+        ("username", SysOperationLogColumn::Username),
+        ("moduleName", SysOperationLogColumn::ModuleName),
+    ],
+    filter_fields: &[
+        ("userId", SysOperationLogColumn::UserId),
+        ("username", SysOperationLogColumn::Username),
+        ("domain", SysOperationLogColumn::Domain),
+        ("moduleName", SysOperationLogColumn::ModuleName),
+        ("method", SysOperationLogColumn::Method),
+        ("requestId", SysOperationLogColumn::RequestId),
+        ("ip", SysOperationLogColumn::Ip),
+    ],
+    status: None,
+    created_at: SysOperationLogColumn::CreatedAt,
+    id: SysOperationLogColumn::Id,
+    default_sort: (SysOperationLogColumn::CreatedAt, SortOrder::Desc),
+};
+
 #[async_trait]
 pub trait TOperationLogService {
     async fn find_paginated_operation_logs(
         &self,
         params: OperationLogPageRequest,
+        domain: &str,
     ) -> Result<PaginatedData<SysOperationLogModel>, AppError>;
 
-    async fn handle_operation_log_event(event: &OperationLogContext) -> Result<(), AppError>;
+    /// 按 `(created_at, id)` 游标分页查询操作日志，翻页代价与页码无关
+    async fn find_cursor_operation_logs(
+        &self,
+        params: OperationLogCursorRequest,
+        domain: &str,
+    ) -> Result<CursorPage<SysOperationLogModel>, AppError>;
+
+    /// 按时间范围导出操作日志，返回逐页生成的 CSV 或 NDJSON 文本块
+    async fn export_operation_logs(
+        &self,
+        params: LogExportRequest,
+        domain: &str,
+    ) -> Result<BoxStream<'static, Result<String, AppError>>, AppError>;
 }
 
 pub struct SysOperationLogService;
@@ -39,20 +73,17 @@ impl TOperationLogService for SysOperationLogService {
     async fn find_paginated_operation_logs(
         &self,
         params: OperationLogPageRequest,
+        domain: &str,
     ) -> Result<PaginatedData<SysOperationLogModel>, AppError> {
         let db = db_helper::get_db_connection().await?;
-        let mut query = SysOperationLog::find();
+        let mut query = SysOperationLog::find()
+            .filter(audit::domain_scope(SysOperationLogColumn::Domain, domain));
 
         if let Some(ref keywords) = params.keywords {
-            let condition = Condition::any()
-                .add(SysOperationLogColumn::Domain.contains(keywords))
-                .add(SysOperationLogColumn::Username.contains(keywords))
-                .add(SysOperationLogColumn::Ip.contains(keywords))
-                .add(SysOperationLogColumn::UserAgent.contains(keywords));
-            query = query.filter(condition);
+            query = query.filter(keywords_condition(keywords));
         }
 
-        query = query.order_by_desc(SysOperationLogColumn::CreatedAt);
+        let query = OPERATION_LOG_QUERY.apply(query, &params.query)?;
 
         let total = query
             .clone()
@@ -74,50 +105,176 @@ impl TOperationLogService for SysOperationLogService {
         })
     }
 
-    async fn handle_operation_log_event(event: &OperationLogContext) -> Result<(), AppError> {
+    async fn find_cursor_operation_logs(
+        &self,
+        params: OperationLogCursorRequest,
+        domain: &str,
+    ) -> Result<CursorPage<SysOperationLogModel>, AppError> {
         let db = db_helper::get_db_connection().await?;
+        let mut query = SysOperationLog::find()
+            .filter(audit::domain_scope(SysOperationLogColumn::Domain, domain));
 
-        SysOperationLogActiveModel {
-            id: Set(Ulid::new().to_string()),
-            user_id: Set(event.user_id.clone().unwrap_or_default()),
-            username: Set(event.username.clone().unwrap_or_default()),
-            domain: Set(event.domain.clone().unwrap_or_default()),
-            module_name: Set(event.module_name.clone()),
-            description: Set(event.description.clone()),
-            request_id: Set(event.request_id.clone()),
-            method: Set(event.method.clone()),
-            url: Set(event.url.clone()),
-            ip: Set(event.ip.clone()),
-            user_agent: Set(event.user_agent.clone()),
-            params: Set(event.params.clone()),
-            body: Set(event.body.clone()),
-            response: Set(event.response.clone()),
-            start_time: Set(event.start_time),
-            end_time: Set(event.end_time),
-            duration: Set(event.duration),
-            created_at: Set(event.created_at),
+        if let Some(ref keywords) = params.keywords {
+            query = query.filter(keywords_condition(keywords));
         }
-        .insert(db.as_ref())
+
+        OPERATION_LOG_QUERY
+            .paginate_by_cursor(db.as_ref(), query, &params.query, &params.page_details)
+            .await
+    }
+
+    async fn export_operation_logs(
+        &self,
+        params: LogExportRequest,
+        domain: &str,
+    ) -> Result<BoxStream<'static, Result<String, AppError>>, AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        Ok(audit::export_stream(
+            db,
+            export_query(&params, domain),
+            SysOperationLogColumn::CreatedAt,
+            SysOperationLogColumn::Id,
+            params.format,
+        ))
+    }
+}
+
+/// 导出的查询条件，与列表查询一样限定在调用方可见的域内
+fn export_query(params: &LogExportRequest, domain: &str) -> Select<SysOperationLog> {
+    let mut query =
+        SysOperationLog::find().filter(audit::domain_scope(SysOperationLogColumn::Domain, domain));
+
+    if let Some(start_time) = params.start_time {
+        query = query.filter(SysOperationLogColumn::CreatedAt.gte(start_time));
+    }
+    if let Some(end_time) = params.end_time {
+        query = query.filter(SysOperationLogColumn::CreatedAt.lt(end_time));
+    }
+    if let Some(ref keywords) = params.keywords {
+        query = query.filter(keywords_condition(keywords));
+    }
+    query
+}
+
+fn keywords_condition(keywords: &str) -> Condition {
+    Condition::any()
+        .add(SysOperationLogColumn::Domain.contains(keywords))
+        .add(SysOperationLogColumn::Username.contains(keywords))
+        .add(SysOperationLogColumn::Ip.contains(keywords))
+        .add(SysOperationLogColumn::UserAgent.contains(keywords))
+}
+
+impl AuditRecord for SysOperationLogModel {
+    const CSV_HEADER: &'static [&'static str] = &[
+        "id",
+        "userId",
+        "username",
+        "domain",
+        "moduleName",
+        "description",
+        "requestId",
+        "method",
+        "url",
+        "route",
+        "ip",
+        "userAgent",
+        "params",
+        "headers",
+        "body",
+        "response",
+        "startTime",
+        "endTime",
+        "duration",
+        "status",
+        "createdAt",
+    ];
+
+    fn csv_row(&self) -> Vec<String> {
+        let json = |value: &Option<serde_json::Value>| {
+            value.as_ref().map(ToString::to_string).unwrap_or_default()
+        };
+
+        vec![
+            self.id.clone(),
+            self.user_id.clone(),
+            self.username.clone(),
+            self.domain.clone(),
+            self.module_name.clone(),
+            self.description.clone(),
+            self.request_id.clone(),
+            self.method.clone(),
+            self.url.clone(),
+            self.route.clone(),
+            self.ip.clone(),
+            self.user_agent.clone().unwrap_or_default(),
+            json(&self.params),
+            json(&self.headers),
+            json(&self.body),
+            json(&self.response),
+            self.start_time.to_string(),
+            self.end_time.to_string(),
+            self.duration.to_string(),
+            self.status.to_string(),
+            self.created_at.to_string(),
+        ]
+    }
+}
+
+/// 操作日志批量写入任务，停机时等待其写完队列中的日志
+static OPERATION_LOG_WRITER: Lazy<Mutex<Option<JoinHandle<()>>>> = Lazy::new(|| Mutex::new(None));
+
+/// 按 `AuditConfig` 选择写入目标并启动批量写入任务
+///
+/// 请求路径通过 `global::send_operation_log` 放入有界队列，队列满时在超时时间内等待。
+pub async fn start_operation_log_writer() {
+    let config = global::get_config::<AuditConfig>()
         .await
-        .map_err(AppError::from)?;
+        .map(|config| config.sink.clone())
+        .unwrap_or_default();
+    let (queue, handle) = sinks::spawn_batch_writer(sinks::create_sink(&config), &config);
 
-        Ok(())
+    global::set_operation_log_queue(Some(queue));
+    if let Some(previous) = OPERATION_LOG_WRITER.lock().await.replace(handle) {
+        previous.abort();
     }
 }
 
-#[instrument(skip(rx))]
-pub async fn sys_operation_log_listener(
-    mut rx: tokio::sync::mpsc::UnboundedReceiver<Box<dyn Any + Send>>,
-) {
-    while let Some(event) = rx.recv().await {
-        if let Some(operation_log_context) = event.downcast_ref::<OperationLogContext>() {
-            if let Err(e) =
-                SysOperationLogService::handle_operation_log_event(operation_log_context).await
-            {
-                project_error!("Failed to handle operation log event: {:?}", e);
-            }
-        } else {
-            project_error!("Received unknown event type in operation log listener");
-        }
+/// 关闭写入队列并等待队列中剩余的日志写完
+pub async fn shutdown_operation_log_writer() {
+    global::set_operation_log_queue(None);
+    let Some(handle) = OPERATION_LOG_WRITER.lock().await.take() else {
+        return;
+    };
+    if let Err(e) = handle.await {
+        project_error!("Operation log writer stopped unexpectedly: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{DbBackend, QueryTrait};
+    use server_model::admin::input::ExportFormat;
+
+    use super::*;
+
+    #[test]
+    fn test_export_query_scoped_to_domain() {
+        let params = LogExportRequest {
+            start_time: None,
+            end_time: None,
+            keywords: None,
+            format: ExportFormat::Csv,
+        };
+
+        let sql = export_query(&params, "tenant")
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.ends_with(r#"WHERE "sys_operation_log"."domain" = 'tenant'"#));
+
+        let sql = export_query(&params, "built-in")
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(!sql.contains(r#""domain" ="#));
     }
 }
@@ -1,15 +1,46 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use sea_orm::{ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter};
-use server_core::web::{error::AppError, page::PaginatedData};
+use chrono::Local;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter,
+    QuerySelect, Set, TransactionTrait,
+};
+use server_core::web::{
+    auth::User,
+    error::AppError,
+    etag::{check_version, map_version_conflict},
+    page::PaginatedData,
+};
 use server_model::admin::{
     entities::{
-        prelude::SysOrganization,
-        sys_organization::{Column as SysOrganizationColumn, Model as SysOrganizationModel},
+        prelude::{SysOrganization, SysUser},
+        sea_orm_active_enums::RecycleEntityType,
+        sys_organization::{
+            ActiveModel as SysOrganizationActiveModel, Column as SysOrganizationColumn,
+            Model as SysOrganizationModel,
+        },
+        sys_user::Column as SysUserColumn,
+    },
+    input::{
+        CreateOrganizationInput, MoveOrganizationInput, OrganizationPageRequest,
+        UpdateOrganizationInput,
+    },
+    output::OrganizationTree,
+};
+use server_utils::TreeBuilder;
+use ulid::Ulid;
+
+use crate::{
+    admin::{
+        dto::sys_recycle_bin_dto::RecycleSnapshot, sys_organization_error::OrganizationError,
+        SysRecycleBinService,
     },
-    input::OrganizationPageRequest,
+    helper::db_helper,
 };
 
-use crate::helper::db_helper;
+/// 根组织的父级ID
+pub(crate) const ROOT_PID: &str = "0";
 
 #[async_trait]
 pub trait TOrganizationService {
@@ -17,10 +48,97 @@ pub trait TOrganizationService {
         &self,
         params: OrganizationPageRequest,
     ) -> Result<PaginatedData<SysOrganizationModel>, AppError>;
+
+    async fn tree_organization(&self) -> Result<Vec<OrganizationTree>, AppError>;
+
+    async fn create_organization(
+        &self,
+        input: CreateOrganizationInput,
+        user: User,
+    ) -> Result<SysOrganizationModel, AppError>;
+    async fn get_organization(&self, id: &str) -> Result<SysOrganizationModel, AppError>;
+    async fn update_organization(
+        &self,
+        input: UpdateOrganizationInput,
+        user: User,
+    ) -> Result<SysOrganizationModel, AppError>;
+    async fn move_organization(
+        &self,
+        input: MoveOrganizationInput,
+        user: User,
+    ) -> Result<SysOrganizationModel, AppError>;
+    /// 软删除组织并移入回收站，组织下仍有子组织或用户时拒绝删除
+    async fn delete_organization(&self, id: &str, user: User) -> Result<(), AppError>;
 }
 
+#[derive(Clone)]
 pub struct SysOrganizationService;
 
+impl SysOrganizationService {
+    async fn check_organization_exists(
+        &self,
+        id: Option<&str>,
+        code: &str,
+    ) -> Result<(), AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        let code_exists = SysOrganization::find()
+            .filter(SysOrganizationColumn::Code.eq(code))
+            .filter(SysOrganizationColumn::Id.ne(id.unwrap_or("-1")))
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .is_some();
+
+        if code_exists {
+            return Err(OrganizationError::DuplicateCode.into());
+        }
+
+        Ok(())
+    }
+
+    /// 校验父级组织
+    ///
+    /// 父级必须为根节点或已存在的组织；更新时还要保证父级不是节点自身或其后代。
+    async fn check_parent(&self, id: Option<&str>, pid: &str) -> Result<(), AppError> {
+        if pid == ROOT_PID {
+            return Ok(());
+        }
+
+        let db = db_helper::get_db_connection().await?;
+        let parents: HashMap<String, String> = SysOrganization::find()
+            .filter(SysOrganizationColumn::DeletedAt.is_null())
+            .select_only()
+            .column(SysOrganizationColumn::Id)
+            .column(SysOrganizationColumn::Pid)
+            .into_tuple::<(String, String)>()
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .into_iter()
+            .collect();
+
+        if !parents.contains_key(pid) {
+            return Err(OrganizationError::ParentNotFound.into());
+        }
+
+        if let Some(id) = id {
+            let is_cyclic =
+                TreeBuilder::is_descendant_or_self(&id.to_string(), &pid.to_string(), |node| {
+                    parents
+                        .get(node)
+                        .filter(|pid| pid.as_str() != ROOT_PID)
+                        .cloned()
+                });
+            if is_cyclic {
+                return Err(OrganizationError::CyclicParent.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl TOrganizationService for SysOrganizationService {
     async fn find_paginated_organizations(
@@ -28,7 +146,7 @@ impl TOrganizationService for SysOrganizationService {
         params: OrganizationPageRequest,
     ) -> Result<PaginatedData<SysOrganizationModel>, AppError> {
         let db = db_helper::get_db_connection().await?;
-        let mut query = SysOrganization::find();
+        let mut query = SysOrganization::find().filter(SysOrganizationColumn::DeletedAt.is_null());
 
         if let Some(ref keywords) = params.keywords {
             let condition = Condition::any()
@@ -57,4 +175,275 @@ impl TOrganizationService for SysOrganizationService {
             records,
         })
     }
+
+    async fn tree_organization(&self) -> Result<Vec<OrganizationTree>, AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        let organizations = SysOrganization::find()
+            .filter(SysOrganizationColumn::DeletedAt.is_null())
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        let nodes: Vec<OrganizationTree> = organizations
+            .into_iter()
+            .map(OrganizationTree::from)
+            .collect();
+
+        Ok(TreeBuilder::build(
+            nodes,
+            |node| node.id.clone(),
+            |node| {
+                if node.pid == ROOT_PID {
+                    None
+                } else {
+                    Some(node.pid.clone())
+                }
+            },
+            |node| node.code.clone(),
+            |node, children| node.children = Some(children),
+        ))
+    }
+
+    async fn create_organization(
+        &self,
+        input: CreateOrganizationInput,
+        user: User,
+    ) -> Result<SysOrganizationModel, AppError> {
+        self.check_organization_exists(None, &input.code).await?;
+        self.check_parent(None, &input.pid).await?;
+
+        let db = db_helper::get_db_connection().await?;
+
+        let organization = SysOrganizationActiveModel {
+            id: Set(Ulid::new().to_string()),
+            code: Set(input.code),
+            name: Set(input.name),
+            description: Set(input.description),
+            pid: Set(input.pid),
+            status: Set(input.status),
+            created_at: Set(Local::now().naive_local()),
+            created_by: Set(user.user_id()),
+            ..Default::default()
+        };
+
+        let result = organization
+            .insert(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+        Ok(result)
+    }
+
+    async fn get_organization(&self, id: &str) -> Result<SysOrganizationModel, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        SysOrganization::find_by_id(id)
+            .filter(SysOrganizationColumn::DeletedAt.is_null())
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .ok_or_else(|| OrganizationError::OrganizationNotFound.into())
+    }
+
+    async fn update_organization(
+        &self,
+        input: UpdateOrganizationInput,
+        user: User,
+    ) -> Result<SysOrganizationModel, AppError> {
+        let existing_organization = self.get_organization(&input.id).await?;
+        let version = check_version(existing_organization.version, input.version)?;
+
+        self.check_organization_exists(Some(&input.id), &input.organization.code)
+            .await?;
+        self.check_parent(Some(&input.id), &input.organization.pid)
+            .await?;
+
+        let db = db_helper::get_db_connection().await?;
+
+        let mut organization: SysOrganizationActiveModel = existing_organization.into();
+        organization.code = Set(input.organization.code);
+        organization.name = Set(input.organization.name);
+        organization.description = Set(input.organization.description);
+        organization.pid = Set(input.organization.pid);
+        organization.status = Set(input.organization.status);
+
+        organization.updated_at = Set(Some(Local::now().naive_local()));
+        organization.updated_by = Set(Some(user.user_id()));
+        organization.version = Set(version + 1);
+
+        let updated_organization = SysOrganization::update(organization)
+            .filter(SysOrganizationColumn::Version.eq(version))
+            .exec(db.as_ref())
+            .await
+            .map_err(map_version_conflict)?;
+        Ok(updated_organization)
+    }
+
+    async fn move_organization(
+        &self,
+        input: MoveOrganizationInput,
+        user: User,
+    ) -> Result<SysOrganizationModel, AppError> {
+        let existing_organization = self.get_organization(&input.id).await?;
+        let version = check_version(existing_organization.version, input.version)?;
+
+        self.check_parent(Some(&input.id), &input.pid).await?;
+
+        let db = db_helper::get_db_connection().await?;
+
+        let mut organization: SysOrganizationActiveModel = existing_organization.into();
+        organization.pid = Set(input.pid);
+        organization.updated_at = Set(Some(Local::now().naive_local()));
+        organization.updated_by = Set(Some(user.user_id()));
+        organization.version = Set(version + 1);
+
+        let moved_organization = SysOrganization::update(organization)
+            .filter(SysOrganizationColumn::Version.eq(version))
+            .exec(db.as_ref())
+            .await
+            .map_err(map_version_conflict)?;
+        Ok(moved_organization)
+    }
+
+    async fn delete_organization(&self, id: &str, user: User) -> Result<(), AppError> {
+        let organization = self.get_organization(id).await?;
+
+        let db = db_helper::get_db_connection().await?;
+
+        let has_children = SysOrganization::find()
+            .filter(SysOrganizationColumn::Pid.eq(id))
+            .filter(SysOrganizationColumn::DeletedAt.is_null())
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .is_some();
+
+        if has_children {
+            return Err(OrganizationError::HasChildren.into());
+        }
+
+        let has_users = SysUser::find()
+            .filter(SysUserColumn::OrganizationId.eq(id))
+            .filter(SysUserColumn::DeletedAt.is_null())
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .is_some();
+
+        if has_users {
+            return Err(OrganizationError::HasUsers.into());
+        }
+
+        let txn = db.begin().await.map_err(AppError::from)?;
+
+        let result = async {
+            let mut deleted_organization: SysOrganizationActiveModel = organization.clone().into();
+            deleted_organization.deleted_at = Set(Some(Local::now().naive_local()));
+            deleted_organization.deleted_by = Set(Some(user.user_id()));
+            deleted_organization.version = Set(deleted_organization.version.as_ref() + 1);
+            deleted_organization
+                .update(&txn)
+                .await
+                .map_err(AppError::from)?;
+
+            SysRecycleBinService::record(
+                &txn,
+                RecycleEntityType::Organization,
+                &organization.id,
+                &organization.name,
+                &RecycleSnapshot::default(),
+                &user.domain(),
+                &user,
+            )
+            .await?;
+
+            Ok::<(), AppError>(())
+        }
+        .await;
+
+        match result {
+            Ok(_) => txn.commit().await.map_err(AppError::from),
+            Err(e) => {
+                txn.rollback().await.map_err(AppError::from)?;
+                Err(e)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use sea_orm::{DatabaseBackend, MockDatabase};
+    use server_core::web::auth::Claims;
+    use server_global::global::GLOBAL_PRIMARY_DB;
+    use server_model::admin::entities::sea_orm_active_enums::Status;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_move_organization_bumps_version() {
+        let now = Local::now().naive_local();
+        let organization = SysOrganizationModel {
+            id: "o2".to_string(),
+            code: "O2".to_string(),
+            name: "child".to_string(),
+            description: None,
+            pid: "o1".to_string(),
+            status: Status::Enabled,
+            created_at: now,
+            created_by: "admin".to_string(),
+            updated_at: None,
+            updated_by: None,
+            version: 3,
+            deleted_at: None,
+            deleted_by: None,
+        };
+        let moved = SysOrganizationModel {
+            pid: ROOT_PID.to_string(),
+            version: 4,
+            ..organization.clone()
+        };
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![organization]])
+            .append_query_results([vec![moved]])
+            .into_connection();
+        *GLOBAL_PRIMARY_DB.write().await = Some(Arc::new(db));
+
+        let user = User::from(Claims::new(
+            "u1".to_string(),
+            "aud".to_string(),
+            "admin".to_string(),
+            vec![],
+            "built-in".to_string(),
+            None,
+        ));
+        let input = MoveOrganizationInput {
+            id: "o2".to_string(),
+            pid: ROOT_PID.to_string(),
+            version: Some(3),
+        };
+        let moved = SysOrganizationService
+            .move_organization(input, user)
+            .await
+            .unwrap();
+        assert_eq!(moved.version, 4);
+
+        let db = GLOBAL_PRIMARY_DB.write().await.take().unwrap();
+        let statements: Vec<String> = Arc::try_unwrap(db)
+            .unwrap()
+            .into_transaction_log()
+            .iter()
+            .flat_map(|transaction| transaction.statements())
+            .map(ToString::to_string)
+            .collect();
+        let update = statements
+            .iter()
+            .find(|sql| sql.starts_with(r#"UPDATE "sys_organization""#))
+            .unwrap();
+        assert!(update.contains(r#""version" = 4"#));
+        assert!(update.contains(
+            r#"WHERE "sys_organization"."id" = 'o2' AND "sys_organization"."version" = 3"#
+        ));
+    }
 }
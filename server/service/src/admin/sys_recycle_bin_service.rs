@@ -0,0 +1,892 @@
+use std::{collections::HashSet, sync::Arc, time::Duration as StdDuration};
+
+use async_trait::async_trait;
+use axum_casbin::casbin::{self, MgmtApi};
+use chrono::{Duration, Local, NaiveDateTime};
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait,
+    DatabaseTransaction, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    TransactionTrait,
+};
+use server_config::RecycleBinConfig;
+use server_core::web::{auth::User, error::AppError, page::PaginatedData};
+use server_model::admin::{
+    entities::{
+        prelude::{
+            SysDomain, SysMenu, SysOrganization, SysRecycleBin, SysRole, SysRoleMenu, SysUser,
+            SysUserRole,
+        },
+        sea_orm_active_enums::RecycleEntityType,
+        sys_domain::{ActiveModel as SysDomainActiveModel, Column as SysDomainColumn},
+        sys_menu::{ActiveModel as SysMenuActiveModel, Column as SysMenuColumn},
+        sys_organization::{
+            ActiveModel as SysOrganizationActiveModel, Column as SysOrganizationColumn,
+        },
+        sys_recycle_bin::{
+            ActiveModel as SysRecycleBinActiveModel, Column as SysRecycleBinColumn,
+            Model as SysRecycleBinModel,
+        },
+        sys_role::{ActiveModel as SysRoleActiveModel, Column as SysRoleColumn},
+        sys_role_menu::ActiveModel as SysRoleMenuActiveModel,
+        sys_user::{ActiveModel as SysUserActiveModel, Column as SysUserColumn},
+        sys_user_role::ActiveModel as SysUserRoleActiveModel,
+    },
+    input::{PurgeRecycleBinInput, RecycleBinPageRequest},
+};
+use tokio::{sync::RwLock, time::MissedTickBehavior};
+use ulid::Ulid;
+
+use crate::{
+    admin::{
+        dto::sys_recycle_bin_dto::RecycleSnapshot, sys_domain_service::BUILT_IN_DOMAIN,
+        sys_organization_service::ROOT_PID, sys_recycle_bin_error::RecycleBinError,
+        SysDomainService,
+    },
+    helper::db_helper,
+    project_error, project_info,
+};
+
+#[async_trait]
+pub trait TRecycleBinService {
+    /// 分页查询回收站条目，内置域可查看全部条目，其他域只能查看本域的条目
+    async fn find_paginated_entries(
+        &self,
+        params: RecycleBinPageRequest,
+        user: User,
+    ) -> Result<PaginatedData<SysRecycleBinModel>, AppError>;
+
+    /// 恢复回收站条目，重建删除时移除的绑定关系和 Casbin 策略
+    ///
+    /// 已失效的绑定（对方已被删除）会被跳过；父级组织、父级菜单或所属域仍在回收站时拒绝恢复。
+    async fn restore_entry(
+        &self,
+        id: &str,
+        user: User,
+        enforcer: Arc<RwLock<impl MgmtApi>>,
+    ) -> Result<(), AppError>;
+
+    /// 彻底删除回收站条目及其对应的实体
+    async fn purge_entry(
+        &self,
+        id: &str,
+        user: User,
+        enforcer: Arc<RwLock<impl MgmtApi>>,
+    ) -> Result<(), AppError>;
+
+    /// 彻底删除当前域超过保留期的回收站条目，返回清理的条目数
+    ///
+    /// 单个条目清理失败时记录错误并继续清理其余条目。
+    async fn purge_expired(
+        &self,
+        input: PurgeRecycleBinInput,
+        user: User,
+        enforcer: Arc<RwLock<impl MgmtApi>>,
+    ) -> Result<u64, AppError>;
+}
+
+#[derive(Clone)]
+pub struct SysRecycleBinService;
+
+impl SysRecycleBinService {
+    /// 软删除实体时写入回收站条目，需与软删除在同一事务中调用
+    ///
+    /// `domain` 为条目所属域，决定哪些域的管理员可以查看、恢复和清理该条目。
+    pub(crate) async fn record<C: ConnectionTrait>(
+        db: &C,
+        entity_type: RecycleEntityType,
+        entity_id: &str,
+        entity_name: &str,
+        snapshot: &RecycleSnapshot,
+        domain: &str,
+        user: &User,
+    ) -> Result<(), AppError> {
+        let snapshot = serde_json::to_string(snapshot)
+            .map_err(|e| RecycleBinError::InvalidSnapshot(e.to_string()))?;
+
+        SysRecycleBinActiveModel {
+            id: Set(Ulid::new().to_string()),
+            entity_type: Set(entity_type),
+            entity_id: Set(entity_id.to_string()),
+            entity_name: Set(entity_name.to_string()),
+            snapshot: Set(snapshot),
+            deleted_at: Set(Local::now().naive_local()),
+            deleted_by: Set(user.user_id()),
+            domain: Set(domain.to_string()),
+        }
+        .insert(db)
+        .await
+        .map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn get_entry(&self, id: &str, domain: &str) -> Result<SysRecycleBinModel, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        SysRecycleBin::find_by_id(id)
+            .filter(domain_scope(domain))
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .ok_or_else(|| RecycleBinError::EntryNotFound.into())
+    }
+
+    fn parse_snapshot(entry: &SysRecycleBinModel) -> Result<RecycleSnapshot, AppError> {
+        serde_json::from_str(&entry.snapshot)
+            .map_err(|e| RecycleBinError::InvalidSnapshot(e.to_string()).into())
+    }
+
+    /// 菜单条目涉及的全部菜单ID，包括一同删除的子孙菜单
+    fn menu_ids(
+        entry: &SysRecycleBinModel,
+        snapshot: &RecycleSnapshot,
+    ) -> Result<Vec<i32>, AppError> {
+        std::iter::once(&entry.entity_id)
+            .chain(snapshot.cascaded_ids.iter())
+            .map(|id| {
+                id.parse::<i32>()
+                    .map_err(|e| RecycleBinError::InvalidSnapshot(e.to_string()).into())
+            })
+            .collect()
+    }
+
+    async fn is_domain_alive<C: ConnectionTrait>(db: &C, code: &str) -> Result<bool, AppError> {
+        Ok(SysDomain::find()
+            .filter(SysDomainColumn::Code.eq(code))
+            .filter(SysDomainColumn::DeletedAt.is_null())
+            .one(db)
+            .await
+            .map_err(AppError::from)?
+            .is_some())
+    }
+
+    async fn alive_user_ids<C: ConnectionTrait>(
+        db: &C,
+        ids: Vec<String>,
+    ) -> Result<HashSet<String>, AppError> {
+        Ok(SysUser::find()
+            .select_only()
+            .column(SysUserColumn::Id)
+            .filter(SysUserColumn::Id.is_in(ids))
+            .filter(SysUserColumn::DeletedAt.is_null())
+            .into_tuple::<String>()
+            .all(db)
+            .await
+            .map_err(AppError::from)?
+            .into_iter()
+            .collect())
+    }
+
+    async fn alive_role_ids<C: ConnectionTrait>(
+        db: &C,
+        ids: Vec<String>,
+    ) -> Result<HashSet<String>, AppError> {
+        Ok(SysRole::find()
+            .select_only()
+            .column(SysRoleColumn::Id)
+            .filter(SysRoleColumn::Id.is_in(ids))
+            .filter(SysRoleColumn::DeletedAt.is_null())
+            .into_tuple::<String>()
+            .all(db)
+            .await
+            .map_err(AppError::from)?
+            .into_iter()
+            .collect())
+    }
+
+    async fn alive_menu_ids<C: ConnectionTrait>(
+        db: &C,
+        ids: Vec<i32>,
+    ) -> Result<HashSet<i32>, AppError> {
+        Ok(SysMenu::find()
+            .select_only()
+            .column(SysMenuColumn::Id)
+            .filter(SysMenuColumn::Id.is_in(ids))
+            .filter(SysMenuColumn::DeletedAt.is_null())
+            .into_tuple::<i32>()
+            .all(db)
+            .await
+            .map_err(AppError::from)?
+            .into_iter()
+            .collect())
+    }
+
+    async fn insert_user_roles(
+        txn: &DatabaseTransaction,
+        user_roles: Vec<(String, String)>,
+    ) -> Result<(), AppError> {
+        if user_roles.is_empty() {
+            return Ok(());
+        }
+
+        SysUserRole::insert_many(user_roles.into_iter().map(|(user_id, role_id)| {
+            SysUserRoleActiveModel {
+                user_id: Set(user_id),
+                role_id: Set(role_id),
+            }
+        }))
+        .exec(txn)
+        .await
+        .map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn insert_role_menus(
+        txn: &DatabaseTransaction,
+        role_menus: Vec<(String, i32, String)>,
+    ) -> Result<(), AppError> {
+        if role_menus.is_empty() {
+            return Ok(());
+        }
+
+        SysRoleMenu::insert_many(role_menus.into_iter().map(|(role_id, menu_id, domain)| {
+            SysRoleMenuActiveModel {
+                role_id: Set(role_id),
+                menu_id: Set(menu_id),
+                domain: Set(domain),
+            }
+        }))
+        .exec(txn)
+        .await
+        .map_err(AppError::from)?;
+        Ok(())
+    }
+
+    /// 在事务内清除删除标记并重建仍然有效的绑定关系
+    async fn restore_in_transaction(
+        txn: &DatabaseTransaction,
+        entry: &SysRecycleBinModel,
+        snapshot: &RecycleSnapshot,
+    ) -> Result<(), AppError> {
+        let not_found = || AppError::from(RecycleBinError::EntityNotFound);
+
+        match entry.entity_type {
+            RecycleEntityType::User => {
+                let user = SysUser::find_by_id(entry.entity_id.as_str())
+                    .filter(SysUserColumn::DeletedAt.is_not_null())
+                    .one(txn)
+                    .await
+                    .map_err(AppError::from)?
+                    .ok_or_else(not_found)?;
+
+                if !Self::is_domain_alive(txn, &user.domain).await? {
+                    return Err(RecycleBinError::DomainDeleted.into());
+                }
+
+                let mut user: SysUserActiveModel = user.into();
+                user.deleted_at = Set(None);
+                user.deleted_by = Set(None);
+                user.version = Set(user.version.as_ref() + 1);
+                user.update(txn).await.map_err(AppError::from)?;
+
+                let role_ids = Self::alive_role_ids(
+                    txn,
+                    snapshot
+                        .user_roles
+                        .iter()
+                        .map(|(_, role_id)| role_id.clone())
+                        .collect(),
+                )
+                .await?;
+                Self::insert_user_roles(
+                    txn,
+                    snapshot
+                        .user_roles
+                        .iter()
+                        .filter(|(_, role_id)| role_ids.contains(role_id))
+                        .cloned()
+                        .collect(),
+                )
+                .await
+            },
+            RecycleEntityType::Role => {
+                let role = SysRole::find_by_id(entry.entity_id.as_str())
+                    .filter(SysRoleColumn::DeletedAt.is_not_null())
+                    .one(txn)
+                    .await
+                    .map_err(AppError::from)?
+                    .ok_or_else(not_found)?;
+
+                let mut role: SysRoleActiveModel = role.into();
+                role.deleted_at = Set(None);
+                role.deleted_by = Set(None);
+                role.version = Set(role.version.as_ref() + 1);
+                role.update(txn).await.map_err(AppError::from)?;
+
+                let user_ids = Self::alive_user_ids(
+                    txn,
+                    snapshot
+                        .user_roles
+                        .iter()
+                        .map(|(user_id, _)| user_id.clone())
+                        .collect(),
+                )
+                .await?;
+                Self::insert_user_roles(
+                    txn,
+                    snapshot
+                        .user_roles
+                        .iter()
+                        .filter(|(user_id, _)| user_ids.contains(user_id))
+                        .cloned()
+                        .collect(),
+                )
+                .await?;
+
+                let menu_ids = Self::alive_menu_ids(
+                    txn,
+                    snapshot
+                        .role_menus
+                        .iter()
+                        .map(|(_, menu_id, _)| *menu_id)
+                        .collect(),
+                )
+                .await?;
+                Self::insert_role_menus(
+                    txn,
+                    snapshot
+                        .role_menus
+                        .iter()
+                        .filter(|(_, menu_id, _)| menu_ids.contains(menu_id))
+                        .cloned()
+                        .collect(),
+                )
+                .await
+            },
+            RecycleEntityType::Menu => {
+                let menu = SysMenu::find_by_id(entry.entity_id.parse::<i32>().unwrap_or_default())
+                    .filter(SysMenuColumn::DeletedAt.is_not_null())
+                    .one(txn)
+                    .await
+                    .map_err(AppError::from)?
+                    .ok_or_else(not_found)?;
+
+                if menu.pid != "0" {
+                    let parent_alive =
+                        SysMenu::find_by_id(menu.pid.parse::<i32>().unwrap_or_default())
+                            .filter(SysMenuColumn::DeletedAt.is_null())
+                            .one(txn)
+                            .await
+                            .map_err(AppError::from)?
+                            .is_some();
+                    if !parent_alive {
+                        return Err(RecycleBinError::ParentDeleted.into());
+                    }
+                }
+
+                SysMenu::update_many()
+                    .set(SysMenuActiveModel {
+                        deleted_at: Set(None),
+                        deleted_by: Set(None),
+                        ..Default::default()
+                    })
+                    .col_expr(
+                        SysMenuColumn::Version,
+                        Expr::col(SysMenuColumn::Version).add(1),
+                    )
+                    .filter(SysMenuColumn::Id.is_in(Self::menu_ids(entry, snapshot)?))
+                    .filter(SysMenuColumn::DeletedAt.is_not_null())
+                    .exec(txn)
+                    .await
+                    .map_err(AppError::from)?;
+
+                let role_ids = Self::alive_role_ids(
+                    txn,
+                    snapshot
+                        .role_menus
+                        .iter()
+                        .map(|(role_id, ..)| role_id.clone())
+                        .collect(),
+                )
+                .await?;
+                Self::insert_role_menus(
+                    txn,
+                    snapshot
+                        .role_menus
+                        .iter()
+                        .filter(|(role_id, ..)| role_ids.contains(role_id))
+                        .cloned()
+                        .collect(),
+                )
+                .await
+            },
+            RecycleEntityType::Domain => {
+                let domain = SysDomain::find_by_id(entry.entity_id.as_str())
+                    .filter(SysDomainColumn::DeletedAt.is_not_null())
+                    .one(txn)
+                    .await
+                    .map_err(AppError::from)?
+                    .ok_or_else(not_found)?;
+
+                let mut domain: SysDomainActiveModel = domain.into();
+                domain.deleted_at = Set(None);
+                domain.deleted_by = Set(None);
+                domain.version = Set(domain.version.as_ref() + 1);
+                domain.update(txn).await.map_err(AppError::from)?;
+                Ok(())
+            },
+            RecycleEntityType::Organization => {
+                let organization = SysOrganization::find_by_id(entry.entity_id.as_str())
+                    .filter(SysOrganizationColumn::DeletedAt.is_not_null())
+                    .one(txn)
+                    .await
+                    .map_err(AppError::from)?
+                    .ok_or_else(not_found)?;
+
+                if organization.pid != ROOT_PID {
+                    let parent_alive = SysOrganization::find_by_id(organization.pid.as_str())
+                        .filter(SysOrganizationColumn::DeletedAt.is_null())
+                        .one(txn)
+                        .await
+                        .map_err(AppError::from)?
+                        .is_some();
+                    if !parent_alive {
+                        return Err(RecycleBinError::ParentDeleted.into());
+                    }
+                }
+
+                let mut organization: SysOrganizationActiveModel = organization.into();
+                organization.deleted_at = Set(None);
+                organization.deleted_by = Set(None);
+                organization.version = Set(organization.version.as_ref() + 1);
+                organization.update(txn).await.map_err(AppError::from)?;
+                Ok(())
+            },
+        }
+    }
+
+    /// 在事务内彻底删除已软删除的实体，绑定关系已在软删除时移除
+    async fn purge_in_transaction(
+        txn: &DatabaseTransaction,
+        entry: &SysRecycleBinModel,
+        snapshot: &RecycleSnapshot,
+    ) -> Result<(), AppError> {
+        match entry.entity_type {
+            RecycleEntityType::User => {
+                SysUser::delete_many()
+                    .filter(SysUserColumn::Id.eq(entry.entity_id.as_str()))
+                    .filter(SysUserColumn::DeletedAt.is_not_null())
+                    .exec(txn)
+                    .await
+                    .map_err(AppError::from)?;
+            },
+            RecycleEntityType::Role => {
+                SysRole::delete_many()
+                    .filter(SysRoleColumn::Id.eq(entry.entity_id.as_str()))
+                    .filter(SysRoleColumn::DeletedAt.is_not_null())
+                    .exec(txn)
+                    .await
+                    .map_err(AppError::from)?;
+            },
+            RecycleEntityType::Menu => {
+                SysMenu::delete_many()
+                    .filter(SysMenuColumn::Id.is_in(Self::menu_ids(entry, snapshot)?))
+                    .filter(SysMenuColumn::DeletedAt.is_not_null())
+                    .exec(txn)
+                    .await
+                    .map_err(AppError::from)?;
+            },
+            RecycleEntityType::Organization => {
+                // 已删除用户可能仍指向该组织，恢复后不应引用不存在的组织
+                SysUser::update_many()
+                    .set(SysUserActiveModel {
+                        organization_id: Set(None),
+                        ..Default::default()
+                    })
+                    .col_expr(
+                        SysUserColumn::Version,
+                        Expr::col(SysUserColumn::Version).add(1),
+                    )
+                    .filter(SysUserColumn::OrganizationId.eq(entry.entity_id.as_str()))
+                    .exec(txn)
+                    .await
+                    .map_err(AppError::from)?;
+
+                SysOrganization::delete_many()
+                    .filter(SysOrganizationColumn::Id.eq(entry.entity_id.as_str()))
+                    .filter(SysOrganizationColumn::DeletedAt.is_not_null())
+                    .exec(txn)
+                    .await
+                    .map_err(AppError::from)?;
+            },
+            // 域的彻底删除需要级联清理域内数据和策略，由 `SysDomainService::purge_domain` 处理
+            RecycleEntityType::Domain => {},
+        }
+
+        SysRecycleBin::delete_by_id(entry.id.as_str())
+            .exec(txn)
+            .await
+            .map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn purge(
+        &self,
+        entry: SysRecycleBinModel,
+        enforcer: &Arc<RwLock<impl MgmtApi>>,
+    ) -> Result<(), AppError> {
+        let snapshot = Self::parse_snapshot(&entry)?;
+        let db = db_helper::get_db_connection().await?;
+
+        if entry.entity_type == RecycleEntityType::Domain {
+            let domain = SysDomain::find_by_id(entry.entity_id.as_str())
+                .filter(SysDomainColumn::DeletedAt.is_not_null())
+                .one(db.as_ref())
+                .await
+                .map_err(AppError::from)?;
+            if let Some(domain) = domain {
+                SysDomainService
+                    .purge_domain(domain, Arc::clone(enforcer))
+                    .await?;
+            }
+        }
+
+        let txn = db.begin().await.map_err(AppError::from)?;
+        let result = Self::purge_in_transaction(&txn, &entry, &snapshot).await;
+
+        match result {
+            Ok(_) => txn.commit().await.map_err(AppError::from),
+            Err(e) => {
+                txn.rollback().await.map_err(AppError::from)?;
+                Err(e)
+            },
+        }
+    }
+
+    /// 彻底删除范围内早于 `cutoff` 的条目，单个条目失败时记录错误并继续，返回清理的条目数
+    async fn purge_deleted_before(
+        &self,
+        cutoff: NaiveDateTime,
+        scope: Condition,
+        enforcer: &Arc<RwLock<impl MgmtApi>>,
+    ) -> Result<u64, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let entries = SysRecycleBin::find()
+            .filter(SysRecycleBinColumn::DeletedAt.lt(cutoff))
+            .filter(scope)
+            .order_by_asc(SysRecycleBinColumn::DeletedAt)
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        let mut purged = 0;
+        for entry in entries {
+            let id = entry.id.clone();
+            match self.purge(entry, enforcer).await {
+                Ok(()) => purged += 1,
+                Err(e) => project_error!("Failed to purge recycle bin entry {}: {:?}", id, e),
+            }
+        }
+
+        Ok(purged)
+    }
+}
+
+/// 回收站的管理范围：内置域可管理全部条目，其他域只能管理本域的条目
+fn domain_scope(domain: &str) -> Condition {
+    if domain == BUILT_IN_DOMAIN {
+        Condition::all()
+    } else {
+        Condition::all().add(SysRecycleBinColumn::Domain.eq(domain))
+    }
+}
+
+/// 回收站清理后台任务，按配置间隔彻底删除超过保留天数的条目
+pub async fn recycle_bin_purge_task<E>(config: RecycleBinConfig, enforcer: Arc<RwLock<E>>)
+where
+    E: MgmtApi + Send + Sync,
+{
+    let mut ticker = tokio::time::interval(StdDuration::from_secs(config.interval_secs.max(1)));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+
+        let cutoff = Local::now().naive_local() - Duration::days(i64::from(config.retention_days));
+        match SysRecycleBinService
+            .purge_deleted_before(cutoff, Condition::all(), &enforcer)
+            .await
+        {
+            Ok(0) => {},
+            Ok(count) => project_info!("Purged {} expired recycle bin entries", count),
+            Err(e) => project_error!("Failed to purge expired recycle bin entries: {:?}", e),
+        }
+    }
+}
+
+#[async_trait]
+impl TRecycleBinService for SysRecycleBinService {
+    async fn find_paginated_entries(
+        &self,
+        params: RecycleBinPageRequest,
+        user: User,
+    ) -> Result<PaginatedData<SysRecycleBinModel>, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let mut query = SysRecycleBin::find()
+            .filter(domain_scope(&user.domain()))
+            .order_by_desc(SysRecycleBinColumn::DeletedAt);
+
+        if let Some(entity_type) = params.entity_type {
+            query = query.filter(SysRecycleBinColumn::EntityType.eq(entity_type));
+        }
+
+        if let Some(ref keywords) = params.keywords {
+            let condition =
+                Condition::any().add(SysRecycleBinColumn::EntityName.contains(keywords));
+            query = query.filter(condition);
+        }
+
+        let total = query
+            .clone()
+            .count(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        let paginator = query.paginate(db.as_ref(), params.page_details.size);
+        let records = paginator
+            .fetch_page(params.page_details.current - 1)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(PaginatedData {
+            current: params.page_details.current,
+            size: params.page_details.size,
+            total,
+            records,
+        })
+    }
+
+    async fn restore_entry(
+        &self,
+        id: &str,
+        user: User,
+        enforcer: Arc<RwLock<impl MgmtApi>>,
+    ) -> Result<(), AppError> {
+        let entry = self.get_entry(id, &user.domain()).await?;
+        let snapshot = Self::parse_snapshot(&entry)?;
+
+        // 只补回当前不存在的策略，回滚时也只移除这些策略
+        let (policies, grouping_policies) = {
+            let enforcer_read = enforcer.read().await;
+            let policies: Vec<Vec<String>> = snapshot
+                .policies
+                .iter()
+                .filter(|policy| !enforcer_read.has_policy((*policy).clone()))
+                .cloned()
+                .collect();
+            let grouping_policies: Vec<Vec<String>> = snapshot
+                .grouping_policies
+                .iter()
+                .filter(|policy| !enforcer_read.has_grouping_policy((*policy).clone()))
+                .cloned()
+                .collect();
+            (policies, grouping_policies)
+        };
+
+        let db = db_helper::get_db_connection().await?;
+        let txn = db.begin().await.map_err(AppError::from)?;
+
+        let result = async {
+            Self::restore_in_transaction(&txn, &entry, &snapshot).await?;
+
+            SysRecycleBin::delete_by_id(entry.id.as_str())
+                .exec(&txn)
+                .await
+                .map_err(AppError::from)?;
+
+            Ok::<(), AppError>(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            txn.rollback().await.map_err(AppError::from)?;
+            return Err(e);
+        }
+
+        let added = async {
+            let mut enforcer_write = enforcer.write().await;
+            if !policies.is_empty() {
+                enforcer_write.add_policies(policies.clone()).await?;
+            }
+            if !grouping_policies.is_empty() {
+                enforcer_write
+                    .add_grouping_policies(grouping_policies.clone())
+                    .await?;
+            }
+            Ok::<(), casbin::Error>(())
+        }
+        .await;
+
+        let revert = || async {
+            let mut enforcer_write = enforcer.write().await;
+            for policy in policies.clone() {
+                if let Err(e) = enforcer_write.remove_policy(policy).await {
+                    project_error!("Failed to revert restored policy: {:?}", e);
+                }
+            }
+            for policy in grouping_policies.clone() {
+                if let Err(e) = enforcer_write.remove_grouping_policy(policy).await {
+                    project_error!("Failed to revert restored grouping policy: {:?}", e);
+                }
+            }
+        };
+
+        if let Err(e) = added {
+            txn.rollback().await.map_err(AppError::from)?;
+            revert().await;
+            return Err(RecycleBinError::PolicyError(e.to_string()).into());
+        }
+
+        if let Err(e) = txn.commit().await {
+            // 数据库提交失败，移除已补回的策略
+            revert().await;
+            return Err(AppError::from(e));
+        }
+
+        Ok(())
+    }
+
+    async fn purge_entry(
+        &self,
+        id: &str,
+        user: User,
+        enforcer: Arc<RwLock<impl MgmtApi>>,
+    ) -> Result<(), AppError> {
+        let entry = self.get_entry(id, &user.domain()).await?;
+        self.purge(entry, &enforcer).await
+    }
+
+    async fn purge_expired(
+        &self,
+        input: PurgeRecycleBinInput,
+        user: User,
+        enforcer: Arc<RwLock<impl MgmtApi>>,
+    ) -> Result<u64, AppError> {
+        let cutoff = Local::now().naive_local() - Duration::days(i64::from(input.retention_days));
+        self.purge_deleted_before(cutoff, domain_scope(&user.domain()), &enforcer)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, QueryTrait, Value};
+    use server_model::admin::entities::{
+        sea_orm_active_enums::Status, sys_role::Model as SysRoleModel,
+    };
+
+    use super::*;
+
+    fn id_rows<T: Into<Value>>(
+        ids: impl IntoIterator<Item = T>,
+    ) -> Vec<BTreeMap<&'static str, Value>> {
+        ids.into_iter()
+            .map(|id| BTreeMap::from([("id", id.into())]))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_restore_role_skips_deleted_bindings() {
+        let now = Local::now().naive_local();
+        let role = SysRoleModel {
+            id: "r1".to_string(),
+            code: "R_TEST".to_string(),
+            name: "test".to_string(),
+            description: None,
+            pid: "0".to_string(),
+            status: Status::Enabled,
+            created_at: now,
+            created_by: "admin".to_string(),
+            updated_at: None,
+            updated_by: None,
+            version: 1,
+            deleted_at: Some(now),
+            deleted_by: Some("admin".to_string()),
+        };
+        let restored = SysRoleModel {
+            deleted_at: None,
+            deleted_by: None,
+            ..role.clone()
+        };
+        let exec = || MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        };
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![role]])
+            .append_query_results([vec![restored]])
+            .append_query_results([id_rows(["u1"])])
+            .append_exec_results([exec()])
+            .append_query_results([id_rows([1])])
+            .append_exec_results([exec()])
+            .into_connection();
+
+        let entry = SysRecycleBinModel {
+            id: "e1".to_string(),
+            entity_type: RecycleEntityType::Role,
+            entity_id: "r1".to_string(),
+            entity_name: "test".to_string(),
+            snapshot: String::new(),
+            deleted_at: now,
+            deleted_by: "admin".to_string(),
+            domain: "built-in".to_string(),
+        };
+        let snapshot = RecycleSnapshot {
+            user_roles: vec![
+                ("u1".to_string(), "r1".to_string()),
+                ("u2".to_string(), "r1".to_string()),
+            ],
+            role_menus: vec![
+                ("r1".to_string(), 1, "built-in".to_string()),
+                ("r1".to_string(), 2, "built-in".to_string()),
+            ],
+            ..Default::default()
+        };
+
+        let txn = db.begin().await.unwrap();
+        SysRecycleBinService::restore_in_transaction(&txn, &entry, &snapshot)
+            .await
+            .unwrap();
+        txn.commit().await.unwrap();
+
+        let statements: Vec<String> = db
+            .into_transaction_log()
+            .iter()
+            .flat_map(|transaction| transaction.statements())
+            .map(ToString::to_string)
+            .collect();
+        let role_update = statements
+            .iter()
+            .find(|sql| sql.starts_with(r#"UPDATE "sys_role""#))
+            .unwrap();
+        assert!(role_update.contains(r#""deleted_at" = NULL"#));
+        assert!(role_update.contains(r#""version" = 2"#));
+        let user_role_insert = statements
+            .iter()
+            .find(|sql| sql.starts_with(r#"INSERT INTO "sys_user_role""#))
+            .unwrap();
+        assert!(user_role_insert.contains("'u1'"));
+        assert!(!user_role_insert.contains("'u2'"));
+        let role_menu_insert = statements
+            .iter()
+            .find(|sql| sql.starts_with(r#"INSERT INTO "sys_role_menu""#))
+            .unwrap();
+        assert!(role_menu_insert.contains("'r1', 1,"));
+        assert!(!role_menu_insert.contains("'r1', 2,"));
+    }
+
+    #[test]
+    fn test_domain_scope() {
+        let sql = SysRecycleBin::find_by_id("e1")
+            .filter(domain_scope("tenant"))
+            .build(DatabaseBackend::Postgres)
+            .to_string();
+        assert!(sql.ends_with(r#"AND "sys_recycle_bin"."domain" = 'tenant'"#));
+
+        let sql = SysRecycleBin::find_by_id("e1")
+            .filter(domain_scope(BUILT_IN_DOMAIN))
+            .build(DatabaseBackend::Postgres)
+            .to_string();
+        assert!(!sql.contains(r#""domain" ="#));
+    }
+}
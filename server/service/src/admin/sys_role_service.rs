@@ -1,23 +1,58 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use axum_casbin::casbin::{self, MgmtApi};
 use chrono::Local;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, Set,
+    TransactionTrait,
+};
+use server_core::web::{
+    auth::User,
+    error::AppError,
+    etag::{check_version, map_version_conflict},
+    page::{PaginatedData, QuerySpec, SortOrder},
 };
-use server_core::web::{error::AppError, page::PaginatedData};
 use server_model::admin::{
     entities::{
-        prelude::SysRole,
+        prelude::{SysRole, SysRoleMenu, SysUserRole},
+        sea_orm_active_enums::RecycleEntityType,
         sys_role::{
             ActiveModel as SysRoleActiveModel, Column as SysRoleColumn, Model as SysRoleModel,
         },
+        sys_role_menu::Column as SysRoleMenuColumn,
+        sys_user_role::Column as SysUserRoleColumn,
     },
     input::{CreateRoleInput, RolePageRequest, UpdateRoleInput},
 };
+use tokio::sync::RwLock;
 
 use super::sys_role_error::RoleError;
-use crate::helper::db_helper;
+use crate::{
+    admin::{
+        dto::sys_recycle_bin_dto::RecycleSnapshot, SysRecycleBinService, SysUserService,
+        STATUS_VALUES,
+    },
+    helper::db_helper,
+    project_error,
+};
 use ulid::Ulid;
 
+/// 角色列表的查询白名单
+const ROLE_QUERY: QuerySpec<SysRoleColumn> = QuerySpec {
+    sort_fields: &[
+        ("createdAt", SysRoleColumn::CreatedAt),
+        ("updatedAt", SysRoleColumn::UpdatedAt),
+        ("code", SysRoleColumn::Code),
+        ("name", SysRoleColumn::Name),
+    ],
+    filter_fields: &[("code", SysRoleColumn::Code), ("pid", SysRoleColumn::Pid)],
+    status: Some((SysRoleColumn::Status, STATUS_VALUES)),
+    created_at: SysRoleColumn::CreatedAt,
+    id: SysRoleColumn::Id,
+    default_sort: (SysRoleColumn::CreatedAt, SortOrder::Desc),
+};
+
 #[async_trait]
 pub trait TRoleService {
     async fn find_paginated_roles(
@@ -28,7 +63,13 @@ pub trait TRoleService {
     async fn create_role(&self, input: CreateRoleInput) -> Result<SysRoleModel, AppError>;
     async fn get_role(&self, id: &str) -> Result<SysRoleModel, AppError>;
     async fn update_role(&self, input: UpdateRoleInput) -> Result<SysRoleModel, AppError>;
-    async fn delete_role(&self, id: &str) -> Result<(), AppError>;
+    /// 软删除角色并移入回收站，同时移除其菜单绑定、用户绑定以及 Casbin 中的 `p` 规则和 `g` 关联
+    async fn delete_role(
+        &self,
+        id: &str,
+        user: User,
+        enforcer: Arc<RwLock<impl MgmtApi>>,
+    ) -> Result<(), AppError>;
 }
 
 #[derive(Clone)]
@@ -51,6 +92,30 @@ impl SysRoleService {
 
         Ok(())
     }
+
+    /// 恢复删除角色时从 Casbin 中移除的策略，逐条添加以跳过未被移除的规则
+    pub(crate) async fn restore_policies(
+        enforcer: &Arc<RwLock<impl MgmtApi>>,
+        role_code: &str,
+        policies: Vec<Vec<String>>,
+        grouping_policies: Vec<Vec<String>>,
+    ) {
+        let mut enforcer_write = enforcer.write().await;
+        for policy in policies {
+            if let Err(e) = enforcer_write.add_policy(policy).await {
+                project_error!("Failed to restore policy of role {}: {:?}", role_code, e);
+            }
+        }
+        for policy in grouping_policies {
+            if let Err(e) = enforcer_write.add_grouping_policy(policy).await {
+                project_error!(
+                    "Failed to restore grouping policy of role {}: {:?}",
+                    role_code,
+                    e
+                );
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -60,13 +125,15 @@ impl TRoleService for SysRoleService {
         params: RolePageRequest,
     ) -> Result<PaginatedData<SysRoleModel>, AppError> {
         let db = db_helper::get_db_connection().await?;
-        let mut query = SysRole::find();
+        let mut query = SysRole::find().filter(SysRoleColumn::DeletedAt.is_null());
 
         if let Some(ref keywords) = params.keywords {
             let condition = Condition::any().add(SysRoleColumn::Name.contains(keywords));
             query = query.filter(condition);
         }
 
+        let query = ROLE_QUERY.apply(query, &params.query)?;
+
         let total = query
             .clone()
             .count(db.as_ref())
@@ -111,6 +178,7 @@ impl TRoleService for SysRoleService {
     async fn get_role(&self, id: &str) -> Result<SysRoleModel, AppError> {
         let db = db_helper::get_db_connection().await?;
         SysRole::find_by_id(id)
+            .filter(SysRoleColumn::DeletedAt.is_null())
             .one(db.as_ref())
             .await
             .map_err(AppError::from)?
@@ -123,12 +191,14 @@ impl TRoleService for SysRoleService {
         self.check_role_exists(Some(&input.id), &input.role.code)
             .await?;
 
-        let role: SysRoleActiveModel = SysRole::find_by_id(&input.id)
+        let existing_role = SysRole::find_by_id(&input.id)
+            .filter(SysRoleColumn::DeletedAt.is_null())
             .one(db.as_ref())
             .await
             .map_err(AppError::from)?
-            .ok_or_else(|| AppError::from(RoleError::RoleNotFound))?
-            .into();
+            .ok_or_else(|| AppError::from(RoleError::RoleNotFound))?;
+        let version = check_version(existing_role.version, input.version)?;
+        let role: SysRoleActiveModel = existing_role.into();
 
         let role = SysRoleActiveModel {
             id: Set(input.id.clone()),
@@ -138,19 +208,137 @@ impl TRoleService for SysRoleService {
             description: Set(input.role.description),
 
             updated_at: Set(Some(Local::now().naive_local())),
+            version: Set(version + 1),
             ..role
         };
 
-        let updated_role = role.update(db.as_ref()).await.map_err(AppError::from)?;
+        let updated_role = SysRole::update(role)
+            .filter(SysRoleColumn::Version.eq(version))
+            .exec(db.as_ref())
+            .await
+            .map_err(map_version_conflict)?;
         Ok(updated_role)
     }
 
-    async fn delete_role(&self, id: &str) -> Result<(), AppError> {
+    async fn delete_role(
+        &self,
+        id: &str,
+        user: User,
+        enforcer: Arc<RwLock<impl MgmtApi>>,
+    ) -> Result<(), AppError> {
+        let role = self.get_role(id).await?;
+
         let db = db_helper::get_db_connection().await?;
-        SysRole::delete_by_id(id)
-            .exec(db.as_ref())
+
+        let (policies, grouping_policies) = {
+            let enforcer_read = enforcer.read().await;
+            let policies = enforcer_read.get_filtered_policy(0, vec![role.code.clone()]);
+            let mut grouping_policies =
+                enforcer_read.get_filtered_grouping_policy(0, vec![role.code.clone()]);
+            grouping_policies
+                .extend(enforcer_read.get_filtered_grouping_policy(1, vec![role.code.clone()]));
+            (policies, grouping_policies)
+        };
+
+        let role_menus = SysRoleMenu::find()
+            .filter(SysRoleMenuColumn::RoleId.eq(role.id.as_str()))
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+        let user_roles = SysUserRole::find()
+            .filter(SysUserRoleColumn::RoleId.eq(role.id.as_str()))
+            .all(db.as_ref())
             .await
             .map_err(AppError::from)?;
+
+        let snapshot = RecycleSnapshot {
+            user_roles: user_roles
+                .into_iter()
+                .map(|user_role| (user_role.user_id, user_role.role_id))
+                .collect(),
+            role_menus: role_menus
+                .into_iter()
+                .map(|role_menu| (role_menu.role_id, role_menu.menu_id, role_menu.domain))
+                .collect(),
+            policies: policies.clone(),
+            grouping_policies: grouping_policies.clone(),
+            ..Default::default()
+        };
+
+        let txn = db.begin().await.map_err(AppError::from)?;
+
+        let result = async {
+            SysUserService::ensure_admins_remain(&txn, &[], std::slice::from_ref(&role.id)).await?;
+
+            SysRoleMenu::delete_many()
+                .filter(SysRoleMenuColumn::RoleId.eq(role.id.as_str()))
+                .exec(&txn)
+                .await
+                .map_err(AppError::from)?;
+
+            SysUserRole::delete_many()
+                .filter(SysUserRoleColumn::RoleId.eq(role.id.as_str()))
+                .exec(&txn)
+                .await
+                .map_err(AppError::from)?;
+
+            let mut deleted_role: SysRoleActiveModel = role.clone().into();
+            deleted_role.deleted_at = Set(Some(Local::now().naive_local()));
+            deleted_role.deleted_by = Set(Some(user.user_id()));
+            deleted_role.version = Set(deleted_role.version.as_ref() + 1);
+            deleted_role.update(&txn).await.map_err(AppError::from)?;
+
+            SysRecycleBinService::record(
+                &txn,
+                RecycleEntityType::Role,
+                &role.id,
+                &role.name,
+                &snapshot,
+                &user.domain(),
+                &user,
+            )
+            .await?;
+
+            Ok::<(), AppError>(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            txn.rollback().await.map_err(AppError::from)?;
+            return Err(e);
+        }
+
+        let removed = async {
+            let mut enforcer_write = enforcer.write().await;
+            if !policies.is_empty() {
+                enforcer_write
+                    .remove_filtered_policy(0, vec![role.code.clone()])
+                    .await?;
+            }
+            if !grouping_policies.is_empty() {
+                enforcer_write
+                    .remove_filtered_grouping_policy(0, vec![role.code.clone()])
+                    .await?;
+                enforcer_write
+                    .remove_filtered_grouping_policy(1, vec![role.code.clone()])
+                    .await?;
+            }
+            Ok::<(), casbin::Error>(())
+        }
+        .await;
+
+        if let Err(e) = removed {
+            txn.rollback().await.map_err(AppError::from)?;
+            Self::restore_policies(&enforcer, &role.code, policies, grouping_policies).await;
+            return Err(RoleError::PolicyError(e.to_string()).into());
+        }
+
+        if let Err(e) = txn.commit().await {
+            // 数据库提交失败，恢复已删除的策略
+            Self::restore_policies(&enforcer, &role.code, policies, grouping_policies).await;
+            return Err(AppError::from(e));
+        }
+
         Ok(())
     }
 }
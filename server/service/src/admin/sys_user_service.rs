@@ -1,16 +1,29 @@
+use std::collections::{HashMap, HashSet};
+
 use async_trait::async_trait;
 use chrono::Local;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, IntoActiveModel, PaginatorTrait,
-    QueryFilter, Set,
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, EntityTrait,
+    IntoActiveModel, JoinType, PaginatorTrait, QueryFilter, QuerySelect, RelationTrait, Set,
+    TransactionTrait,
+};
+use server_constant::definition::consts::{TokenStatus, ADMIN_ROLE_CODES};
+use server_core::web::{
+    auth::User,
+    error::AppError,
+    etag::{check_version, map_version_conflict},
+    page::{PaginatedData, QuerySpec, SortOrder},
 };
-use server_core::web::{error::AppError, page::PaginatedData};
 use server_model::admin::{
     entities::{
-        prelude::SysUser,
+        prelude::{SysTokens, SysUser, SysUserRole},
+        sea_orm_active_enums::{RecycleEntityType, Status},
+        sys_role::Column as SysRoleColumn,
+        sys_tokens::Column as SysTokensColumn,
         sys_user::{
             ActiveModel as SysUserActiveModel, Column as SysUserColumn, Model as SysUserModel,
         },
+        sys_user_role::{Column as SysUserRoleColumn, Relation as SysUserRoleRelation},
     },
     input::{CreateUserInput, UpdateUserInput, UserPageRequest},
     output::UserWithoutPassword,
@@ -19,7 +32,33 @@ use server_utils::SecureUtil;
 use ulid::Ulid;
 
 use super::sys_user_error::UserError;
-use crate::helper::db_helper;
+use crate::{
+    admin::{
+        dto::sys_recycle_bin_dto::RecycleSnapshot, sys_auth_service::sync_revoked_tokens,
+        SysRecycleBinService, STATUS_VALUES,
+    },
+    helper::db_helper,
+};
+
+/// 用户列表的查询白名单
+const USER_QUERY: QuerySpec<SysUserColumn> = QuerySpec {
+    sort_fields: &[
+        ("createdAt", SysUserColumn::CreatedAt),
+        ("updatedAt", SysUserColumn::UpdatedAt),
+        ("username", SysUserColumn::Username),
+        ("nickName", SysUserColumn::NickName),
+    ],
+    filter_fields: &[
+        ("domain", SysUserColumn::Domain),
+        ("username", SysUserColumn::Username),
+        ("organizationId", SysUserColumn::OrganizationId),
+        ("builtIn", SysUserColumn::BuiltIn),
+    ],
+    status: Some((SysUserColumn::Status, STATUS_VALUES)),
+    created_at: SysUserColumn::CreatedAt,
+    id: SysUserColumn::Id,
+    default_sort: (SysUserColumn::CreatedAt, SortOrder::Desc),
+};
 
 #[async_trait]
 pub trait TUserService {
@@ -32,7 +71,8 @@ pub trait TUserService {
     async fn create_user(&self, input: CreateUserInput) -> Result<UserWithoutPassword, AppError>;
     async fn get_user(&self, id: &str) -> Result<UserWithoutPassword, AppError>;
     async fn update_user(&self, input: UpdateUserInput) -> Result<UserWithoutPassword, AppError>;
-    async fn delete_user(&self, id: &str) -> Result<(), AppError>;
+    /// 软删除用户并移入回收站，同时移除其角色绑定并撤销令牌
+    async fn delete_user(&self, id: &str, user: User) -> Result<(), AppError>;
 }
 
 #[derive(Clone)]
@@ -56,11 +96,88 @@ impl SysUserService {
     async fn get_user_by_id(&self, id: String) -> Result<SysUserModel, AppError> {
         let db = db_helper::get_db_connection().await?;
         SysUser::find_by_id(id)
+            .filter(SysUserColumn::DeletedAt.is_null())
             .one(db.as_ref())
             .await
             .map_err(AppError::from)?
             .ok_or_else(|| UserError::UserNotFound.into())
     }
+
+    /// 判断角色是否为管理员角色，包括开通租户时复制出的 `{角色编码}_{域编码}`
+    pub(crate) fn is_admin_role(role_code: &str) -> bool {
+        ADMIN_ROLE_CODES.iter().any(|admin| {
+            role_code == *admin
+                || role_code
+                    .strip_prefix(admin)
+                    .is_some_and(|rest| rest.starts_with('_'))
+        })
+    }
+
+    /// 校验删除用户或角色后，原本有启用管理员的域仍至少保留一名
+    ///
+    /// 须在执行删除的事务内调用：查询以 `FOR UPDATE` 锁定用户角色绑定，
+    /// 并发的删除会等待前一个事务提交后再重新校验。
+    pub(crate) async fn ensure_admins_remain<C: ConnectionTrait>(
+        db: &C,
+        removed_user_ids: &[String],
+        removed_role_ids: &[String],
+    ) -> Result<(), AppError> {
+        let bindings = SysUserRole::find()
+            .select_only()
+            .column(SysUserRoleColumn::UserId)
+            .column(SysUserRoleColumn::RoleId)
+            .column(SysUserColumn::Domain)
+            .column(SysRoleColumn::Code)
+            .join(JoinType::InnerJoin, SysUserRoleRelation::SysUser.def())
+            .join(JoinType::InnerJoin, SysUserRoleRelation::SysRole.def())
+            .filter(SysUserColumn::Status.eq(Status::Enabled))
+            .lock_exclusive()
+            .into_tuple::<(String, String, String, String)>()
+            .all(db)
+            .await
+            .map_err(AppError::from)?;
+
+        let mut admins: HashMap<String, (HashSet<String>, HashSet<String>)> = HashMap::new();
+        for (user_id, role_id, domain, role_code) in bindings {
+            if !Self::is_admin_role(&role_code) {
+                continue;
+            }
+            let (before, after) = admins.entry(domain).or_default();
+            if !removed_user_ids.contains(&user_id) && !removed_role_ids.contains(&role_id) {
+                after.insert(user_id.clone());
+            }
+            before.insert(user_id);
+        }
+
+        if admins
+            .values()
+            .any(|(before, after)| !before.is_empty() && after.is_empty())
+        {
+            return Err(UserError::LastAdmin.into());
+        }
+
+        Ok(())
+    }
+
+    /// 撤销用户仍然有效的令牌
+    ///
+    /// 事务提交后需调用 `sync_revoked_tokens`，令牌才会在各实例上立即失效。
+    pub(crate) async fn revoke_tokens<C: ConnectionTrait>(
+        db: &C,
+        condition: Condition,
+    ) -> Result<(), AppError> {
+        SysTokens::update_many()
+            .col_expr(
+                SysTokensColumn::Status,
+                Expr::value(TokenStatus::Revoked.to_string()),
+            )
+            .filter(SysTokensColumn::Status.eq(TokenStatus::Active.to_string()))
+            .filter(condition)
+            .exec(db)
+            .await
+            .map_err(AppError::from)?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -68,6 +185,7 @@ impl TUserService for SysUserService {
     async fn find_all(&self) -> Result<Vec<UserWithoutPassword>, AppError> {
         let db = db_helper::get_db_connection().await?;
         SysUser::find()
+            .filter(SysUserColumn::DeletedAt.is_null())
             .all(db.as_ref())
             .await
             .map(|users| users.into_iter().map(UserWithoutPassword::from).collect())
@@ -79,13 +197,15 @@ impl TUserService for SysUserService {
         params: UserPageRequest,
     ) -> Result<PaginatedData<UserWithoutPassword>, AppError> {
         let db = db_helper::get_db_connection().await?;
-        let mut query = SysUser::find();
+        let mut query = SysUser::find().filter(SysUserColumn::DeletedAt.is_null());
 
         if let Some(ref keywords) = params.keywords {
             let condition = Condition::any().add(SysUserColumn::Username.contains(keywords));
             query = query.filter(condition);
         }
 
+        let query = USER_QUERY.apply(query, &params.query)?;
+
         let total = query
             .clone()
             .count(db.as_ref())
@@ -123,6 +243,7 @@ impl TUserService for SysUserService {
             avatar: Set(input.avatar),
             email: Set(input.email),
             phone_number: Set(input.phone_number),
+            organization_id: Set(input.organization_id),
             status: Set(input.status),
             created_at: Set(Local::now().naive_local()),
             created_by: Set("TODO".to_string()),
@@ -136,6 +257,7 @@ impl TUserService for SysUserService {
     async fn get_user(&self, id: &str) -> Result<UserWithoutPassword, AppError> {
         let db = db_helper::get_db_connection().await?;
         SysUser::find_by_id(id)
+            .filter(SysUserColumn::DeletedAt.is_null())
             .one(db.as_ref())
             .await
             .map_err(AppError::from)?
@@ -144,7 +266,9 @@ impl TUserService for SysUserService {
     }
 
     async fn update_user(&self, input: UpdateUserInput) -> Result<UserWithoutPassword, AppError> {
-        let mut user = self.get_user_by_id(input.id).await?.into_active_model();
+        let existing_user = self.get_user_by_id(input.id).await?;
+        let version = check_version(existing_user.version, input.version)?;
+        let mut user = existing_user.into_active_model();
 
         if input.user.username != *user.username.as_ref() {
             self.check_username_unique(&input.user.username).await?;
@@ -157,25 +281,114 @@ impl TUserService for SysUserService {
         user.avatar = Set(input.user.avatar);
         user.email = Set(input.user.email);
         user.phone_number = Set(input.user.phone_number);
+        user.organization_id = Set(input.user.organization_id);
         user.status = Set(input.user.status);
+        user.version = Set(version + 1);
 
         let db = db_helper::get_db_connection().await?;
-        let updated_user = user.update(db.as_ref()).await.map_err(AppError::from)?;
+        let updated_user = SysUser::update(user)
+            .filter(SysUserColumn::Version.eq(version))
+            .exec(db.as_ref())
+            .await
+            .map_err(map_version_conflict)?;
         Ok(UserWithoutPassword::from(updated_user))
     }
 
-    async fn delete_user(&self, id: &str) -> Result<(), AppError> {
+    async fn delete_user(&self, id: &str, user: User) -> Result<(), AppError> {
+        let target = self.get_user_by_id(id.to_string()).await?;
+
+        if target.built_in {
+            return Err(UserError::BuiltInUser.into());
+        }
+
         let db = db_helper::get_db_connection().await?;
 
-        let result = SysUser::delete_by_id(id)
-            .exec(db.as_ref())
+        let user_roles = SysUserRole::find()
+            .filter(SysUserRoleColumn::UserId.eq(target.id.as_str()))
+            .all(db.as_ref())
             .await
             .map_err(AppError::from)?;
 
-        if result.rows_affected == 0 {
-            return Err(UserError::UserNotFound.into());
+        let snapshot = RecycleSnapshot {
+            user_roles: user_roles
+                .into_iter()
+                .map(|user_role| (user_role.user_id, user_role.role_id))
+                .collect(),
+            ..Default::default()
+        };
+
+        let txn = db.begin().await.map_err(AppError::from)?;
+
+        let result = async {
+            Self::ensure_admins_remain(&txn, std::slice::from_ref(&target.id), &[]).await?;
+
+            SysUserRole::delete_many()
+                .filter(SysUserRoleColumn::UserId.eq(target.id.as_str()))
+                .exec(&txn)
+                .await
+                .map_err(AppError::from)?;
+
+            Self::revoke_tokens(
+                &txn,
+                Condition::all().add(SysTokensColumn::UserId.eq(target.id.as_str())),
+            )
+            .await?;
+
+            let mut deleted_user: SysUserActiveModel = target.clone().into();
+            deleted_user.deleted_at = Set(Some(Local::now().naive_local()));
+            deleted_user.deleted_by = Set(Some(user.user_id()));
+            deleted_user.version = Set(deleted_user.version.as_ref() + 1);
+            deleted_user.update(&txn).await.map_err(AppError::from)?;
+
+            SysRecycleBinService::record(
+                &txn,
+                RecycleEntityType::User,
+                &target.id,
+                &target.username,
+                &snapshot,
+                &target.domain,
+                &user,
+            )
+            .await?;
+
+            Ok::<(), AppError>(())
         }
+        .await;
 
-        Ok(())
+        match result {
+            Ok(_) => {
+                txn.commit().await.map_err(AppError::from)?;
+                sync_revoked_tokens().await;
+                Ok(())
+            },
+            Err(e) => {
+                txn.rollback().await.map_err(AppError::from)?;
+                Err(e)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use sea_orm::{DbBackend, MockDatabase, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_ensure_admins_remain_locks_bindings() {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([Vec::<BTreeMap<&str, Value>>::new()])
+            .into_connection();
+
+        SysUserService::ensure_admins_remain(&db, &["user-1".to_string()], &[])
+            .await
+            .unwrap();
+
+        let log = db.into_transaction_log();
+        let sql = log[0].statements()[0].to_string();
+        assert!(sql.ends_with("FOR UPDATE"));
     }
 }
@@ -0,0 +1,182 @@
+//! 进程内缓存的跨实例同步
+//!
+//! 变更方通过 Redis 发布消息，其他实例收到后从数据库重新加载。发布订阅的消息可能丢失，
+//! 因此每次（重新）订阅成功后以及按固定间隔都会从数据库全量重新加载一次。
+//! Redis 集群模式下向任意节点发布的消息会广播到全部节点，订阅连接依次尝试配置中的节点。
+
+use std::{future::Future, time::Duration};
+
+use futures::StreamExt;
+use once_cell::sync::Lazy;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use server_config::RedisConfig;
+use server_global::global::{get_config, RedisConnection, GLOBAL_PRIMARY_REDIS};
+use tokio::time::MissedTickBehavior;
+use ulid::Ulid;
+
+use crate::{project_error, project_info, project_warn};
+
+/// Redis 订阅断开后的重连间隔（秒）
+const RESUBSCRIBE_DELAY_SECS: u64 = 5;
+
+/// 当前实例的标识，用于忽略自身发布的消息
+static INSTANCE_ID: Lazy<String> = Lazy::new(|| Ulid::new().to_string());
+
+/// 频道中传递的消息
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope<T> {
+    /// 发布消息的实例
+    origin: String,
+    event: T,
+}
+
+/// 通知其他实例缓存已变更，未配置 Redis 时视为单实例部署，不做处理
+pub async fn publish<T: Serialize>(channel: &str, event: &T) {
+    let Some(redis) = GLOBAL_PRIMARY_REDIS.read().await.clone() else {
+        return;
+    };
+
+    let Ok(payload) = serde_json::to_string(&Envelope {
+        origin: INSTANCE_ID.clone(),
+        event,
+    }) else {
+        return;
+    };
+    let command = redis::cmd("PUBLISH").arg(channel).arg(payload).to_owned();
+    let result = match redis {
+        RedisConnection::Single(client) => match client.get_multiplexed_async_connection().await {
+            Ok(mut conn) => command.query_async::<()>(&mut conn).await,
+            Err(e) => Err(e),
+        },
+        RedisConnection::Cluster(client) => match client.get_async_connection().await {
+            Ok(mut conn) => command.query_async::<()>(&mut conn).await,
+            Err(e) => Err(e),
+        },
+    };
+
+    if let Err(e) = result {
+        project_error!("Failed to publish to {}: {}", channel, e);
+    }
+}
+
+/// 接收其他实例发布的变更并定期全量重新加载，不会返回
+///
+/// # 参数
+/// * `channel` - Redis 频道
+/// * `reconcile_interval` - 全量重新加载的间隔
+/// * `on_event` - 处理其他实例发布的变更
+/// * `reload` - 从数据库全量重新加载缓存
+pub async fn run<T, E, EF, R, RF>(
+    channel: &'static str,
+    reconcile_interval: Duration,
+    on_event: E,
+    reload: R,
+) where
+    T: DeserializeOwned,
+    E: Fn(T) -> EF,
+    EF: Future<Output = ()>,
+    R: Fn() -> RF,
+    RF: Future<Output = ()>,
+{
+    let reconcile = async {
+        let start = tokio::time::Instant::now() + reconcile_interval;
+        let mut ticker = tokio::time::interval_at(start, reconcile_interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            reload().await;
+        }
+    };
+
+    let listen = async {
+        let clients = subscriber_clients().await;
+        if clients.is_empty() {
+            return std::future::pending::<()>().await;
+        }
+
+        // 断开后换下一个节点重新订阅，避免单个集群节点故障导致同步中断
+        for client in clients.iter().cycle() {
+            match subscribe(client, channel, &on_event, &reload).await {
+                Ok(()) => project_warn!("Subscription to {} closed", channel),
+                Err(e) => project_error!("Subscription to {} failed: {}", channel, e),
+            }
+            tokio::time::sleep(Duration::from_secs(RESUBSCRIBE_DELAY_SECS)).await;
+        }
+    };
+
+    tokio::join!(reconcile, listen);
+}
+
+/// 订阅可使用的连接，集群模式下为配置中的全部节点，未配置 Redis 时为空
+async fn subscriber_clients() -> Vec<redis::Client> {
+    match GLOBAL_PRIMARY_REDIS.read().await.clone() {
+        Some(RedisConnection::Single(client)) => vec![client.as_ref().clone()],
+        Some(RedisConnection::Cluster(_)) => get_config::<RedisConfig>()
+            .await
+            .and_then(|config| config.get_urls())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|url| {
+                redis::Client::open(url)
+                    .inspect_err(|e| project_error!("Failed to open Redis subscriber: {}", e))
+                    .ok()
+            })
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+async fn subscribe<T, E, EF, R, RF>(
+    client: &redis::Client,
+    channel: &str,
+    on_event: &E,
+    reload: &R,
+) -> redis::RedisResult<()>
+where
+    T: DeserializeOwned,
+    E: Fn(T) -> EF,
+    EF: Future<Output = ()>,
+    R: Fn() -> RF,
+    RF: Future<Output = ()>,
+{
+    let mut pubsub = client.get_async_pubsub().await?;
+    pubsub.subscribe(channel).await?;
+    project_info!("Subscribed to {}", channel);
+
+    // 断开期间发布的消息已丢失，订阅成功后全量重新加载
+    reload().await;
+
+    let mut messages = pubsub.on_message();
+    while let Some(message) = messages.next().await {
+        let envelope = message
+            .get_payload::<String>()
+            .ok()
+            .and_then(|payload| serde_json::from_str::<Envelope<T>>(&payload).ok());
+        match envelope {
+            Some(envelope) if envelope.origin != *INSTANCE_ID => on_event(envelope.event).await,
+            Some(_) => {},
+            None => project_warn!("Ignored malformed message on {}", channel),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelope() {
+        let payload = serde_json::to_string(&Envelope {
+            origin: "instance".to_string(),
+            event: ["a", "b"],
+        })
+        .unwrap();
+        assert_eq!(payload, r#"{"origin":"instance","event":["a","b"]}"#);
+
+        let envelope: Envelope<Vec<String>> = serde_json::from_str(&payload).unwrap();
+        assert_eq!(envelope.origin, "instance");
+        assert_eq!(envelope.event, vec!["a", "b"]);
+    }
+}
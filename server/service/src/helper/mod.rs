@@ -1,3 +1,5 @@
+pub mod cache_sync;
 pub mod db_helper;
 pub mod mongo_helper;
 pub mod redis_helper;
+pub mod s3_helper;
@@ -0,0 +1,39 @@
+use aws_sdk_s3::{primitives::ByteStream, Client};
+use server_core::web::error::AppError;
+use server_global::global::GLOBAL_PRIMARY_S3;
+
+/// 获取主 S3 客户端
+pub async fn get_primary_client() -> Result<Client, AppError> {
+    let client = GLOBAL_PRIMARY_S3
+        .read()
+        .await
+        .clone()
+        .ok_or_else(|| AppError {
+            code: 500,
+            message: "Primary S3 not initialized".to_string(),
+        })?;
+    Ok(client.as_ref().clone())
+}
+
+/// 使用主 S3 客户端上传对象
+pub async fn put_object(
+    bucket: &str,
+    key: &str,
+    body: Vec<u8>,
+    content_type: &str,
+) -> Result<(), AppError> {
+    let client = get_primary_client().await?;
+    client
+        .put_object()
+        .bucket(bucket)
+        .key(key)
+        .content_type(content_type)
+        .body(ByteStream::from(body))
+        .send()
+        .await
+        .map_err(|e| AppError {
+            code: 500,
+            message: format!("Failed to upload '{}' to S3: {}", key, e),
+        })?;
+    Ok(())
+}
@@ -1,7 +1,10 @@
 use std::error::Error;
 
 use argon2::{
-    password_hash::{rand_core::OsRng, SaltString},
+    password_hash::{
+        rand_core::{OsRng, RngCore},
+        SaltString,
+    },
     Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
 };
 use lazy_static::lazy_static;
@@ -10,6 +13,10 @@ lazy_static! {
     static ref ARGON2: Argon2<'static> = Argon2::default();
 }
 
+/// 随机密码字符集，去掉了容易混淆的 `0/O`、`1/l/I`
+const PASSWORD_CHARSET: &[u8] =
+    b"ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz23456789!@#$%^&*";
+
 pub struct SecureUtil;
 
 impl SecureUtil {
@@ -27,6 +34,24 @@ impl SecureUtil {
             Err(e) => Err(Box::new(e)),
         }
     }
+
+    /// 生成指定长度的随机密码
+    ///
+    /// 使用操作系统随机源，拒绝采样保证字符分布均匀。
+    pub fn generate_password(length: usize) -> String {
+        let bound = u8::MAX - (u8::MAX % PASSWORD_CHARSET.len() as u8);
+        let mut password = String::with_capacity(length);
+        let mut buf = [0u8; 1];
+
+        while password.len() < length {
+            OsRng.fill_bytes(&mut buf);
+            if buf[0] < bound {
+                password.push(PASSWORD_CHARSET[(buf[0] as usize) % PASSWORD_CHARSET.len()] as char);
+            }
+        }
+
+        password
+    }
 }
 
 #[cfg(test)]
@@ -51,6 +76,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_generate_password() {
+        let password = SecureUtil::generate_password(16);
+        assert_eq!(password.len(), 16);
+        assert!(password.bytes().all(|b| PASSWORD_CHARSET.contains(&b)));
+        assert_ne!(password, SecureUtil::generate_password(16));
+    }
+
     #[test]
     fn test_print_hashed_password() {
         let password = b"123456";
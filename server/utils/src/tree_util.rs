@@ -1,4 +1,7 @@
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
 
 use rayon::prelude::*;
 
@@ -164,6 +167,46 @@ impl TreeBuilder {
         root_nodes
     }
 
+    /// 判断节点是否为另一节点自身或其后代
+    ///
+    /// 从 `candidate` 沿父节点链向上查找，若遇到 `ancestor` 则返回 `true`。
+    /// 常用于移动节点前的环检测：将节点挂到自身或其后代下会形成环。
+    ///
+    /// # 参数
+    /// - `ancestor`: 祖先节点ID
+    /// - `candidate`: 待检测节点ID
+    /// - `parent_fn`: 根据节点ID获取父节点ID的函数，根节点返回 `None`
+    ///
+    /// # 示例
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use server_utils::TreeBuilder;
+    /// let parents: HashMap<i32, i32> = HashMap::from([(2, 1), (3, 2)]);
+    /// assert!(TreeBuilder::is_descendant_or_self(&1, &3, |id| parents.get(id).copied()));
+    /// ```
+    ///
+    /// 已存在的脏数据（父节点链本身有环）不会导致死循环。
+    pub fn is_descendant_or_self<Id, F>(ancestor: &Id, candidate: &Id, parent_fn: F) -> bool
+    where
+        Id: Eq + Hash + Clone,
+        F: Fn(&Id) -> Option<Id>,
+    {
+        let mut visited = HashSet::new();
+        let mut current = Some(candidate.clone());
+
+        while let Some(id) = current {
+            if &id == ancestor {
+                return true;
+            }
+            if !visited.insert(id.clone()) {
+                return false;
+            }
+            current = parent_fn(&id);
+        }
+
+        false
+    }
+
     /// 计算最优容量分配
     #[inline]
     fn calculate_capacity(len: usize) -> (usize, usize) {
@@ -177,7 +220,7 @@ impl TreeBuilder {
     /// 递归构建树结构
     #[inline]
     fn attach_children<T, Id, F1, F2>(
-        nodes: &mut Vec<T>,
+        nodes: &mut [T],
         child_map: &mut HashMap<Id, Vec<T>>,
         id_fn: &F1,
         set_children_fn: &mut F2,
@@ -246,6 +289,19 @@ mod tests {
         assert_eq!(tree[0].children[1].id, 2);
     }
 
+    #[test]
+    fn test_is_descendant_or_self() {
+        let parents: HashMap<i32, i32> = HashMap::from([(2, 1), (3, 2), (4, 1), (5, 6), (6, 5)]);
+        let parent_fn = |id: &i32| parents.get(id).copied();
+
+        assert!(TreeBuilder::is_descendant_or_self(&1, &1, parent_fn));
+        assert!(TreeBuilder::is_descendant_or_self(&1, &3, parent_fn));
+        assert!(TreeBuilder::is_descendant_or_self(&2, &3, parent_fn));
+        assert!(!TreeBuilder::is_descendant_or_self(&2, &4, parent_fn));
+        assert!(!TreeBuilder::is_descendant_or_self(&3, &1, parent_fn));
+        assert!(!TreeBuilder::is_descendant_or_self(&1, &5, parent_fn));
+    }
+
     #[test]
     fn test_performance() {
         let mut nodes = Vec::with_capacity(10000);
@@ -1,9 +1,16 @@
+use std::net::Ipv6Addr;
+
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use rand;
-use xdb::searcher::{
-    get_block_by_size, get_full_cache, get_vector_index_cache, search_by_ip, searcher_init,
+use xdb::{
+    maker::Maker,
+    searcher::{
+        get_block_by_size, get_full_cache, get_vector_index_cache, search_by_ip, search_by_ipv6,
+        searcher_init, LoadMode, Searcher,
+    },
 };
 
+const XDB_FILEPATH: &str = "../server/resources/ip2region.xdb";
+
 fn search_by_ip_bench(c: &mut Criterion) {
     c.bench_function("search_by_ip_bench", |b| {
         searcher_init(None);
@@ -13,30 +20,65 @@ fn search_by_ip_bench(c: &mut Criterion) {
     });
 }
 
+fn search_by_ipv6_bench(c: &mut Criterion) {
+    // 生成覆盖全部 IPv6 地址的 xdb 文件，按首个十六进制位分为 16 段
+    let source: String = (0..16u128)
+        .map(|i| {
+            let start = Ipv6Addr::from(i << 124);
+            let end = Ipv6Addr::from(i << 124 | u128::MAX >> 4);
+            format!("{}|{}|中国|0|网段{}|0|0\n", start, end, i)
+        })
+        .collect();
+    let path = std::env::temp_dir().join("ip2region_v6_bench.xdb");
+    std::fs::write(&path, Maker::new().make(&source).unwrap()).unwrap();
+
+    c.bench_function("search_by_ipv6_bench", |b| {
+        searcher_init(Some(path.to_string_lossy().into_owned()));
+        b.iter(|| {
+            search_by_ipv6(rand::random::<u128>()).unwrap();
+        })
+    });
+}
+
+fn searcher_load_mode_bench(c: &mut Criterion) {
+    for (name, mode) in [
+        ("searcher_mmap_bench", LoadMode::Mmap),
+        ("searcher_vector_index_bench", LoadMode::VectorIndex),
+        ("searcher_full_bench", LoadMode::Full),
+    ] {
+        let searcher = Searcher::from_path(XDB_FILEPATH, mode).unwrap();
+        c.bench_function(name, |b| {
+            b.iter(|| {
+                searcher.search_by_ip(rand::random::<u32>()).unwrap();
+            })
+        });
+    }
+}
+
 fn get_block_by_size_bench(c: &mut Criterion) {
+    searcher_init(None);
+    let bytes = get_full_cache().unwrap();
     c.bench_function("get_block_by_size_bench", |b| {
         b.iter(|| {
-            black_box(get_block_by_size(
-                get_full_cache(),
-                rand::random::<u16>() as usize,
-                4,
-            ));
+            black_box(get_block_by_size(&bytes, rand::random::<u16>() as usize, 4).unwrap());
         })
     });
 }
 
 fn get_full_cache_bench(c: &mut Criterion) {
     c.bench_function("get_full_cache_bench", |b| {
+        searcher_init(None);
         b.iter(|| {
-            black_box(get_full_cache());
+            black_box(get_full_cache().unwrap());
         })
     });
 }
 
 fn get_vec_index_cache_bench(c: &mut Criterion) {
     c.bench_function("get_vec_index_cache_bench", |b| {
+        searcher_init(None);
         b.iter(|| {
-            black_box(get_vector_index_cache());
+            black_box(get_vector_index_cache().unwrap());
         })
     });
 }
@@ -44,6 +86,8 @@ fn get_vec_index_cache_bench(c: &mut Criterion) {
 criterion_group!(
     benches,
     search_by_ip_bench,
+    search_by_ipv6_bench,
+    searcher_load_mode_bench,
     get_block_by_size_bench,
     get_full_cache_bench,
     get_vec_index_cache_bench,
@@ -0,0 +1,50 @@
+//! 从 `start_ip|end_ip|region` 源文件生成 xdb 文件
+//!
+//! ```text
+//! xdb_maker --src ip.merge.txt --dst ip2region.xdb [--allow-gaps]
+//! ```
+
+use std::process::ExitCode;
+
+use xdb::maker::Maker;
+
+const USAGE: &str = "usage: xdb_maker --src <source file> --dst <xdb file> [--allow-gaps]";
+
+fn main() -> ExitCode {
+    let mut src = None;
+    let mut dst = None;
+    let mut allow_gaps = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--src" => src = args.next(),
+            "--dst" => dst = args.next(),
+            "--allow-gaps" => allow_gaps = true,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            },
+            _ => {
+                eprintln!("unknown argument: {arg}\n{USAGE}");
+                return ExitCode::FAILURE;
+            },
+        }
+    }
+
+    let (Some(src), Some(dst)) = (src, dst) else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    match Maker::new().allow_gaps(allow_gaps).make_file(&src, &dst) {
+        Ok(()) => {
+            println!("xdb file generated: {dst}");
+            ExitCode::SUCCESS
+        },
+        Err(e) => {
+            eprintln!("failed to make xdb from {src}: {e}");
+            ExitCode::FAILURE
+        },
+    }
+}
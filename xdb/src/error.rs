@@ -0,0 +1,32 @@
+use thiserror::Error;
+
+use crate::searcher::{IpVersion, LoadMode};
+
+#[derive(Debug, Error)]
+pub enum XdbError {
+    #[error("xdb io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid xdb header: {0}")]
+    InvalidHeader(&'static str),
+    #[error("unsupported xdb ip version: {0}")]
+    UnsupportedIpVersion(u16),
+    #[error("xdb data out of bounds: offset {offset}, length {length}")]
+    OutOfBounds { offset: usize, length: usize },
+    #[error("expected {expected:?} xdb, found {actual:?}")]
+    IpVersionMismatch {
+        expected: IpVersion,
+        actual: IpVersion,
+    },
+    #[error("invalid ip address: {0}")]
+    InvalidIp(String),
+    #[error("invalid region: {0}")]
+    InvalidRegion(String),
+    #[error("invalid region data: {0}")]
+    InvalidData(#[from] std::string::FromUtf8Error),
+    #[error("xdb data not cached in {0:?} mode")]
+    NotCached(LoadMode),
+    #[error("{0:?} xdb not loaded")]
+    NotLoaded(IpVersion),
+    #[error("ip not matched")]
+    NotMatched,
+}
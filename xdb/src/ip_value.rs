@@ -1,4 +1,8 @@
-use std::{error::Error, net::Ipv4Addr, str::FromStr};
+use std::{
+    error::Error,
+    net::{Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
 
 pub trait ToUIntIP {
     fn to_u32_ip(&self) -> Result<u32, Box<dyn Error>>;
@@ -28,6 +32,35 @@ impl ToUIntIP for Ipv4Addr {
     }
 }
 
+/// IPv6 地址转换为 128 位整数
+pub trait ToUIntIPv6 {
+    fn to_u128_ip(&self) -> Result<u128, Box<dyn Error>>;
+}
+
+impl ToUIntIPv6 for u128 {
+    #[inline(always)]
+    fn to_u128_ip(&self) -> Result<u128, Box<dyn Error>> {
+        Ok(*self)
+    }
+}
+
+impl ToUIntIPv6 for &str {
+    #[inline(always)]
+    fn to_u128_ip(&self) -> Result<u128, Box<dyn Error>> {
+        if let Ok(num) = self.parse::<u128>() {
+            return Ok(num);
+        }
+        Ok(u128::from(Ipv6Addr::from_str(self)?))
+    }
+}
+
+impl ToUIntIPv6 for Ipv6Addr {
+    #[inline(always)]
+    fn to_u128_ip(&self) -> Result<u128, Box<dyn Error>> {
+        Ok(u128::from(*self))
+    }
+}
+
 #[cfg(test)]
 mod test_ip {
     use super::*;
@@ -59,4 +92,21 @@ mod test_ip {
         let result = ip.to_u32_ip().unwrap();
         assert_eq!(result, 3 << 8 | 12)
     }
+
+    #[test]
+    fn test_ipv6_str_2_u128() {
+        let result = "2001:db8::1".to_u128_ip().unwrap();
+        assert_eq!(result, 0x2001_0db8 << 96 | 1);
+
+        let result = "::ffff:1.1.1.1".to_u128_ip().unwrap();
+        assert_eq!(result, 0xffff << 32 | 0x0101_0101);
+    }
+
+    #[test]
+    fn test_ipv6_u128() {
+        assert_eq!("12".to_u128_ip().unwrap(), 12);
+        assert_eq!(33u128.to_u128_ip().unwrap(), 33);
+        assert_eq!(Ipv6Addr::LOCALHOST.to_u128_ip().unwrap(), 1);
+        assert!("1.1.1.1".to_u128_ip().is_err());
+    }
 }
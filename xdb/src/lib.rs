@@ -1,4 +1,14 @@
+mod error;
 mod ip_value;
-pub use self::ip_value::ToUIntIP;
+pub mod maker;
+mod region;
+pub use self::{
+    error::XdbError,
+    ip_value::{ToUIntIP, ToUIntIPv6},
+    region::Region,
+};
 pub mod searcher;
-pub use searcher::{search_by_ip, searcher_init};
+pub use searcher::{
+    search, search_by_ip, search_by_ipv6, search_region, searcher_init, IpVersion, LoadMode,
+    Searcher,
+};
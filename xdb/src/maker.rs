@@ -0,0 +1,406 @@
+//! xdb 文件生成
+//!
+//! 源文件每行一个网段 `start_ip|end_ip|region`，与 ip2region 的 `ip.merge.txt` 格式一致，
+//! 空行与 `#` 开头的行会被忽略。生成的文件使用 v3 格式，头部记录 IP 版本。
+
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    path::Path,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use thiserror::Error;
+
+use crate::searcher::{
+    IpVersion, HEADER_END_INDEX_OFFSET, HEADER_INFO_LENGTH, HEADER_IP_VERSION_OFFSET,
+    HEADER_START_INDEX_OFFSET, VECTOR_INDEX_LENGTH, VECTOR_INDEX_SIZE,
+};
+
+/// 生成的文件格式版本
+const XDB_VERSION: u16 = 3;
+/// 索引策略：向量索引
+const INDEX_POLICY_VECTOR: u16 = 1;
+/// 数据指针字节数
+const RUNTIME_PTR_BYTES: u16 = 4;
+
+#[derive(Debug, Error)]
+pub enum MakerError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("line {line}: {reason}")]
+    InvalidLine { line: usize, reason: String },
+    #[error("line {line}: mixed ipv4 and ipv6 segments")]
+    MixedIpVersion { line: usize },
+    #[error("line {line}: segment overlaps the previous one")]
+    Overlap { line: usize },
+    #[error("line {line}: gap after the previous segment")]
+    Gap { line: usize },
+    #[error("no segments in source")]
+    Empty,
+    #[error("xdb file exceeds 4 GiB")]
+    TooLarge,
+}
+
+/// 网段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub start_ip: u128,
+    pub end_ip: u128,
+    pub region: String,
+    /// 源文件中的行号，从 1 开始
+    pub line: usize,
+}
+
+/// 解析源文件内容，返回 IP 版本与按行顺序排列的网段
+pub fn parse_segments(source: &str) -> Result<(IpVersion, Vec<Segment>), MakerError> {
+    let mut version = None;
+    let mut segments = Vec::new();
+
+    for (index, raw) in source.lines().enumerate() {
+        let line = index + 1;
+        let text = raw.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+
+        let invalid = |reason: &str| MakerError::InvalidLine {
+            line,
+            reason: reason.to_string(),
+        };
+        let mut parts = text.splitn(3, '|');
+        let (Some(start), Some(end), Some(region)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid("expected start_ip|end_ip|region"));
+        };
+        if region.is_empty() {
+            return Err(invalid("empty region"));
+        }
+
+        let start = IpAddr::from_str(start.trim()).map_err(|_| invalid("invalid start ip"))?;
+        let end = IpAddr::from_str(end.trim()).map_err(|_| invalid("invalid end ip"))?;
+        let (line_version, start_ip, end_ip) = match (start, end) {
+            (IpAddr::V4(start), IpAddr::V4(end)) => (
+                IpVersion::V4,
+                u128::from(u32::from(start)),
+                u128::from(u32::from(end)),
+            ),
+            (IpAddr::V6(start), IpAddr::V6(end)) => {
+                (IpVersion::V6, u128::from(start), u128::from(end))
+            },
+            _ => return Err(MakerError::MixedIpVersion { line }),
+        };
+        if *version.get_or_insert(line_version) != line_version {
+            return Err(MakerError::MixedIpVersion { line });
+        }
+        if start_ip > end_ip {
+            return Err(invalid("start ip is greater than end ip"));
+        }
+
+        segments.push(Segment {
+            start_ip,
+            end_ip,
+            region: region.to_string(),
+            line,
+        });
+    }
+
+    version
+        .map(|version| (version, segments))
+        .ok_or(MakerError::Empty)
+}
+
+/// xdb 生成器
+#[derive(Debug, Clone, Default)]
+pub struct Maker {
+    allow_gaps: bool,
+}
+
+impl Maker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 是否允许网段之间存在空隙，完整的 IP 库不应有空隙，自定义网段可以开启
+    pub fn allow_gaps(mut self, allow_gaps: bool) -> Self {
+        self.allow_gaps = allow_gaps;
+        self
+    }
+
+    /// 排序并校验网段，合并相邻且归属地相同的网段
+    pub fn normalize(&self, mut segments: Vec<Segment>) -> Result<Vec<Segment>, MakerError> {
+        segments.sort_by_key(|segment| segment.start_ip);
+
+        let mut merged: Vec<Segment> = Vec::with_capacity(segments.len());
+        for segment in segments {
+            let Some(last) = merged.last_mut() else {
+                merged.push(segment);
+                continue;
+            };
+
+            if segment.start_ip <= last.end_ip {
+                return Err(MakerError::Overlap { line: segment.line });
+            }
+            let adjacent = segment.start_ip == last.end_ip + 1;
+            if !adjacent && !self.allow_gaps {
+                return Err(MakerError::Gap { line: segment.line });
+            }
+
+            if adjacent && segment.region == last.region {
+                last.end_ip = segment.end_ip;
+            } else {
+                merged.push(segment);
+            }
+        }
+
+        Ok(merged)
+    }
+
+    /// 从源文件内容生成 xdb 数据
+    pub fn make(&self, source: &str) -> Result<Vec<u8>, MakerError> {
+        let (version, segments) = parse_segments(source)?;
+        self.build(version, self.normalize(segments)?)
+    }
+
+    /// 读取源文件并写入 xdb 文件
+    pub fn make_file(
+        &self,
+        source: impl AsRef<Path>,
+        target: impl AsRef<Path>,
+    ) -> Result<(), MakerError> {
+        let source = std::fs::read_to_string(source)?;
+        std::fs::write(target, self.make(&source)?)?;
+        Ok(())
+    }
+
+    /// 按已排序、无重叠的网段生成 xdb 数据
+    ///
+    /// 文件依次为头部、向量索引、归属地数据与段索引；
+    /// 段索引按 IP 前两个字节切分，使每个向量索引只指向落在其中的段。
+    pub fn build(&self, version: IpVersion, segments: Vec<Segment>) -> Result<Vec<u8>, MakerError> {
+        if segments.is_empty() {
+            return Err(MakerError::Empty);
+        }
+
+        let to_u32 = |value: usize| u32::try_from(value).map_err(|_| MakerError::TooLarge);
+        let data_start = HEADER_INFO_LENGTH + VECTOR_INDEX_LENGTH;
+
+        // 相同归属地只写入一次
+        let mut data = Vec::new();
+        let mut data_ptrs: HashMap<&str, (usize, usize)> = HashMap::new();
+        for segment in &segments {
+            data_ptrs.entry(segment.region.as_str()).or_insert_with(|| {
+                let ptr = data_start + data.len();
+                data.extend_from_slice(segment.region.as_bytes());
+                (ptr, segment.region.len())
+            });
+        }
+
+        let segment_start = data_start + data.len();
+        let segment_size = version.segment_index_size();
+        let mut index = Vec::new();
+        let mut vector = vec![0u8; VECTOR_INDEX_LENGTH];
+
+        for segment in &segments {
+            let (data_ptr, data_len) = data_ptrs[segment.region.as_str()];
+            let data_len = u16::try_from(data_len).map_err(|_| MakerError::InvalidLine {
+                line: segment.line,
+                reason: "region too long".to_string(),
+            })?;
+
+            for (start_ip, end_ip) in split_by_vector(version, segment.start_ip, segment.end_ip) {
+                let ptr = segment_start + index.len();
+                index.extend_from_slice(&encode_ip(version, start_ip));
+                index.extend_from_slice(&encode_ip(version, end_ip));
+                index.extend_from_slice(&data_len.to_le_bytes());
+                index.extend_from_slice(&to_u32(data_ptr)?.to_le_bytes());
+
+                // 向量索引记录该前缀下第一个段的位置与最后一个段的结束位置
+                let offset = VECTOR_INDEX_SIZE * version.vector_index(start_ip);
+                if vector[offset..offset + 4] == [0u8; 4] {
+                    vector[offset..offset + 4].copy_from_slice(&to_u32(ptr)?.to_le_bytes());
+                }
+                vector[offset + 4..offset + 8]
+                    .copy_from_slice(&to_u32(ptr + segment_size)?.to_le_bytes());
+            }
+        }
+
+        let mut header = vec![0u8; HEADER_INFO_LENGTH];
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as u32)
+            .unwrap_or_default();
+        let ip_version: u16 = match version {
+            IpVersion::V4 => 4,
+            IpVersion::V6 => 6,
+        };
+        header[0..2].copy_from_slice(&XDB_VERSION.to_le_bytes());
+        header[2..4].copy_from_slice(&INDEX_POLICY_VECTOR.to_le_bytes());
+        header[4..8].copy_from_slice(&created_at.to_le_bytes());
+        header[HEADER_START_INDEX_OFFSET..HEADER_START_INDEX_OFFSET + 4]
+            .copy_from_slice(&to_u32(segment_start)?.to_le_bytes());
+        header[HEADER_END_INDEX_OFFSET..HEADER_END_INDEX_OFFSET + 4]
+            .copy_from_slice(&to_u32(segment_start + index.len() - segment_size)?.to_le_bytes());
+        header[HEADER_IP_VERSION_OFFSET..HEADER_IP_VERSION_OFFSET + 2]
+            .copy_from_slice(&ip_version.to_le_bytes());
+        header[HEADER_IP_VERSION_OFFSET + 2..HEADER_IP_VERSION_OFFSET + 4]
+            .copy_from_slice(&RUNTIME_PTR_BYTES.to_le_bytes());
+
+        to_u32(segment_start + index.len())?;
+        Ok([header, vector, data, index].concat())
+    }
+}
+
+/// IPv4 按小端存储，IPv6 按大端存储
+fn encode_ip(version: IpVersion, ip: u128) -> Vec<u8> {
+    match version {
+        IpVersion::V4 => (ip as u32).to_le_bytes().to_vec(),
+        IpVersion::V6 => ip.to_be_bytes().to_vec(),
+    }
+}
+
+/// 按 IP 前两个字节切分网段
+fn split_by_vector(version: IpVersion, start_ip: u128, end_ip: u128) -> Vec<(u128, u128)> {
+    let shift = match version {
+        IpVersion::V4 => 16,
+        IpVersion::V6 => 112,
+    };
+
+    let mut parts = Vec::new();
+    let mut start = start_ip;
+    loop {
+        let block_end = start | ((1u128 << shift) - 1);
+        if block_end >= end_ip {
+            parts.push((start, end_ip));
+            return parts;
+        }
+        parts.push((start, block_end));
+        start = block_end + 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use super::*;
+    use crate::{Searcher, XdbError};
+
+    fn ipv4(ip: &str) -> u128 {
+        u128::from(u32::from(Ipv4Addr::from_str(ip).unwrap()))
+    }
+
+    #[test]
+    fn test_round_trip_ip_test_file() {
+        let source = std::fs::read_to_string("../server/resources/ip.test.txt").unwrap();
+        let searcher = Searcher::from_bytes(Maker::new().make(&source).unwrap()).unwrap();
+        assert_eq!(searcher.ip_version(), IpVersion::V4);
+
+        let (_, segments) = parse_segments(&source).unwrap();
+        assert!(!segments.is_empty());
+        for segment in segments {
+            for ip in [
+                segment.start_ip,
+                (segment.start_ip + segment.end_ip) / 2,
+                segment.end_ip,
+            ] {
+                assert_eq!(
+                    searcher.search_by_ip(ip as u32).unwrap(),
+                    segment.region,
+                    "line {}",
+                    segment.line
+                );
+            }
+        }
+        assert!(matches!(
+            searcher.search("1.0.176.0"),
+            Err(XdbError::NotMatched)
+        ));
+    }
+
+    #[test]
+    fn test_normalize_segments() {
+        let source = "\
+# 办公网
+10.0.0.0|10.0.0.255|中国|0|上海|上海市|办公网
+10.0.1.0|10.0.1.255|中国|0|上海|上海市|办公网
+10.0.2.0|10.0.255.255|中国|0|上海|上海市|VPN
+";
+        let (version, segments) = parse_segments(source).unwrap();
+        assert_eq!(version, IpVersion::V4);
+        let merged = Maker::new().normalize(segments).unwrap();
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].end_ip, ipv4("10.0.1.255"));
+
+        let overlap = "10.0.0.0|10.0.0.255|A\n10.0.0.128|10.0.1.255|B\n";
+        let (_, segments) = parse_segments(overlap).unwrap();
+        assert!(matches!(
+            Maker::new().normalize(segments),
+            Err(MakerError::Overlap { line: 2 })
+        ));
+
+        let gap = "10.0.0.0|10.0.0.255|A\n10.0.2.0|10.0.2.255|B\n";
+        let (_, segments) = parse_segments(gap).unwrap();
+        assert!(matches!(
+            Maker::new().normalize(segments.clone()),
+            Err(MakerError::Gap { line: 2 })
+        ));
+        assert_eq!(
+            Maker::new()
+                .allow_gaps(true)
+                .normalize(segments)
+                .unwrap()
+                .len(),
+            2
+        );
+
+        assert!(matches!(
+            parse_segments("10.0.0.0|::1|A"),
+            Err(MakerError::MixedIpVersion { line: 1 })
+        ));
+        assert!(matches!(
+            parse_segments("10.0.0.9|10.0.0.1|A"),
+            Err(MakerError::InvalidLine { line: 1, .. })
+        ));
+        assert!(matches!(parse_segments("# empty"), Err(MakerError::Empty)));
+    }
+
+    #[test]
+    fn test_make_across_vector_blocks() {
+        let source = "\
+10.0.0.0|10.2.255.255|中国|0|上海|上海市|办公网
+10.3.0.0|10.3.0.255|中国|0|北京|北京市|VPN
+";
+        let searcher = Searcher::from_bytes(Maker::new().make(source).unwrap()).unwrap();
+        assert_eq!(
+            searcher.search("10.1.128.1").unwrap(),
+            "中国|0|上海|上海市|办公网"
+        );
+        assert_eq!(
+            searcher.search("10.3.0.1").unwrap(),
+            "中国|0|北京|北京市|VPN"
+        );
+
+        let source = "\
+2001:db8::|2001:db8::ffff|中国|0|上海|上海市|办公网
+2001:db8::1:0|2002::ffff|中国|0|北京|北京市|VPN
+";
+        let searcher = Searcher::from_bytes(Maker::new().make(source).unwrap()).unwrap();
+        assert_eq!(searcher.ip_version(), IpVersion::V6);
+        assert_eq!(
+            searcher
+                .search_by_ipv6(Ipv6Addr::from_str("2001:db8::1").unwrap())
+                .unwrap(),
+            "中国|0|上海|上海市|办公网"
+        );
+        assert_eq!(
+            searcher.search("2002::1").unwrap(),
+            "中国|0|北京|北京市|VPN"
+        );
+        assert!(matches!(
+            searcher.search("2003::1"),
+            Err(XdbError::NotMatched)
+        ));
+    }
+}
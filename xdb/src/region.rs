@@ -0,0 +1,98 @@
+use std::{fmt, str::FromStr};
+
+use crate::XdbError;
+
+/// 未知字段在 xdb 中的占位值
+const UNKNOWN: &str = "0";
+
+/// 解析后的归属地，对应 xdb 数据 `国家|区域|省份|城市|ISP`
+///
+/// 值为 `0` 或空的字段视为未知。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Region {
+    pub country: Option<String>,
+    pub region: Option<String>,
+    pub province: Option<String>,
+    pub city: Option<String>,
+    pub isp: Option<String>,
+}
+
+impl Region {
+    /// 所有字段均未知
+    pub fn is_unknown(&self) -> bool {
+        self.country.is_none()
+            && self.region.is_none()
+            && self.province.is_none()
+            && self.city.is_none()
+            && self.isp.is_none()
+    }
+}
+
+impl FromStr for Region {
+    type Err = XdbError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split('|').collect();
+        let [country, region, province, city, isp] = parts[..] else {
+            return Err(XdbError::InvalidRegion(s.to_string()));
+        };
+
+        let field = |value: &str| {
+            let value = value.trim();
+            (!value.is_empty() && value != UNKNOWN).then(|| value.to_string())
+        };
+        Ok(Self {
+            country: field(country),
+            region: field(region),
+            province: field(province),
+            city: field(city),
+            isp: field(isp),
+        })
+    }
+}
+
+/// 输出 xdb 原始格式，未知字段为 `0`
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field = |value: &Option<String>| value.as_deref().unwrap_or(UNKNOWN).to_string();
+        write!(
+            f,
+            "{}|{}|{}|{}|{}",
+            field(&self.country),
+            field(&self.region),
+            field(&self.province),
+            field(&self.city),
+            field(&self.isp)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_region() {
+        let region: Region = "中国|0|广东省|深圳市|电信".parse().unwrap();
+        assert_eq!(region.country.as_deref(), Some("中国"));
+        assert_eq!(region.region, None);
+        assert_eq!(region.province.as_deref(), Some("广东省"));
+        assert_eq!(region.city.as_deref(), Some("深圳市"));
+        assert_eq!(region.isp.as_deref(), Some("电信"));
+        assert_eq!(region.to_string(), "中国|0|广东省|深圳市|电信");
+
+        let region: Region = "0|0|0|内网IP|内网IP".parse().unwrap();
+        assert_eq!(region.country, None);
+        assert_eq!(region.city.as_deref(), Some("内网IP"));
+
+        let region: Region = "0|0|0|0|0".parse().unwrap();
+        assert!(region.is_unknown());
+        assert_eq!(region, Region::default());
+        assert_eq!(Region::default().to_string(), "0|0|0|0|0");
+
+        assert!(matches!(
+            "Unknown Location".parse::<Region>(),
+            Err(XdbError::InvalidRegion(_))
+        ));
+    }
+}
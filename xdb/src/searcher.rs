@@ -1,151 +1,661 @@
-use std::{error::Error, fmt::Display, fs::File, io::Read, path::Path};
-
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    fs::File,
+    io::{self, Read},
+    net::IpAddr,
+    ops::Deref,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    thread::{self, JoinHandle},
+    time::{Duration, SystemTime},
+};
+
+use arc_swap::ArcSwap;
+use memmap2::Mmap;
 use once_cell::sync::OnceCell;
 
-use crate::ToUIntIP;
+use crate::{Region, ToUIntIP, ToUIntIPv6, XdbError};
 
-const HEADER_INFO_LENGTH: usize = 256;
+pub(crate) const HEADER_INFO_LENGTH: usize = 256;
 const VECTOR_INDEX_COLS: usize = 256;
-const VECTOR_INDEX_SIZE: usize = 8;
+pub(crate) const VECTOR_INDEX_SIZE: usize = 8;
 const SEGMENT_INDEX_SIZE: usize = 14;
-const VECTOR_INDEX_LENGTH: usize = 512 * 1024;
+pub(crate) const VECTOR_INDEX_LENGTH: usize = 512 * 1024;
+// IPv6 段索引：起始 IP(16) + 结束 IP(16) + 数据长度(2) + 数据偏移(4)
+const IPV6_SEGMENT_INDEX_SIZE: usize = 38;
+
+// 头部中段索引起止位置与 v3 格式 IP 版本字段的偏移
+pub(crate) const HEADER_START_INDEX_OFFSET: usize = 8;
+pub(crate) const HEADER_END_INDEX_OFFSET: usize = 12;
+pub(crate) const HEADER_IP_VERSION_OFFSET: usize = 16;
 
 const XDB_FILEPATH_ENV: &str = "XDB_FILEPATH";
+const XDB_V6_FILEPATH_ENV: &str = "XDB_V6_FILEPATH";
 
-// 只保留一个缓存，用于存储完整数据
-static CACHE: OnceCell<Vec<u8>> = OnceCell::new();
+// 全局查询器，IPv4 与 IPv6 各一个
+static SEARCHER: OnceCell<Arc<Searcher>> = OnceCell::new();
+static SEARCHER_V6: OnceCell<Arc<Searcher>> = OnceCell::new();
 
-// 添加专门的向量缓存
-static VECTOR_CACHE: OnceCell<&'static [u8]> = OnceCell::new();
+/// xdb 文件存储的 IP 版本
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpVersion {
+    V4,
+    V6,
+}
 
-fn default_detect_xdb_file() -> Result<String, Box<dyn Error>> {
-    let prefix = "../".to_owned();
-    for recurse in 1..4 {
-        let filepath = prefix.repeat(recurse) + "server/resources/ip2region.xdb";
-        if Path::new(&filepath).exists() {
-            return Ok(filepath);
+impl IpVersion {
+    pub(crate) fn segment_index_size(self) -> usize {
+        match self {
+            IpVersion::V4 => SEGMENT_INDEX_SIZE,
+            IpVersion::V6 => IPV6_SEGMENT_INDEX_SIZE,
+        }
+    }
+
+    fn ip_bytes(self) -> usize {
+        match self {
+            IpVersion::V4 => 4,
+            IpVersion::V6 => 16,
+        }
+    }
+
+    /// 向量索引使用 IP 的前两个字节
+    pub(crate) fn vector_index(self, ip: u128) -> usize {
+        let prefix = match self {
+            IpVersion::V4 => (ip >> 16) as usize,
+            IpVersion::V6 => (ip >> 112) as usize,
+        };
+        ((prefix >> 8) & 0xFF) * VECTOR_INDEX_COLS + (prefix & 0xFF)
+    }
+
+    /// IPv4 按小端存储，IPv6 按大端存储
+    fn decode_ip(self, bytes: &[u8]) -> u128 {
+        match self {
+            IpVersion::V4 => {
+                u128::from(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            },
+            IpVersion::V6 => bytes.iter().fold(0, |acc, &b| acc << 8 | u128::from(b)),
         }
     }
-    Err("default filepath not find the xdb file".into())
 }
 
+/// xdb 加载方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LoadMode {
+    /// 内存映射文件，由操作系统按需换页
+    Mmap,
+    /// 只缓存向量索引，段索引与数据每次从文件读取
+    VectorIndex,
+    /// 整个文件读入内存
+    #[default]
+    Full,
+}
+
+/// 按小端读取 `length` 字节（不超过 8）的无符号整数
 #[inline(always)]
-pub fn get_block_by_size(bytes: &[u8], offset: usize, length: usize) -> usize {
-    unsafe {
-        let ptr = bytes.as_ptr().add(offset);
-        match length {
-            4 => {
-                let mut buf = [0u8; 4];
-                std::ptr::copy_nonoverlapping(ptr, buf.as_mut_ptr(), 4);
-                u32::from_ne_bytes(buf) as usize
-            },
-            2 => {
-                let mut buf = [0u8; 2];
-                std::ptr::copy_nonoverlapping(ptr, buf.as_mut_ptr(), 2);
-                u16::from_ne_bytes(buf) as usize
+pub fn get_block_by_size(bytes: &[u8], offset: usize, length: usize) -> Result<usize, XdbError> {
+    let block = bytes
+        .get(offset..offset.saturating_add(length))
+        .filter(|_| length <= std::mem::size_of::<usize>())
+        .ok_or(XdbError::OutOfBounds { offset, length })?;
+    Ok(block
+        .iter()
+        .rev()
+        .fold(0, |acc, &b| acc << 8 | usize::from(b)))
+}
+
+/// 从 xdb 头部识别 IP 版本
+///
+/// v2 及更早的格式只支持 IPv4；v3 格式在偏移 16 处记录 IP 版本（4 或 6）。
+pub fn detect_ip_version(header: &[u8]) -> Result<IpVersion, XdbError> {
+    if header.len() < HEADER_INFO_LENGTH {
+        return Err(XdbError::InvalidHeader("header too short"));
+    }
+
+    if get_block_by_size(header, 0, 2)? < 3 {
+        return Ok(IpVersion::V4);
+    }
+
+    match get_block_by_size(header, HEADER_IP_VERSION_OFFSET, 2)? {
+        4 => Ok(IpVersion::V4),
+        6 => Ok(IpVersion::V6),
+        version => Err(XdbError::UnsupportedIpVersion(version as u16)),
+    }
+}
+
+enum Storage {
+    Full(Vec<u8>),
+    /// 只读内存映射，热更新时应以重命名的方式替换 xdb 文件，原地改写会影响已映射的内容
+    Mmap(Mmap),
+    VectorIndex {
+        header: Vec<u8>,
+        vector: Vec<u8>,
+        /// 按偏移读取，不移动共享的文件游标，多线程查询无需加锁
+        file: File,
+        len: usize,
+    },
+}
+
+impl Storage {
+    fn len(&self) -> usize {
+        match self {
+            Storage::Full(data) => data.len(),
+            Storage::Mmap(data) => data.len(),
+            Storage::VectorIndex { len, .. } => *len,
+        }
+    }
+
+    /// 常驻内存的数据，向量索引模式下只有头部与向量索引常驻内存
+    fn cached(&self, offset: usize, length: usize) -> Option<&[u8]> {
+        let end = offset.checked_add(length)?;
+        match self {
+            Storage::Full(data) => data.get(offset..end),
+            Storage::Mmap(data) => data.get(offset..end),
+            Storage::VectorIndex { header, vector, .. } => header.get(offset..end).or_else(|| {
+                vector.get(offset.checked_sub(HEADER_INFO_LENGTH)?..end - HEADER_INFO_LENGTH)
+            }),
+        }
+    }
+
+    fn read(&self, offset: usize, length: usize) -> Result<Cow<'_, [u8]>, XdbError> {
+        let out_of_bounds = XdbError::OutOfBounds { offset, length };
+        if offset
+            .checked_add(length)
+            .is_none_or(|end| end > self.len())
+        {
+            return Err(out_of_bounds);
+        }
+
+        match self {
+            Storage::Full(data) => Ok(Cow::Borrowed(&data[offset..offset + length])),
+            Storage::Mmap(data) => Ok(Cow::Borrowed(&data[offset..offset + length])),
+            Storage::VectorIndex {
+                header,
+                vector,
+                file,
+                ..
+            } => {
+                if offset + length <= HEADER_INFO_LENGTH {
+                    return Ok(Cow::Borrowed(&header[offset..offset + length]));
+                }
+                if offset >= HEADER_INFO_LENGTH
+                    && offset + length <= HEADER_INFO_LENGTH + VECTOR_INDEX_LENGTH
+                {
+                    let offset = offset - HEADER_INFO_LENGTH;
+                    return Ok(Cow::Borrowed(&vector[offset..offset + length]));
+                }
+
+                let mut buffer = vec![0u8; length];
+                read_exact_at(file, &mut buffer, offset as u64)?;
+                Ok(Cow::Owned(buffer))
             },
-            _ => {
-                let mut result = 0usize;
-                std::ptr::copy_nonoverlapping(ptr, &mut result as *mut usize as *mut u8, length);
-                result
+        }
+    }
+}
+
+#[cfg(unix)]
+fn read_exact_at(file: &File, buffer: &mut [u8], offset: u64) -> io::Result<()> {
+    std::os::unix::fs::FileExt::read_exact_at(file, buffer, offset)
+}
+
+#[cfg(windows)]
+fn read_exact_at(file: &File, mut buffer: &mut [u8], mut offset: u64) -> io::Result<()> {
+    use std::os::windows::fs::FileExt;
+
+    while !buffer.is_empty() {
+        match file.seek_read(buffer, offset) {
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(read) => {
+                buffer = &mut buffer[read..];
+                offset += read as u64;
             },
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {},
+            Err(e) => return Err(e),
         }
     }
+    Ok(())
 }
 
-#[inline(always)]
-pub fn search_by_ip<T>(ip: T) -> Result<String, Box<dyn Error>>
-where
-    T: ToUIntIP + Display,
-{
-    let ip = ip.to_u32_ip()?;
+/// 文件的修改时间与大小，用于判断是否需要热更新
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileStamp {
+    fn of(path: &Path) -> Result<Self, XdbError> {
+        let metadata = std::fs::metadata(path)?;
+        Ok(Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+}
+
+/// 已校验的 xdb 数据
+struct Xdb {
+    ip_version: IpVersion,
+    storage: Storage,
+    stamp: Option<FileStamp>,
+}
 
-    unsafe {
-        let vector_cache = get_vector_index_cache();
-        let offset = VECTOR_INDEX_SIZE
-            * ((((ip >> 24) & 0xFF) as usize) * VECTOR_INDEX_COLS + ((ip >> 16) & 0xFF) as usize);
+impl Xdb {
+    fn from_path(path: &Path, mode: LoadMode) -> Result<Self, XdbError> {
+        let stamp = FileStamp::of(path)?;
+        let storage = match mode {
+            LoadMode::Full => Storage::Full(std::fs::read(path)?),
+            LoadMode::Mmap => {
+                let file = File::open(path)?;
+                // SAFETY: 映射只读，xdb 文件按约定以重命名方式替换，不会在映射期间被原地改写
+                Storage::Mmap(unsafe { Mmap::map(&file)? })
+            },
+            LoadMode::VectorIndex => {
+                let mut file = File::open(path)?;
+                let len = usize::try_from(file.metadata()?.len())
+                    .map_err(|_| XdbError::InvalidHeader("file too large"))?;
+                if len < HEADER_INFO_LENGTH + VECTOR_INDEX_LENGTH {
+                    return Err(XdbError::InvalidHeader("file too short"));
+                }
+
+                let mut header = vec![0u8; HEADER_INFO_LENGTH];
+                let mut vector = vec![0u8; VECTOR_INDEX_LENGTH];
+                file.read_exact(&mut header)?;
+                file.read_exact(&mut vector)?;
+                Storage::VectorIndex {
+                    header,
+                    vector,
+                    file,
+                    len,
+                }
+            },
+        };
 
-        // 使用 get_block_by_size 来安全地读取数据
-        let start_ptr = get_block_by_size(vector_cache, offset, 4);
-        let end_ptr = get_block_by_size(vector_cache, offset + 4, 4);
+        let mut xdb = Self::new(storage)?;
+        xdb.stamp = Some(stamp);
+        Ok(xdb)
+    }
+
+    /// 校验头部与段索引范围
+    fn new(storage: Storage) -> Result<Self, XdbError> {
+        if storage.len() < HEADER_INFO_LENGTH + VECTOR_INDEX_LENGTH {
+            return Err(XdbError::InvalidHeader("file too short"));
+        }
+
+        let header = storage.read(0, HEADER_INFO_LENGTH)?;
+        let ip_version = detect_ip_version(&header)?;
+        let start_index = get_block_by_size(&header, HEADER_START_INDEX_OFFSET, 4)?;
+        let end_index = get_block_by_size(&header, HEADER_END_INDEX_OFFSET, 4)?;
+
+        if start_index < HEADER_INFO_LENGTH + VECTOR_INDEX_LENGTH
+            || start_index > end_index
+            || end_index + ip_version.segment_index_size() > storage.len()
+        {
+            return Err(XdbError::InvalidHeader("segment index out of range"));
+        }
+        drop(header);
+
+        Ok(Self {
+            ip_version,
+            storage,
+            stamp: None,
+        })
+    }
+
+    fn search(&self, ip: u128) -> Result<String, XdbError> {
+        let segment_size = self.ip_version.segment_index_size();
+        let ip_bytes = self.ip_version.ip_bytes();
+
+        let vector = self.storage.read(
+            HEADER_INFO_LENGTH + VECTOR_INDEX_SIZE * self.ip_version.vector_index(ip),
+            VECTOR_INDEX_SIZE,
+        )?;
+        let start_ptr = get_block_by_size(&vector, 0, 4)?;
+        let end_ptr = get_block_by_size(&vector, 4, 4)?;
 
-        let full_cache = get_full_cache();
-        let cache_ptr = full_cache.as_ptr().add(start_ptr);
         let mut left = 0;
-        let mut right = (end_ptr - start_ptr) / SEGMENT_INDEX_SIZE;
+        let mut right = end_ptr.saturating_sub(start_ptr) / segment_size;
 
         while left < right {
             let mid = (left + right) >> 1;
-            let segment_offset = mid * SEGMENT_INDEX_SIZE;
-            let _segment_ptr = cache_ptr.add(segment_offset);
+            let segment = self
+                .storage
+                .read(start_ptr + mid * segment_size, segment_size)?;
 
-            // 使用 get_block_by_size 读取所有数据
-            let start_ip = get_block_by_size(full_cache, start_ptr + segment_offset, 4) as u32;
+            let start_ip = self.ip_version.decode_ip(&segment[..ip_bytes]);
             if ip < start_ip {
                 right = mid;
                 continue;
             }
 
-            let end_ip = get_block_by_size(full_cache, start_ptr + segment_offset + 4, 4) as u32;
+            let end_ip = self.ip_version.decode_ip(&segment[ip_bytes..ip_bytes * 2]);
             if ip > end_ip {
                 left = mid + 1;
                 continue;
             }
 
-            let data_len = get_block_by_size(full_cache, start_ptr + segment_offset + 8, 2);
-            let data_offset = get_block_by_size(full_cache, start_ptr + segment_offset + 10, 4);
+            let data_len = get_block_by_size(&segment, ip_bytes * 2, 2)?;
+            let data_offset = get_block_by_size(&segment, ip_bytes * 2 + 2, 4)?;
+            let data = self.storage.read(data_offset, data_len)?;
+
+            return Ok(String::from_utf8(data.into_owned())?);
+        }
+
+        Err(XdbError::NotMatched)
+    }
+}
 
-            let data = std::slice::from_raw_parts(full_cache.as_ptr().add(data_offset), data_len);
+/// 查询器当前数据中常驻内存的一段，持有期间热更新不会释放这份数据
+pub struct CachedBytes {
+    xdb: Arc<Xdb>,
+    offset: usize,
+    length: usize,
+}
 
-            return Ok(String::from_utf8_unchecked(data.to_vec()));
+impl CachedBytes {
+    fn new(xdb: Arc<Xdb>, mode: LoadMode, offset: usize, length: usize) -> Result<Self, XdbError> {
+        if xdb.storage.cached(offset, length).is_none() {
+            return Err(XdbError::NotCached(mode));
         }
+        Ok(Self {
+            xdb,
+            offset,
+            length,
+        })
+    }
+}
 
-        Err("not matched".into())
+impl Deref for CachedBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // 创建时已校验范围
+        self.xdb
+            .storage
+            .cached(self.offset, self.length)
+            .unwrap_or_default()
     }
 }
 
-// 优化向量索引缓存访问
-#[inline(always)]
-pub fn get_vector_index_cache() -> &'static [u8] {
-    // 使用静态缓存，只计算一次
-    VECTOR_CACHE.get_or_init(|| unsafe {
-        let full_cache = get_full_cache();
-        let ptr = full_cache.as_ptr().add(HEADER_INFO_LENGTH);
-        std::slice::from_raw_parts(ptr, VECTOR_INDEX_LENGTH)
+/// xdb 查询器
+///
+/// 从文件加载的查询器可通过 [`Searcher::reload`] 原子替换数据，
+/// 正在进行的查询继续使用旧数据，新文件校验失败时保留旧数据。
+pub struct Searcher {
+    xdb: ArcSwap<Xdb>,
+    path: Option<PathBuf>,
+    mode: LoadMode,
+}
+
+impl Searcher {
+    /// 从文件加载
+    pub fn from_path(path: impl AsRef<Path>, mode: LoadMode) -> Result<Self, XdbError> {
+        let path = path.as_ref().to_path_buf();
+        let xdb = Xdb::from_path(&path, mode)?;
+        Ok(Self {
+            xdb: ArcSwap::from_pointee(xdb),
+            path: Some(path),
+            mode,
+        })
+    }
+
+    /// 从内存中的 xdb 数据加载，不支持热更新
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, XdbError> {
+        Ok(Self {
+            xdb: ArcSwap::from_pointee(Xdb::new(Storage::Full(bytes))?),
+            path: None,
+            mode: LoadMode::Full,
+        })
+    }
+
+    pub fn ip_version(&self) -> IpVersion {
+        self.xdb.load().ip_version
+    }
+
+    pub fn mode(&self) -> LoadMode {
+        self.mode
+    }
+
+    /// 整个 xdb 文件的内存数据，向量索引模式下文件不常驻内存，返回 [`XdbError::NotCached`]
+    pub fn full_cache(&self) -> Result<CachedBytes, XdbError> {
+        let xdb = self.xdb.load_full();
+        let length = xdb.storage.len();
+        CachedBytes::new(xdb, self.mode, 0, length)
+    }
+
+    /// 向量索引的内存数据
+    pub fn vector_index_cache(&self) -> Result<CachedBytes, XdbError> {
+        CachedBytes::new(
+            self.xdb.load_full(),
+            self.mode,
+            HEADER_INFO_LENGTH,
+            VECTOR_INDEX_LENGTH,
+        )
+    }
+
+    /// 按 IPv4 地址查询
+    pub fn search_by_ip<T: ToUIntIP>(&self, ip: T) -> Result<String, XdbError> {
+        let ip = ip
+            .to_u32_ip()
+            .map_err(|e| XdbError::InvalidIp(e.to_string()))?;
+        self.search_key(IpVersion::V4, u128::from(ip))
+    }
+
+    /// 按 IPv6 地址查询
+    pub fn search_by_ipv6<T: ToUIntIPv6>(&self, ip: T) -> Result<String, XdbError> {
+        let ip = ip
+            .to_u128_ip()
+            .map_err(|e| XdbError::InvalidIp(e.to_string()))?;
+        self.search_key(IpVersion::V6, ip)
+    }
+
+    /// 解析地址后按查询器的 IP 版本查询
+    ///
+    /// IPv4 映射的 IPv6 地址（`::ffff:a.b.c.d`）视为 IPv4。
+    pub fn search(&self, ip: &str) -> Result<String, XdbError> {
+        match parse_ip(ip)? {
+            IpAddr::V4(ip) => self.search_by_ip(ip),
+            IpAddr::V6(ip) => self.search_by_ipv6(ip),
+        }
+    }
+
+    /// 查询并解析为 [`Region`]
+    pub fn search_region(&self, ip: &str) -> Result<Region, XdbError> {
+        self.search(ip)?.parse()
+    }
+
+    fn search_key(&self, expected: IpVersion, ip: u128) -> Result<String, XdbError> {
+        let xdb = self.xdb.load();
+        if xdb.ip_version != expected {
+            return Err(XdbError::IpVersionMismatch {
+                expected,
+                actual: xdb.ip_version,
+            });
+        }
+        xdb.search(ip)
+    }
+
+    /// 重新加载文件并原子替换
+    ///
+    /// 新文件的 IP 版本必须与当前一致。
+    pub fn reload(&self) -> Result<(), XdbError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let xdb = Xdb::from_path(path, self.mode)?;
+        let current = self.ip_version();
+        if xdb.ip_version != current {
+            return Err(XdbError::IpVersionMismatch {
+                expected: current,
+                actual: xdb.ip_version,
+            });
+        }
+
+        self.xdb.store(Arc::new(xdb));
+        Ok(())
+    }
+
+    /// 文件的修改时间或大小变化时重新加载，返回是否已替换
+    pub fn reload_if_changed(&self) -> Result<bool, XdbError> {
+        let Some(path) = &self.path else {
+            return Ok(false);
+        };
+
+        if self.xdb.load().stamp == Some(FileStamp::of(path)?) {
+            return Ok(false);
+        }
+
+        self.reload()?;
+        Ok(true)
+    }
+
+    /// 后台线程定期检查文件变化并热更新，查询器释放后线程退出
+    pub fn watch(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let searcher = Arc::downgrade(self);
+        thread::spawn(move || loop {
+            thread::sleep(interval);
+            let Some(searcher) = searcher.upgrade() else {
+                break;
+            };
+
+            match searcher.reload_if_changed() {
+                Ok(true) => tracing::info!("xdb reloaded: {:?}", searcher.path),
+                Ok(false) => {},
+                Err(e) => tracing::warn!("xdb reload failed, keep current data: {}", e),
+            }
+        })
+    }
+}
+
+fn parse_ip(ip: &str) -> Result<IpAddr, XdbError> {
+    let ip = IpAddr::from_str(ip).map_err(|e| XdbError::InvalidIp(e.to_string()))?;
+    Ok(match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+        ip => ip,
     })
 }
 
-#[inline(always)]
-pub fn get_full_cache() -> &'static Vec<u8> {
-    CACHE.get_or_init(|| {
-        let xdb_filepath =
-            std::env::var(XDB_FILEPATH_ENV).unwrap_or_else(|_| default_detect_xdb_file().unwrap());
-
-        let size = std::fs::metadata(&xdb_filepath)
-            .map(|m| m.len() as usize)
-            .unwrap_or(1024 * 1024);
-
-        let mut buffer = Vec::with_capacity(size);
-        File::open(&xdb_filepath)
-            .expect("file open error")
-            .read_to_end(&mut buffer)
-            .expect("load file error");
-
-        buffer
+fn default_detect_xdb_file() -> Result<String, XdbError> {
+    let prefix = "../".to_owned();
+    for recurse in 1..4 {
+        let filepath = prefix.repeat(recurse) + "server/resources/ip2region.xdb";
+        if Path::new(&filepath).exists() {
+            return Ok(filepath);
+        }
+    }
+    Err(XdbError::NotLoaded(IpVersion::V4))
+}
+
+fn global_cell(version: IpVersion) -> &'static OnceCell<Arc<Searcher>> {
+    match version {
+        IpVersion::V4 => &SEARCHER,
+        IpVersion::V6 => &SEARCHER_V6,
+    }
+}
+
+/// 获取全局查询器，未初始化时尝试从环境变量指定的文件加载
+pub fn global_searcher(version: IpVersion) -> Result<&'static Arc<Searcher>, XdbError> {
+    global_cell(version).get_or_try_init(|| {
+        let path = match version {
+            IpVersion::V4 => {
+                std::env::var(XDB_FILEPATH_ENV).or_else(|_| default_detect_xdb_file())?
+            },
+            IpVersion::V6 => {
+                std::env::var(XDB_V6_FILEPATH_ENV).map_err(|_| XdbError::NotLoaded(version))?
+            },
+        };
+
+        let searcher = Searcher::from_path(path, LoadMode::Full)?;
+        if searcher.ip_version() != version {
+            return Err(XdbError::IpVersionMismatch {
+                expected: version,
+                actual: searcher.ip_version(),
+            });
+        }
+        Ok(Arc::new(searcher))
     })
 }
 
+/// 全局 IPv4 查询器的整个 xdb 文件数据，见 [`Searcher::full_cache`]
+#[inline(always)]
+pub fn get_full_cache() -> Result<CachedBytes, XdbError> {
+    global_searcher(IpVersion::V4)?.full_cache()
+}
+
+/// 全局 IPv4 查询器的向量索引数据，见 [`Searcher::vector_index_cache`]
+#[inline(always)]
+pub fn get_vector_index_cache() -> Result<CachedBytes, XdbError> {
+    global_searcher(IpVersion::V4)?.vector_index_cache()
+}
+
+/// 按 IPv4 地址查询全局查询器
+#[inline(always)]
+pub fn search_by_ip<T>(ip: T) -> Result<String, XdbError>
+where
+    T: ToUIntIP + Display,
+{
+    global_searcher(IpVersion::V4)?.search_by_ip(ip)
+}
+
+/// 按 IPv6 地址查询全局查询器，需先用 IPv6 xdb 文件调用 [`searcher_init`]
+pub fn search_by_ipv6<T>(ip: T) -> Result<String, XdbError>
+where
+    T: ToUIntIPv6 + Display,
+{
+    global_searcher(IpVersion::V6)?.search_by_ipv6(ip)
+}
+
+/// 按地址族自动选择全局 IPv4 或 IPv6 查询器
+pub fn search(ip: &str) -> Result<String, XdbError> {
+    match parse_ip(ip)? {
+        IpAddr::V4(ip) => search_by_ip(ip),
+        IpAddr::V6(ip) => search_by_ipv6(ip),
+    }
+}
+
+/// 按地址族查询全局查询器并解析为 [`Region`]
+pub fn search_region(ip: &str) -> Result<Region, XdbError> {
+    search(ip)?.parse()
+}
+
+/// 按指定方式加载 xdb 文件作为全局查询器，返回文件的 IP 版本
+///
+/// 对应版本的全局查询器已存在时不替换，需要更新数据请使用 [`Searcher::reload`]。
+pub fn searcher_init_with_mode(
+    xdb_filepath: impl AsRef<Path>,
+    mode: LoadMode,
+) -> Result<IpVersion, XdbError> {
+    let mut header = [0u8; HEADER_INFO_LENGTH];
+    File::open(xdb_filepath.as_ref())?.read_exact(&mut header)?;
+    let version = detect_ip_version(&header)?;
+
+    global_cell(version)
+        .get_or_try_init(|| Searcher::from_path(xdb_filepath, mode).map(Arc::new))?;
+    Ok(version)
+}
+
+/// 以全量内存方式初始化全局查询器，按 xdb 头部识别的 IP 版本加载到对应位置
 pub fn searcher_init(xdb_filepath: Option<String>) {
-    let xdb_filepath = xdb_filepath.unwrap_or_else(|| default_detect_xdb_file().unwrap());
-    std::env::set_var(XDB_FILEPATH_ENV, xdb_filepath);
-    // 初始化并预热两个缓存
-    let _ = get_full_cache();
-    let _ = get_vector_index_cache();
+    let result = xdb_filepath
+        .map_or_else(default_detect_xdb_file, Ok)
+        .and_then(|path| searcher_init_with_mode(path, LoadMode::Full));
+
+    if let Err(e) = result {
+        tracing::error!("xdb init failed: {}", e);
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{fs::File, io::Read, net::Ipv4Addr, str::FromStr, thread};
+    use std::{
+        fs::File,
+        io::Read,
+        net::{Ipv4Addr, Ipv6Addr},
+        str::FromStr,
+        thread,
+    };
 
     use super::*;
 
@@ -203,4 +713,213 @@ mod tests {
         searcher_init(Some(String::from("test")));
         search_by_ip(123).unwrap();
     }
+
+    /// 构造 xdb 数据，所有网段需落在同一个向量索引（前两个字节相同）内
+    fn build_xdb(version: IpVersion, segments: &[(u128, u128, &str)]) -> Vec<u8> {
+        let segment_size = version.segment_index_size();
+        let ip_bytes = version.ip_bytes();
+        let encode_ip = |ip: u128| match version {
+            IpVersion::V4 => (ip as u32).to_le_bytes().to_vec(),
+            IpVersion::V6 => ip.to_be_bytes().to_vec(),
+        };
+
+        let mut data = Vec::new();
+        let data_start = HEADER_INFO_LENGTH + VECTOR_INDEX_LENGTH;
+        let mut data_ptrs = Vec::new();
+        for (_, _, region) in segments {
+            data_ptrs.push(data_start + data.len());
+            data.extend_from_slice(region.as_bytes());
+        }
+
+        let segment_start = data_start + data.len();
+        let mut index = Vec::new();
+        for ((start_ip, end_ip, region), ptr) in segments.iter().zip(data_ptrs) {
+            index.extend_from_slice(&encode_ip(*start_ip));
+            index.extend_from_slice(&encode_ip(*end_ip));
+            index.extend_from_slice(&(region.len() as u16).to_le_bytes());
+            index.extend_from_slice(&(ptr as u32).to_le_bytes());
+        }
+        assert_eq!(index.len(), segments.len() * segment_size);
+        assert!(ip_bytes * 2 + 6 == segment_size);
+
+        let mut header = vec![0u8; HEADER_INFO_LENGTH];
+        header[0..2].copy_from_slice(&3u16.to_le_bytes());
+        header[HEADER_START_INDEX_OFFSET..HEADER_START_INDEX_OFFSET + 4]
+            .copy_from_slice(&(segment_start as u32).to_le_bytes());
+        header[HEADER_END_INDEX_OFFSET..HEADER_END_INDEX_OFFSET + 4]
+            .copy_from_slice(&((segment_start + index.len() - segment_size) as u32).to_le_bytes());
+        let ip_version: u16 = match version {
+            IpVersion::V4 => 4,
+            IpVersion::V6 => 6,
+        };
+        header[HEADER_IP_VERSION_OFFSET..HEADER_IP_VERSION_OFFSET + 2]
+            .copy_from_slice(&ip_version.to_le_bytes());
+
+        let mut vector = vec![0u8; VECTOR_INDEX_LENGTH];
+        let offset = VECTOR_INDEX_SIZE * version.vector_index(segments[0].0);
+        vector[offset..offset + 4].copy_from_slice(&(segment_start as u32).to_le_bytes());
+        vector[offset + 4..offset + 8]
+            .copy_from_slice(&((segment_start + index.len()) as u32).to_le_bytes());
+
+        [header, vector, data, index].concat()
+    }
+
+    fn ipv6_xdb(region: &str) -> Vec<u8> {
+        let first = u128::from(Ipv6Addr::from_str("2001::").unwrap());
+        build_xdb(
+            IpVersion::V6,
+            &[
+                (first, first + 0xffff, "美国|0|0|0|0"),
+                (first + 0x1_0000, (0x2002 << 112) - 1, region),
+            ],
+        )
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("{}_{}.xdb", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_detect_ip_version() {
+        let mut header = vec![0u8; HEADER_INFO_LENGTH];
+        header[0] = 2;
+        assert_eq!(detect_ip_version(&header).unwrap(), IpVersion::V4);
+
+        header[0] = 3;
+        header[HEADER_IP_VERSION_OFFSET] = 4;
+        assert_eq!(detect_ip_version(&header).unwrap(), IpVersion::V4);
+
+        header[HEADER_IP_VERSION_OFFSET] = 6;
+        assert_eq!(detect_ip_version(&header).unwrap(), IpVersion::V6);
+
+        header[HEADER_IP_VERSION_OFFSET] = 5;
+        assert!(matches!(
+            detect_ip_version(&header),
+            Err(XdbError::UnsupportedIpVersion(5))
+        ));
+        assert!(matches!(
+            detect_ip_version(&header[..16]),
+            Err(XdbError::InvalidHeader(_))
+        ));
+    }
+
+    #[test]
+    fn test_search_by_ipv6() {
+        let path = temp_path("ip2region_v6");
+        std::fs::write(&path, ipv6_xdb("中国|0|广东省|深圳市|电信")).unwrap();
+
+        searcher_init(Some(path.to_string_lossy().to_string()));
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(search_by_ipv6("2001::1").unwrap(), "美国|0|0|0|0");
+        assert_eq!(
+            search_by_ipv6(Ipv6Addr::from_str("2001:db8::1").unwrap()).unwrap(),
+            "中国|0|广东省|深圳市|电信"
+        );
+        assert_eq!(search("2001:db8::1").unwrap(), "中国|0|广东省|深圳市|电信");
+        let region = search_region("2001:db8::1").unwrap();
+        assert_eq!(region.province.as_deref(), Some("广东省"));
+        assert_eq!(region.isp.as_deref(), Some("电信"));
+        assert!(matches!(
+            search_by_ipv6("2400::1"),
+            Err(XdbError::NotMatched)
+        ));
+        assert!(matches!(search("not an ip"), Err(XdbError::InvalidIp(_))));
+    }
+
+    #[test]
+    fn test_searcher_load_modes() {
+        let first = u128::from(u32::from(Ipv4Addr::new(1, 1, 0, 0)));
+        let xdb = build_xdb(
+            IpVersion::V4,
+            &[
+                (first, first + 0xff, "澳大利亚|0|0|0|0"),
+                (first + 0x100, first + 0xffff, "中国|0|福建省|福州市|电信"),
+            ],
+        );
+        let path = temp_path("ip2region_modes");
+        std::fs::write(&path, &xdb).unwrap();
+
+        for mode in [LoadMode::Mmap, LoadMode::VectorIndex, LoadMode::Full] {
+            let searcher = Searcher::from_path(&path, mode).unwrap();
+            assert_eq!(searcher.ip_version(), IpVersion::V4);
+            assert_eq!(searcher.search("1.1.0.1").unwrap(), "澳大利亚|0|0|0|0");
+            assert_eq!(
+                searcher.search("::ffff:1.1.8.8").unwrap(),
+                "中国|0|福建省|福州市|电信"
+            );
+            assert!(matches!(
+                searcher.search("1.2.0.1"),
+                Err(XdbError::NotMatched)
+            ));
+            assert!(matches!(
+                searcher.search("2001::1"),
+                Err(XdbError::IpVersionMismatch { .. })
+            ));
+
+            assert_eq!(
+                &*searcher.vector_index_cache().unwrap(),
+                &xdb[HEADER_INFO_LENGTH..HEADER_INFO_LENGTH + VECTOR_INDEX_LENGTH]
+            );
+            match mode {
+                LoadMode::VectorIndex => assert!(matches!(
+                    searcher.full_cache(),
+                    Err(XdbError::NotCached(LoadMode::VectorIndex))
+                )),
+                _ => assert_eq!(&*searcher.full_cache().unwrap(), &xdb[..]),
+            }
+        }
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            Searcher::from_path(&path, LoadMode::Full),
+            Err(XdbError::Io(_))
+        ));
+        assert!(matches!(
+            Searcher::from_bytes(xdb[..HEADER_INFO_LENGTH].to_vec()),
+            Err(XdbError::InvalidHeader(_))
+        ));
+
+        // 段索引指向文件外
+        let mut corrupted = xdb;
+        corrupted[HEADER_END_INDEX_OFFSET..HEADER_END_INDEX_OFFSET + 4]
+            .copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            Searcher::from_bytes(corrupted),
+            Err(XdbError::InvalidHeader(_))
+        ));
+    }
+
+    #[test]
+    fn test_searcher_reload() {
+        let path = temp_path("ip2region_reload");
+        std::fs::write(&path, ipv6_xdb("中国|0|北京|北京市|联通")).unwrap();
+
+        let searcher = Searcher::from_path(&path, LoadMode::Mmap).unwrap();
+        assert_eq!(
+            searcher.search("2001:db8::1").unwrap(),
+            "中国|0|北京|北京市|联通"
+        );
+        assert!(!searcher.reload_if_changed().unwrap());
+
+        // 以重命名的方式替换文件
+        let staged = temp_path("ip2region_reload_staged");
+        std::fs::write(&staged, ipv6_xdb("中国|0|上海|上海市|中国移动")).unwrap();
+        std::fs::rename(&staged, &path).unwrap();
+        assert!(searcher.reload_if_changed().unwrap());
+        assert_eq!(
+            searcher.search("2001:db8::1").unwrap(),
+            "中国|0|上海|上海市|中国移动"
+        );
+
+        // 损坏的文件不替换当前数据
+        std::fs::write(&staged, b"broken").unwrap();
+        std::fs::rename(&staged, &path).unwrap();
+        assert!(searcher.reload().is_err());
+        assert_eq!(
+            searcher.search("2001:db8::1").unwrap(),
+            "中国|0|上海|上海市|中国移动"
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
 }